@@ -0,0 +1,6 @@
+-- Revert initial database schema
+
+DROP TABLE IF EXISTS contact_messages;
+DROP TABLE IF EXISTS skills;
+DROP TABLE IF EXISTS projects;
+DROP TABLE IF EXISTS profile;
@@ -0,0 +1,3 @@
+-- Revert admin accounts
+
+DROP TABLE IF EXISTS admin_users;
@@ -0,0 +1,10 @@
+-- Admin accounts allowed to manage portfolio content
+
+-- Admin users table - stores administrator credentials (argon2 password hashes)
+CREATE TABLE IF NOT EXISTS admin_users (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    username TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    last_login_at DATETIME
+);
@@ -0,0 +1,3 @@
+-- Revert scoped API keys
+
+DROP TABLE IF EXISTS api_keys;
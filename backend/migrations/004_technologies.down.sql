@@ -0,0 +1,16 @@
+-- Revert normalized technologies back to JSON arrays on projects
+
+ALTER TABLE projects ADD COLUMN technologies TEXT NOT NULL DEFAULT '[]';
+
+UPDATE projects SET technologies = (
+    SELECT json_group_array(name) FROM (
+        SELECT t.name
+        FROM project_technologies pt
+        JOIN technologies t ON t.id = pt.technology_id
+        WHERE pt.project_id = projects.id
+        ORDER BY pt.position
+    )
+);
+
+DROP TABLE IF EXISTS project_technologies;
+DROP TABLE IF EXISTS technologies;
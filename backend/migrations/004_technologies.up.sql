@@ -0,0 +1,36 @@
+-- Normalize project technologies into their own tables
+
+-- Technologies table - one row per technology, matched case-insensitively by slug
+CREATE TABLE IF NOT EXISTS technologies (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE, -- lowercased, trimmed name
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Project technologies table - links projects to technologies in display order
+CREATE TABLE IF NOT EXISTS project_technologies (
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    technology_id INTEGER NOT NULL REFERENCES technologies(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (project_id, technology_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_technologies_technology ON project_technologies(technology_id);
+
+-- Convert the JSON arrays stored on projects, keeping the first spelling of each technology
+-- (values that are not valid JSON arrays cannot be recovered and are dropped)
+INSERT OR IGNORE INTO technologies (name, slug)
+SELECT TRIM(t.value), LOWER(TRIM(t.value))
+FROM projects p, json_each(p.technologies) t
+WHERE json_valid(p.technologies) AND t.type = 'text' AND TRIM(t.value) <> ''
+ORDER BY p.id, t.key;
+
+INSERT OR IGNORE INTO project_technologies (project_id, technology_id, position)
+SELECT p.id, tech.id, t.key
+FROM projects p, json_each(p.technologies) t
+JOIN technologies tech ON tech.slug = LOWER(TRIM(t.value))
+WHERE json_valid(p.technologies) AND t.type = 'text'
+ORDER BY p.id, t.key;
+
+ALTER TABLE projects DROP COLUMN technologies;
@@ -0,0 +1,3 @@
+-- Revert project skill links
+
+DROP TABLE IF EXISTS project_skills;
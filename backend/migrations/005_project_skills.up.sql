@@ -0,0 +1,19 @@
+-- Link projects to the skills they demonstrate
+
+-- Project skills table - 'suggested' links come from matching technologies against skill names
+CREATE TABLE IF NOT EXISTS project_skills (
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    skill_id INTEGER NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+    source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'suggested')),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (project_id, skill_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_skills_skill ON project_skills(skill_id);
+
+-- Suggest links for existing projects
+INSERT OR IGNORE INTO project_skills (project_id, skill_id, source)
+SELECT pt.project_id, s.id, 'suggested'
+FROM project_technologies pt
+JOIN technologies t ON t.id = pt.technology_id
+JOIN skills s ON LOWER(TRIM(s.name)) = t.slug;
@@ -0,0 +1,17 @@
+-- Revert the full-text search index
+
+DROP TRIGGER IF EXISTS search_profile_ad;
+DROP TRIGGER IF EXISTS search_profile_au;
+DROP TRIGGER IF EXISTS search_profile_ai;
+DROP TRIGGER IF EXISTS search_contact_messages_ad;
+DROP TRIGGER IF EXISTS search_contact_messages_au;
+DROP TRIGGER IF EXISTS search_contact_messages_ai;
+DROP TRIGGER IF EXISTS search_skills_ad;
+DROP TRIGGER IF EXISTS search_skills_au;
+DROP TRIGGER IF EXISTS search_skills_ai;
+DROP TRIGGER IF EXISTS search_project_technologies_ad;
+DROP TRIGGER IF EXISTS search_project_technologies_ai;
+DROP TRIGGER IF EXISTS search_projects_ad;
+DROP TRIGGER IF EXISTS search_projects_au;
+DROP TRIGGER IF EXISTS search_projects_ai;
+DROP TABLE IF EXISTS search_index;
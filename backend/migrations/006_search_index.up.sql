@@ -0,0 +1,116 @@
+-- Full-text search index over projects, skills, contact messages and the profile
+
+-- Search index - one row per searchable record, kept in sync by the triggers below
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+    kind UNINDEXED,   -- 'project', 'skill', 'contact_message' or 'profile'
+    ref_id UNINDEXED, -- ID of the record in its own table
+    title,
+    body,
+    tags,
+    tokenize = 'porter unicode61 remove_diacritics 2'
+);
+
+-- Projects: technologies are indexed as tags
+CREATE TRIGGER IF NOT EXISTS search_projects_ai AFTER INSERT ON projects BEGIN
+    INSERT INTO search_index (kind, ref_id, title, body, tags)
+    VALUES ('project', NEW.id, NEW.title, NEW.description || ' ' || IFNULL(NEW.long_description, ''), NULL);
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_projects_au AFTER UPDATE ON projects BEGIN
+    UPDATE search_index
+    SET title = NEW.title, body = NEW.description || ' ' || IFNULL(NEW.long_description, '')
+    WHERE kind = 'project' AND ref_id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_projects_ad AFTER DELETE ON projects BEGIN
+    DELETE FROM search_index WHERE kind = 'project' AND ref_id = OLD.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_project_technologies_ai AFTER INSERT ON project_technologies BEGIN
+    UPDATE search_index
+    SET tags = (
+        SELECT group_concat(t.name, ' ')
+        FROM project_technologies pt
+        JOIN technologies t ON t.id = pt.technology_id
+        WHERE pt.project_id = NEW.project_id
+    )
+    WHERE kind = 'project' AND ref_id = NEW.project_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_project_technologies_ad AFTER DELETE ON project_technologies BEGIN
+    UPDATE search_index
+    SET tags = (
+        SELECT group_concat(t.name, ' ')
+        FROM project_technologies pt
+        JOIN technologies t ON t.id = pt.technology_id
+        WHERE pt.project_id = OLD.project_id
+    )
+    WHERE kind = 'project' AND ref_id = OLD.project_id;
+END;
+
+-- Skills
+CREATE TRIGGER IF NOT EXISTS search_skills_ai AFTER INSERT ON skills BEGIN
+    INSERT INTO search_index (kind, ref_id, title, body, tags)
+    VALUES ('skill', NEW.id, NEW.name, IFNULL(NEW.description, ''), NEW.category);
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_skills_au AFTER UPDATE ON skills BEGIN
+    UPDATE search_index
+    SET title = NEW.name, body = IFNULL(NEW.description, ''), tags = NEW.category
+    WHERE kind = 'skill' AND ref_id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_skills_ad AFTER DELETE ON skills BEGIN
+    DELETE FROM search_index WHERE kind = 'skill' AND ref_id = OLD.id;
+END;
+
+-- Contact messages: sender name and email are indexed as tags
+CREATE TRIGGER IF NOT EXISTS search_contact_messages_ai AFTER INSERT ON contact_messages BEGIN
+    INSERT INTO search_index (kind, ref_id, title, body, tags)
+    VALUES ('contact_message', NEW.id, NEW.subject, NEW.message, NEW.name || ' ' || NEW.email);
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_contact_messages_au AFTER UPDATE ON contact_messages BEGIN
+    UPDATE search_index
+    SET title = NEW.subject, body = NEW.message, tags = NEW.name || ' ' || NEW.email
+    WHERE kind = 'contact_message' AND ref_id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_contact_messages_ad AFTER DELETE ON contact_messages BEGIN
+    DELETE FROM search_index WHERE kind = 'contact_message' AND ref_id = OLD.id;
+END;
+
+-- Profile
+CREATE TRIGGER IF NOT EXISTS search_profile_ai AFTER INSERT ON profile BEGIN
+    INSERT INTO search_index (kind, ref_id, title, body, tags)
+    VALUES ('profile', NEW.id, NEW.name, NEW.title || ' ' || NEW.bio, NEW.location);
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_profile_au AFTER UPDATE ON profile BEGIN
+    UPDATE search_index
+    SET title = NEW.name, body = NEW.title || ' ' || NEW.bio, tags = NEW.location
+    WHERE kind = 'profile' AND ref_id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_profile_ad AFTER DELETE ON profile BEGIN
+    DELETE FROM search_index WHERE kind = 'profile' AND ref_id = OLD.id;
+END;
+
+-- Index existing records
+INSERT INTO search_index (kind, ref_id, title, body, tags)
+SELECT 'project', p.id, p.title, p.description || ' ' || IFNULL(p.long_description, ''), (
+    SELECT group_concat(t.name, ' ')
+    FROM project_technologies pt
+    JOIN technologies t ON t.id = pt.technology_id
+    WHERE pt.project_id = p.id
+)
+FROM projects p;
+
+INSERT INTO search_index (kind, ref_id, title, body, tags)
+SELECT 'skill', id, name, IFNULL(description, ''), category FROM skills;
+
+INSERT INTO search_index (kind, ref_id, title, body, tags)
+SELECT 'contact_message', id, subject, message, name || ' ' || email FROM contact_messages;
+
+INSERT INTO search_index (kind, ref_id, title, body, tags)
+SELECT 'profile', id, name, title || ' ' || bio, location FROM profile;
@@ -0,0 +1,3 @@
+-- Revert the notification queue
+
+DROP TABLE IF EXISTS notification_jobs;
@@ -0,0 +1,11 @@
+-- Revert contact message moderation
+
+DROP TABLE IF EXISTS contact_replies;
+DROP INDEX IF EXISTS idx_contact_messages_status;
+
+ALTER TABLE contact_messages DROP COLUMN replied_at;
+ALTER TABLE contact_messages DROP COLUMN status_changed_at;
+ALTER TABLE contact_messages DROP COLUMN notes;
+ALTER TABLE contact_messages DROP COLUMN starred;
+ALTER TABLE contact_messages DROP COLUMN is_spam;
+ALTER TABLE contact_messages DROP COLUMN status;
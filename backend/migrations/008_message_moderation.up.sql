@@ -0,0 +1,23 @@
+-- Moderation state of contact messages, and the replies sent to them
+
+-- 'spam' is set at submission when the heuristic flags a message; is_spam keeps that verdict
+-- even after an admin moves the message elsewhere. Earlier messages were never checked.
+ALTER TABLE contact_messages ADD COLUMN status TEXT NOT NULL DEFAULT 'new'
+    CHECK (status IN ('new', 'read', 'replied', 'archived', 'spam'));
+ALTER TABLE contact_messages ADD COLUMN is_spam BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE contact_messages ADD COLUMN starred BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE contact_messages ADD COLUMN notes TEXT;
+ALTER TABLE contact_messages ADD COLUMN status_changed_at DATETIME;
+ALTER TABLE contact_messages ADD COLUMN replied_at DATETIME;
+
+CREATE INDEX IF NOT EXISTS idx_contact_messages_status ON contact_messages(status, created_at);
+
+CREATE TABLE IF NOT EXISTS contact_replies (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    message_id INTEGER NOT NULL REFERENCES contact_messages(id) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    replied_by TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_replies_message ON contact_replies(message_id);
@@ -0,0 +1,8 @@
+-- Revert spam scoring
+
+DROP TABLE IF EXISTS spam_corpus;
+DROP TABLE IF EXISTS spam_tokens;
+
+ALTER TABLE contact_messages DROP COLUMN spam_trained;
+ALTER TABLE contact_messages DROP COLUMN spam_reasons;
+ALTER TABLE contact_messages DROP COLUMN spam_score;
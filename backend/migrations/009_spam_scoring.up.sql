@@ -0,0 +1,22 @@
+-- Spam scores of contact messages and the naive Bayes model trained by admins
+
+ALTER TABLE contact_messages ADD COLUMN spam_score REAL;
+-- JSON array of {filter, score, reason}
+ALTER TABLE contact_messages ADD COLUMN spam_reasons TEXT;
+-- Label the message was last used to train the model with, so it is never counted twice
+ALTER TABLE contact_messages ADD COLUMN spam_trained TEXT CHECK (spam_trained IN ('spam', 'ham'));
+
+-- Number of trained messages of each label containing a token
+CREATE TABLE IF NOT EXISTS spam_tokens (
+    token TEXT PRIMARY KEY,
+    spam_count INTEGER NOT NULL DEFAULT 0,
+    ham_count INTEGER NOT NULL DEFAULT 0
+);
+
+-- Number of trained messages of each label
+CREATE TABLE IF NOT EXISTS spam_corpus (
+    label TEXT PRIMARY KEY CHECK (label IN ('spam', 'ham')),
+    messages INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO spam_corpus (label, messages) VALUES ('spam', 0), ('ham', 0);
@@ -0,0 +1,4 @@
+-- Revert the rate limit store
+
+DROP INDEX IF EXISTS idx_rate_limit_buckets_updated;
+DROP TABLE IF EXISTS rate_limit_buckets;
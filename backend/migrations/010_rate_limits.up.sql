@@ -0,0 +1,13 @@
+-- Token buckets of the SQLite rate limit store, so limits survive restarts
+
+CREATE TABLE IF NOT EXISTS rate_limit_buckets (
+    -- Route group and client, e.g. 'contact:203.0.113.7'
+    key TEXT PRIMARY KEY,
+    tokens REAL NOT NULL,
+    -- Whether the last request was let through
+    allowed BOOLEAN NOT NULL,
+    -- Milliseconds since the Unix epoch
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_rate_limit_buckets_updated ON rate_limit_buckets(updated_at);
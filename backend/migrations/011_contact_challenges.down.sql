@@ -0,0 +1,4 @@
+-- Revert the contact challenge replay store
+
+DROP INDEX IF EXISTS idx_used_challenges_expires;
+DROP TABLE IF EXISTS used_challenges;
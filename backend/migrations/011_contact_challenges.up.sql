@@ -0,0 +1,10 @@
+-- Proof-of-work challenges already redeemed, so a solution cannot be replayed
+
+CREATE TABLE IF NOT EXISTS used_challenges (
+    -- Random part of the signed challenge
+    id TEXT PRIMARY KEY,
+    -- Milliseconds since the Unix epoch; past this the challenge is refused anyway
+    expires_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_used_challenges_expires ON used_challenges(expires_at);
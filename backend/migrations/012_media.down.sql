@@ -0,0 +1,5 @@
+-- Revert uploaded media and project galleries
+
+DROP INDEX IF EXISTS idx_project_media_media;
+DROP TABLE IF EXISTS project_media;
+DROP TABLE IF EXISTS media;
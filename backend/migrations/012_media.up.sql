@@ -0,0 +1,21 @@
+-- Uploaded media, stored on disk under their content hash, and ordered project galleries
+
+CREATE TABLE IF NOT EXISTS media (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- SHA-256 of the content, hex encoded; identical uploads share a row and a file
+    hash TEXT NOT NULL UNIQUE,
+    content_type TEXT NOT NULL CHECK (content_type IN ('image/png', 'image/jpeg', 'image/webp', 'image/gif')),
+    size_bytes INTEGER NOT NULL,
+    original_name TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS project_media (
+    project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    media_id INTEGER NOT NULL REFERENCES media(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    alt_text TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (project_id, media_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_media_media ON project_media(media_id);
@@ -0,0 +1,8 @@
+-- Revert media dimensions and derivatives
+
+DROP INDEX IF EXISTS idx_media_pending;
+DROP TABLE IF EXISTS media_variants;
+
+ALTER TABLE media DROP COLUMN processed_at;
+ALTER TABLE media DROP COLUMN height;
+ALTER TABLE media DROP COLUMN width;
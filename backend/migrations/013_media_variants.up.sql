@@ -0,0 +1,19 @@
+-- Image dimensions and the resized or converted derivatives rendered from each upload
+
+ALTER TABLE media ADD COLUMN width INTEGER;
+ALTER TABLE media ADD COLUMN height INTEGER;
+-- Set once the background worker has rendered the configured derivatives
+ALTER TABLE media ADD COLUMN processed_at DATETIME;
+
+CREATE TABLE IF NOT EXISTS media_variants (
+    media_id INTEGER NOT NULL REFERENCES media(id) ON DELETE CASCADE,
+    -- Never wider than the original, so requests for larger sizes share the full-size variant
+    width INTEGER NOT NULL,
+    format TEXT NOT NULL CHECK (format IN ('jpeg', 'png', 'webp', 'avif')),
+    height INTEGER NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (media_id, width, format)
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_pending ON media(id) WHERE processed_at IS NULL;
@@ -0,0 +1,6 @@
+-- Revert blog posts
+
+DROP INDEX IF EXISTS idx_post_tags_tag;
+DROP INDEX IF EXISTS idx_posts_published_at;
+DROP TABLE IF EXISTS post_tags;
+DROP TABLE IF EXISTS posts;
@@ -0,0 +1,7 @@
+-- Revert the CV timeline
+
+DROP TABLE IF EXISTS certifications;
+DROP TABLE IF EXISTS education;
+DROP INDEX IF EXISTS idx_experience_technologies_technology;
+DROP TABLE IF EXISTS experience_technologies;
+DROP TABLE IF EXISTS experiences;
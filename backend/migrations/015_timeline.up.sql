@@ -0,0 +1,57 @@
+-- Work experience, education and certifications shown on the CV timeline.
+-- Months are stored as 'YYYY-MM' text so they sort chronologically.
+
+CREATE TABLE IF NOT EXISTS experiences (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    company TEXT NOT NULL,
+    role TEXT NOT NULL,
+    location TEXT,
+    start_month TEXT NOT NULL,
+    -- NULL while the position is held
+    end_month TEXT,
+    is_current BOOLEAN NOT NULL DEFAULT FALSE,
+    -- JSON array of bullet points, in display order
+    highlights TEXT NOT NULL DEFAULT '[]',
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (end_month IS NULL OR end_month >= start_month)
+);
+
+-- Experience technologies table - links experiences to technologies in display order
+CREATE TABLE IF NOT EXISTS experience_technologies (
+    experience_id INTEGER NOT NULL REFERENCES experiences(id) ON DELETE CASCADE,
+    technology_id INTEGER NOT NULL REFERENCES technologies(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (experience_id, technology_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_experience_technologies_technology ON experience_technologies(technology_id);
+
+CREATE TABLE IF NOT EXISTS education (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    institution TEXT NOT NULL,
+    degree TEXT NOT NULL,
+    field_of_study TEXT,
+    location TEXT,
+    start_month TEXT NOT NULL,
+    -- NULL while studying
+    end_month TEXT,
+    description TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (end_month IS NULL OR end_month >= start_month)
+);
+
+CREATE TABLE IF NOT EXISTS certifications (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    issuer TEXT NOT NULL,
+    issued_month TEXT NOT NULL,
+    -- NULL when the certification does not expire
+    expires_month TEXT,
+    credential_id TEXT,
+    credential_url TEXT,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (expires_month IS NULL OR expires_month >= issued_month)
+);
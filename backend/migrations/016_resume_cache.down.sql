@@ -0,0 +1,3 @@
+-- Revert the PDF résumé cache
+
+DROP TABLE IF EXISTS resume_cache;
@@ -0,0 +1,12 @@
+-- Rendered PDF résumés, one per combination of options, reused until their content changes
+
+CREATE TABLE IF NOT EXISTS resume_cache (
+    template TEXT NOT NULL,
+    page_size TEXT NOT NULL,
+    language TEXT NOT NULL,
+    -- Hash of the rendered content (including each record's updated_at) and renderer version
+    fingerprint TEXT NOT NULL,
+    content BLOB NOT NULL,
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (template, page_size, language)
+);
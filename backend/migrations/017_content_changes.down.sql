@@ -0,0 +1,16 @@
+-- Revert content change tracking
+
+DROP TRIGGER IF EXISTS content_changes_profile_ai;
+DROP TRIGGER IF EXISTS content_changes_profile_au;
+DROP TRIGGER IF EXISTS content_changes_profile_ad;
+DROP TRIGGER IF EXISTS content_changes_projects_ai;
+DROP TRIGGER IF EXISTS content_changes_projects_au;
+DROP TRIGGER IF EXISTS content_changes_projects_ad;
+DROP TRIGGER IF EXISTS content_changes_skills_ai;
+DROP TRIGGER IF EXISTS content_changes_skills_au;
+DROP TRIGGER IF EXISTS content_changes_skills_ad;
+DROP TRIGGER IF EXISTS content_changes_posts_ai;
+DROP TRIGGER IF EXISTS content_changes_posts_au;
+DROP TRIGGER IF EXISTS content_changes_posts_ad;
+
+DROP TABLE IF EXISTS content_changes;
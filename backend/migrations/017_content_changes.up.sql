@@ -0,0 +1,50 @@
+-- Revision of the content behind the public pages, bumped by triggers on every change, so that
+-- documents derived from it (the sitemap) are rebuilt only when something changed
+
+CREATE TABLE IF NOT EXISTS content_changes (
+    source TEXT PRIMARY KEY,
+    revision INTEGER NOT NULL DEFAULT 0,
+    changed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO content_changes (source) VALUES ('profile'), ('projects'), ('skills'), ('posts');
+
+CREATE TRIGGER IF NOT EXISTS content_changes_profile_ai AFTER INSERT ON profile BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'profile';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_profile_au AFTER UPDATE ON profile BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'profile';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_profile_ad AFTER DELETE ON profile BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'profile';
+END;
+
+CREATE TRIGGER IF NOT EXISTS content_changes_projects_ai AFTER INSERT ON projects BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'projects';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_projects_au AFTER UPDATE ON projects BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'projects';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_projects_ad AFTER DELETE ON projects BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'projects';
+END;
+
+CREATE TRIGGER IF NOT EXISTS content_changes_skills_ai AFTER INSERT ON skills BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'skills';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_skills_au AFTER UPDATE ON skills BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'skills';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_skills_ad AFTER DELETE ON skills BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'skills';
+END;
+
+CREATE TRIGGER IF NOT EXISTS content_changes_posts_ai AFTER INSERT ON posts BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'posts';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_posts_au AFTER UPDATE ON posts BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'posts';
+END;
+CREATE TRIGGER IF NOT EXISTS content_changes_posts_ad AFTER DELETE ON posts BEGIN
+    UPDATE content_changes SET revision = revision + 1, changed_at = CURRENT_TIMESTAMP WHERE source = 'posts';
+END;
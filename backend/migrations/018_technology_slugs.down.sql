@@ -0,0 +1 @@
+-- Nothing to revert: the recomputed slugs are the ones the application expects
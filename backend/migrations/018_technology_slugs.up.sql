@@ -0,0 +1,6 @@
+-- Recompute technology slugs with the application's Unicode-aware lowercasing
+--
+-- SQLite's LOWER() only folds ASCII, so 004_technologies slugged "Élixir" as "Élixir" while the
+-- application looks it up as "élixir". The conversion is done in Rust after this script
+-- (Fixup::ReslugTechnologies): technologies that now share a slug are merged and suggested
+-- project skills are recomputed.
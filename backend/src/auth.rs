@@ -0,0 +1,431 @@
+use std::{fmt, sync::Arc};
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::SqlitePool;
+use tracing::warn;
+
+use crate::{
+    config::AuthSettings,
+    error::ApiError,
+    models::{api_key::API_KEY_PREFIX, AdminUser, ApiScope},
+    services::{ApiKeyService, AuthService},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying an API key, as an alternative to `Authorization: Bearer`
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Minimum length of the token signing secret, in bytes
+pub const MIN_SECRET_LEN: usize = 32;
+
+/// Authentication configuration: session token signing secret and lifetime
+#[derive(Clone)]
+pub struct AuthConfig {
+    secret: Arc<[u8]>,
+    pub token_ttl: Duration,
+}
+
+impl AuthConfig {
+    pub fn new(secret: impl AsRef<[u8]>, token_ttl: Duration) -> Self {
+        Self {
+            secret: Arc::from(secret.as_ref()),
+            token_ttl,
+        }
+    }
+
+    /// Configuration with a random secret (tokens do not survive a restart)
+    pub fn ephemeral() -> Self {
+        let mut secret = [0u8; MIN_SECRET_LEN];
+        OsRng.fill_bytes(&mut secret);
+        Self::new(secret, Duration::hours(12))
+    }
+
+    /// Build the configuration from validated settings
+    pub fn from_settings(settings: &AuthSettings) -> Self {
+        let token_ttl = Duration::hours(settings.token_ttl_hours.max(1));
+
+        match &settings.secret {
+            Some(secret) if !secret.is_empty() => Self::new(secret, token_ttl),
+            _ => {
+                warn!("auth.secret is not set; using a random secret, sessions will not survive a restart");
+                Self {
+                    token_ttl,
+                    ..Self::ephemeral()
+                }
+            }
+        }
+    }
+
+    /// Issue a signed session token for an admin user
+    pub fn issue_token(&self, admin: &AdminUser) -> (String, DateTime<Utc>) {
+        let issued_at = Utc::now();
+        let expires_at = issued_at + self.token_ttl;
+        let claims = TokenClaims {
+            sub: admin.id,
+            username: admin.username.clone(),
+            iat: issued_at.timestamp(),
+            exp: expires_at.timestamp(),
+        };
+
+        // Claims only hold plain fields, so serialization cannot fail
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).unwrap_or_default());
+        let signature = URL_SAFE_NO_PAD.encode(self.sign(payload.as_bytes()));
+
+        (format!("{}.{}", payload, signature), expires_at)
+    }
+
+    /// Verify a session token's signature and expiry and return its claims
+    pub fn verify_token(&self, token: &str) -> Result<TokenClaims, ApiError> {
+        let (payload, signature) = token.split_once('.').ok_or(ApiError::Unauthorized)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| ApiError::Unauthorized)?;
+
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature).map_err(|_| ApiError::Unauthorized)?;
+
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| ApiError::Unauthorized)?;
+        let claims: TokenClaims = serde_json::from_slice(&payload).map_err(|_| ApiError::Unauthorized)?;
+
+        if claims.exp <= Utc::now().timestamp() {
+            return Err(ApiError::Unauthorized);
+        }
+
+        Ok(claims)
+    }
+
+    /// Key for signing something other than session tokens, derived from the secret so
+    /// that a signature made for one purpose is never accepted for another
+    pub fn derive_key(&self, purpose: &str) -> Vec<u8> {
+        self.sign(format!("portfolio:{}", purpose).as_bytes())
+    }
+
+    fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let mut mac = self.mac();
+        mac.update(data);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length")
+    }
+}
+
+impl fmt::Debug for AuthConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthConfig")
+            .field("secret", &"<redacted>")
+            .field("token_ttl", &self.token_ttl)
+            .finish()
+    }
+}
+
+/// Claims carried by a session token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenClaims {
+    pub sub: i32,
+    pub username: String,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+/// State shared by the authentication middleware and the auth routes
+#[derive(Debug, Clone)]
+pub struct AuthState {
+    pub pool: SqlitePool,
+    pub config: AuthConfig,
+}
+
+impl AuthState {
+    pub fn new(pool: SqlitePool, config: AuthConfig) -> Self {
+        Self { pool, config }
+    }
+}
+
+/// The admin that authenticated the current request
+#[derive(Debug, Clone)]
+pub struct AuthenticatedAdmin {
+    pub id: i32,
+    pub username: String,
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for AuthenticatedAdmin {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthenticatedAdmin>()
+            .cloned()
+            .ok_or(ApiError::Unauthorized)
+    }
+}
+
+/// Whoever authenticated the current request: an admin session or an API key
+#[derive(Debug, Clone)]
+pub enum Principal {
+    Admin(AuthenticatedAdmin),
+    ApiKey { id: i32, name: String },
+}
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Principal {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Principal>()
+            .cloned()
+            .ok_or(ApiError::Unauthorized)
+    }
+}
+
+/// Middleware rejecting requests without a valid admin session token
+pub async fn require_admin(
+    State(auth): State<AuthState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    authorize(auth, None, request, next).await
+}
+
+/// Middleware accepting an admin session, or an API key granted the given scope
+pub async fn require_scope(
+    State((auth, scope)): State<(AuthState, ApiScope)>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    authorize(auth, Some(scope), request, next).await
+}
+
+/// Middleware authenticating requests that carry credentials (as `require_scope` does)
+/// and letting anonymous requests through, for routes that show more to admins
+pub async fn optional_scope(
+    State((auth, scope)): State<(AuthState, ApiScope)>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if api_key_header(request.headers()).is_none() && bearer_token(request.headers()).is_none() {
+        return Ok(next.run(request).await);
+    }
+    authorize(auth, Some(scope), request, next).await
+}
+
+/// Authenticate the request and record the principal in its extensions.
+/// API keys are only accepted when a scope is given; admin-only routes reject them.
+async fn authorize(
+    auth: AuthState,
+    scope: Option<ApiScope>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let credential = api_key_header(request.headers())
+        .or_else(|| bearer_token(request.headers()))
+        .ok_or(ApiError::Unauthorized)?;
+
+    let principal = if credential.starts_with(API_KEY_PREFIX) {
+        let scope = scope.ok_or(ApiError::Forbidden)?;
+        let api_key = ApiKeyService::new(auth.pool).authenticate(credential, scope).await?;
+        Principal::ApiKey {
+            id: api_key.id,
+            name: api_key.name,
+        }
+    } else {
+        let admin = AuthService::new(auth.pool, auth.config)
+            .authenticate_token(credential)
+            .await?;
+        let admin = AuthenticatedAdmin {
+            id: admin.id,
+            username: admin.username,
+        };
+        request.extensions_mut().insert(admin.clone());
+        Principal::Admin(admin)
+    };
+
+    request.extensions_mut().insert(principal);
+    Ok(next.run(request).await)
+}
+
+/// Whether the request carries a valid admin session token, for responses that show admins more
+pub async fn authenticates_admin(auth: &AuthState, headers: &HeaderMap) -> bool {
+    match bearer_token(headers) {
+        Some(token) if !token.starts_with(API_KEY_PREFIX) => AuthService::new(auth.pool.clone(), auth.config.clone())
+            .authenticate_token(token)
+            .await
+            .is_ok(),
+        _ => false,
+    }
+}
+
+/// Extract an API key from the `X-API-Key` header
+fn api_key_header(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(API_KEY_HEADER)?
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// Create an admin directly in an initialized database and return a valid session token for it
+#[cfg(test)]
+pub(crate) async fn test_admin_token(pool: &SqlitePool, config: &AuthConfig) -> String {
+    let admin = crate::database::AdminRepository::new(pool.clone())
+        .create("test-admin", "unused")
+        .await
+        .unwrap();
+
+    config.issue_token(&admin).0
+}
+
+/// Create an API key with the given scopes and return its plaintext value
+#[cfg(test)]
+pub(crate) async fn test_api_key(pool: &SqlitePool, scopes: &[&str]) -> String {
+    let key_data = crate::models::CreateApiKey {
+        name: "test-key".to_string(),
+        scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        expires_at: None,
+    };
+
+    ApiKeyService::new(pool.clone())
+        .create_key(key_data, None)
+        .await
+        .unwrap()
+        .key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn test_admin() -> AdminUser {
+        AdminUser {
+            id: 7,
+            username: "admin".to_string(),
+            password_hash: String::new(),
+            created_at: Utc::now(),
+            last_login_at: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_and_verify_token() {
+        let config = AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::hours(1));
+        let (token, expires_at) = config.issue_token(&test_admin());
+
+        assert!(expires_at > Utc::now());
+
+        let claims = config.verify_token(&token).unwrap();
+        assert_eq!(claims.sub, 7);
+        assert_eq!(claims.username, "admin");
+    }
+
+    #[test]
+    fn test_token_with_other_secret_rejected() {
+        let config = AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::hours(1));
+        let other = AuthConfig::new("another-secret-that-is-long-enough", Duration::hours(1));
+        let (token, _) = other.issue_token(&test_admin());
+
+        assert!(matches!(config.verify_token(&token), Err(ApiError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_tampered_token_rejected() {
+        let config = AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::hours(1));
+        let (token, _) = config.issue_token(&test_admin());
+        let (_, signature) = token.split_once('.').unwrap();
+
+        let forged_claims = TokenClaims {
+            sub: 1,
+            username: "root".to_string(),
+            iat: 0,
+            exp: i64::MAX,
+        };
+        let forged_payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&forged_claims).unwrap());
+        let forged = format!("{}.{}", forged_payload, signature);
+
+        assert!(config.verify_token(&forged).is_err());
+        assert!(config.verify_token("garbage").is_err());
+    }
+
+    #[test]
+    fn test_expired_token_rejected() {
+        let config = AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::seconds(-1));
+        let (token, _) = config.issue_token(&test_admin());
+
+        assert!(config.verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_bearer_token_parsing() {
+        let mut headers = HeaderMap::new();
+        assert!(bearer_token(&headers).is_none());
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic abc"));
+        assert!(bearer_token(&headers).is_none());
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer abc.def"));
+        assert_eq!(bearer_token(&headers), Some("abc.def"));
+    }
+
+    #[test]
+    fn test_api_key_header_parsing() {
+        let mut headers = HeaderMap::new();
+        assert!(api_key_header(&headers).is_none());
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static(" pfk_abc "));
+        assert_eq!(api_key_header(&headers), Some("pfk_abc"));
+    }
+
+    #[test]
+    fn test_from_settings() {
+        let settings = AuthSettings {
+            secret: Some("a-test-secret-that-is-long-enough!".to_string()),
+            token_ttl_hours: 2,
+            ..AuthSettings::default()
+        };
+        let config = AuthConfig::from_settings(&settings);
+        let same = AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::hours(2));
+        assert_eq!(config.token_ttl, Duration::hours(2));
+
+        let (token, _) = config.issue_token(&test_admin());
+        assert!(same.verify_token(&token).is_ok());
+
+        // Without a secret, a random one is used
+        let random = AuthConfig::from_settings(&AuthSettings::default());
+        assert!(random.verify_token(&token).is_err());
+    }
+
+    #[test]
+    fn test_debug_redacts_secret() {
+        let config = AuthConfig::new("super-secret-value", Duration::hours(1));
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("super-secret-value"));
+    }
+}
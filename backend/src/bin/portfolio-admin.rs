@@ -0,0 +1,290 @@
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+use clap::{Parser, Subcommand};
+use portfolio_backend::{
+    auth::AuthConfig,
+    config::AppConfig,
+    database::{self, DatabaseConfig, MigrationManager},
+    models::{CreateAdminUser, PortfolioExport},
+    server,
+    services::{AuthService, ContactService, ExportService},
+};
+use sqlx::SqlitePool;
+use tracing::Level;
+
+/// Administrative tasks for the portfolio backend
+#[derive(Debug, Parser)]
+#[command(name = "portfolio-admin", version)]
+struct Cli {
+    /// TOML configuration file (defaults to PORTFOLIO_CONFIG, then ./portfolio.toml if present)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// SQLite database URL, overriding the configuration
+    #[arg(long, global = true)]
+    database_url: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Manage schema migrations
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+    /// Insert the default profile, skills and projects where missing
+    Seed,
+    /// Create an admin account (the password is read from stdin unless given)
+    CreateAdmin {
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Export profile, projects and skills as JSON
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Import a JSON export, updating existing projects and skills by title/name
+    Import {
+        /// File produced by `export`
+        file: PathBuf,
+    },
+    /// Write a consistent copy of the database file
+    Backup {
+        /// Destination file (defaults to backups/portfolio-<timestamp>.db)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Delete contact messages older than the given number of days
+    PurgeContacts {
+        #[arg(long, value_name = "DAYS")]
+        older_than: u32,
+    },
+    /// Run the HTTP server
+    Serve {
+        /// Listen address, overriding the configuration
+        #[arg(long)]
+        host: Option<String>,
+        /// Listen port, overriding the configuration
+        #[arg(long)]
+        port: Option<u16>,
+        /// Seed default data before starting
+        #[arg(long)]
+        seed: bool,
+    },
+    /// Print the effective configuration with secrets redacted
+    PrintConfig,
+}
+
+#[derive(Debug, Subcommand)]
+enum MigrateAction {
+    /// Apply all pending migrations
+    Up,
+    /// Revert the most recent migrations
+    Down {
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+    /// Show applied, pending and modified migrations
+    Status,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    // Keep routine commands quiet; the server logs as usual
+    let max_level = if matches!(cli.command, Command::Serve { .. }) { Level::INFO } else { Level::WARN };
+    tracing_subscriber::fmt()
+        .with_max_level(max_level)
+        .with_writer(io::stderr)
+        .init();
+
+    let mut app_config = AppConfig::load(cli.config.as_deref())?;
+    if let Some(database_url) = cli.database_url {
+        app_config.database.url = database_url;
+    }
+    let config = DatabaseConfig::from(&app_config.database);
+
+    match cli.command {
+        Command::Migrate { action } => {
+            let pool = database::connect_database(&config).await?;
+            migrate(pool, action).await?;
+        }
+        Command::Seed => {
+            let pool = database::open_database(&config).await?;
+            database::seed_database(&pool).await?;
+            println!("Database seeded");
+        }
+        Command::CreateAdmin { username, password } => {
+            let pool = database::open_database(&config).await?;
+            let password = match password {
+                Some(password) => password,
+                None => read_password()?,
+            };
+
+            let auth_service = AuthService::new(pool, AuthConfig::ephemeral());
+            let admin = auth_service.create_admin(CreateAdminUser { username, password }).await?;
+            println!("Created admin '{}' (ID: {})", admin.username, admin.id);
+        }
+        Command::Export { output } => {
+            let pool = database::open_database(&config).await?;
+            let export = ExportService::new(pool).export().await?;
+            let json = serde_json::to_string_pretty(&export)?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, json).with_context(|| format!("writing {}", path.display()))?;
+                    eprintln!(
+                        "Exported {} projects and {} skills to {}",
+                        export.projects.len(),
+                        export.skills.len(),
+                        path.display()
+                    );
+                }
+                None => println!("{}", json),
+            }
+        }
+        Command::Import { file } => {
+            let pool = database::open_database(&config).await?;
+            let content = std::fs::read_to_string(&file).with_context(|| format!("reading {}", file.display()))?;
+            let data: PortfolioExport = serde_json::from_str(&content).context("parsing export file")?;
+
+            let summary = ExportService::new(pool).import(data).await?;
+            println!(
+                "Imported: profile {}, projects {} created / {} updated, skills {} created / {} updated",
+                if summary.profile_updated { "updated" } else { "unchanged" },
+                summary.projects_created,
+                summary.projects_updated,
+                summary.skills_created,
+                summary.skills_updated
+            );
+        }
+        Command::Backup { output } => {
+            let pool = database::connect_database(&config).await?;
+            let destination = output.unwrap_or_else(|| {
+                PathBuf::from("backups").join(format!("portfolio-{}.db", chrono::Utc::now().format("%Y%m%d-%H%M%S")))
+            });
+
+            let size = database::backup_database(&pool, &destination).await?;
+            println!("Backup written to {} ({} bytes)", destination.display(), size);
+        }
+        Command::PurgeContacts { older_than } => {
+            let pool = database::open_database(&config).await?;
+            let deleted = ContactService::new(pool, None).cleanup_old_messages(older_than).await?;
+            println!("Deleted {} contact message(s) older than {} days", deleted, older_than);
+        }
+        Command::Serve { host, port, seed } => {
+            if let Some(host) = host {
+                app_config.server.host = host;
+            }
+            if let Some(port) = port {
+                app_config.server.port = port;
+            }
+            app_config.validate()?;
+
+            let pool = database::open_database(&config).await?;
+            if seed || app_config.database.seed {
+                database::seed_database(&pool).await?;
+            }
+
+            let auth_config = AuthConfig::from_settings(&app_config.auth);
+            if let Some(admin) = server::bootstrap_admin(&pool, &auth_config, &app_config.auth).await? {
+                println!("👤 Created admin account '{}'", admin.username);
+            }
+
+            server::serve(pool, auth_config, &app_config).await?;
+        }
+        Command::PrintConfig => {
+            print!("{}", app_config.to_redacted_toml());
+        }
+    }
+
+    Ok(())
+}
+
+async fn migrate(pool: SqlitePool, action: MigrateAction) -> anyhow::Result<()> {
+    let manager = MigrationManager::new(pool);
+
+    match action {
+        MigrateAction::Up => {
+            let applied = manager.run_migrations().await?;
+            if applied.is_empty() {
+                println!("Database schema is up to date");
+            }
+            for migration in applied {
+                println!("Applied {}", migration.name);
+            }
+        }
+        MigrateAction::Down { steps } => {
+            let reverted = manager.rollback(steps).await?;
+            if reverted.is_empty() {
+                println!("No migrations to revert");
+            }
+            for migration in reverted {
+                println!("Reverted {}", migration.name);
+            }
+        }
+        MigrateAction::Status => {
+            println!("{:<8} {:<32} {:<10} APPLIED AT", "VERSION", "NAME", "STATE");
+            for status in manager.status().await? {
+                println!(
+                    "{:<8} {:<32} {:<10} {}",
+                    status.version,
+                    status.name,
+                    status.state.as_str(),
+                    status.applied_at.map(|at| at.to_rfc3339()).unwrap_or_else(|| "-".to_string())
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a password from stdin so it stays out of the shell history
+fn read_password() -> anyhow::Result<String> {
+    eprint!("Password: ");
+    io::stderr().flush()?;
+
+    let mut password = String::new();
+    io::stdin().lock().read_line(&mut password)?;
+    let password = password.trim_end_matches(['\r', '\n']).to_string();
+
+    if password.is_empty() {
+        bail!("a password is required");
+    }
+    Ok(password)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_purge_contacts() {
+        let cli = Cli::try_parse_from(["portfolio-admin", "purge-contacts", "--older-than", "90"]).unwrap();
+        assert!(matches!(cli.command, Command::PurgeContacts { older_than: 90 }));
+    }
+
+    #[test]
+    fn test_parse_migrate_down() {
+        let cli = Cli::try_parse_from(["portfolio-admin", "--database-url", "sqlite::memory:", "migrate", "down", "--steps", "2"]).unwrap();
+        assert_eq!(cli.database_url.as_deref(), Some("sqlite::memory:"));
+        assert!(matches!(cli.command, Command::Migrate { action: MigrateAction::Down { steps: 2 } }));
+    }
+}
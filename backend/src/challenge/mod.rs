@@ -0,0 +1,262 @@
+//! Self-hosted proof-of-work challenges for the contact form
+pub mod pow;
+pub mod surge;
+
+use std::{fmt, sync::Arc};
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::SqlitePool;
+
+use crate::{auth::AuthConfig, config::ChallengeConfig, database::ChallengeRepository};
+
+pub use surge::SurgeMonitor;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Hash clients must use to solve a challenge
+pub const ALGORITHM: &str = "sha256";
+
+/// Why a submitted solution was refused
+#[derive(Debug, thiserror::Error)]
+pub enum ChallengeError {
+    #[error("A solved challenge is required")]
+    Missing,
+    #[error("Invalid challenge")]
+    Invalid,
+    #[error("Challenge expired, please request a new one")]
+    Expired,
+    #[error("Challenge not solved")]
+    Unsolved,
+    #[error("Challenge already used")]
+    Replayed,
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// A puzzle for the client: find a nonce such that the SHA-256 hash of
+/// `<challenge>:<nonce>` starts with `difficulty` zero bits
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct Challenge {
+    pub challenge: String,
+    pub algorithm: &'static str,
+    pub difficulty: u8,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Issues signed, expiring challenges and redeems their solutions once
+#[derive(Clone)]
+pub struct Challenges {
+    key: Arc<[u8]>,
+    ttl: Duration,
+    difficulty: u8,
+    max_difficulty: u8,
+    surge: Arc<SurgeMonitor>,
+    repository: Arc<ChallengeRepository>,
+}
+
+impl Challenges {
+    pub fn new(pool: SqlitePool, key: impl AsRef<[u8]>, config: &ChallengeConfig) -> Self {
+        Self {
+            key: Arc::from(key.as_ref()),
+            ttl: Duration::seconds(config.ttl_secs as i64),
+            difficulty: config.difficulty,
+            max_difficulty: config.max_difficulty.max(config.difficulty),
+            surge: Arc::new(SurgeMonitor::new(
+                Duration::seconds(config.surge_window_secs as i64),
+                config.surge_requests,
+                config.surge_spam,
+            )),
+            repository: Arc::new(ChallengeRepository::new(pool)),
+        }
+    }
+
+    /// Challenges signed with a key derived from the session secret
+    pub fn from_config(pool: SqlitePool, auth: &AuthConfig, config: &ChallengeConfig) -> Self {
+        Self::new(pool, auth.derive_key("contact-challenge"), config)
+    }
+
+    /// Difficulty of challenges issued now, raised during a surge
+    pub fn current_difficulty(&self) -> u8 {
+        (self.difficulty + self.surge.extra_bits(Utc::now())).min(self.max_difficulty)
+    }
+
+    /// Issue a challenge at the current difficulty
+    pub fn issue(&self) -> Challenge {
+        let now = Utc::now();
+        self.surge.record_request(now);
+        self.issue_at(now, self.current_difficulty())
+    }
+
+    pub fn issue_at(&self, at: DateTime<Utc>, difficulty: u8) -> Challenge {
+        let mut id = [0u8; 16];
+        OsRng.fill_bytes(&mut id);
+
+        let expires_at = at + self.ttl;
+        let payload = format!(
+            "{}.{}.{}",
+            difficulty,
+            expires_at.timestamp_millis(),
+            URL_SAFE_NO_PAD.encode(id)
+        );
+
+        Challenge {
+            challenge: format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(self.sign(&payload))),
+            algorithm: ALGORITHM,
+            difficulty,
+            expires_at,
+        }
+    }
+
+    /// Check a solution and redeem the challenge so the same solution cannot be sent twice
+    pub async fn redeem(&self, challenge: Option<&str>, nonce: Option<&str>) -> Result<(), ChallengeError> {
+        let (Some(challenge), Some(nonce)) = (challenge.map(str::trim), nonce.map(str::trim)) else {
+            return Err(ChallengeError::Missing);
+        };
+
+        let (difficulty, expires_at, id) = self.parse(challenge).ok_or(ChallengeError::Invalid)?;
+        let now = Utc::now();
+        if now > expires_at {
+            return Err(ChallengeError::Expired);
+        }
+        if !pow::is_solution(challenge, nonce, difficulty) {
+            return Err(ChallengeError::Unsolved);
+        }
+
+        if self
+            .repository
+            .claim(id, expires_at.timestamp_millis(), now.timestamp_millis())
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(ChallengeError::Replayed)
+        }
+    }
+
+    /// A submission was scored as spam, which makes the next challenges harder for a while
+    pub fn record_spam(&self) {
+        self.surge.record_spam(Utc::now());
+    }
+
+    /// Difficulty, expiry and id of a challenge carrying a valid signature
+    fn parse<'a>(&self, challenge: &'a str) -> Option<(u8, DateTime<Utc>, &'a str)> {
+        let (payload, signature) = challenge.rsplit_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature).ok()?;
+
+        let mut parts = payload.splitn(3, '.');
+        let difficulty = parts.next()?.parse().ok()?;
+        let expires_at = Utc.timestamp_millis_opt(parts.next()?.parse().ok()?).single()?;
+        let id = parts.next()?;
+        Some((difficulty, expires_at, id))
+    }
+
+    fn sign(&self, payload: &str) -> Vec<u8> {
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length")
+    }
+}
+
+impl fmt::Debug for Challenges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Challenges")
+            .field("key", &"<redacted>")
+            .field("ttl", &self.ttl)
+            .field("difficulty", &self.difficulty)
+            .field("max_difficulty", &self.max_difficulty)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_challenges(config: &ChallengeConfig) -> Challenges {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        Challenges::new(pool, "a-test-key", config)
+    }
+
+    #[tokio::test]
+    async fn test_redeem_once() {
+        let challenges = create_challenges(&ChallengeConfig::default()).await;
+        let issued = challenges.issue_at(Utc::now(), 8);
+        let nonce = pow::solve(&issued.challenge, issued.difficulty);
+
+        challenges.redeem(Some(&issued.challenge), Some(&nonce)).await.unwrap();
+        assert!(matches!(
+            challenges.redeem(Some(&issued.challenge), Some(&nonce)).await,
+            Err(ChallengeError::Replayed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_solutions_rejected() {
+        let challenges = create_challenges(&ChallengeConfig::default()).await;
+        let issued = challenges.issue_at(Utc::now(), 8);
+        let nonce = pow::solve(&issued.challenge, issued.difficulty);
+
+        assert!(matches!(challenges.redeem(None, Some(&nonce)).await, Err(ChallengeError::Missing)));
+        assert!(matches!(
+            challenges.redeem(Some(&issued.challenge), Some("not-a-solution")).await,
+            Err(ChallengeError::Unsolved)
+        ));
+
+        // Lowering the difficulty breaks the signature
+        let easier = format!("0{}", &issued.challenge[1..]);
+        assert!(matches!(
+            challenges.redeem(Some(&easier), Some(&nonce)).await,
+            Err(ChallengeError::Invalid)
+        ));
+        let other_key = Challenges {
+            key: Arc::from(&b"another-key"[..]),
+            ..challenges.clone()
+        };
+        assert!(matches!(
+            other_key.redeem(Some(&issued.challenge), Some(&nonce)).await,
+            Err(ChallengeError::Invalid)
+        ));
+
+        let expired = challenges.issue_at(Utc::now() - Duration::hours(1), 1);
+        let nonce = pow::solve(&expired.challenge, expired.difficulty);
+        assert!(matches!(
+            challenges.redeem(Some(&expired.challenge), Some(&nonce)).await,
+            Err(ChallengeError::Expired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_difficulty_rises_during_surge() {
+        let config = ChallengeConfig {
+            difficulty: 10,
+            max_difficulty: 13,
+            surge_requests: 5,
+            surge_spam: 2,
+            ..ChallengeConfig::default()
+        };
+        let challenges = create_challenges(&config).await;
+
+        let difficulties: Vec<u8> = (0..10).map(|_| challenges.issue().difficulty).collect();
+        assert_eq!(difficulties, [10, 10, 10, 10, 11, 11, 11, 11, 11, 12]);
+
+        challenges.record_spam();
+        challenges.record_spam();
+        assert_eq!(challenges.current_difficulty(), 13);
+    }
+}
@@ -0,0 +1,60 @@
+use sha2::{Digest, Sha256};
+
+/// Longest nonce accepted, so verifying stays cheap
+pub const MAX_NONCE_LEN: usize = 64;
+
+/// Hash a solution attempt: SHA-256 of the challenge, a colon and the nonce
+pub fn digest(challenge: &str, nonce: &str) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(challenge.as_bytes());
+    hasher.update(b":");
+    hasher.update(nonce.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Number of zero bits the hash starts with
+pub fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Whether `nonce` solves the challenge at the given difficulty
+pub fn is_solution(challenge: &str, nonce: &str, difficulty: u8) -> bool {
+    !nonce.is_empty()
+        && nonce.len() <= MAX_NONCE_LEN
+        && leading_zero_bits(&digest(challenge, nonce)) >= difficulty as u32
+}
+
+/// Find a solution by counting up from zero, as the contact form does in the browser
+pub fn solve(challenge: &str, difficulty: u8) -> String {
+    (0u64..)
+        .map(|nonce| nonce.to_string())
+        .find(|nonce| is_solution(challenge, nonce, difficulty))
+        .expect("a solution exists for any difficulty below 64 bits")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leading_zero_bits() {
+        assert_eq!(leading_zero_bits(&[0xff]), 0);
+        assert_eq!(leading_zero_bits(&[0x00, 0x10, 0xff]), 11);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00]), 16);
+    }
+
+    #[test]
+    fn test_solve() {
+        let nonce = solve("a-challenge", 10);
+        assert!(is_solution("a-challenge", &nonce, 10));
+        assert!(!is_solution("a-challenge", "", 0));
+        assert!(!is_solution("a-challenge", &"0".repeat(MAX_NONCE_LEN + 1), 0));
+    }
+}
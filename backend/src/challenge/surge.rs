@@ -0,0 +1,109 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Extra bits of difficulty while spam is coming in
+const SPAM_SURGE_BITS: u8 = 2;
+/// Events remembered per surge threshold; beyond this the difficulty is maxed out anyway
+const MAX_TRACKED_PER_THRESHOLD: usize = 64;
+
+/// Counts recent challenge requests and spam submissions to raise the difficulty during a surge
+#[derive(Debug)]
+pub struct SurgeMonitor {
+    window: Duration,
+    surge_requests: u32,
+    surge_spam: u32,
+    requests: Mutex<VecDeque<DateTime<Utc>>>,
+    spam: Mutex<VecDeque<DateTime<Utc>>>,
+}
+
+impl SurgeMonitor {
+    pub fn new(window: Duration, surge_requests: u32, surge_spam: u32) -> Self {
+        Self {
+            window,
+            surge_requests: surge_requests.max(1),
+            surge_spam: surge_spam.max(1),
+            requests: Mutex::new(VecDeque::new()),
+            spam: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// A challenge was requested
+    pub fn record_request(&self, at: DateTime<Utc>) {
+        self.record(&self.requests, self.surge_requests, at);
+    }
+
+    /// A submission was scored as spam
+    pub fn record_spam(&self, at: DateTime<Utc>) {
+        self.record(&self.spam, self.surge_spam, at);
+    }
+
+    /// Bits to add to the base difficulty: one once requests reach the surge threshold and
+    /// another each time they double, plus a fixed step while spam keeps arriving
+    pub fn extra_bits(&self, now: DateTime<Utc>) -> u8 {
+        let requests = self.count(&self.requests, now) as u32;
+        let spam = self.count(&self.spam, now) as u32;
+
+        let request_bits = if requests >= self.surge_requests {
+            1 + (requests / self.surge_requests).ilog2() as u8
+        } else {
+            0
+        };
+        let spam_bits = if spam >= self.surge_spam { SPAM_SURGE_BITS } else { 0 };
+
+        request_bits + spam_bits
+    }
+
+    fn record(&self, events: &Mutex<VecDeque<DateTime<Utc>>>, threshold: u32, at: DateTime<Utc>) {
+        let mut events = events.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.expire(&mut events, at);
+        if events.len() >= threshold as usize * MAX_TRACKED_PER_THRESHOLD {
+            events.pop_front();
+        }
+        events.push_back(at);
+    }
+
+    fn count(&self, events: &Mutex<VecDeque<DateTime<Utc>>>, now: DateTime<Utc>) -> usize {
+        let mut events = events.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.expire(&mut events, now);
+        events.len()
+    }
+
+    fn expire(&self, events: &mut VecDeque<DateTime<Utc>>, now: DateTime<Utc>) {
+        while events.front().is_some_and(|at| now - *at > self.window) {
+            events.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_bits() {
+        let monitor = SurgeMonitor::new(Duration::minutes(10), 10, 3);
+        let start = Utc::now();
+
+        for _ in 0..9 {
+            monitor.record_request(start);
+        }
+        assert_eq!(monitor.extra_bits(start), 0);
+
+        monitor.record_request(start);
+        assert_eq!(monitor.extra_bits(start), 1);
+
+        for _ in 0..30 {
+            monitor.record_request(start);
+        }
+        assert_eq!(monitor.extra_bits(start), 3);
+
+        for _ in 0..3 {
+            monitor.record_spam(start);
+        }
+        assert_eq!(monitor.extra_bits(start), 3 + SPAM_SURGE_BITS);
+
+        // The surge is forgotten once it leaves the window
+        assert_eq!(monitor.extra_bits(start + Duration::minutes(11)), 0);
+    }
+}
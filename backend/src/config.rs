@@ -0,0 +1,1541 @@
+use std::{
+    fmt,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use axum::http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+use crate::{auth::MIN_SECRET_LEN, database::DatabaseConfig, models::VariantFormat};
+
+/// Prefix of environment variables overriding configuration values.
+///
+/// Nested keys are separated by a double underscore: `PORTFOLIO_SERVER__PORT=8080`
+/// sets `server.port`. List values are comma-separated.
+pub const ENV_PREFIX: &str = "PORTFOLIO_";
+
+/// Environment variable naming the configuration file
+pub const CONFIG_FILE_ENV: &str = "PORTFOLIO_CONFIG";
+
+/// Configuration file read from the working directory when no path is given
+pub const DEFAULT_CONFIG_FILE: &str = "portfolio.toml";
+
+/// Unprefixed variables from earlier releases, still honored (prefixed ones win)
+const LEGACY_ENV_VARS: &[(&str, &str)] = &[
+    ("DATABASE_URL", "database.url"),
+    ("SEED_DATABASE", "database.seed"),
+    ("HOST", "server.host"),
+    ("PORT", "server.port"),
+    ("CORS_ORIGIN", "cors.allowed_origins"),
+    ("AUTH_SECRET", "auth.secret"),
+    ("AUTH_TOKEN_TTL_HOURS", "auth.token_ttl_hours"),
+    ("ADMIN_USERNAME", "auth.admin_username"),
+    ("ADMIN_PASSWORD", "auth.admin_password"),
+];
+
+/// Placeholder shown instead of secret values
+const REDACTED: &str = "<redacted>";
+
+/// Configuration error types
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Cannot read config file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("Invalid config file {path}: {message}")]
+    File { path: PathBuf, message: String },
+    #[error("Invalid value for {var}: {message}")]
+    Env { var: String, message: String },
+    #[error("Invalid configuration: {0}")]
+    Parse(String),
+    #[error("Invalid configuration:\n{}", format_problems(.0))]
+    Invalid(Vec<String>),
+}
+
+fn format_problems(problems: &[String]) -> String {
+    problems
+        .iter()
+        .map(|problem| format!("  - {}", problem))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Complete application configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub site: SiteConfig,
+    pub database: DatabaseSettings,
+    pub cors: CorsConfig,
+    pub auth: AuthSettings,
+    pub rate_limit: RateLimitConfig,
+    pub uploads: UploadConfig,
+    pub features: FeatureToggles,
+    pub notifications: NotificationConfig,
+    pub spam: SpamConfig,
+    pub challenge: ChallengeConfig,
+}
+
+/// HTTP listener settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// Show the internal cause of errors (database, serialization...) to every client, not only admins
+    pub dev_mode: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 3001,
+            dev_mode: false,
+        }
+    }
+}
+
+/// Database connection pool settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    pub url: String,
+    pub max_connections: u32,
+    pub connect_timeout_secs: u64,
+    /// Insert default content on startup where missing
+    pub seed: bool,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        let defaults = DatabaseConfig::default();
+        Self {
+            url: defaults.database_url,
+            max_connections: defaults.max_connections,
+            connect_timeout_secs: defaults.connection_timeout.as_secs(),
+            seed: false,
+        }
+    }
+}
+
+impl From<&DatabaseSettings> for DatabaseConfig {
+    fn from(settings: &DatabaseSettings) -> Self {
+        Self {
+            database_url: settings.url.clone(),
+            max_connections: settings.max_connections,
+            connection_timeout: Duration::from_secs(settings.connect_timeout_secs),
+        }
+    }
+}
+
+/// Browser origins allowed to call the API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        let origins = ["localhost", "127.0.0.1"]
+            .iter()
+            .flat_map(|host| {
+                [3000, 5173, 5174, 5175, 5176, 5177]
+                    .iter()
+                    .map(move |port| format!("http://{}:{}", host, port))
+            })
+            .collect();
+
+        Self { allowed_origins: origins }
+    }
+}
+
+/// Session token signing and first admin bootstrap
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthSettings {
+    /// Token signing secret; a random one is generated when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub token_ttl_hours: i64,
+    /// First admin account, created on startup if no admin exists yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_password: Option<String>,
+}
+
+impl Default for AuthSettings {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            token_ttl_hours: 12,
+            admin_username: None,
+            admin_password: None,
+        }
+    }
+}
+
+impl fmt::Debug for AuthSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthSettings")
+            .field("secret", &self.secret.as_ref().map(|_| REDACTED))
+            .field("token_ttl_hours", &self.token_ttl_hours)
+            .field("admin_username", &self.admin_username)
+            .field("admin_password", &self.admin_password.as_ref().map(|_| REDACTED))
+            .finish()
+    }
+}
+
+/// Request rate limiting: a token bucket per client and route group
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Sustained rate of requests outside the groups below (writes, admin routes...)
+    pub requests_per_minute: u32,
+    /// Requests allowed at once before the rate applies
+    pub burst: u32,
+    /// Public reads (`GET` and `HEAD`)
+    pub reads: RateLimitRule,
+    /// Contact form submissions (`POST /api/contact`)
+    pub contact: RateLimitRule,
+    /// Admin logins (`POST /api/auth/login`)
+    pub login: RateLimitRule,
+    /// Proxies whose `X-Forwarded-For` header is believed, as IP addresses or CIDR ranges
+    pub trusted_proxies: Vec<String>,
+    /// What a client is: `ip`, or `fingerprint` to also give each browser behind an address a
+    /// bucket of half the address's limit, checked on top of it
+    pub key: String,
+    /// Where buckets are kept: `memory`, or `sqlite` so limits survive restarts
+    pub store: String,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            requests_per_minute: 120,
+            burst: 30,
+            reads: RateLimitRule {
+                requests_per_minute: 300,
+                burst: 60,
+            },
+            contact: RateLimitRule {
+                requests_per_minute: 1,
+                burst: 3,
+            },
+            login: RateLimitRule {
+                requests_per_minute: 5,
+                burst: 5,
+            },
+            trusted_proxies: Vec::new(),
+            key: "ip".to_string(),
+            store: "memory".to_string(),
+        }
+    }
+}
+
+impl RateLimitConfig {
+    pub const KEYS: &'static [&'static str] = &["ip", "fingerprint"];
+    pub const STORES: &'static [&'static str] = &["memory", "sqlite"];
+
+    /// Limit applied to requests outside the named groups
+    pub fn default_rule(&self) -> RateLimitRule {
+        RateLimitRule {
+            requests_per_minute: self.requests_per_minute,
+            burst: self.burst,
+        }
+    }
+}
+
+/// Token bucket settings of a route group
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitRule {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+}
+
+/// Uploaded media storage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UploadConfig {
+    /// Root of the content-addressed media store
+    pub dir: PathBuf,
+    /// Largest file accepted by `POST /api/media`
+    pub max_file_size_bytes: u64,
+    /// Widths, in pixels, of the derivatives rendered for each image
+    pub variant_widths: Vec<u32>,
+    /// Formats rendered at each width: `jpeg`, `png`, `webp` or `avif`
+    pub variant_formats: Vec<String>,
+    /// Render derivatives in the background after each upload; they are otherwise only
+    /// rendered when first requested
+    pub generate_variants: bool,
+    pub variant_poll_interval_secs: u64,
+}
+
+impl UploadConfig {
+    /// Widest derivative that can be configured
+    pub const MAX_VARIANT_WIDTH: u32 = 4096;
+}
+
+impl Default for UploadConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("data/uploads"),
+            max_file_size_bytes: 10 * 1024 * 1024,
+            variant_widths: vec![320, 640, 1280],
+            variant_formats: vec!["webp".to_string(), "avif".to_string()],
+            generate_variants: true,
+            variant_poll_interval_secs: 30,
+        }
+    }
+}
+
+/// Notifications sent when a contact message arrives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    pub enabled: bool,
+    /// Channel told about new messages: `smtp`, `webhook`, `file` or `stdout`
+    pub owner_channel: String,
+    /// Channel told about likely spam; spam is not reported when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spam_channel: Option<String>,
+    /// Channel used to acknowledge messages to their senders; no auto-reply when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_reply_channel: Option<String>,
+    /// Recipient of owner and spam notifications sent by email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner_email: Option<String>,
+    pub from_email: String,
+    /// Attempts before a notification is given up
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each failed attempt
+    pub retry_delay_secs: u64,
+    /// How often the queue is checked for due notifications
+    pub poll_interval_secs: u64,
+    pub smtp: SmtpConfig,
+    pub webhook: WebhookConfig,
+    pub file: FileSinkConfig,
+    pub templates: NotificationTemplates,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            owner_channel: "stdout".to_string(),
+            spam_channel: None,
+            auto_reply_channel: None,
+            owner_email: None,
+            from_email: "portfolio@localhost".to_string(),
+            max_attempts: 5,
+            retry_delay_secs: 30,
+            poll_interval_secs: 5,
+            smtp: SmtpConfig::default(),
+            webhook: WebhookConfig::default(),
+            file: FileSinkConfig::default(),
+            templates: NotificationTemplates::default(),
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Channels notifications can be sent through
+    pub const CHANNELS: &'static [&'static str] = &["smtp", "webhook", "file", "stdout"];
+
+    /// Every channel in use, without duplicates
+    pub fn channels(&self) -> Vec<&str> {
+        let mut channels = vec![self.owner_channel.as_str()];
+        for channel in [&self.spam_channel, &self.auto_reply_channel].into_iter().flatten() {
+            if !channels.contains(&channel.as_str()) {
+                channels.push(channel);
+            }
+        }
+        channels
+    }
+}
+
+/// Outgoing mail server
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    /// `none`, `starttls` or `tls`
+    pub tls: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub timeout_secs: u64,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 25,
+            tls: "none".to_string(),
+            username: None,
+            password: None,
+            timeout_secs: 10,
+        }
+    }
+}
+
+impl fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| REDACTED))
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+/// Endpoint receiving notifications as JSON
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhookConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub timeout_secs: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// File notifications are appended to, one JSON object per line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileSinkConfig {
+    pub path: PathBuf,
+}
+
+impl Default for FileSinkConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("data/notifications.log"),
+        }
+    }
+}
+
+/// Notification texts. `{{name}}`, `{{email}}`, `{{subject}}`, `{{message}}`, `{{date}}`
+/// and `{{id}}` are replaced with the contact message's values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationTemplates {
+    pub owner_subject: String,
+    pub owner_body: String,
+    pub spam_subject: String,
+    pub auto_reply_subject: String,
+    pub auto_reply_body: String,
+}
+
+impl Default for NotificationTemplates {
+    fn default() -> Self {
+        Self {
+            owner_subject: "New contact message: {{subject}}".to_string(),
+            owner_body: "{{name}} <{{email}}> wrote on {{date}}:\n\n{{message}}\n\nMessage #{{id}}".to_string(),
+            spam_subject: "[Likely spam] {{subject}}".to_string(),
+            auto_reply_subject: "Re: {{subject}}".to_string(),
+            auto_reply_body: "Hello {{name}},\n\nThank you for your message. I will get back to you as soon as possible.\n\n> {{message}}".to_string(),
+        }
+    }
+}
+
+/// Spam scoring of contact form submissions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpamConfig {
+    /// Score from which a submission is stored in quarantine
+    pub quarantine_threshold: f64,
+    /// Score from which a submission is refused
+    pub reject_threshold: f64,
+    /// Case-insensitive words or phrases, each adding `keyword_score`
+    pub keywords: Vec<String>,
+    /// Regular expressions matched against the subject and message, each adding `keyword_score`
+    pub patterns: Vec<String>,
+    pub keyword_score: f64,
+    /// Links allowed before each further one adds a point
+    pub max_links: u32,
+    /// Submissions sent sooner than this after the form token was issued are assumed automated
+    pub min_submit_secs: u64,
+    /// Form tokens older than this are treated as missing
+    pub form_token_ttl_secs: u64,
+    /// Disposable email domains, in addition to the built-in list
+    pub disposable_domains: Vec<String>,
+    /// How far back identical messages count as duplicates
+    pub duplicate_window_hours: u32,
+    /// Messages of each label the Bayes model needs before it is consulted
+    pub bayes_min_messages: u32,
+    /// Score given by the Bayes model to a certain spam, and taken off for a certain ham
+    pub bayes_score: f64,
+}
+
+impl Default for SpamConfig {
+    fn default() -> Self {
+        Self {
+            quarantine_threshold: 5.0,
+            reject_threshold: 12.0,
+            keywords: [
+                "viagra", "casino", "lottery", "winner", "congratulations",
+                "click here", "free money", "urgent", "act now", "limited time",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            patterns: Vec::new(),
+            keyword_score: 2.5,
+            max_links: 2,
+            min_submit_secs: 3,
+            form_token_ttl_secs: 24 * 60 * 60,
+            disposable_domains: Vec::new(),
+            duplicate_window_hours: 24,
+            bayes_min_messages: 10,
+            bayes_score: 5.0,
+        }
+    }
+}
+
+/// Proof-of-work challenges the contact form must solve before submitting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChallengeConfig {
+    /// Require a solved challenge with every submission
+    pub enabled: bool,
+    /// Leading zero bits the solution's hash needs in normal times
+    pub difficulty: u8,
+    /// Ceiling the difficulty is raised to under load
+    pub max_difficulty: u8,
+    /// Time allowed to solve a challenge and submit the form
+    pub ttl_secs: u64,
+    /// How far back challenge requests and spam are counted to detect a surge
+    pub surge_window_secs: u64,
+    /// Challenges issued within the window before the difficulty goes up a bit,
+    /// and another bit each time the count doubles
+    pub surge_requests: u32,
+    /// Spam submissions within the window before the difficulty goes up
+    pub surge_spam: u32,
+}
+
+impl Default for ChallengeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            difficulty: 16,
+            max_difficulty: 22,
+            ttl_secs: 10 * 60,
+            surge_window_secs: 10 * 60,
+            surge_requests: 60,
+            surge_spam: 5,
+        }
+    }
+}
+
+impl ChallengeConfig {
+    /// Hardest puzzle a browser can be asked to solve in reasonable time
+    pub const MAX_BITS: u8 = 32;
+}
+
+/// Public addresses, used wherever the API links back to the site or to itself
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteConfig {
+    /// Frontend, where projects and posts are read
+    pub url: String,
+    /// The API as reached from outside, for links to its own resources (e.g. feeds)
+    pub api_url: String,
+    /// Most URLs in one sitemap file; beyond, `/sitemap.xml` becomes an index of several files
+    pub sitemap_max_urls: usize,
+    pub pages: SitePages,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:5173".to_string(),
+            api_url: "http://localhost:3001".to_string(),
+            sitemap_max_urls: 10_000,
+            pages: SitePages::default(),
+        }
+    }
+}
+
+impl SiteConfig {
+    /// Most URLs a sitemap file may hold
+    pub const MAX_SITEMAP_URLS: usize = 50_000;
+
+    /// Absolute address of a frontend page
+    pub fn page_url(&self, path: &str) -> String {
+        format!("{}{}", self.url.trim_end_matches('/'), path)
+    }
+
+    /// Absolute address of an API resource
+    pub fn api_url(&self, path: &str) -> String {
+        format!("{}{}", self.api_url.trim_end_matches('/'), path)
+    }
+
+    /// Absolute address of a project's page
+    pub fn project_url(&self, id: i32) -> String {
+        self.page_url(&self.pages.project.replace("{id}", &id.to_string()))
+    }
+
+    /// Absolute address of a post's page
+    pub fn post_url(&self, slug: &str) -> String {
+        self.page_url(&self.pages.post.replace("{slug}", slug))
+    }
+}
+
+/// Paths of the frontend routes, relative to `site.url`. An empty path leaves the page out of
+/// the sitemap.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SitePages {
+    pub home: String,
+    /// Profile and skills
+    pub about: String,
+    pub projects: String,
+    /// A project, `{id}` standing for its ID
+    pub project: String,
+    pub contact: String,
+    pub blog: String,
+    /// A post, `{slug}` standing for its slug
+    pub post: String,
+}
+
+impl Default for SitePages {
+    fn default() -> Self {
+        Self {
+            home: "/".to_string(),
+            about: "/about".to_string(),
+            projects: "/projects".to_string(),
+            project: "/projects/{id}".to_string(),
+            contact: "/contact".to_string(),
+            blog: "/blog".to_string(),
+            post: "/blog/{slug}".to_string(),
+        }
+    }
+}
+
+/// Optional parts of the API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureToggles {
+    /// Accept public submissions on `POST /api/contact`
+    pub contact_form: bool,
+    /// Expose the `/api/keys` management endpoints
+    pub api_keys: bool,
+    /// Serve the OpenAPI document at `/api/openapi.json` and its browser at `/api/docs/`
+    pub api_docs: bool,
+}
+
+impl Default for FeatureToggles {
+    fn default() -> Self {
+        Self {
+            contact_form: true,
+            api_keys: true,
+            api_docs: true,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Load defaults, then the config file, then environment overrides, and validate.
+    ///
+    /// Without an explicit path, `PORTFOLIO_CONFIG` is used, falling back to
+    /// `portfolio.toml` in the working directory if it exists.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let path = path
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(CONFIG_FILE_ENV).map(PathBuf::from))
+            .or_else(|| Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|p| p.exists()));
+
+        let file = match &path {
+            Some(path) => Some(std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+                path: path.clone(),
+                source,
+            })?),
+            None => None,
+        };
+
+        Self::from_sources(
+            file.as_deref().zip(path.as_deref()),
+            std::env::vars(),
+        )
+    }
+
+    /// Build a configuration from TOML file content and environment variables
+    pub fn from_sources(
+        file: Option<(&str, &Path)>,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let mut value = Value::try_from(AppConfig::default())
+            .map_err(|e| ConfigError::Parse(e.to_string()))?;
+
+        if let Some((content, path)) = file {
+            let overrides: Value = toml::from_str(content).map_err(|e| ConfigError::File {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+            merge(&mut value, overrides);
+        }
+
+        let env: Vec<(String, String)> = env.into_iter().collect();
+
+        for (var, key) in LEGACY_ENV_VARS {
+            if let Some((_, raw)) = env.iter().find(|(name, _)| name == var) {
+                set_from_env(&mut value, var, key, raw)?;
+            }
+        }
+
+        let mut prefixed: Vec<&(String, String)> = env
+            .iter()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX) && name != CONFIG_FILE_ENV)
+            .collect();
+        prefixed.sort();
+        for (var, raw) in prefixed {
+            let key = var[ENV_PREFIX.len()..].to_lowercase().replace("__", ".");
+            set_from_env(&mut value, var, &key, raw)?;
+        }
+
+        let config: AppConfig = value
+            .try_into()
+            .map_err(|e: toml::de::Error| ConfigError::Parse(e.message().to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check every setting, reporting all problems at once
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if self.server.host.trim().is_empty() {
+            problems.push("server.host must not be empty".to_string());
+        } else if self.server.host.parse::<IpAddr>().is_err() && !is_hostname(&self.server.host) {
+            problems.push(format!("server.host '{}' is not an IP address or hostname", self.server.host));
+        }
+        if self.server.port == 0 {
+            problems.push("server.port must be between 1 and 65535".to_string());
+        }
+
+        if !self.database.url.starts_with("sqlite:") {
+            problems.push(format!("database.url '{}' must be a sqlite: URL", self.database.url));
+        }
+        if self.database.max_connections == 0 {
+            problems.push("database.max_connections must be at least 1".to_string());
+        }
+        if self.database.connect_timeout_secs == 0 {
+            problems.push("database.connect_timeout_secs must be at least 1".to_string());
+        }
+
+        for origin in &self.cors.allowed_origins {
+            if !is_origin(origin) {
+                problems.push(format!(
+                    "cors.allowed_origins entry '{}' must look like http(s)://host[:port] with no path",
+                    origin
+                ));
+            }
+        }
+
+        self.validate_site(&mut problems);
+
+        if let Some(secret) = &self.auth.secret {
+            if secret.len() < MIN_SECRET_LEN {
+                problems.push(format!("auth.secret must be at least {} bytes long", MIN_SECRET_LEN));
+            }
+        }
+        if self.auth.token_ttl_hours < 1 {
+            problems.push("auth.token_ttl_hours must be at least 1".to_string());
+        }
+        if self.auth.admin_username.is_some() != self.auth.admin_password.is_some() {
+            problems.push("auth.admin_username and auth.admin_password must be set together".to_string());
+        }
+
+        if self.rate_limit.enabled {
+            self.validate_rate_limit(&mut problems);
+        }
+
+        if self.uploads.dir.as_os_str().is_empty() {
+            problems.push("uploads.dir must not be empty".to_string());
+        }
+        if self.uploads.max_file_size_bytes == 0 {
+            problems.push("uploads.max_file_size_bytes must be at least 1".to_string());
+        }
+        self.validate_variants(&mut problems);
+
+        if self.notifications.enabled {
+            self.validate_notifications(&mut problems);
+        }
+
+        self.validate_spam(&mut problems);
+
+        if self.challenge.enabled {
+            self.validate_challenge(&mut problems);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+
+    fn validate_site(&self, problems: &mut Vec<String>) {
+        let site = &self.site;
+
+        for (key, url) in [("site.url", &site.url), ("site.api_url", &site.api_url)] {
+            if !is_base_url(url) {
+                problems.push(format!("{} '{}' must look like http(s)://host[:port][/path]", key, url));
+            }
+        }
+        if site.sitemap_max_urls == 0 || site.sitemap_max_urls > SiteConfig::MAX_SITEMAP_URLS {
+            problems.push(format!(
+                "site.sitemap_max_urls must be between 1 and {}",
+                SiteConfig::MAX_SITEMAP_URLS
+            ));
+        }
+
+        let pages = &site.pages;
+        for (key, path, placeholder) in [
+            ("home", &pages.home, None),
+            ("about", &pages.about, None),
+            ("projects", &pages.projects, None),
+            ("project", &pages.project, Some("{id}")),
+            ("contact", &pages.contact, None),
+            ("blog", &pages.blog, None),
+            ("post", &pages.post, Some("{slug}")),
+        ] {
+            if path.is_empty() {
+                continue;
+            }
+            if !path.starts_with('/') || path.contains(char::is_whitespace) {
+                problems.push(format!("site.pages.{} '{}' must be a path starting with /", key, path));
+            }
+            if let Some(placeholder) = placeholder {
+                if !path.contains(placeholder) {
+                    problems.push(format!("site.pages.{} '{}' must contain {}", key, path, placeholder));
+                }
+            }
+        }
+    }
+
+    fn validate_rate_limit(&self, problems: &mut Vec<String>) {
+        let rate_limit = &self.rate_limit;
+
+        let rules = [
+            ("rate_limit", rate_limit.default_rule()),
+            ("rate_limit.reads", rate_limit.reads),
+            ("rate_limit.contact", rate_limit.contact),
+            ("rate_limit.login", rate_limit.login),
+        ];
+        for (key, rule) in rules {
+            if rule.requests_per_minute == 0 {
+                problems.push(format!("{}.requests_per_minute must be at least 1", key));
+            }
+            if rule.burst == 0 {
+                problems.push(format!("{}.burst must be at least 1", key));
+            }
+        }
+
+        for proxy in &rate_limit.trusted_proxies {
+            if proxy.parse::<ipnet::IpNet>().is_err() && proxy.parse::<IpAddr>().is_err() {
+                problems.push(format!(
+                    "rate_limit.trusted_proxies entry '{}' is not an IP address or CIDR range",
+                    proxy
+                ));
+            }
+        }
+        if !RateLimitConfig::KEYS.contains(&rate_limit.key.as_str()) {
+            problems.push(format!(
+                "rate_limit.key '{}' must be one of: {}",
+                rate_limit.key,
+                RateLimitConfig::KEYS.join(", ")
+            ));
+        }
+        if !RateLimitConfig::STORES.contains(&rate_limit.store.as_str()) {
+            problems.push(format!(
+                "rate_limit.store '{}' must be one of: {}",
+                rate_limit.store,
+                RateLimitConfig::STORES.join(", ")
+            ));
+        }
+    }
+
+    fn validate_spam(&self, problems: &mut Vec<String>) {
+        let spam = &self.spam;
+
+        if spam.quarantine_threshold.is_nan() || spam.quarantine_threshold <= 0.0 {
+            problems.push("spam.quarantine_threshold must be greater than 0".to_string());
+        }
+        if spam.reject_threshold.is_nan() || spam.reject_threshold <= spam.quarantine_threshold {
+            problems.push("spam.reject_threshold must be greater than spam.quarantine_threshold".to_string());
+        }
+        if spam.keywords.iter().any(|keyword| keyword.trim().is_empty()) {
+            problems.push("spam.keywords must not contain empty entries".to_string());
+        }
+        for pattern in &spam.patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                problems.push(format!("spam.patterns entry '{}' is not a valid regular expression: {}", pattern, e));
+            }
+        }
+        for domain in &spam.disposable_domains {
+            if !is_hostname(domain) {
+                problems.push(format!("spam.disposable_domains entry '{}' is not a domain name", domain));
+            }
+        }
+        if spam.form_token_ttl_secs <= spam.min_submit_secs {
+            problems.push("spam.form_token_ttl_secs must be greater than spam.min_submit_secs".to_string());
+        }
+        if spam.bayes_min_messages == 0 {
+            problems.push("spam.bayes_min_messages must be at least 1".to_string());
+        }
+    }
+
+    fn validate_variants(&self, problems: &mut Vec<String>) {
+        let uploads = &self.uploads;
+
+        for width in &uploads.variant_widths {
+            if *width == 0 || *width > UploadConfig::MAX_VARIANT_WIDTH {
+                problems.push(format!(
+                    "uploads.variant_widths entry {} must be between 1 and {}",
+                    width,
+                    UploadConfig::MAX_VARIANT_WIDTH
+                ));
+            }
+        }
+        for format in &uploads.variant_formats {
+            if VariantFormat::from_str(format).is_none() {
+                problems.push(format!(
+                    "uploads.variant_formats entry '{}' must be one of: {}",
+                    format,
+                    VariantFormat::all().join(", ")
+                ));
+            }
+        }
+        if uploads.generate_variants && uploads.variant_poll_interval_secs == 0 {
+            problems.push("uploads.variant_poll_interval_secs must be at least 1".to_string());
+        }
+    }
+
+    fn validate_challenge(&self, problems: &mut Vec<String>) {
+        let challenge = &self.challenge;
+
+        if challenge.difficulty == 0 {
+            problems.push("challenge.difficulty must be at least 1".to_string());
+        }
+        if challenge.max_difficulty < challenge.difficulty || challenge.max_difficulty > ChallengeConfig::MAX_BITS {
+            problems.push(format!(
+                "challenge.max_difficulty must be between challenge.difficulty and {}",
+                ChallengeConfig::MAX_BITS
+            ));
+        }
+        if challenge.ttl_secs == 0 {
+            problems.push("challenge.ttl_secs must be at least 1".to_string());
+        }
+        if challenge.surge_window_secs == 0 {
+            problems.push("challenge.surge_window_secs must be at least 1".to_string());
+        }
+        if challenge.surge_requests == 0 || challenge.surge_spam == 0 {
+            problems.push("challenge.surge_requests and challenge.surge_spam must be at least 1".to_string());
+        }
+    }
+
+    fn validate_notifications(&self, problems: &mut Vec<String>) {
+        let notifications = &self.notifications;
+
+        let channels = [
+            ("owner_channel", Some(&notifications.owner_channel)),
+            ("spam_channel", notifications.spam_channel.as_ref()),
+            ("auto_reply_channel", notifications.auto_reply_channel.as_ref()),
+        ];
+        for (key, channel) in channels {
+            if let Some(channel) = channel {
+                if !NotificationConfig::CHANNELS.contains(&channel.as_str()) {
+                    problems.push(format!(
+                        "notifications.{} '{}' must be one of: {}",
+                        key,
+                        channel,
+                        NotificationConfig::CHANNELS.join(", ")
+                    ));
+                }
+            }
+        }
+
+        let channels = notifications.channels();
+        let by_email = |channel: &Option<String>| channel.as_deref() == Some("smtp");
+        if (notifications.owner_channel == "smtp" || by_email(&notifications.spam_channel))
+            && notifications.owner_email.is_none()
+        {
+            problems.push("notifications.owner_email is required to notify by smtp".to_string());
+        }
+        for (key, address) in [("owner_email", notifications.owner_email.as_ref()), ("from_email", Some(&notifications.from_email))] {
+            if let Some(address) = address {
+                if address.parse::<lettre::Address>().is_err() {
+                    problems.push(format!("notifications.{} '{}' is not an email address", key, address));
+                }
+            }
+        }
+        if notifications.max_attempts == 0 {
+            problems.push("notifications.max_attempts must be at least 1".to_string());
+        }
+        if notifications.poll_interval_secs == 0 {
+            problems.push("notifications.poll_interval_secs must be at least 1".to_string());
+        }
+
+        if channels.contains(&"smtp") {
+            if notifications.smtp.host.trim().is_empty() {
+                problems.push("notifications.smtp.host must not be empty".to_string());
+            }
+            if !["none", "starttls", "tls"].contains(&notifications.smtp.tls.as_str()) {
+                problems.push(format!("notifications.smtp.tls '{}' must be none, starttls or tls", notifications.smtp.tls));
+            }
+            if notifications.smtp.username.is_some() != notifications.smtp.password.is_some() {
+                problems.push("notifications.smtp.username and notifications.smtp.password must be set together".to_string());
+            }
+        }
+        if channels.contains(&"webhook") {
+            match &notifications.webhook.url {
+                Some(url) if url.starts_with("http://") || url.starts_with("https://") => {}
+                Some(url) => problems.push(format!("notifications.webhook.url '{}' must be an http(s) URL", url)),
+                None => problems.push("notifications.webhook.url is required by the webhook channel".to_string()),
+            }
+        }
+        if channels.contains(&"file") && notifications.file.path.as_os_str().is_empty() {
+            problems.push("notifications.file.path must not be empty".to_string());
+        }
+
+        let templates = &notifications.templates;
+        for (key, template) in [
+            ("owner_subject", &templates.owner_subject),
+            ("owner_body", &templates.owner_body),
+            ("spam_subject", &templates.spam_subject),
+            ("auto_reply_subject", &templates.auto_reply_subject),
+            ("auto_reply_body", &templates.auto_reply_body),
+        ] {
+            if let Err(placeholder) = crate::notifier::template::check(template) {
+                problems.push(format!("notifications.templates.{} uses unknown placeholder '{}'", key, placeholder));
+            }
+        }
+    }
+
+    /// Address the server listens on
+    pub fn bind_address(&self) -> String {
+        match self.server.host.parse::<IpAddr>() {
+            Ok(IpAddr::V6(ip)) => format!("[{}]:{}", ip, self.server.port),
+            _ => format!("{}:{}", self.server.host, self.server.port),
+        }
+    }
+
+    /// A copy safe to display, with secrets replaced by a placeholder
+    pub fn redacted(&self) -> Self {
+        let mut config = self.clone();
+        for secret in [
+            &mut config.auth.secret,
+            &mut config.auth.admin_password,
+            &mut config.notifications.smtp.password,
+        ] {
+            if secret.is_some() {
+                *secret = Some(REDACTED.to_string());
+            }
+        }
+        config
+    }
+
+    /// Effective configuration as TOML, with secrets redacted
+    pub fn to_redacted_toml(&self) -> String {
+        toml::to_string_pretty(&self.redacted()).unwrap_or_default()
+    }
+}
+
+/// Recursively merge `overrides` into `base`
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Table(base), Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
+/// Set a dotted key from an environment variable, parsed after the type of the current value
+fn set_from_env(root: &mut Value, var: &str, key: &str, raw: &str) -> Result<(), ConfigError> {
+    let env_error = |message: String| ConfigError::Env {
+        var: var.to_string(),
+        message,
+    };
+
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let field = parts.pop().unwrap_or_default();
+
+    let mut table = root;
+    for part in parts {
+        table = table
+            .get_mut(part)
+            .filter(|value| value.is_table())
+            .ok_or_else(|| env_error(format!("unknown setting '{}'", key)))?;
+    }
+    let Value::Table(table) = table else {
+        return Err(env_error(format!("unknown setting '{}'", key)));
+    };
+
+    let value = match table.get(field) {
+        Some(Value::Integer(_)) => Value::Integer(
+            raw.trim()
+                .parse()
+                .map_err(|_| env_error(format!("'{}' is not an integer", raw)))?,
+        ),
+        Some(Value::Float(_)) => Value::Float(
+            raw.trim()
+                .parse()
+                .map_err(|_| env_error(format!("'{}' is not a number", raw)))?,
+        ),
+        Some(Value::Boolean(_)) => Value::Boolean(match raw.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => true,
+            "false" | "0" | "no" | "off" => false,
+            _ => return Err(env_error(format!("'{}' is not a boolean", raw))),
+        }),
+        // Lists of numbers stay numbers; anything else is a list of strings
+        Some(Value::Array(current)) if current.first().is_some_and(Value::is_integer) => Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| {
+                    item.parse()
+                        .map(Value::Integer)
+                        .map_err(|_| env_error(format!("'{}' is not an integer", item)))
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        Some(Value::Array(_)) => Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| Value::String(item.to_string()))
+                .collect(),
+        ),
+        Some(Value::Table(_)) => return Err(env_error(format!("'{}' is a section, not a setting", key))),
+        // Strings and optional values that are currently unset
+        _ => Value::String(raw.to_string()),
+    };
+
+    table.insert(field.to_string(), value);
+    Ok(())
+}
+
+fn is_hostname(host: &str) -> bool {
+    host.split('.').all(|label| {
+        !label.is_empty()
+            && label.len() <= 63
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    })
+}
+
+/// An origin optionally followed by a path
+fn is_base_url(url: &str) -> bool {
+    let Some(scheme_len) = ["http://", "https://"]
+        .iter()
+        .find(|scheme| url.starts_with(*scheme))
+        .map(|scheme| scheme.len())
+    else {
+        return false;
+    };
+
+    let end = url[scheme_len..].find('/').map_or(url.len(), |i| scheme_len + i);
+    is_origin(&url[..end]) && !url[end..].contains(['?', '#'])
+}
+
+fn is_origin(origin: &str) -> bool {
+    let Some(authority) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+
+    if authority.is_empty() || authority.contains(['/', '?', '#', '@']) || HeaderValue::from_str(origin).is_err() {
+        return false;
+    }
+
+    let host = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => {
+            if port.parse::<u16>().is_err() {
+                return false;
+            }
+            host
+        }
+        _ => authority,
+    };
+
+    is_hostname(host)
+        || host
+            .strip_prefix('[')
+            .and_then(|ip| ip.strip_suffix(']'))
+            .is_some_and(|ip| ip.parse::<IpAddr>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn load(file: Option<&str>, vars: &[(&str, &str)]) -> Result<AppConfig, ConfigError> {
+        AppConfig::from_sources(file.map(|content| (content, Path::new("portfolio.toml"))), env(vars))
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let config = load(None, &[]).unwrap();
+        assert_eq!(config, AppConfig::default());
+        assert_eq!(config.bind_address(), "0.0.0.0:3001");
+        assert_eq!(config.cors.allowed_origins.len(), 12);
+        assert!(config.features.contact_form);
+    }
+
+    #[test]
+    fn test_file_overrides_defaults() {
+        let file = r#"
+            [server]
+            port = 8080
+
+            [cors]
+            allowed_origins = ["https://example.com"]
+
+            [features]
+            contact_form = false
+        "#;
+
+        let config = load(Some(file), &[]).unwrap();
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.cors.allowed_origins, vec!["https://example.com"]);
+        assert!(!config.features.contact_form);
+        assert!(config.features.api_keys);
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let file = "[server]\nport = 8080\n";
+        let config = load(
+            Some(file),
+            &[
+                ("PORTFOLIO_SERVER__PORT", "9090"),
+                ("PORTFOLIO_DATABASE__SEED", "true"),
+                ("PORTFOLIO_CORS__ALLOWED_ORIGINS", "https://a.example, https://b.example"),
+                ("PORTFOLIO_AUTH__SECRET", "an-environment-secret-that-is-long-enough"),
+                ("PORTFOLIO_CONFIG", "ignored.toml"),
+                ("UNRELATED", "value"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.server.port, 9090);
+        assert!(config.database.seed);
+        assert_eq!(config.cors.allowed_origins, vec!["https://a.example", "https://b.example"]);
+        assert_eq!(config.auth.secret.as_deref(), Some("an-environment-secret-that-is-long-enough"));
+    }
+
+    #[test]
+    fn test_legacy_env_vars() {
+        let config = load(
+            None,
+            &[
+                ("DATABASE_URL", "sqlite:./data/other.db"),
+                ("PORT", "4000"),
+                ("SEED_DATABASE", "true"),
+                ("PORTFOLIO_SERVER__PORT", "5000"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.database.url, "sqlite:./data/other.db");
+        assert!(config.database.seed);
+        // Prefixed variables take precedence
+        assert_eq!(config.server.port, 5000);
+    }
+
+    #[test]
+    fn test_env_errors_name_the_variable() {
+        let result = load(None, &[("PORTFOLIO_SERVER__PORT", "eighty")]);
+        assert!(matches!(result, Err(ConfigError::Env { var, .. }) if var == "PORTFOLIO_SERVER__PORT"));
+
+        let result = load(None, &[("PORTFOLIO_NOPE__VALUE", "1")]);
+        assert!(matches!(result, Err(ConfigError::Env { .. })));
+
+        let result = load(None, &[("PORTFOLIO_SERVER__NOPE", "1")]);
+        assert!(matches!(result, Err(ConfigError::Parse(message)) if message.contains("nope")));
+    }
+
+    #[test]
+    fn test_unknown_file_key_rejected() {
+        let result = load(Some("[server]\nprot = 80\n"), &[]);
+        assert!(matches!(result, Err(ConfigError::Parse(message)) if message.contains("prot")));
+
+        let result = load(Some("[server\n"), &[]);
+        assert!(matches!(result, Err(ConfigError::File { .. })));
+    }
+
+    #[test]
+    fn test_validation_reports_all_problems() {
+        let file = r#"
+            [server]
+            port = 0
+
+            [database]
+            url = "postgres://localhost/portfolio"
+
+            [cors]
+            allowed_origins = ["localhost:5173", "https://example.com/app", "https://ok.example:8443"]
+
+            [auth]
+            secret = "short"
+            admin_username = "admin"
+        "#;
+
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[]) else {
+            panic!("expected validation errors");
+        };
+
+        assert_eq!(problems.len(), 6, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("server.port")));
+        assert!(problems.iter().any(|p| p.contains("database.url")));
+        assert!(problems.iter().any(|p| p.contains("localhost:5173")));
+        assert!(problems.iter().any(|p| p.contains("/app")));
+        assert!(problems.iter().any(|p| p.contains("auth.secret")));
+        assert!(problems.iter().any(|p| p.contains("admin_password")));
+    }
+
+    #[test]
+    fn test_site_urls() {
+        let config = load(None, &[("PORTFOLIO_SITE__URL", "https://example.com/portfolio/")]).unwrap();
+        assert_eq!(config.site.page_url("/projects/1"), "https://example.com/portfolio/projects/1");
+        assert_eq!(config.site.api_url("/feeds/posts.rss"), "http://localhost:3001/feeds/posts.rss");
+
+        assert_eq!(config.site.project_url(7), "https://example.com/portfolio/projects/7");
+
+        let file = r#"
+            [site]
+            url = "example.com"
+            api_url = "https://api.example.com/?v=1"
+            sitemap_max_urls = 0
+
+            [site.pages]
+            project = "/work/{slug}"
+            blog = "blog"
+            post = ""
+        "#;
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[]) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(problems.len(), 5, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("site.pages.project")));
+    }
+
+    #[test]
+    fn test_redacted_output_hides_secrets() {
+        let config = load(
+            None,
+            &[
+                ("AUTH_SECRET", "a-very-secret-signing-key-of-enough-length"),
+                ("ADMIN_USERNAME", "admin"),
+                ("ADMIN_PASSWORD", "hunter2-but-longer"),
+            ],
+        )
+        .unwrap();
+
+        let printed = config.to_redacted_toml();
+        assert!(!printed.contains("a-very-secret-signing-key"));
+        assert!(!printed.contains("hunter2"));
+        assert!(printed.contains(REDACTED));
+        assert!(printed.contains("admin_username = \"admin\""));
+
+        // The redacted output is itself a loadable config file, apart from the placeholders
+        let reparsed: AppConfig = toml::from_str(&printed).unwrap();
+        assert_eq!(reparsed.server, config.server);
+
+        assert!(!format!("{:?}", config).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_notification_settings_validated_when_enabled() {
+        let file = r#"
+            [notifications]
+            owner_channel = "smtp"
+            spam_channel = "pager"
+            auto_reply_channel = "webhook"
+            from_email = "not-an-address"
+
+            [notifications.templates]
+            owner_body = "{{name}} called from {{phone}}"
+        "#;
+
+        // Nothing is checked while notifications are disabled
+        assert!(load(Some(file), &[]).is_ok());
+
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[("PORTFOLIO_NOTIFICATIONS__ENABLED", "true")]) else {
+            panic!("expected validation errors");
+        };
+
+        assert_eq!(problems.len(), 5, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("spam_channel 'pager'")));
+        assert!(problems.iter().any(|p| p.contains("owner_email is required")));
+        assert!(problems.iter().any(|p| p.contains("from_email")));
+        assert!(problems.iter().any(|p| p.contains("webhook.url is required")));
+        assert!(problems.iter().any(|p| p.contains("'phone'")));
+
+        let config = load(
+            Some(file),
+            &[
+                ("PORTFOLIO_NOTIFICATIONS__ENABLED", "true"),
+                ("PORTFOLIO_NOTIFICATIONS__SPAM_CHANNEL", "file"),
+                ("PORTFOLIO_NOTIFICATIONS__OWNER_EMAIL", "owner@example.com"),
+                ("PORTFOLIO_NOTIFICATIONS__FROM_EMAIL", "portfolio@example.com"),
+                ("PORTFOLIO_NOTIFICATIONS__WEBHOOK__URL", "https://hooks.example.com/contact"),
+                ("PORTFOLIO_NOTIFICATIONS__TEMPLATES__OWNER_BODY", "{{name}} wrote {{message}}"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.notifications.channels(), vec!["smtp", "file", "webhook"]);
+    }
+
+    #[test]
+    fn test_spam_settings_validated() {
+        let file = r#"
+            [spam]
+            quarantine_threshold = 6.0
+            reject_threshold = 4.0
+            patterns = ["(unclosed"]
+            disposable_domains = ["not a domain"]
+        "#;
+
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[]) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("spam.reject_threshold")));
+        assert!(problems.iter().any(|p| p.contains("'(unclosed'")));
+        assert!(problems.iter().any(|p| p.contains("'not a domain'")));
+
+        let config = load(
+            None,
+            &[
+                ("PORTFOLIO_SPAM__REJECT_THRESHOLD", "20.5"),
+                ("PORTFOLIO_SPAM__PATTERNS", r"bit\.ly/\w+, (?i)seo services"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.spam.reject_threshold, 20.5);
+        assert_eq!(config.spam.patterns.len(), 2);
+    }
+
+    #[test]
+    fn test_rate_limit_settings_validated() {
+        let file = r#"
+            [rate_limit]
+            burst = 0
+            login = { requests_per_minute = 0, burst = 5 }
+            trusted_proxies = ["10.0.0.0/8", "192.0.2.1", "proxy.local"]
+            key = "cookie"
+            store = "redis"
+        "#;
+
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[]) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(problems.len(), 5, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("rate_limit.burst")));
+        assert!(problems.iter().any(|p| p.contains("rate_limit.login.requests_per_minute")));
+        assert!(problems.iter().any(|p| p.contains("'proxy.local'")));
+        assert!(problems.iter().any(|p| p.contains("rate_limit.key")));
+        assert!(problems.iter().any(|p| p.contains("rate_limit.store")));
+
+        // Disabled limits are not checked
+        let file = format!("{}\nenabled = false", file.trim_end());
+        assert!(load(Some(&file), &[]).is_ok());
+
+        let config = load(None, &[("PORTFOLIO_RATE_LIMIT__CONTACT__BURST", "5")]).unwrap();
+        assert_eq!(config.rate_limit.contact.burst, 5);
+        assert_eq!(config.rate_limit.contact.requests_per_minute, 1);
+    }
+
+    #[test]
+    fn test_challenge_settings_validated_when_enabled() {
+        let file = r#"
+            [challenge]
+            difficulty = 24
+            max_difficulty = 40
+            surge_spam = 0
+        "#;
+        assert!(load(Some(file), &[]).is_ok());
+
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[("PORTFOLIO_CHALLENGE__ENABLED", "true")]) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("challenge.max_difficulty")));
+        assert!(problems.iter().any(|p| p.contains("challenge.surge_spam")));
+    }
+
+    #[test]
+    fn test_variant_settings() {
+        let config = load(None, &[("PORTFOLIO_UPLOADS__VARIANT_WIDTHS", "480, 960")]).unwrap();
+        assert_eq!(config.uploads.variant_widths, vec![480, 960]);
+        assert!(load(None, &[("PORTFOLIO_UPLOADS__VARIANT_WIDTHS", "480,wide")]).is_err());
+
+        let file = r#"
+            [uploads]
+            variant_widths = [0, 640, 10000]
+            variant_formats = ["webp", "tiff"]
+        "#;
+        let Err(ConfigError::Invalid(problems)) = load(Some(file), &[]) else {
+            panic!("expected validation errors");
+        };
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("'tiff'")));
+    }
+
+    #[test]
+    fn test_smtp_password_redacted() {
+        let config = load(None, &[("PORTFOLIO_NOTIFICATIONS__SMTP__PASSWORD", "smtp-hunter2")]).unwrap();
+        assert!(!config.to_redacted_toml().contains("smtp-hunter2"));
+        assert!(!format!("{:?}", config).contains("smtp-hunter2"));
+    }
+
+    #[test]
+    fn test_bind_address_ipv6() {
+        let config = load(None, &[("PORTFOLIO_SERVER__HOST", "::1")]).unwrap();
+        assert_eq!(config.bind_address(), "[::1]:3001");
+    }
+}
@@ -0,0 +1,75 @@
+use sqlx::SqlitePool;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Backup error types
+#[derive(Debug, thiserror::Error)]
+pub enum BackupError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Backup destination already exists: {0}")]
+    AlreadyExists(PathBuf),
+}
+
+/// Write a consistent snapshot of the database to a new file, returning its size in bytes.
+///
+/// Uses `VACUUM INTO`, so the copy is safe to take while the server is running.
+pub async fn backup_database(pool: &SqlitePool, destination: &Path) -> Result<u64, BackupError> {
+    if destination.exists() {
+        return Err(BackupError::AlreadyExists(destination.to_path_buf()));
+    }
+
+    if let Some(parent) = destination.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    info!("Backing up database to {}", destination.display());
+
+    // VACUUM does not accept bound parameters, so quote the path as a string literal
+    let path = destination.to_string_lossy().replace('\'', "''");
+    sqlx::query(&format!("VACUUM INTO '{}'", path))
+        .execute(pool)
+        .await?;
+
+    let size = std::fs::metadata(destination)?.len();
+    info!("Database backup completed ({} bytes)", size);
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::migrations::initialize_database;
+    use sqlx::sqlite::SqliteConnectOptions;
+
+    #[tokio::test]
+    async fn test_backup_database() {
+        let dir = tempfile::tempdir().unwrap();
+        // In-memory databases cannot be vacuumed into a file, so back up a real one
+        let source = SqliteConnectOptions::new()
+            .filename(dir.path().join("portfolio.db"))
+            .create_if_missing(true);
+        let pool = SqlitePool::connect_with(source).await.unwrap();
+        initialize_database(pool.clone()).await.unwrap();
+
+        let destination = dir.path().join("nested").join("backup.db");
+        let size = backup_database(&pool, &destination).await.unwrap();
+        assert!(size > 0);
+
+        // The backup is a usable database with the same schema
+        let backup = SqlitePool::connect_with(SqliteConnectOptions::new().filename(&destination))
+            .await
+            .unwrap();
+        let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM profile")
+            .fetch_one(&backup)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+
+        // Existing files are never overwritten
+        let result = backup_database(&pool, &destination).await;
+        assert!(matches!(result, Err(BackupError::AlreadyExists(_))));
+    }
+}
@@ -1,5 +1,5 @@
 use sqlx::SqlitePool;
-use tracing::{info, error};
+use tracing::info;
 use crate::database::{
     connection::{DatabaseConfig, create_pool, test_connection},
     migrations::initialize_database,
@@ -18,20 +18,28 @@ pub enum InitError {
     Seed(#[from] SeedError),
 }
 
+/// Connect to the database and check it is reachable, without touching the schema
+pub async fn connect_database(config: &DatabaseConfig) -> Result<SqlitePool, InitError> {
+    let pool = create_pool(config).await?;
+    test_connection(&pool).await?;
+    Ok(pool)
+}
+
+/// Connect to the database and apply pending migrations
+pub async fn open_database(config: &DatabaseConfig) -> Result<SqlitePool, InitError> {
+    let pool = connect_database(config).await?;
+    initialize_database(pool.clone()).await?;
+    Ok(pool)
+}
+
 /// Initialize the complete database setup
 pub async fn initialize_complete_database(config: Option<DatabaseConfig>) -> Result<SqlitePool, InitError> {
     let config = config.unwrap_or_default();
     
     info!("Initializing database with configuration: {:?}", config);
 
-    // Create connection pool
-    let pool = create_pool(&config).await?;
-    
-    // Test connection
-    test_connection(&pool).await?;
-    
-    // Run migrations
-    initialize_database(pool.clone()).await?;
+    // Connect and run migrations
+    let pool = open_database(&config).await?;
     
     // Seed initial data
     seed_database(&pool).await?;
@@ -84,7 +92,6 @@ mod tests {
                 title TEXT NOT NULL,
                 description TEXT NOT NULL,
                 long_description TEXT,
-                technologies TEXT NOT NULL,
                 github_url TEXT,
                 demo_url TEXT,
                 image_url TEXT,
@@ -94,6 +101,20 @@ mod tests {
                 updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
             );
 
+            CREATE TABLE IF NOT EXISTS technologies (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                slug TEXT NOT NULL UNIQUE,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS project_technologies (
+                project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+                technology_id INTEGER NOT NULL REFERENCES technologies(id) ON DELETE CASCADE,
+                position INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (project_id, technology_id)
+            );
+
             CREATE TABLE IF NOT EXISTS skills (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 name TEXT NOT NULL,
@@ -104,6 +125,14 @@ mod tests {
                 created_at DATETIME DEFAULT CURRENT_TIMESTAMP
             );
 
+            CREATE TABLE IF NOT EXISTS project_skills (
+                project_id INTEGER NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+                skill_id INTEGER NOT NULL REFERENCES skills(id) ON DELETE CASCADE,
+                source TEXT NOT NULL DEFAULT 'manual' CHECK (source IN ('manual', 'suggested')),
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (project_id, skill_id)
+            );
+
             CREATE TABLE IF NOT EXISTS contact_messages (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 name TEXT NOT NULL,
@@ -132,7 +161,7 @@ mod tests {
         .await
         .unwrap();
         
-        let expected_tables = vec!["contact_messages", "profile", "projects", "skills"];
+        let expected_tables = vec!["contact_messages", "profile", "project_skills", "project_technologies", "projects", "skills", "technologies"];
         assert_eq!(tables, expected_tables);
         
         // Verify data exists
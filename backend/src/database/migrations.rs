@@ -1,76 +1,286 @@
-use sqlx::SqlitePool;
-use std::fs;
-use std::path::Path;
-use tracing::{info, error, warn};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{SqliteConnection, SqlitePool};
+use tracing::{info, warn, error};
+
+use crate::database::{ProjectSkillRepository, TechnologyRepository};
 
 /// Migration error types
 #[derive(Debug, thiserror::Error)]
 pub enum MigrationError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
-    #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
-    #[error("Migration file not found: {0}")]
-    FileNotFound(String),
+    #[error("Migration {name} failed: {source}")]
+    Failed {
+        name: String,
+        #[source]
+        source: sqlx::Error,
+    },
+    #[error("Migration {0} was modified after being applied (checksum mismatch)")]
+    ChecksumMismatch(String),
+    #[error("Migration {0} is recorded as applied but is not known to this build")]
+    UnknownMigration(String),
+}
+
+/// A schema migration embedded in the binary, with its paired down script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+    /// Conversion run in Rust after the up script, in the same transaction
+    pub fixup: Option<Fixup>,
+}
+
+/// Data conversions SQL cannot express the way the application does
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fixup {
+    /// Recompute technology slugs with `Technology::slug_for`, merging technologies that end up
+    /// sharing one, then recompute suggested project skills
+    ReslugTechnologies,
+}
+
+impl Fixup {
+    async fn run(self, conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+        match self {
+            Fixup::ReslugTechnologies => {
+                let merged = TechnologyRepository::reslug_all(conn).await?;
+                if merged > 0 {
+                    info!("Merged {} technologies sharing a slug", merged);
+                }
+                let projects: Vec<i32> = sqlx::query_scalar("SELECT id FROM projects")
+                    .fetch_all(&mut *conn)
+                    .await?;
+                for project_id in projects {
+                    ProjectSkillRepository::suggest_for_project(conn, project_id).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Migration {
+    /// SHA-256 of the up script, ignoring line ending differences
+    pub fn checksum(&self) -> String {
+        let normalized = self.up.replace("\r\n", "\n");
+        format!("{:x}", Sha256::digest(normalized.as_bytes()))
+    }
+}
+
+/// Embed the `.up.sql` / `.down.sql` pair for a migration
+macro_rules! migration {
+    ($version:literal, $name:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            up: include_str!(concat!("../../migrations/", $name, ".up.sql")),
+            down: include_str!(concat!("../../migrations/", $name, ".down.sql")),
+            fixup: None,
+        }
+    };
+    ($version:literal, $name:literal, $fixup:expr) => {
+        Migration {
+            fixup: Some($fixup),
+            ..migration!($version, $name)
+        }
+    };
+}
+
+/// All migrations shipped with the application, in order
+pub static MIGRATIONS: &[Migration] = &[
+    migration!(1, "001_initial_schema"),
+    migration!(2, "002_admin_users"),
+    migration!(3, "003_api_keys"),
+    migration!(4, "004_technologies"),
+    migration!(5, "005_project_skills"),
+    migration!(6, "006_search_index"),
+    migration!(7, "007_notification_jobs"),
+    migration!(8, "008_message_moderation"),
+    migration!(9, "009_spam_scoring"),
+    migration!(10, "010_rate_limits"),
+    migration!(11, "011_contact_challenges"),
+    migration!(12, "012_media"),
+    migration!(13, "013_media_variants"),
+    migration!(14, "014_posts"),
+    migration!(15, "015_timeline"),
+    migration!(16, "016_resume_cache"),
+    migration!(17, "017_content_changes"),
+    migration!(18, "018_technology_slugs", Fixup::ReslugTechnologies),
+];
+
+/// State of a migration relative to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    /// Not applied yet
+    Pending,
+    /// Applied and unchanged since
+    Applied,
+    /// Applied, but the embedded script no longer matches the recorded checksum
+    Modified,
+    /// Recorded as applied but not known to this build
+    Missing,
+}
+
+impl MigrationState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MigrationState::Pending => "pending",
+            MigrationState::Applied => "applied",
+            MigrationState::Modified => "modified",
+            MigrationState::Missing => "missing",
+        }
+    }
+}
+
+/// Status report entry for a single migration
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<DateTime<Utc>>,
+    pub checksum: Option<String>,
+}
+
+/// A row of the `_migrations` tracking table
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct AppliedMigration {
+    name: String,
+    version: Option<i64>,
+    checksum: Option<String>,
+    applied_at: DateTime<Utc>,
 }
 
 /// Migration manager for handling database schema changes
 pub struct MigrationManager {
     pool: SqlitePool,
-    migrations_dir: String,
+    migrations: &'static [Migration],
 }
 
 impl MigrationManager {
-    pub fn new(pool: SqlitePool, migrations_dir: String) -> Self {
-        Self {
-            pool,
-            migrations_dir,
-        }
+    /// Manager for the migrations embedded in the binary
+    pub fn new(pool: SqlitePool) -> Self {
+        Self::with_migrations(pool, MIGRATIONS)
+    }
+
+    /// Manager for a custom set of migrations, ordered by version
+    pub fn with_migrations(pool: SqlitePool, migrations: &'static [Migration]) -> Self {
+        Self { pool, migrations }
     }
 
-    /// Run all pending migrations
-    pub async fn run_migrations(&self) -> Result<(), MigrationError> {
+    /// Run all pending migrations, returning the ones that were applied
+    pub async fn run_migrations(&self) -> Result<Vec<Migration>, MigrationError> {
         info!("Starting database migrations...");
 
-        // Create migrations table if it doesn't exist
         self.create_migrations_table().await?;
+        let applied = self.verify_applied().await?;
 
-        // Get list of migration files
-        let migration_files = self.get_migration_files()?;
-        
-        if migration_files.is_empty() {
-            warn!("No migration files found in {}", self.migrations_dir);
-            return Ok(());
+        let mut newly_applied = Vec::new();
+        for migration in self.migrations {
+            if applied.contains_key(migration.name) {
+                continue;
+            }
+
+            info!("Running migration: {}", migration.name);
+            self.apply(migration).await?;
+            info!("Migration {} completed successfully", migration.name);
+            newly_applied.push(*migration);
         }
 
-        // Run each migration
-        for file_path in migration_files {
-            let migration_name = Path::new(&file_path)
-                .file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown");
+        if newly_applied.is_empty() {
+            info!("Database schema is up to date");
+        } else {
+            info!("Applied {} migration(s)", newly_applied.len());
+        }
+        Ok(newly_applied)
+    }
 
-            if self.is_migration_applied(migration_name).await? {
-                info!("Migration {} already applied, skipping", migration_name);
-                continue;
-            }
+    /// Revert the last `steps` applied migrations, newest first
+    pub async fn rollback(&self, steps: usize) -> Result<Vec<Migration>, MigrationError> {
+        self.create_migrations_table().await?;
+        let applied = self.verify_applied().await?;
 
-            info!("Running migration: {}", migration_name);
-            self.run_migration(&file_path, migration_name).await?;
-            info!("Migration {} completed successfully", migration_name);
+        let mut latest: Vec<&AppliedMigration> = applied.values().collect();
+        latest.sort_by(|a, b| b.version.cmp(&a.version).then_with(|| b.name.cmp(&a.name)));
+
+        let mut reverted = Vec::new();
+        for record in latest.into_iter().take(steps) {
+            let migration = self
+                .find(&record.name)
+                .ok_or_else(|| MigrationError::UnknownMigration(record.name.clone()))?;
+
+            info!("Reverting migration: {}", migration.name);
+            self.revert(migration).await?;
+            info!("Migration {} reverted", migration.name);
+            reverted.push(*migration);
         }
 
-        info!("All migrations completed successfully");
-        Ok(())
+        Ok(reverted)
     }
 
-    /// Create the migrations tracking table
+    /// Report the state of every known and applied migration
+    pub async fn status(&self) -> Result<Vec<MigrationStatus>, MigrationError> {
+        self.create_migrations_table().await?;
+        let mut applied = self.load_applied().await?;
+
+        let mut report: Vec<MigrationStatus> = self
+            .migrations
+            .iter()
+            .map(|migration| {
+                let checksum = migration.checksum();
+                match applied.remove(migration.name) {
+                    Some(record) => MigrationStatus {
+                        version: migration.version,
+                        name: migration.name.to_string(),
+                        state: match &record.checksum {
+                            Some(recorded) if *recorded != checksum => MigrationState::Modified,
+                            _ => MigrationState::Applied,
+                        },
+                        applied_at: Some(record.applied_at),
+                        checksum: Some(checksum),
+                    },
+                    None => MigrationStatus {
+                        version: migration.version,
+                        name: migration.name.to_string(),
+                        state: MigrationState::Pending,
+                        applied_at: None,
+                        checksum: Some(checksum),
+                    },
+                }
+            })
+            .collect();
+
+        let mut missing: Vec<MigrationStatus> = applied
+            .into_values()
+            .map(|record| MigrationStatus {
+                version: record.version.unwrap_or_default(),
+                name: record.name,
+                state: MigrationState::Missing,
+                applied_at: Some(record.applied_at),
+                checksum: record.checksum,
+            })
+            .collect();
+        missing.sort_by(|a, b| a.version.cmp(&b.version).then_with(|| a.name.cmp(&b.name)));
+        report.extend(missing);
+
+        Ok(report)
+    }
+
+    /// Create the migrations tracking table, upgrading the legacy layout if needed
     async fn create_migrations_table(&self) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS _migrations (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 name TEXT NOT NULL UNIQUE,
+                version INTEGER,
+                checksum TEXT,
                 applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
             )
             "#,
@@ -78,65 +288,121 @@ impl MigrationManager {
         .execute(&self.pool)
         .await?;
 
+        // Tables created before versioning only tracked names
+        let columns = sqlx::query_scalar::<_, String>("SELECT name FROM pragma_table_info('_migrations')")
+            .fetch_all(&self.pool)
+            .await?;
+        for (column, definition) in [("version", "INTEGER"), ("checksum", "TEXT")] {
+            if !columns.iter().any(|c| c == column) {
+                sqlx::query(&format!("ALTER TABLE _migrations ADD COLUMN {} {}", column, definition))
+                    .execute(&self.pool)
+                    .await?;
+            }
+        }
+
         Ok(())
     }
 
-    /// Get list of migration files sorted by name
-    fn get_migration_files(&self) -> Result<Vec<String>, std::io::Error> {
-        let migrations_path = Path::new(&self.migrations_dir);
-        
-        if !migrations_path.exists() {
-            return Ok(Vec::new());
-        }
+    /// Load the applied migrations keyed by name
+    async fn load_applied(&self) -> Result<HashMap<String, AppliedMigration>, sqlx::Error> {
+        let records = sqlx::query_as::<_, AppliedMigration>(
+            "SELECT name, version, checksum, applied_at FROM _migrations",
+        )
+        .fetch_all(&self.pool)
+        .await?;
 
-        let mut files = Vec::new();
-        
-        for entry in fs::read_dir(migrations_path)? {
-            let entry = entry?;
-            let path = entry.path();
-            
-            if path.extension().and_then(|s| s.to_str()) == Some("sql") {
-                if let Some(path_str) = path.to_str() {
-                    files.push(path_str.to_string());
+        Ok(records.into_iter().map(|r| (r.name.clone(), r)).collect())
+    }
+
+    /// Check applied migrations against the embedded scripts
+    async fn verify_applied(&self) -> Result<HashMap<String, AppliedMigration>, MigrationError> {
+        let mut applied = self.load_applied().await?;
+
+        for record in applied.values_mut() {
+            let Some(migration) = self.find(&record.name) else {
+                warn!("Migration {} is applied but not known to this build", record.name);
+                continue;
+            };
+
+            let checksum = migration.checksum();
+            match &record.checksum {
+                Some(recorded) if *recorded != checksum => {
+                    error!("Migration {} was modified after being applied", record.name);
+                    return Err(MigrationError::ChecksumMismatch(record.name.clone()));
+                }
+                Some(_) => {}
+                None => {
+                    // Recorded before checksums existed: trust it and record the current one
+                    sqlx::query("UPDATE _migrations SET version = ?, checksum = ? WHERE name = ?")
+                        .bind(migration.version)
+                        .bind(&checksum)
+                        .bind(migration.name)
+                        .execute(&self.pool)
+                        .await?;
+                    record.version = Some(migration.version);
+                    record.checksum = Some(checksum);
                 }
             }
         }
 
-        files.sort();
-        Ok(files)
+        Ok(applied)
     }
 
-    /// Check if a migration has already been applied
-    async fn is_migration_applied(&self, migration_name: &str) -> Result<bool, sqlx::Error> {
-        let result = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM _migrations WHERE name = ?",
-        )
-        .bind(migration_name)
-        .fetch_one(&self.pool)
-        .await?;
+    fn find(&self, name: &str) -> Option<&'static Migration> {
+        self.migrations.iter().find(|m| m.name == name)
+    }
+
+    /// Run a migration's up script and record it, in a single transaction
+    async fn apply(&self, migration: &Migration) -> Result<(), MigrationError> {
+        let failed = |source| MigrationError::Failed {
+            name: migration.name.to_string(),
+            source,
+        };
+
+        let mut tx = self.pool.begin().await?;
+
+        for statement in split_statements(migration.up) {
+            if let Err(e) = sqlx::query(&statement).execute(&mut *tx).await {
+                tx.rollback().await?;
+                return Err(failed(e));
+            }
+        }
+        if let Some(fixup) = migration.fixup {
+            if let Err(e) = fixup.run(&mut tx).await {
+                tx.rollback().await?;
+                return Err(failed(e));
+            }
+        }
+
+        sqlx::query("INSERT INTO _migrations (name, version, checksum) VALUES (?, ?, ?)")
+            .bind(migration.name)
+            .bind(migration.version)
+            .bind(migration.checksum())
+            .execute(&mut *tx)
+            .await?;
 
-        Ok(result > 0)
+        tx.commit().await?;
+        Ok(())
     }
 
-    /// Run a single migration file
-    async fn run_migration(&self, file_path: &str, migration_name: &str) -> Result<(), MigrationError> {
-        // Read migration file
-        let sql_content = fs::read_to_string(file_path)?;
+    /// Run a migration's down script and forget it, in a single transaction
+    async fn revert(&self, migration: &Migration) -> Result<(), MigrationError> {
+        let failed = |source| MigrationError::Failed {
+            name: migration.name.to_string(),
+            source,
+        };
 
-        // Execute migration in a transaction
         let mut tx = self.pool.begin().await?;
 
-        // Split SQL content by semicolons and execute each statement
-        for statement in sql_content.split(';') {
-            let statement = statement.trim();
-            if !statement.is_empty() {
-                sqlx::query(statement).execute(&mut *tx).await?;
+        for statement in split_statements(migration.down) {
+            if let Err(e) = sqlx::query(&statement).execute(&mut *tx).await {
+                tx.rollback().await?;
+                return Err(failed(e));
             }
         }
 
-        // Record migration as applied
-        sqlx::query("INSERT INTO _migrations (name) VALUES (?)")
-            .bind(migration_name)
+        sqlx::query("DELETE FROM _migrations WHERE name = ?")
+            .bind(migration.name)
             .execute(&mut *tx)
             .await?;
 
@@ -145,9 +411,122 @@ impl MigrationManager {
     }
 }
 
+/// Split a SQL script into individual statements.
+///
+/// Semicolons inside string literals, quoted identifiers, comments and
+/// `CREATE TRIGGER ... BEGIN ... END` bodies do not end a statement.
+/// Comments before a statement are dropped, as are chunks containing only
+/// whitespace or comments.
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+
+    let mut start = 0;
+    let mut has_code = false;
+    let mut leading_words: Vec<String> = Vec::new();
+    let mut in_trigger = false;
+    let mut depth = 0usize;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                mark_code(&mut has_code, &mut start, i);
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == quote {
+                        // A doubled quote is an escaped quote
+                        if bytes.get(i + 1) == Some(&quote) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'[' => {
+                mark_code(&mut has_code, &mut start, i);
+                while i < bytes.len() && bytes[i] != b']' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !(bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/')) {
+                    i += 1;
+                }
+                i += 2;
+            }
+            b';' if !(in_trigger && depth > 0) => {
+                if has_code {
+                    statements.push(sql[start..i].trim().to_string());
+                }
+                i += 1;
+                start = i;
+                has_code = false;
+                leading_words.clear();
+                in_trigger = false;
+                depth = 0;
+            }
+            b if b.is_ascii_alphabetic() || b == b'_' => {
+                mark_code(&mut has_code, &mut start, i);
+                let word_start = i;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'$') {
+                    i += 1;
+                }
+                let word = sql[word_start..i].to_ascii_uppercase();
+
+                if leading_words.len() < 3 {
+                    leading_words.push(word.clone());
+                    in_trigger = matches!(
+                        leading_words.iter().map(String::as_str).collect::<Vec<_>>().as_slice(),
+                        ["CREATE", "TRIGGER", ..] | ["CREATE", "TEMP" | "TEMPORARY", "TRIGGER"]
+                    );
+                }
+
+                if in_trigger {
+                    match word.as_str() {
+                        "BEGIN" | "CASE" => depth += 1,
+                        "END" => depth = depth.saturating_sub(1),
+                        _ => {}
+                    }
+                }
+            }
+            b => {
+                if !b.is_ascii_whitespace() {
+                    mark_code(&mut has_code, &mut start, i);
+                }
+                i += 1;
+            }
+        }
+    }
+
+    if has_code {
+        statements.push(sql[start..].trim().to_string());
+    }
+
+    statements
+}
+
+/// Start a statement at its first non-comment byte
+fn mark_code(has_code: &mut bool, start: &mut usize, i: usize) {
+    if !*has_code {
+        *has_code = true;
+        *start = i;
+    }
+}
+
 /// Initialize database with migrations
 pub async fn initialize_database(pool: SqlitePool) -> Result<(), MigrationError> {
-    let migration_manager = MigrationManager::new(pool, "migrations".to_string());
+    let migration_manager = MigrationManager::new(pool);
     migration_manager.run_migrations().await?;
     Ok(())
 }
@@ -155,7 +534,7 @@ pub async fn initialize_database(pool: SqlitePool) -> Result<(), MigrationError>
 #[cfg(test)]
 mod tests {
     use super::*;
-
+    use std::path::Path;
 
     async fn create_test_pool() -> SqlitePool {
         sqlx::SqlitePool::connect("sqlite::memory:")
@@ -163,45 +542,361 @@ mod tests {
             .unwrap()
     }
 
+    async fn table_exists(pool: &SqlitePool, name: &str) -> bool {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name = ?")
+            .bind(name)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            > 0
+    }
+
+    static NOTES_V1: &[Migration] = &[Migration {
+        version: 1,
+        name: "001_notes",
+        up: "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT);",
+        down: "DROP TABLE notes;",
+        fixup: None,
+    }];
+
+    static NOTES_V1_EDITED: &[Migration] = &[Migration {
+        version: 1,
+        name: "001_notes",
+        up: "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT NOT NULL);",
+        down: "DROP TABLE notes;",
+        fixup: None,
+    }];
+
     #[tokio::test]
     async fn test_create_migrations_table() {
         let pool = create_test_pool().await;
-        let manager = MigrationManager::new(pool.clone(), "test_migrations".to_string());
-        
+        let manager = MigrationManager::new(pool.clone());
+
         let result = manager.create_migrations_table().await;
         assert!(result.is_ok());
 
-        // Verify table was created
-        let count = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='_migrations'"
+        assert!(table_exists(&pool, "_migrations").await);
+    }
+
+    #[tokio::test]
+    async fn test_run_embedded_migrations() {
+        let pool = create_test_pool().await;
+        let manager = MigrationManager::new(pool.clone());
+
+        let applied = manager.run_migrations().await.unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+
+        for table in ["profile", "projects", "skills", "contact_messages", "admin_users", "api_keys"] {
+            assert!(table_exists(&pool, table).await, "missing table {}", table);
+        }
+
+        // Running again is a no-op
+        assert!(manager.run_migrations().await.unwrap().is_empty());
+
+        let status = manager.status().await.unwrap();
+        assert_eq!(status.len(), MIGRATIONS.len());
+        assert!(status.iter().all(|s| s.state == MigrationState::Applied));
+    }
+
+    #[tokio::test]
+    async fn test_rollback_and_reapply() {
+        let pool = create_test_pool().await;
+        let manager = MigrationManager::new(pool.clone());
+        manager.run_migrations().await.unwrap();
+
+        let reverted = manager.rollback(2).await.unwrap();
+        assert_eq!(reverted.len(), 2);
+        assert_eq!(reverted[0].name, MIGRATIONS.last().unwrap().name);
+        assert_eq!(reverted[1].name, "017_content_changes");
+        assert!(!table_exists(&pool, "content_changes").await);
+
+        let status = manager.status().await.unwrap();
+        assert_eq!(status.last().unwrap().state, MigrationState::Pending);
+
+        // Rolling back more than applied reverts everything
+        let reverted = manager.rollback(100).await.unwrap();
+        assert_eq!(reverted.len(), MIGRATIONS.len() - 2);
+        assert!(!table_exists(&pool, "projects").await);
+
+        let applied = manager.run_migrations().await.unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert!(table_exists(&pool, "content_changes").await);
+    }
+
+    #[tokio::test]
+    async fn test_technologies_converted_and_restored() {
+        let pool = create_test_pool().await;
+        MigrationManager::with_migrations(pool.clone(), &MIGRATIONS[..3])
+            .run_migrations()
+            .await
+            .unwrap();
+
+        for (title, technologies) in [("First", r#"["Rust", "SQLite"]"#), ("Second", r#"["rust ", "Svelte", "RUST"]"#), ("Broken", "not json")] {
+            sqlx::query("INSERT INTO projects (title, description, technologies, category) VALUES (?, 'Description', ?, 'web')")
+                .bind(title)
+                .bind(technologies)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        let manager = MigrationManager::with_migrations(pool.clone(), &MIGRATIONS[..4]);
+        manager.run_migrations().await.unwrap();
+
+        let technologies: Vec<(String, String)> = sqlx::query_as("SELECT name, slug FROM technologies ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            technologies,
+            vec![
+                ("Rust".to_string(), "rust".to_string()),
+                ("SQLite".to_string(), "sqlite".to_string()),
+                ("Svelte".to_string(), "svelte".to_string()),
+            ]
+        );
+
+        let links: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM project_technologies")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(links, 4);
+
+        manager.rollback(1).await.unwrap();
+
+        let restored: Vec<String> = sqlx::query_scalar("SELECT technologies FROM projects ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(restored, vec![r#"["Rust","SQLite"]"#, r#"["Rust","Svelte"]"#, "[]"]);
+    }
+
+    #[tokio::test]
+    async fn test_non_ascii_technologies_reslugged() {
+        let pool = create_test_pool().await;
+        MigrationManager::with_migrations(pool.clone(), &MIGRATIONS[..3])
+            .run_migrations()
+            .await
+            .unwrap();
+
+        for (title, technologies) in [("First", r#"["Élixir", "Rust"]"#), ("Second", r#"["élixir"]"#)] {
+            sqlx::query("INSERT INTO projects (title, description, technologies, category) VALUES (?, 'Description', ?, 'web')")
+                .bind(title)
+                .bind(technologies)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        sqlx::query("INSERT INTO skills (name, category, level) VALUES ('ÉLIXIR', 'Backend', 3)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        MigrationManager::new(pool.clone()).run_migrations().await.unwrap();
+
+        // SQLite's LOWER() left "Élixir" and "élixir" apart; they are one technology now
+        let technologies: Vec<(String, String)> = sqlx::query_as("SELECT name, slug FROM technologies ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            technologies,
+            vec![("Élixir".to_string(), "élixir".to_string()), ("Rust".to_string(), "rust".to_string())]
+        );
+
+        let projects = crate::database::ProjectRepository::new(pool.clone())
+            .get_by_technology(&crate::models::Technology::slug_for("ÉLIXIR"))
+            .await
+            .unwrap();
+        assert_eq!(projects.len(), 2);
+
+        let suggested: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM project_skills WHERE source = 'suggested'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(suggested, 2);
+
+        // Saving the same spelling again reuses the migrated technology
+        let mut conn = pool.acquire().await.unwrap();
+        TechnologyRepository::replace_for_project(&mut conn, 2, &["élixir".to_string()]).await.unwrap();
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM technologies")
+            .fetch_one(&mut *conn)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_modified_migration_detected() {
+        let pool = create_test_pool().await;
+        MigrationManager::with_migrations(pool.clone(), NOTES_V1)
+            .run_migrations()
+            .await
+            .unwrap();
+
+        let manager = MigrationManager::with_migrations(pool.clone(), NOTES_V1_EDITED);
+        let result = manager.run_migrations().await;
+        assert!(matches!(result, Err(MigrationError::ChecksumMismatch(name)) if name == "001_notes"));
+
+        let status = manager.status().await.unwrap();
+        assert_eq!(status[0].state, MigrationState::Modified);
+
+        assert!(matches!(manager.rollback(1).await, Err(MigrationError::ChecksumMismatch(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_applied_migration_reported() {
+        let pool = create_test_pool().await;
+        MigrationManager::with_migrations(pool.clone(), NOTES_V1)
+            .run_migrations()
+            .await
+            .unwrap();
+
+        let manager = MigrationManager::with_migrations(pool.clone(), &[]);
+        let status = manager.status().await.unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].state, MigrationState::Missing);
+
+        assert!(matches!(manager.rollback(1).await, Err(MigrationError::UnknownMigration(_))));
+    }
+
+    #[tokio::test]
+    async fn test_legacy_migrations_table_upgraded() {
+        let pool = create_test_pool().await;
+
+        // Layout used before checksums were tracked
+        sqlx::query(
+            "CREATE TABLE _migrations (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL UNIQUE, applied_at DATETIME DEFAULT CURRENT_TIMESTAMP)",
         )
-        .fetch_one(&pool)
+        .execute(&pool)
         .await
         .unwrap();
-        
-        assert_eq!(count, 1);
+        sqlx::query("CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO _migrations (name) VALUES ('001_notes')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let manager = MigrationManager::with_migrations(pool.clone(), NOTES_V1);
+        assert!(manager.run_migrations().await.unwrap().is_empty());
+
+        let checksum = sqlx::query_scalar::<_, Option<String>>("SELECT checksum FROM _migrations WHERE name = '001_notes'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(checksum, Some(NOTES_V1[0].checksum()));
     }
 
     #[tokio::test]
-    async fn test_migration_applied_check() {
+    async fn test_failed_migration_rolled_back() {
+        static BROKEN: &[Migration] = &[Migration {
+            version: 1,
+            name: "001_broken",
+            up: "CREATE TABLE notes (id INTEGER PRIMARY KEY); INSERT INTO missing_table VALUES (1);",
+            down: "DROP TABLE notes;",
+            fixup: None,
+        }];
+
         let pool = create_test_pool().await;
-        let manager = MigrationManager::new(pool.clone(), "test_migrations".to_string());
-        
-        manager.create_migrations_table().await.unwrap();
-        
-        // Initially should not be applied
-        let applied = manager.is_migration_applied("test_migration").await.unwrap();
-        assert!(!applied);
-
-        // Insert migration record
-        sqlx::query("INSERT INTO _migrations (name) VALUES (?)")
-            .bind("test_migration")
-            .execute(&pool)
+        let manager = MigrationManager::with_migrations(pool.clone(), BROKEN);
+
+        let result = manager.run_migrations().await;
+        assert!(matches!(result, Err(MigrationError::Failed { .. })));
+        assert!(!table_exists(&pool, "notes").await);
+        assert_eq!(manager.status().await.unwrap()[0].state, MigrationState::Pending);
+    }
+
+    #[test]
+    fn test_embedded_migrations_match_files() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("migrations");
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+
+        let mut expected: Vec<String> = MIGRATIONS
+            .iter()
+            .flat_map(|m| [format!("{}.down.sql", m.name), format!("{}.up.sql", m.name)])
+            .collect();
+        expected.sort();
+        assert_eq!(files, expected, "every migration file must be registered in MIGRATIONS");
+
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as i64 + 1);
+            assert!(migration.name.starts_with(&format!("{:03}_", migration.version)));
+        }
+    }
+
+    #[test]
+    fn test_split_simple_statements() {
+        let sql = "CREATE TABLE a (id INTEGER);\n\nCREATE TABLE b (id INTEGER);\n-- trailing comment\n";
+        assert_eq!(
+            split_statements(sql),
+            vec!["CREATE TABLE a (id INTEGER)", "CREATE TABLE b (id INTEGER)"]
+        );
+    }
+
+    #[test]
+    fn test_split_ignores_semicolons_in_literals_and_comments() {
+        let sql = r#"
+            -- a comment; with a semicolon
+            INSERT INTO t (a, b) VALUES ('x; y', 'it''s; fine');
+            /* block; comment */
+            SELECT "weird;column", [other;column] FROM t
+        "#;
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("VALUES ('x; y', 'it''s; fine')"));
+        assert!(statements[1].starts_with("SELECT"));
+        assert!(statements[1].ends_with("[other;column] FROM t"));
+    }
+
+    #[test]
+    fn test_split_keeps_trigger_bodies_together() {
+        let sql = r#"
+            CREATE TRIGGER IF NOT EXISTS projects_touch AFTER UPDATE ON projects
+            BEGIN
+                UPDATE projects SET updated_at = CURRENT_TIMESTAMP WHERE id = NEW.id;
+                UPDATE projects SET featured = CASE WHEN NEW.featured THEN 1 ELSE 0 END WHERE id = NEW.id;
+            END;
+            CREATE TEMP TRIGGER t AFTER INSERT ON a BEGIN SELECT 1; END;
+            BEGIN;
+            COMMIT;
+        "#;
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 4);
+        assert!(statements[0].starts_with("CREATE TRIGGER"));
+        assert!(statements[0].ends_with("END"));
+        assert!(statements[1].starts_with("CREATE TEMP TRIGGER"));
+        assert_eq!(statements[2], "BEGIN");
+        assert_eq!(statements[3], "COMMIT");
+    }
+
+    #[tokio::test]
+    async fn test_split_trigger_executes() {
+        let pool = create_test_pool().await;
+        let sql = r#"
+            CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT, log TEXT);
+            CREATE TABLE audit (entry TEXT);
+            CREATE TRIGGER items_audit AFTER INSERT ON items
+            BEGIN
+                INSERT INTO audit (entry) VALUES ('inserted; ' || NEW.name);
+            END;
+            INSERT INTO items (name) VALUES ('first');
+        "#;
+
+        for statement in split_statements(sql) {
+            sqlx::query(&statement).execute(&pool).await.unwrap();
+        }
+
+        let entry = sqlx::query_scalar::<_, String>("SELECT entry FROM audit")
+            .fetch_one(&pool)
             .await
             .unwrap();
-
-        // Now should be applied
-        let applied = manager.is_migration_applied("test_migration").await.unwrap();
-        assert!(applied);
+        assert_eq!(entry, "inserted; first");
     }
-}
\ No newline at end of file
+}
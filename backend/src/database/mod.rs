@@ -3,10 +3,12 @@ pub mod connection;
 pub mod migrations;
 pub mod seed;
 pub mod init;
+pub mod backup;
 pub mod repositories;
 
 pub use connection::{DatabaseConfig, create_pool, test_connection};
-pub use migrations::{MigrationManager, initialize_database, MigrationError};
+pub use migrations::{MigrationManager, Migration, MigrationState, MigrationStatus, initialize_database, MigrationError};
 pub use seed::{seed_database, SeedError};
-pub use init::{initialize_complete_database, initialize_test_database, InitError};
-pub use repositories::{ProjectRepository, SkillRepository, ProfileRepository, ContactRepository};
\ No newline at end of file
+pub use backup::{backup_database, BackupError};
+pub use init::{connect_database, open_database, initialize_complete_database, initialize_test_database, InitError};
+pub use repositories::{ProjectRepository, SkillRepository, ProfileRepository, ContactRepository, AdminRepository, ApiKeyRepository, TechnologyRepository, ProjectSkillRepository, SearchRepository, NotificationRepository, SpamRepository, RateLimitRepository, ChallengeRepository, MediaRepository, PostRepository, ExperienceRepository, EducationRepository, CertificationRepository, ResumeCacheRepository, SitemapRepository, ContentChangeRepository};
\ No newline at end of file
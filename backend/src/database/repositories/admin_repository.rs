@@ -0,0 +1,172 @@
+use sqlx::SqlitePool;
+use chrono::Utc;
+use crate::models::AdminUser;
+
+/// Repository for admin user database operations
+pub struct AdminRepository {
+    pool: SqlitePool,
+}
+
+impl AdminRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get all admin users
+    pub async fn get_all(&self) -> Result<Vec<AdminUser>, sqlx::Error> {
+        sqlx::query_as::<_, AdminUser>(
+            "SELECT id, username, password_hash, created_at, last_login_at FROM admin_users ORDER BY username"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get admin user by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<AdminUser>, sqlx::Error> {
+        sqlx::query_as::<_, AdminUser>(
+            "SELECT id, username, password_hash, created_at, last_login_at FROM admin_users WHERE id = ?"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Get admin user by username
+    pub async fn get_by_username(&self, username: &str) -> Result<Option<AdminUser>, sqlx::Error> {
+        sqlx::query_as::<_, AdminUser>(
+            "SELECT id, username, password_hash, created_at, last_login_at FROM admin_users WHERE username = ?"
+        )
+        .bind(username)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Create a new admin user from an already hashed password
+    pub async fn create(&self, username: &str, password_hash: &str) -> Result<AdminUser, sqlx::Error> {
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            "INSERT INTO admin_users (username, password_hash, created_at) VALUES (?, ?, ?)"
+        )
+        .bind(username)
+        .bind(password_hash)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let id = result.last_insert_rowid() as i32;
+
+        // Fetch the created admin user
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace the password hash of an admin user
+    pub async fn update_password(&self, id: i32, password_hash: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE admin_users SET password_hash = ? WHERE id = ?")
+            .bind(password_hash)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a successful login
+    pub async fn touch_last_login(&self, id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE admin_users SET last_login_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Delete an admin user
+    pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM admin_users WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Count admin users
+    pub async fn count(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM admin_users")
+            .fetch_one(&self.pool)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_repository() -> AdminRepository {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        // Create tables manually for testing
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS admin_users (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                username TEXT NOT NULL UNIQUE,
+                password_hash TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                last_login_at DATETIME
+            );
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        AdminRepository::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_admin() {
+        let repo = create_test_repository().await;
+
+        let created = repo.create("admin", "hash").await.unwrap();
+        assert_eq!(created.username, "admin");
+        assert!(created.last_login_at.is_none());
+
+        let by_name = repo.get_by_username("admin").await.unwrap().unwrap();
+        assert_eq!(by_name.id, created.id);
+        assert_eq!(by_name.password_hash, "hash");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_username_rejected() {
+        let repo = create_test_repository().await;
+
+        repo.create("admin", "hash").await.unwrap();
+        assert!(repo.create("admin", "other").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_touch_last_login() {
+        let repo = create_test_repository().await;
+        let created = repo.create("admin", "hash").await.unwrap();
+
+        repo.touch_last_login(created.id).await.unwrap();
+
+        let admin = repo.get_by_id(created.id).await.unwrap().unwrap();
+        assert!(admin.last_login_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_count() {
+        let repo = create_test_repository().await;
+        let created = repo.create("admin", "hash").await.unwrap();
+        assert_eq!(repo.count().await.unwrap(), 1);
+
+        assert!(repo.delete(created.id).await.unwrap());
+        assert_eq!(repo.count().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,178 @@
+use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use crate::models::ApiKey;
+
+/// Repository for API key database operations
+pub struct ApiKeyRepository {
+    pool: SqlitePool,
+}
+
+impl ApiKeyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get all API keys
+    pub async fn get_all(&self) -> Result<Vec<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>(
+            "SELECT id, name, key_prefix, key_hash, scopes, created_by, expires_at, last_used_at, revoked_at, created_at FROM api_keys ORDER BY created_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get API key by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>(
+            "SELECT id, name, key_prefix, key_hash, scopes, created_by, expires_at, last_used_at, revoked_at, created_at FROM api_keys WHERE id = ?"
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Get API key by the hash of its plaintext value
+    pub async fn get_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, sqlx::Error> {
+        sqlx::query_as::<_, ApiKey>(
+            "SELECT id, name, key_prefix, key_hash, scopes, created_by, expires_at, last_used_at, revoked_at, created_at FROM api_keys WHERE key_hash = ?"
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Create a new API key
+    pub async fn create(
+        &self,
+        name: &str,
+        key_prefix: &str,
+        key_hash: &str,
+        scopes_json: &str,
+        created_by: Option<i32>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, sqlx::Error> {
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO api_keys (name, key_prefix, key_hash, scopes, created_by, expires_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(name)
+        .bind(key_prefix)
+        .bind(key_hash)
+        .bind(scopes_json)
+        .bind(created_by)
+        .bind(expires_at)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let id = result.last_insert_rowid() as i32;
+
+        // Fetch the created key
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Revoke an API key; returns false if it does not exist or was already revoked
+    pub async fn revoke(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE api_keys SET revoked_at = ? WHERE id = ? AND revoked_at IS NULL")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that a key was used
+    pub async fn touch_last_used(&self, id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE api_keys SET last_used_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_repository() -> ApiKeyRepository {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        // Create tables manually for testing
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_keys (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                key_prefix TEXT NOT NULL,
+                key_hash TEXT NOT NULL UNIQUE,
+                scopes TEXT NOT NULL,
+                created_by INTEGER,
+                expires_at DATETIME,
+                last_used_at DATETIME,
+                revoked_at DATETIME,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        ApiKeyRepository::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_by_hash() {
+        let repo = create_test_repository().await;
+
+        let created = repo
+            .create("CI", "pfk_abcdefgh", "hash-1", r#"["projects:write"]"#, None, None)
+            .await
+            .unwrap();
+        assert_eq!(created.name, "CI");
+
+        let found = repo.get_by_hash("hash-1").await.unwrap().unwrap();
+        assert_eq!(found.id, created.id);
+        assert!(repo.get_by_hash("hash-2").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_key() {
+        let repo = create_test_repository().await;
+        let created = repo
+            .create("CI", "pfk_abcdefgh", "hash-1", r#"["projects:write"]"#, None, None)
+            .await
+            .unwrap();
+
+        assert!(repo.revoke(created.id).await.unwrap());
+        // Revoking twice is reported as a no-op
+        assert!(!repo.revoke(created.id).await.unwrap());
+
+        let key = repo.get_by_id(created.id).await.unwrap().unwrap();
+        assert!(key.revoked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_touch_last_used() {
+        let repo = create_test_repository().await;
+        let created = repo
+            .create("CI", "pfk_abcdefgh", "hash-1", r#"["projects:write"]"#, None, None)
+            .await
+            .unwrap();
+
+        repo.touch_last_used(created.id).await.unwrap();
+
+        let key = repo.get_by_id(created.id).await.unwrap().unwrap();
+        assert!(key.last_used_at.is_some());
+    }
+}
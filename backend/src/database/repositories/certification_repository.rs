@@ -0,0 +1,116 @@
+use sqlx::{SqliteConnection, SqlitePool};
+use chrono::Utc;
+use crate::models::{Certification, CreateCertification, UpdateCertification};
+
+const CERTIFICATION_COLUMNS: &str =
+    "id, name, issuer, issued_month, expires_month, credential_id, credential_url, created_at, updated_at";
+
+/// Repository for certification database operations
+pub struct CertificationRepository {
+    pool: SqlitePool,
+}
+
+impl CertificationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get all certifications, most recent first
+    pub async fn get_all(&self) -> Result<Vec<Certification>, sqlx::Error> {
+        sqlx::query_as::<_, Certification>(&format!(
+            "SELECT {} FROM certifications ORDER BY issued_month DESC, id DESC",
+            CERTIFICATION_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get certification by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<Certification>, sqlx::Error> {
+        sqlx::query_as::<_, Certification>(&format!(
+            "SELECT {} FROM certifications WHERE id = ?",
+            CERTIFICATION_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Create a new certification
+    pub async fn create(&self, certification: &CreateCertification) -> Result<Certification, sqlx::Error> {
+        Self::insert(&mut *self.pool.acquire().await?, certification).await
+    }
+
+    /// Update an existing certification
+    pub async fn update(&self, id: i32, certification: &UpdateCertification) -> Result<Option<Certification>, sqlx::Error> {
+        Self::update_on(&mut *self.pool.acquire().await?, id, certification).await
+    }
+
+    /// Insert a certification on an open connection
+    pub async fn insert(conn: &mut SqliteConnection, certification: &CreateCertification) -> Result<Certification, sqlx::Error> {
+        let now = Utc::now();
+
+        sqlx::query_as::<_, Certification>(&format!(
+            r#"
+            INSERT INTO certifications (name, issuer, issued_month, expires_month, credential_id, credential_url, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING {}
+            "#,
+            CERTIFICATION_COLUMNS
+        ))
+        .bind(&certification.name)
+        .bind(&certification.issuer)
+        .bind(&certification.issued_month)
+        .bind(&certification.expires_month)
+        .bind(&certification.credential_id)
+        .bind(&certification.credential_url)
+        .bind(now)
+        .bind(now)
+        .fetch_one(conn)
+        .await
+    }
+
+    /// Update a certification on an open connection
+    pub async fn update_on(
+        conn: &mut SqliteConnection,
+        id: i32,
+        certification: &UpdateCertification,
+    ) -> Result<Option<Certification>, sqlx::Error> {
+        // Use COALESCE to keep existing values for fields that are None
+        sqlx::query_as::<_, Certification>(&format!(
+            r#"
+            UPDATE certifications SET
+                name = COALESCE(?, name),
+                issuer = COALESCE(?, issuer),
+                issued_month = COALESCE(?, issued_month),
+                expires_month = COALESCE(?, expires_month),
+                credential_id = COALESCE(?, credential_id),
+                credential_url = COALESCE(?, credential_url),
+                updated_at = ?
+            WHERE id = ?
+            RETURNING {}
+            "#,
+            CERTIFICATION_COLUMNS
+        ))
+        .bind(&certification.name)
+        .bind(&certification.issuer)
+        .bind(&certification.issued_month)
+        .bind(&certification.expires_month)
+        .bind(&certification.credential_id)
+        .bind(&certification.credential_url)
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_optional(conn)
+        .await
+    }
+
+    /// Delete a certification
+    pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM certifications WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
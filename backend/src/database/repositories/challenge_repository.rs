@@ -0,0 +1,55 @@
+use sqlx::SqlitePool;
+
+/// Repository for proof-of-work challenges already redeemed
+pub struct ChallengeRepository {
+    pool: SqlitePool,
+}
+
+impl ChallengeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Mark a challenge as redeemed. Returns false if it already was, in which case the
+    /// solution is being replayed. Expired entries are forgotten along the way.
+    pub async fn claim(&self, id: &str, expires_at_ms: i64, now_ms: i64) -> Result<bool, sqlx::Error> {
+        sqlx::query("DELETE FROM used_challenges WHERE expires_at < ?")
+            .bind(now_ms)
+            .execute(&self.pool)
+            .await?;
+
+        let result = sqlx::query("INSERT OR IGNORE INTO used_challenges (id, expires_at) VALUES (?, ?)")
+            .bind(id)
+            .bind(expires_at_ms)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_claim_once() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let repo = ChallengeRepository::new(pool.clone());
+
+        assert!(repo.claim("a", 1_000, 0).await.unwrap());
+        assert!(!repo.claim("a", 1_000, 500).await.unwrap());
+        assert!(repo.claim("b", 1_000, 500).await.unwrap());
+
+        // Expired entries are swept
+        assert!(repo.claim("c", 5_000, 2_000).await.unwrap());
+        let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM used_challenges")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+}
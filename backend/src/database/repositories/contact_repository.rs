@@ -1,6 +1,8 @@
-use sqlx::SqlitePool;
-use chrono::Utc;
-use crate::models::{ContactMessage, CreateContactMessage};
+use sqlx::{types::Json, QueryBuilder, Sqlite, SqlitePool};
+use chrono::{DateTime, Utc};
+use crate::models::{ContactMessage, ContactReply, CreateContactMessage, MessageFilter, MessageStatus, SpamVerdict};
+
+const MESSAGE_COLUMNS: &str = "id, name, email, subject, message, status, is_spam, spam_score, spam_reasons, spam_trained, starred, notes, status_changed_at, replied_at, created_at";
 
 /// Repository for contact message database operations
 pub struct ContactRepository {
@@ -14,34 +16,44 @@ impl ContactRepository {
 
     /// Get all contact messages
     pub async fn get_all(&self) -> Result<Vec<ContactMessage>, sqlx::Error> {
-        sqlx::query_as::<_, ContactMessage>(
-            "SELECT id, name, email, subject, message, created_at FROM contact_messages ORDER BY created_at DESC"
-        )
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages ORDER BY created_at DESC",
+            MESSAGE_COLUMNS
+        ))
         .fetch_all(&self.pool)
         .await
     }
 
     /// Get contact message by ID
     pub async fn get_by_id(&self, id: i32) -> Result<Option<ContactMessage>, sqlx::Error> {
-        sqlx::query_as::<_, ContactMessage>(
-            "SELECT id, name, email, subject, message, created_at FROM contact_messages WHERE id = ?"
-        )
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages WHERE id = ?",
+            MESSAGE_COLUMNS
+        ))
         .bind(id)
         .fetch_optional(&self.pool)
         .await
     }
 
-    /// Create a new contact message
-    pub async fn create(&self, message: &CreateContactMessage) -> Result<ContactMessage, sqlx::Error> {
+    /// Create a new contact message with its spam verdict; likely spam is quarantined straight away
+    pub async fn create(&self, message: &CreateContactMessage, verdict: &SpamVerdict) -> Result<ContactMessage, sqlx::Error> {
         let now = Utc::now();
+        let status = if verdict.is_spam() { MessageStatus::Spam } else { MessageStatus::New };
         
         let result = sqlx::query(
-            "INSERT INTO contact_messages (name, email, subject, message, created_at) VALUES (?, ?, ?, ?, ?)"
+            r#"
+            INSERT INTO contact_messages (name, email, subject, message, status, is_spam, spam_score, spam_reasons, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
         )
         .bind(&message.name)
         .bind(&message.email)
         .bind(&message.subject)
         .bind(&message.message)
+        .bind(status.as_str())
+        .bind(verdict.is_spam())
+        .bind(verdict.score)
+        .bind(Json(&verdict.signals))
         .bind(now)
         .execute(&self.pool)
         .await?;
@@ -52,6 +64,137 @@ impl ContactRepository {
         self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
     }
 
+    /// Get messages matching a filter, newest first (all of them when no limit is given)
+    pub async fn find(&self, filter: &MessageFilter, limit: Option<i64>, offset: i64) -> Result<Vec<ContactMessage>, sqlx::Error> {
+        let mut builder = QueryBuilder::<Sqlite>::new(format!("SELECT {} FROM contact_messages WHERE 1 = 1", MESSAGE_COLUMNS));
+        push_filters(&mut builder, filter);
+        builder.push(" ORDER BY created_at DESC, id DESC");
+        if let Some(limit) = limit {
+            builder.push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+        }
+
+        builder.build_query_as::<ContactMessage>().fetch_all(&self.pool).await
+    }
+
+    /// Count messages matching a filter
+    pub async fn count_matching(&self, filter: &MessageFilter) -> Result<i64, sqlx::Error> {
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM contact_messages WHERE 1 = 1");
+        push_filters(&mut builder, filter);
+
+        builder.build_query_scalar().fetch_one(&self.pool).await
+    }
+
+    /// Get the messages with the given IDs (missing ones are left out)
+    pub async fn get_by_ids(&self, ids: &[i32]) -> Result<Vec<ContactMessage>, sqlx::Error> {
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages WHERE id IN (SELECT value FROM json_each(?)) ORDER BY id",
+            MESSAGE_COLUMNS
+        ))
+        .bind(serde_json::to_string(ids).unwrap_or_default())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Message bodies received since a date, to spot repeated submissions
+    pub async fn get_bodies_since(&self, since: DateTime<Utc>) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT message FROM contact_messages WHERE julianday(created_at) >= julianday(?)")
+            .bind(sqlite_timestamp(since))
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Move messages to a status; messages already in it are left untouched
+    pub async fn set_status(&self, ids: &[i32], status: MessageStatus) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE contact_messages SET status = ?, status_changed_at = ? WHERE status != ? AND id IN (SELECT value FROM json_each(?))"
+        )
+        .bind(status.as_str())
+        .bind(Utc::now())
+        .bind(status.as_str())
+        .bind(serde_json::to_string(ids).unwrap_or_default())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Update the starred flag and notes of a message
+    pub async fn update_flags(&self, id: i32, starred: bool, notes: Option<&str>) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("UPDATE contact_messages SET starred = ?, notes = ? WHERE id = ?")
+            .bind(starred)
+            .bind(notes)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a reply and mark the message as replied
+    pub async fn add_reply(&self, id: i32, body: &str, replied_by: Option<&str>) -> Result<ContactReply, sqlx::Error> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("INSERT INTO contact_replies (message_id, body, replied_by, created_at) VALUES (?, ?, ?, ?)")
+            .bind(id)
+            .bind(body)
+            .bind(replied_by)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        let reply_id = result.last_insert_rowid() as i32;
+
+        sqlx::query(
+            r#"
+            UPDATE contact_messages
+            SET status = ?, replied_at = ?,
+                status_changed_at = CASE WHEN status = ? THEN status_changed_at ELSE ? END
+            WHERE id = ?
+            "#
+        )
+        .bind(MessageStatus::Replied.as_str())
+        .bind(now)
+        .bind(MessageStatus::Replied.as_str())
+        .bind(now)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        let reply = sqlx::query_as::<_, ContactReply>(
+            "SELECT id, message_id, body, replied_by, created_at FROM contact_replies WHERE id = ?"
+        )
+        .bind(reply_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(reply)
+    }
+
+    /// Get the replies recorded for a message, oldest first
+    pub async fn get_replies(&self, id: i32) -> Result<Vec<ContactReply>, sqlx::Error> {
+        sqlx::query_as::<_, ContactReply>(
+            "SELECT id, message_id, body, replied_by, created_at FROM contact_replies WHERE message_id = ? ORDER BY created_at, id"
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Count messages per status (statuses without messages are left out)
+    pub async fn count_by_status(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as("SELECT status, COUNT(*) FROM contact_messages GROUP BY status")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Count starred messages
+    pub async fn count_starred(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM contact_messages WHERE starred = 1")
+            .fetch_one(&self.pool)
+            .await
+    }
+
     /// Delete a contact message
     pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
         let result = sqlx::query("DELETE FROM contact_messages WHERE id = ?")
@@ -64,9 +207,10 @@ impl ContactRepository {
 
     /// Get messages with pagination
     pub async fn get_paginated(&self, limit: i64, offset: i64) -> Result<Vec<ContactMessage>, sqlx::Error> {
-        sqlx::query_as::<_, ContactMessage>(
-            "SELECT id, name, email, subject, message, created_at FROM contact_messages ORDER BY created_at DESC LIMIT ? OFFSET ?"
-        )
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages ORDER BY created_at DESC LIMIT ? OFFSET ?",
+            MESSAGE_COLUMNS
+        ))
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
@@ -84,38 +228,50 @@ impl ContactRepository {
     pub async fn get_recent(&self, days: i64) -> Result<Vec<ContactMessage>, sqlx::Error> {
         let cutoff_date = Utc::now() - chrono::Duration::days(days);
         
-        sqlx::query_as::<_, ContactMessage>(
-            "SELECT id, name, email, subject, message, created_at FROM contact_messages WHERE created_at >= ? ORDER BY created_at DESC"
-        )
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages WHERE created_at >= ? ORDER BY created_at DESC",
+            MESSAGE_COLUMNS
+        ))
         .bind(cutoff_date)
         .fetch_all(&self.pool)
         .await
     }
 
-    /// Search messages by email or name
-    pub async fn search(&self, query: &str) -> Result<Vec<ContactMessage>, sqlx::Error> {
-        let search_pattern = format!("%{}%", query);
-        
-        sqlx::query_as::<_, ContactMessage>(
-            "SELECT id, name, email, subject, message, created_at FROM contact_messages WHERE name LIKE ? OR email LIKE ? OR subject LIKE ? ORDER BY created_at DESC"
-        )
-        .bind(&search_pattern)
-        .bind(&search_pattern)
-        .bind(&search_pattern)
+    /// Run an FTS5 match expression against the indexed messages, best matches first.
+    /// Subjects, message bodies, names and emails are all searched.
+    pub async fn search(&self, match_expression: &str) -> Result<Vec<ContactMessage>, sqlx::Error> {
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages JOIN (\
+                 SELECT CAST(ref_id AS INTEGER) AS ref_id, bm25(search_index, 0.0, 0.0, 10.0, 2.0, 5.0) AS rank \
+                 FROM search_index WHERE search_index MATCH ? AND kind = 'contact_message'\
+             ) hits ON hits.ref_id = contact_messages.id ORDER BY hits.rank, contact_messages.id",
+            MESSAGE_COLUMNS
+        ))
+        .bind(match_expression)
         .fetch_all(&self.pool)
         .await
     }
 
     /// Get messages by email address
     pub async fn get_by_email(&self, email: &str) -> Result<Vec<ContactMessage>, sqlx::Error> {
-        sqlx::query_as::<_, ContactMessage>(
-            "SELECT id, name, email, subject, message, created_at FROM contact_messages WHERE email = ? ORDER BY created_at DESC"
-        )
+        sqlx::query_as::<_, ContactMessage>(&format!(
+            "SELECT {} FROM contact_messages WHERE email = ? ORDER BY created_at DESC",
+            MESSAGE_COLUMNS
+        ))
         .bind(email)
         .fetch_all(&self.pool)
         .await
     }
 
+    /// Count messages sent from an email address since a date
+    pub async fn count_by_email_since(&self, email: &str, since: DateTime<Utc>) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM contact_messages WHERE email = ? AND julianday(created_at) >= julianday(?)")
+            .bind(email)
+            .bind(sqlite_timestamp(since))
+            .fetch_one(&self.pool)
+            .await
+    }
+
     /// Delete old messages (older than N days)
     pub async fn delete_old(&self, days: i64) -> Result<u64, sqlx::Error> {
         let cutoff_date = Utc::now() - chrono::Duration::days(days);
@@ -129,9 +285,39 @@ impl ContactRepository {
     }
 }
 
+/// Append the WHERE conditions of a filter to a query
+fn push_filters(builder: &mut QueryBuilder<'_, Sqlite>, filter: &MessageFilter) {
+    match filter.status {
+        Some(status) => {
+            builder.push(" AND status = ").push_bind(status.as_str());
+        }
+        None => {
+            builder.push(" AND status != ").push_bind(MessageStatus::Spam.as_str());
+        }
+    }
+    if let Some(starred) = filter.starred {
+        builder.push(" AND starred = ").push_bind(starred);
+    }
+    if let Some(search) = &filter.search {
+        builder
+            .push(" AND id IN (SELECT ref_id FROM search_index WHERE kind = 'contact_message' AND search_index MATCH ")
+            .push_bind(search.clone())
+            .push(")");
+    }
+    if let Some(since) = filter.since {
+        builder.push(" AND julianday(created_at) >= julianday(").push_bind(sqlite_timestamp(since)).push(")");
+    }
+}
+
+/// Format a timestamp the way SQLite date functions expect it
+fn sqlite_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M:%S%.f").to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{SpamDecision, SpamSignal};
 
 
     async fn create_test_repository() -> ContactRepository {
@@ -139,22 +325,7 @@ mod tests {
             .await
             .unwrap();
 
-        // Create tables manually for testing
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS contact_messages (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                email TEXT NOT NULL,
-                subject TEXT NOT NULL,
-                message TEXT NOT NULL,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
 
         ContactRepository::new(pool)
     }
@@ -165,6 +336,7 @@ mod tests {
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message with sufficient content for testing purposes.".to_string(),
+            ..Default::default()
         }
     }
 
@@ -173,7 +345,7 @@ mod tests {
         let repo = create_test_repository().await;
         let message_data = create_test_message();
         
-        let created = repo.create(&message_data).await.unwrap();
+        let created = repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         assert_eq!(created.name, message_data.name);
         assert_eq!(created.email, message_data.email);
         assert_eq!(created.subject, message_data.subject);
@@ -189,10 +361,10 @@ mod tests {
         let repo = create_test_repository().await;
         let message_data = create_test_message();
         
-        repo.create(&message_data).await.unwrap();
+        repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         
         let messages = repo.get_all().await.unwrap();
-        assert!(messages.len() >= 1);
+        assert!(!messages.is_empty());
     }
 
     #[tokio::test]
@@ -200,7 +372,7 @@ mod tests {
         let repo = create_test_repository().await;
         let message_data = create_test_message();
         
-        let created = repo.create(&message_data).await.unwrap();
+        let created = repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         
         let deleted = repo.delete(created.id).await.unwrap();
         assert!(deleted);
@@ -218,7 +390,7 @@ mod tests {
         for i in 0..5 {
             let mut msg = message_data.clone();
             msg.subject = format!("Test Subject {}", i);
-            repo.create(&msg).await.unwrap();
+            repo.create(&msg, &SpamVerdict::default()).await.unwrap();
         }
         
         let messages = repo.get_paginated(3, 0).await.unwrap();
@@ -231,7 +403,7 @@ mod tests {
         let message_data = create_test_message();
         
         let initial_count = repo.count().await.unwrap();
-        repo.create(&message_data).await.unwrap();
+        repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         let new_count = repo.count().await.unwrap();
         
         assert_eq!(new_count, initial_count + 1);
@@ -242,11 +414,12 @@ mod tests {
         let repo = create_test_repository().await;
         let message_data = create_test_message();
         
-        repo.create(&message_data).await.unwrap();
+        repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         
-        let results = repo.search("John").await.unwrap();
-        assert!(results.len() >= 1);
+        let results = repo.search("\"john\"").await.unwrap();
+        assert!(!results.is_empty());
         assert!(results.iter().any(|m| m.name.contains("John")));
+        assert!(repo.search("\"nobody\"").await.unwrap().is_empty());
     }
 
     #[tokio::test]
@@ -254,10 +427,13 @@ mod tests {
         let repo = create_test_repository().await;
         let message_data = create_test_message();
         
-        repo.create(&message_data).await.unwrap();
+        repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         
         let messages = repo.get_by_email("john.doe@example.com").await.unwrap();
-        assert!(messages.len() >= 1);
+        let since = Utc::now() - chrono::Duration::hours(24);
+        assert_eq!(repo.count_by_email_since("john.doe@example.com", since).await.unwrap(), messages.len() as i64);
+        assert_eq!(repo.count_by_email_since("nobody@example.com", since).await.unwrap(), 0);
+        assert!(!messages.is_empty());
         assert!(messages.iter().all(|m| m.email == "john.doe@example.com"));
     }
 
@@ -266,9 +442,73 @@ mod tests {
         let repo = create_test_repository().await;
         let message_data = create_test_message();
         
-        repo.create(&message_data).await.unwrap();
+        repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
         
         let recent = repo.get_recent(1).await.unwrap();
-        assert!(recent.len() >= 1);
+        assert!(!recent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_with_filters() {
+        let repo = create_test_repository().await;
+        let message_data = create_test_message();
+
+        let first = repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
+        let verdict = SpamVerdict {
+            score: 7.5,
+            decision: SpamDecision::Quarantine,
+            signals: vec![SpamSignal::new("keywords", 7.5, "Contains 'casino'")],
+        };
+        let spam = repo.create(&message_data, &verdict).await.unwrap();
+        assert_eq!(spam.status, "spam");
+        assert!(spam.is_spam);
+        assert_eq!(spam.spam_score, Some(7.5));
+        assert_eq!(spam.spam_reasons.unwrap().0, verdict.signals);
+        repo.update_flags(first.id, true, Some("Call back")).await.unwrap();
+
+        // Spam stays out of the default listing
+        let inbox = repo.find(&MessageFilter::default(), None, 0).await.unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].id, first.id);
+        assert_eq!(inbox[0].notes.as_deref(), Some("Call back"));
+
+        let filter = MessageFilter {
+            status: Some(MessageStatus::Spam),
+            ..Default::default()
+        };
+        assert_eq!(repo.count_matching(&filter).await.unwrap(), 1);
+
+        let filter = MessageFilter {
+            starred: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(repo.count_matching(&filter).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_set_status_and_reply() {
+        let repo = create_test_repository().await;
+        let message_data = create_test_message();
+
+        let first = repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
+        let second = repo.create(&message_data, &SpamVerdict::default()).await.unwrap();
+
+        let updated = repo.set_status(&[first.id, second.id], MessageStatus::Read).await.unwrap();
+        assert_eq!(updated, 2);
+        // Already read
+        assert_eq!(repo.set_status(&[first.id], MessageStatus::Read).await.unwrap(), 0);
+
+        let reply = repo.add_reply(first.id, "Thanks, let's talk on Monday.", Some("admin")).await.unwrap();
+        assert_eq!(reply.message_id, first.id);
+
+        let message = repo.get_by_id(first.id).await.unwrap().unwrap();
+        assert_eq!(message.status, "replied");
+        assert!(message.replied_at.is_some());
+        assert!(message.status_changed_at.is_some());
+        assert_eq!(repo.get_replies(first.id).await.unwrap().len(), 1);
+
+        let counts = repo.count_by_status().await.unwrap();
+        assert!(counts.contains(&("read".to_string(), 1)));
+        assert!(counts.contains(&("replied".to_string(), 1)));
     }
 }
\ No newline at end of file
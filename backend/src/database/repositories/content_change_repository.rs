@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+/// Repository for `content_changes`, which triggers keep up to date on every change to the
+/// profile, projects, skills or posts, deletions included
+pub struct ContentChangeRepository {
+    pool: SqlitePool,
+}
+
+impl ContentChangeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Counter bumped by every change to the profile, projects, skills or posts
+    pub async fn revision(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COALESCE(SUM(revision), 0) FROM content_changes")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// When each source (`profile`, `projects`, `skills`, `posts`) last changed, deletions included
+    pub async fn changes(&self) -> Result<Vec<(String, DateTime<Utc>)>, sqlx::Error> {
+        sqlx::query_as("SELECT source, changed_at FROM content_changes")
+            .fetch_all(&self.pool)
+            .await
+    }
+}
@@ -0,0 +1,116 @@
+use sqlx::{SqliteConnection, SqlitePool};
+use chrono::Utc;
+use crate::models::{Education, CreateEducation, UpdateEducation};
+
+const EDUCATION_COLUMNS: &str =
+    "id, institution, degree, field_of_study, location, start_month, end_month, description, created_at, updated_at";
+
+/// Repository for education database operations
+pub struct EducationRepository {
+    pool: SqlitePool,
+}
+
+impl EducationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get all education entries, most recent first
+    pub async fn get_all(&self) -> Result<Vec<Education>, sqlx::Error> {
+        sqlx::query_as::<_, Education>(&format!(
+            "SELECT {} FROM education ORDER BY start_month DESC, id DESC",
+            EDUCATION_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get education entry by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<Education>, sqlx::Error> {
+        sqlx::query_as::<_, Education>(&format!("SELECT {} FROM education WHERE id = ?", EDUCATION_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Create a new education entry
+    pub async fn create(&self, education: &CreateEducation) -> Result<Education, sqlx::Error> {
+        Self::insert(&mut *self.pool.acquire().await?, education).await
+    }
+
+    /// Update an existing education entry
+    pub async fn update(&self, id: i32, education: &UpdateEducation) -> Result<Option<Education>, sqlx::Error> {
+        Self::update_on(&mut *self.pool.acquire().await?, id, education).await
+    }
+
+    /// Insert a education entry on an open connection
+    pub async fn insert(conn: &mut SqliteConnection, education: &CreateEducation) -> Result<Education, sqlx::Error> {
+        let now = Utc::now();
+
+        sqlx::query_as::<_, Education>(&format!(
+            r#"
+            INSERT INTO education (institution, degree, field_of_study, location, start_month, end_month, description, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            RETURNING {}
+            "#,
+            EDUCATION_COLUMNS
+        ))
+        .bind(&education.institution)
+        .bind(&education.degree)
+        .bind(&education.field_of_study)
+        .bind(&education.location)
+        .bind(&education.start_month)
+        .bind(&education.end_month)
+        .bind(&education.description)
+        .bind(now)
+        .bind(now)
+        .fetch_one(conn)
+        .await
+    }
+
+    /// Update a education entry on an open connection
+    pub async fn update_on(
+        conn: &mut SqliteConnection,
+        id: i32,
+        education: &UpdateEducation,
+    ) -> Result<Option<Education>, sqlx::Error> {
+        // Use COALESCE to keep existing values for fields that are None
+        sqlx::query_as::<_, Education>(&format!(
+            r#"
+            UPDATE education SET
+                institution = COALESCE(?, institution),
+                degree = COALESCE(?, degree),
+                field_of_study = COALESCE(?, field_of_study),
+                location = COALESCE(?, location),
+                start_month = COALESCE(?, start_month),
+                end_month = COALESCE(?, end_month),
+                description = COALESCE(?, description),
+                updated_at = ?
+            WHERE id = ?
+            RETURNING {}
+            "#,
+            EDUCATION_COLUMNS
+        ))
+        .bind(&education.institution)
+        .bind(&education.degree)
+        .bind(&education.field_of_study)
+        .bind(&education.location)
+        .bind(&education.start_month)
+        .bind(&education.end_month)
+        .bind(&education.description)
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_optional(conn)
+        .await
+    }
+
+    /// Delete an education entry
+    pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM education WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,190 @@
+use sqlx::{SqliteConnection, SqlitePool};
+use chrono::Utc;
+use crate::{
+    database::TechnologyRepository,
+    models::{Experience, CreateExperience},
+};
+
+/// Columns selected for an `Experience`; technologies are aggregated from their link table in display order
+const EXPERIENCE_COLUMNS: &str = r#"id, company, role, location, start_month, end_month, is_current, highlights,
+    (SELECT json_group_array(name) FROM (
+        SELECT t.name FROM experience_technologies et
+        JOIN technologies t ON t.id = et.technology_id
+        WHERE et.experience_id = experiences.id
+        ORDER BY et.position
+    )) AS technologies,
+    created_at, updated_at"#;
+
+/// Repository for work experience database operations
+pub struct ExperienceRepository {
+    pool: SqlitePool,
+}
+
+impl ExperienceRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get all experiences, current and most recent first
+    pub async fn get_all(&self) -> Result<Vec<Experience>, sqlx::Error> {
+        sqlx::query_as::<_, Experience>(&format!(
+            "SELECT {} FROM experiences ORDER BY is_current DESC, start_month DESC, id DESC",
+            EXPERIENCE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get experience by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<Experience>, sqlx::Error> {
+        sqlx::query_as::<_, Experience>(&format!("SELECT {} FROM experiences WHERE id = ?", EXPERIENCE_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Create a new experience
+    pub async fn create(&self, experience: &CreateExperience) -> Result<Experience, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let id = Self::insert(&mut tx, experience).await?;
+        tx.commit().await?;
+
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace every field of an existing experience. Updates are merged into a full
+    /// experience first, since clearing the end month cannot be expressed otherwise.
+    pub async fn update(&self, id: i32, experience: &CreateExperience) -> Result<Option<Experience>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        if !Self::replace(&mut tx, id, experience).await? {
+            return Ok(None);
+        }
+        tx.commit().await?;
+
+        self.get_by_id(id).await
+    }
+
+    /// Insert an experience and its technologies on an open connection; returns the new ID
+    pub async fn insert(conn: &mut SqliteConnection, experience: &CreateExperience) -> Result<i32, sqlx::Error> {
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO experiences (company, role, location, start_month, end_month, is_current, highlights, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&experience.company)
+        .bind(&experience.role)
+        .bind(&experience.location)
+        .bind(&experience.start_month)
+        .bind(&experience.end_month)
+        .bind(experience.is_current)
+        .bind(sqlx::types::Json(&experience.highlights))
+        .bind(now)
+        .bind(now)
+        .execute(&mut *conn)
+        .await?;
+
+        let id = result.last_insert_rowid() as i32;
+        TechnologyRepository::replace_for_experience(conn, id, &experience.technologies).await?;
+        Ok(id)
+    }
+
+    /// Replace an experience on an open connection; returns whether the experience exists
+    pub async fn replace(conn: &mut SqliteConnection, id: i32, experience: &CreateExperience) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+            UPDATE experiences SET
+                company = ?,
+                role = ?,
+                location = ?,
+                start_month = ?,
+                end_month = ?,
+                is_current = ?,
+                highlights = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(&experience.company)
+        .bind(&experience.role)
+        .bind(&experience.location)
+        .bind(&experience.start_month)
+        .bind(&experience.end_month)
+        .bind(experience.is_current)
+        .bind(sqlx::types::Json(&experience.highlights))
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        TechnologyRepository::replace_for_experience(conn, id, &experience.technologies).await?;
+        Ok(true)
+    }
+
+    /// Delete an experience
+    pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM experiences WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_repository() -> ExperienceRepository {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        ExperienceRepository::new(pool)
+    }
+
+    fn create_test_experience() -> CreateExperience {
+        CreateExperience {
+            company: "Acme".to_string(),
+            role: "Backend developer".to_string(),
+            location: Some("Lyon".to_string()),
+            start_month: "2019-03".to_string(),
+            end_month: Some("2022-08".to_string()),
+            is_current: false,
+            highlights: vec!["Built the billing API".to_string(), "Mentored two juniors".to_string()],
+            technologies: vec!["Rust".to_string(), "PostgreSQL".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_update_experience() {
+        let repo = create_test_repository().await;
+
+        let created = repo.create(&create_test_experience()).await.unwrap();
+        assert_eq!(created.highlights.0, vec!["Built the billing API", "Mentored two juniors"]);
+        assert_eq!(created.technologies.0, vec!["Rust", "PostgreSQL"]);
+
+        let current = CreateExperience {
+            end_month: None,
+            is_current: true,
+            technologies: vec!["Go".to_string()],
+            ..create_test_experience()
+        };
+        let updated = repo.update(created.id, &current).await.unwrap().unwrap();
+        assert!(updated.is_current);
+        assert_eq!(updated.end_month, None);
+        assert_eq!(updated.technologies.0, vec!["Go"]);
+
+        assert!(repo.update(999, &current).await.unwrap().is_none());
+        assert!(repo.delete(created.id).await.unwrap());
+        assert!(repo.get_all().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+use sqlx::{FromRow, SqlitePool};
+use crate::models::{GalleryItem, Media, MediaResponse, MediaType, MediaVariant, VariantFormat};
+
+const MEDIA_COLUMNS: &str = "id, hash, content_type, size_bytes, original_name, created_at, width, height, processed_at";
+
+const VARIANT_COLUMNS: &str = "media_id, width, height, format, size_bytes, created_at";
+
+/// Gallery row joined with its media
+#[derive(FromRow)]
+struct GalleryRow {
+    position: i32,
+    alt_text: String,
+    #[sqlx(flatten)]
+    media: Media,
+}
+
+/// Repository for uploaded media and project galleries
+pub struct MediaRepository {
+    pool: SqlitePool,
+}
+
+impl MediaRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Record an uploaded file. Content already stored is not added again: the existing
+    /// media is returned, along with whether a new row was created.
+    pub async fn create(
+        &self,
+        hash: &str,
+        media_type: MediaType,
+        size_bytes: i64,
+        dimensions: Option<(u32, u32)>,
+        original_name: Option<&str>,
+    ) -> Result<(Media, bool), sqlx::Error> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO media (hash, content_type, size_bytes, width, height, original_name) VALUES (?, ?, ?, ?, ?, ?)"
+        )
+        .bind(hash)
+        .bind(media_type.as_str())
+        .bind(size_bytes)
+        .bind(dimensions.map(|(width, _)| width))
+        .bind(dimensions.map(|(_, height)| height))
+        .bind(original_name)
+        .execute(&self.pool)
+        .await?;
+
+        let media = self.get_by_hash(hash).await?.ok_or(sqlx::Error::RowNotFound)?;
+        Ok((media, result.rows_affected() > 0))
+    }
+
+    /// Get media by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<Media>, sqlx::Error> {
+        sqlx::query_as::<_, Media>(&format!("SELECT {} FROM media WHERE id = ?", MEDIA_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Get media by content hash
+    pub async fn get_by_hash(&self, hash: &str) -> Result<Option<Media>, sqlx::Error> {
+        sqlx::query_as::<_, Media>(&format!("SELECT {} FROM media WHERE hash = ?", MEDIA_COLUMNS))
+            .bind(hash)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Get a page of media, newest first
+    pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<Media>, sqlx::Error> {
+        sqlx::query_as::<_, Media>(&format!(
+            "SELECT {} FROM media ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?",
+            MEDIA_COLUMNS
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Count stored media
+    pub async fn count(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM media")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Delete media by ID
+    pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM media WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Number of galleries showing the media
+    pub async fn count_uses(&self, id: i32) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM project_media WHERE media_id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Media whose derivatives have not been rendered yet, oldest first
+    pub async fn list_unprocessed(&self, limit: i64) -> Result<Vec<Media>, sqlx::Error> {
+        sqlx::query_as::<_, Media>(&format!(
+            "SELECT {} FROM media WHERE processed_at IS NULL ORDER BY id LIMIT ?",
+            MEDIA_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Record that the derivatives of a media were rendered, along with its dimensions when
+    /// they were not known yet
+    pub async fn mark_processed(&self, id: i32, dimensions: Option<(u32, u32)>) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE media SET processed_at = CURRENT_TIMESTAMP, width = COALESCE(width, ?), height = COALESCE(height, ?) WHERE id = ?"
+        )
+        .bind(dimensions.map(|(width, _)| width))
+        .bind(dimensions.map(|(_, height)| height))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record a rendered derivative, replacing an earlier rendering of it
+    pub async fn save_variant(
+        &self,
+        media_id: i32,
+        format: VariantFormat,
+        (width, height): (u32, u32),
+        size_bytes: i64,
+    ) -> Result<MediaVariant, sqlx::Error> {
+        sqlx::query_as::<_, MediaVariant>(&format!(
+            "INSERT OR REPLACE INTO media_variants (media_id, width, height, format, size_bytes) VALUES (?, ?, ?, ?, ?) RETURNING {}",
+            VARIANT_COLUMNS
+        ))
+        .bind(media_id)
+        .bind(width)
+        .bind(height)
+        .bind(format.as_str())
+        .bind(size_bytes)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Get a derivative of a media
+    pub async fn get_variant(
+        &self,
+        media_id: i32,
+        width: u32,
+        format: VariantFormat,
+    ) -> Result<Option<MediaVariant>, sqlx::Error> {
+        sqlx::query_as::<_, MediaVariant>(&format!(
+            "SELECT {} FROM media_variants WHERE media_id = ? AND width = ? AND format = ?",
+            VARIANT_COLUMNS
+        ))
+        .bind(media_id)
+        .bind(width)
+        .bind(format.as_str())
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Derivatives of the given media
+    pub async fn list_variants(&self, media_ids: &[i32]) -> Result<Vec<MediaVariant>, sqlx::Error> {
+        sqlx::query_as::<_, MediaVariant>(&format!(
+            "SELECT {} FROM media_variants WHERE media_id IN (SELECT value FROM json_each(?)) ORDER BY media_id, format, width",
+            VARIANT_COLUMNS
+        ))
+        .bind(serde_json::to_string(media_ids).unwrap_or_default())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// IDs among the given ones that match no media
+    pub async fn find_missing(&self, ids: &[i32]) -> Result<Vec<i32>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT value FROM json_each(?) WHERE value NOT IN (SELECT id FROM media) ORDER BY value"
+        )
+        .bind(serde_json::to_string(ids).unwrap_or_default())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Gallery of a project, in order
+    pub async fn get_gallery(&self, project_id: i32) -> Result<Vec<GalleryItem>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, GalleryRow>(
+            r#"
+            SELECT pm.position, pm.alt_text, m.id, m.hash, m.content_type, m.size_bytes, m.original_name,
+                   m.created_at, m.width, m.height, m.processed_at
+            FROM project_media pm
+            JOIN media m ON m.id = pm.media_id
+            WHERE pm.project_id = ?
+            ORDER BY pm.position
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let media_ids: Vec<i32> = rows.iter().map(|row| row.media.id).collect();
+        let variants = self.list_variants(&media_ids).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| GalleryItem {
+                position: row.position,
+                alt_text: row.alt_text,
+                media: MediaResponse::new(row.media, variants.clone()),
+            })
+            .collect())
+    }
+
+    /// Replace the gallery of a project with the given media and alt texts, in order
+    pub async fn set_gallery(&self, project_id: i32, items: &[(i32, String)]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM project_media WHERE project_id = ?")
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for (position, (media_id, alt_text)) in items.iter().enumerate() {
+            sqlx::query("INSERT INTO project_media (project_id, media_id, position, alt_text) VALUES (?, ?, ?, ?)")
+                .bind(project_id)
+                .bind(media_id)
+                .bind(position as i32)
+                .bind(alt_text)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_repository() -> (MediaRepository, SqlitePool) {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        (MediaRepository::new(pool.clone()), pool)
+    }
+
+    #[tokio::test]
+    async fn test_create_deduplicates() {
+        let (repo, _pool) = create_test_repository().await;
+
+        let (first, created) = repo.create("abc", MediaType::Png, 10, Some((4, 3)), Some("shot.png")).await.unwrap();
+        assert!(created);
+        let (second, created) = repo.create("abc", MediaType::Png, 10, Some((4, 3)), Some("copy.png")).await.unwrap();
+        assert!(!created);
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.original_name.as_deref(), Some("shot.png"));
+        assert_eq!(repo.count().await.unwrap(), 1);
+        assert_eq!((second.width, second.height), (Some(4), Some(3)));
+    }
+
+    #[tokio::test]
+    async fn test_variants() {
+        let (repo, _pool) = create_test_repository().await;
+        let (media, _) = repo.create("abc", MediaType::Gif, 10, None, None).await.unwrap();
+        assert_eq!(repo.list_unprocessed(10).await.unwrap().len(), 1);
+
+        repo.save_variant(media.id, VariantFormat::Webp, (320, 240), 100).await.unwrap();
+        let variant = repo.save_variant(media.id, VariantFormat::Webp, (320, 240), 90).await.unwrap();
+        assert_eq!(variant.size_bytes, 90);
+        assert!(repo.get_variant(media.id, 320, VariantFormat::Avif).await.unwrap().is_none());
+        assert_eq!(repo.list_variants(&[media.id]).await.unwrap().len(), 1);
+
+        repo.mark_processed(media.id, Some((800, 600))).await.unwrap();
+        assert!(repo.list_unprocessed(10).await.unwrap().is_empty());
+        let media = repo.get_by_id(media.id).await.unwrap().unwrap();
+        assert_eq!((media.width, media.height), (Some(800), Some(600)));
+        assert!(media.processed_at.is_some());
+
+        repo.delete(media.id).await.unwrap();
+        assert!(repo.list_variants(&[media.id]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gallery_order() {
+        let (repo, pool) = create_test_repository().await;
+        let project_id: i32 = sqlx::query_scalar(
+            "INSERT INTO projects (title, description, category) VALUES ('Gallery', 'A project', 'web') RETURNING id"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let (a, _) = repo.create("aaa", MediaType::Png, 1, None, None).await.unwrap();
+        let (b, _) = repo.create("bbb", MediaType::Jpeg, 2, Some((640, 480)), None).await.unwrap();
+        assert_eq!(repo.find_missing(&[a.id, 999]).await.unwrap(), vec![999]);
+
+        repo.set_gallery(project_id, &[(b.id, "Second upload".to_string()), (a.id, String::new())])
+            .await
+            .unwrap();
+        repo.save_variant(b.id, VariantFormat::Webp, (320, 240), 50).await.unwrap();
+        let gallery = repo.get_gallery(project_id).await.unwrap();
+        let ids: Vec<i32> = gallery.iter().map(|item| item.media.id).collect();
+        assert_eq!(ids, vec![b.id, a.id]);
+        assert_eq!(gallery[0].alt_text, "Second upload");
+        assert_eq!(gallery[0].media.url, "/api/media/bbb");
+        assert_eq!(gallery[0].media.variants[0].url, "/api/media/bbb?w=320&fmt=webp");
+        assert!(gallery[1].media.variants.is_empty());
+        assert_eq!(repo.count_uses(a.id).await.unwrap(), 1);
+
+        // Deleting the project empties its gallery
+        sqlx::query("DELETE FROM projects WHERE id = ?").bind(project_id).execute(&pool).await.unwrap();
+        assert_eq!(repo.count_uses(a.id).await.unwrap(), 0);
+    }
+}
@@ -3,8 +3,42 @@ pub mod project_repository;
 pub mod skill_repository;
 pub mod profile_repository;
 pub mod contact_repository;
+pub mod admin_repository;
+pub mod api_key_repository;
+pub mod technology_repository;
+pub mod project_skill_repository;
+pub mod search_repository;
+pub mod notification_repository;
+pub mod spam_repository;
+pub mod rate_limit_repository;
+pub mod challenge_repository;
+pub mod media_repository;
+pub mod post_repository;
+pub mod experience_repository;
+pub mod education_repository;
+pub mod certification_repository;
+pub mod resume_cache_repository;
+pub mod sitemap_repository;
+pub mod content_change_repository;
 
 pub use project_repository::ProjectRepository;
 pub use skill_repository::SkillRepository;
 pub use profile_repository::ProfileRepository;
-pub use contact_repository::ContactRepository;
\ No newline at end of file
+pub use contact_repository::ContactRepository;
+pub use admin_repository::AdminRepository;
+pub use api_key_repository::ApiKeyRepository;
+pub use technology_repository::TechnologyRepository;
+pub use project_skill_repository::ProjectSkillRepository;
+pub use search_repository::SearchRepository;
+pub use notification_repository::NotificationRepository;
+pub use spam_repository::SpamRepository;
+pub use rate_limit_repository::RateLimitRepository;
+pub use challenge_repository::ChallengeRepository;
+pub use media_repository::MediaRepository;
+pub use post_repository::PostRepository;
+pub use experience_repository::ExperienceRepository;
+pub use education_repository::EducationRepository;
+pub use certification_repository::CertificationRepository;
+pub use resume_cache_repository::ResumeCacheRepository;
+pub use sitemap_repository::SitemapRepository;
+pub use content_change_repository::ContentChangeRepository;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use crate::models::{Notification, NotificationJob, NotificationStatus};
+
+const JOB_COLUMNS: &str = "id, kind, channel, contact_message_id, recipient, reply_to, subject, body, status, attempts, last_error, next_attempt_at, sent_at, created_at";
+
+/// Repository for the notification queue
+pub struct NotificationRepository {
+    pool: SqlitePool,
+}
+
+impl NotificationRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue a notification for immediate delivery through a channel
+    pub async fn enqueue(&self, notification: &Notification, channel: &str) -> Result<NotificationJob, sqlx::Error> {
+        let now = Utc::now();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO notification_jobs (kind, channel, contact_message_id, recipient, reply_to, subject, body, next_attempt_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(notification.kind.as_str())
+        .bind(channel)
+        .bind(notification.contact_message_id)
+        .bind(&notification.to)
+        .bind(&notification.reply_to)
+        .bind(&notification.subject)
+        .bind(&notification.body)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        let id = result.last_insert_rowid() as i32;
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Get a queued notification by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<NotificationJob>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationJob>(&format!("SELECT {} FROM notification_jobs WHERE id = ?", JOB_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Get the notifications queued for a contact message
+    pub async fn get_for_message(&self, contact_message_id: i32) -> Result<Vec<NotificationJob>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationJob>(&format!(
+            "SELECT {} FROM notification_jobs WHERE contact_message_id = ? ORDER BY id",
+            JOB_COLUMNS
+        ))
+        .bind(contact_message_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get pending notifications whose next attempt is due, oldest first
+    pub async fn get_due(&self, now: DateTime<Utc>, limit: i64) -> Result<Vec<NotificationJob>, sqlx::Error> {
+        sqlx::query_as::<_, NotificationJob>(&format!(
+            r#"
+            SELECT {} FROM notification_jobs
+            WHERE status = ? AND julianday(next_attempt_at) <= julianday(?)
+            ORDER BY next_attempt_at, id
+            LIMIT ?
+            "#,
+            JOB_COLUMNS
+        ))
+        .bind(NotificationStatus::Pending.as_str())
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Record a successful delivery
+    pub async fn mark_sent(&self, id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE notification_jobs SET status = ?, attempts = attempts + 1, last_error = NULL, sent_at = ? WHERE id = ?")
+            .bind(NotificationStatus::Sent.as_str())
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a failed attempt, scheduling a retry or, without one, giving up
+    pub async fn record_failure(&self, id: i32, error: &str, retry_at: Option<DateTime<Utc>>) -> Result<(), sqlx::Error> {
+        let status = match retry_at {
+            Some(_) => NotificationStatus::Pending,
+            None => NotificationStatus::Failed,
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE notification_jobs
+            SET status = ?, attempts = attempts + 1, last_error = ?, next_attempt_at = COALESCE(?, next_attempt_at)
+            WHERE id = ?
+            "#
+        )
+        .bind(status.as_str())
+        .bind(error)
+        .bind(retry_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
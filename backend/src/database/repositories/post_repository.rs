@@ -0,0 +1,277 @@
+use sqlx::{QueryBuilder, Sqlite, SqlitePool, Transaction};
+use chrono::{DateTime, Utc};
+use crate::models::{Post, PostFilter, PostRecord};
+
+/// Columns selected for a `Post`; tags are aggregated from their link table in display order
+const POST_COLUMNS: &str = r#"id, slug, title, summary, body_markdown, body_html,
+    (SELECT json_group_array(tag) FROM (
+        SELECT tag FROM post_tags WHERE post_id = posts.id ORDER BY position
+    )) AS tags,
+    reading_time_minutes, status, published_at, created_at, updated_at"#;
+
+/// Newest first; drafts, which have no publication date, are placed by their last edit
+const POST_ORDER: &str = " ORDER BY julianday(COALESCE(published_at, updated_at)) DESC, id DESC";
+
+/// Repository for blog post database operations
+pub struct PostRepository {
+    pool: SqlitePool,
+}
+
+impl PostRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get post by ID
+    pub async fn get_by_id(&self, id: i32) -> Result<Option<Post>, sqlx::Error> {
+        sqlx::query_as::<_, Post>(&format!("SELECT {} FROM posts WHERE id = ?", POST_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Get post by slug
+    pub async fn get_by_slug(&self, slug: &str) -> Result<Option<Post>, sqlx::Error> {
+        sqlx::query_as::<_, Post>(&format!("SELECT {} FROM posts WHERE slug = ?", POST_COLUMNS))
+            .bind(slug)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Whether a post other than `except_id` uses the slug
+    pub async fn slug_exists(&self, slug: &str, except_id: Option<i32>) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM posts WHERE slug = ? AND id IS NOT ?)")
+            .bind(slug)
+            .bind(except_id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Create a new post
+    pub async fn create(&self, post: &PostRecord) -> Result<Post, sqlx::Error> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO posts (slug, title, summary, body_markdown, body_html, reading_time_minutes, status, published_at, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&post.slug)
+        .bind(&post.title)
+        .bind(&post.summary)
+        .bind(&post.body_markdown)
+        .bind(&post.body_html)
+        .bind(post.reading_time_minutes)
+        .bind(post.status.as_str())
+        .bind(post.published_at)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        let id = result.last_insert_rowid() as i32;
+        replace_tags(&mut tx, id, &post.tags).await?;
+        tx.commit().await?;
+
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Replace every field of an existing post
+    pub async fn update(&self, id: i32, post: &PostRecord) -> Result<Option<Post>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE posts SET
+                slug = ?,
+                title = ?,
+                summary = ?,
+                body_markdown = ?,
+                body_html = ?,
+                reading_time_minutes = ?,
+                status = ?,
+                published_at = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#
+        )
+        .bind(&post.slug)
+        .bind(&post.title)
+        .bind(&post.summary)
+        .bind(&post.body_markdown)
+        .bind(&post.body_html)
+        .bind(post.reading_time_minutes)
+        .bind(post.status.as_str())
+        .bind(post.published_at)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        replace_tags(&mut tx, id, &post.tags).await?;
+        tx.commit().await?;
+
+        self.get_by_id(id).await
+    }
+
+    /// Delete a post
+    pub async fn delete(&self, id: i32) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM posts WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// List posts matching every set filter, newest first
+    pub async fn find(&self, filter: &PostFilter, limit: i64, offset: i64) -> Result<Vec<Post>, sqlx::Error> {
+        let mut builder = QueryBuilder::<Sqlite>::new(format!("SELECT {} FROM posts WHERE 1 = 1", POST_COLUMNS));
+        push_filters(&mut builder, filter);
+
+        builder
+            .push(POST_ORDER)
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        builder.build_query_as::<Post>().fetch_all(&self.pool).await
+    }
+
+    /// Count posts matching every set filter
+    pub async fn count_matching(&self, filter: &PostFilter) -> Result<i64, sqlx::Error> {
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM posts WHERE 1 = 1");
+        push_filters(&mut builder, filter);
+
+        builder.build_query_scalar().fetch_one(&self.pool).await
+    }
+}
+
+/// Replace the tags of a post, keeping their order
+async fn replace_tags(tx: &mut Transaction<'_, Sqlite>, post_id: i32, tags: &[String]) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM post_tags WHERE post_id = ?")
+        .bind(post_id)
+        .execute(&mut **tx)
+        .await?;
+
+    for (position, tag) in tags.iter().enumerate() {
+        sqlx::query("INSERT OR IGNORE INTO post_tags (post_id, tag, position) VALUES (?, ?, ?)")
+            .bind(post_id)
+            .bind(tag)
+            .bind(position as i32)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Append a `WHERE` condition for each set filter
+fn push_filters(builder: &mut QueryBuilder<'_, Sqlite>, filter: &PostFilter) {
+    // Timestamps are stored in more than one text format, so compare them as julian days
+    if let Some(public_at) = filter.public_at {
+        builder
+            .push(" AND status != 'draft' AND published_at IS NOT NULL AND julianday(published_at) <= julianday(")
+            .push_bind(sqlite_timestamp(public_at))
+            .push(")");
+    }
+    if let Some(status) = filter.status {
+        builder.push(" AND status = ").push_bind(status.as_str());
+    }
+    if let Some(tag) = &filter.tag {
+        builder
+            .push(" AND id IN (SELECT post_id FROM post_tags WHERE tag = ")
+            .push_bind(tag.clone())
+            .push(")");
+    }
+}
+
+/// Format a timestamp the way SQLite date functions expect it
+pub(crate) fn sqlite_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M:%S%.f").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::models::PostStatus;
+
+    async fn create_test_repository() -> PostRepository {
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        PostRepository::new(pool)
+    }
+
+    fn create_test_record(slug: &str, status: PostStatus, published_at: Option<DateTime<Utc>>) -> PostRecord {
+        PostRecord {
+            slug: slug.to_string(),
+            title: "Test Post".to_string(),
+            summary: "A test post".to_string(),
+            body_markdown: "Hello *world*".to_string(),
+            body_html: "<p>Hello <em>world</em></p>\n".to_string(),
+            reading_time_minutes: 1,
+            tags: vec!["rust".to_string(), "sqlite".to_string()],
+            status,
+            published_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_delete() {
+        let repo = create_test_repository().await;
+
+        let created = repo.create(&create_test_record("hello", PostStatus::Draft, None)).await.unwrap();
+        assert_eq!(created.slug, "hello");
+        assert_eq!(created.tags.0, vec!["rust", "sqlite"]);
+        assert!(repo.slug_exists("hello", None).await.unwrap());
+        assert!(!repo.slug_exists("hello", Some(created.id)).await.unwrap());
+
+        let mut record = create_test_record("hello-again", PostStatus::Published, Some(Utc::now()));
+        record.tags = vec!["sqlite".to_string(), "blog".to_string()];
+        let updated = repo.update(created.id, &record).await.unwrap().unwrap();
+        assert_eq!(updated.slug, "hello-again");
+        assert_eq!(updated.status, "published");
+        assert_eq!(updated.tags.0, vec!["sqlite", "blog"]);
+        assert!(repo.get_by_slug("hello").await.unwrap().is_none());
+
+        assert!(repo.update(999, &record).await.unwrap().is_none());
+        assert!(repo.delete(created.id).await.unwrap());
+        assert!(repo.get_by_id(created.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_find_public_posts() {
+        let repo = create_test_repository().await;
+        let now = Utc::now();
+
+        repo.create(&create_test_record("draft", PostStatus::Draft, Some(now - Duration::days(3)))).await.unwrap();
+        repo.create(&create_test_record("old", PostStatus::Published, Some(now - Duration::days(2)))).await.unwrap();
+        repo.create(&create_test_record("recent", PostStatus::Published, Some(now - Duration::days(1)))).await.unwrap();
+        repo.create(&create_test_record("due", PostStatus::Scheduled, Some(now - Duration::minutes(1)))).await.unwrap();
+        repo.create(&create_test_record("later", PostStatus::Scheduled, Some(now + Duration::days(1)))).await.unwrap();
+
+        let public = PostFilter { public_at: Some(now), ..Default::default() };
+        let slugs: Vec<String> = repo.find(&public, 10, 0).await.unwrap().into_iter().map(|p| p.slug).collect();
+        assert_eq!(slugs, vec!["due", "recent", "old"]);
+        assert_eq!(repo.count_matching(&public).await.unwrap(), 3);
+
+        let scheduled = PostFilter { status: Some(PostStatus::Scheduled), ..Default::default() };
+        assert_eq!(repo.count_matching(&scheduled).await.unwrap(), 2);
+
+        let tagged = PostFilter { tag: Some("sqlite".to_string()), ..public };
+        assert_eq!(repo.find(&tagged, 1, 1).await.unwrap()[0].slug, "recent");
+        let untagged = PostFilter { tag: Some("go".to_string()), ..Default::default() };
+        assert_eq!(repo.count_matching(&untagged).await.unwrap(), 0);
+    }
+}
@@ -1,4 +1,4 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use chrono::Utc;
 use crate::models::{Profile, UpdateProfile};
 
@@ -28,8 +28,12 @@ impl ProfileRepository {
             return Ok(None);
         }
 
-        let now = Utc::now();
+        Self::update_on(&mut *self.pool.acquire().await?, profile).await?;
+        self.get().await
+    }
 
+    /// Update the profile on an open connection
+    pub async fn update_on(conn: &mut SqliteConnection, profile: &UpdateProfile) -> Result<(), sqlx::Error> {
         // Use COALESCE to keep existing values for fields that are None
         sqlx::query(
             r#"
@@ -56,17 +60,28 @@ impl ProfileRepository {
         .bind(&profile.linkedin_url)
         .bind(&profile.github_url)
         .bind(&profile.twitter_url)
-        .bind(now)
-        .execute(&self.pool)
+        .bind(Utc::now())
+        .execute(conn)
         .await?;
-        
-        self.get().await
+
+        Ok(())
     }
 
     /// Create initial profile (used during setup)
     pub async fn create_initial(&self, name: &str, title: &str, bio: &str, email: &str, location: &str) -> Result<Profile, sqlx::Error> {
-        let now = Utc::now();
-        
+        Self::insert_initial(&mut *self.pool.acquire().await?, name, title, bio, email, location).await?;
+        self.get().await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Create the initial profile on an open connection, replacing any existing one
+    pub async fn insert_initial(
+        conn: &mut SqliteConnection,
+        name: &str,
+        title: &str,
+        bio: &str,
+        email: &str,
+        location: &str,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query(
             "INSERT OR REPLACE INTO profile (id, name, title, bio, email, location, updated_at) VALUES (1, ?, ?, ?, ?, ?, ?)"
         )
@@ -75,11 +90,11 @@ impl ProfileRepository {
         .bind(bio)
         .bind(email)
         .bind(location)
-        .bind(now)
-        .execute(&self.pool)
+        .bind(Utc::now())
+        .execute(conn)
         .await?;
 
-        self.get().await?.ok_or(sqlx::Error::RowNotFound)
+        Ok(())
     }
 
     /// Check if profile exists
@@ -90,6 +105,15 @@ impl ProfileRepository {
         
         Ok(count > 0)
     }
+
+    /// Check if profile exists, on a connection that may be inside a transaction
+    pub async fn exists_on(conn: &mut SqliteConnection) -> Result<bool, sqlx::Error> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM profile WHERE id = 1")
+            .fetch_one(conn)
+            .await?;
+
+        Ok(count > 0)
+    }
 }
 
 #[cfg(test)]
@@ -230,21 +254,4 @@ mod tests {
         let result = repo.update(&update_data).await.unwrap();
         assert!(result.is_some());
     }
-}
-
-impl Default for UpdateProfile {
-    fn default() -> Self {
-        Self {
-            name: None,
-            title: None,
-            bio: None,
-            email: None,
-            phone: None,
-            location: None,
-
-            linkedin_url: None,
-            github_url: None,
-            twitter_url: None,
-        }
-    }
 }
\ No newline at end of file
@@ -1,6 +1,19 @@
-use sqlx::SqlitePool;
-use chrono::Utc;
-use crate::models::{Project, CreateProject, UpdateProject};
+use sqlx::{QueryBuilder, Sqlite, SqliteConnection, SqlitePool};
+use chrono::{DateTime, Utc};
+use crate::{
+    database::{ProjectSkillRepository, TechnologyRepository},
+    models::{Project, CreateProject, UpdateProject, ProjectFilter, ProjectSort, ProjectSortField},
+};
+
+/// Columns selected for a `Project`; technologies are aggregated from their link table in display order
+const PROJECT_COLUMNS: &str = r#"id, title, description, long_description,
+    (SELECT json_group_array(name) FROM (
+        SELECT t.name FROM project_technologies pt
+        JOIN technologies t ON t.id = pt.technology_id
+        WHERE pt.project_id = projects.id
+        ORDER BY pt.position
+    )) AS technologies,
+    github_url, demo_url, image_url, category, featured, created_at, updated_at"#;
 
 /// Repository for project database operations
 pub struct ProjectRepository {
@@ -15,16 +28,25 @@ impl ProjectRepository {
     /// Get all projects
     pub async fn get_all(&self) -> Result<Vec<Project>, sqlx::Error> {
         sqlx::query_as::<_, Project>(
-            "SELECT id, title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at FROM projects ORDER BY created_at DESC"
+            &format!("SELECT {} FROM projects ORDER BY created_at DESC", PROJECT_COLUMNS)
         )
         .fetch_all(&self.pool)
         .await
     }
 
+    /// Get all projects, on a connection that may be inside a transaction
+    pub async fn get_all_on(conn: &mut SqliteConnection) -> Result<Vec<Project>, sqlx::Error> {
+        sqlx::query_as::<_, Project>(
+            &format!("SELECT {} FROM projects ORDER BY created_at DESC", PROJECT_COLUMNS)
+        )
+        .fetch_all(conn)
+        .await
+    }
+
     /// Get project by ID
     pub async fn get_by_id(&self, id: i32) -> Result<Option<Project>, sqlx::Error> {
         sqlx::query_as::<_, Project>(
-            "SELECT id, title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at FROM projects WHERE id = ?"
+            &format!("SELECT {} FROM projects WHERE id = ?", PROJECT_COLUMNS)
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -34,7 +56,7 @@ impl ProjectRepository {
     /// Get projects by category
     pub async fn get_by_category(&self, category: &str) -> Result<Vec<Project>, sqlx::Error> {
         sqlx::query_as::<_, Project>(
-            "SELECT id, title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at FROM projects WHERE category = ? ORDER BY created_at DESC"
+            &format!("SELECT {} FROM projects WHERE category = ? ORDER BY created_at DESC", PROJECT_COLUMNS)
         )
         .bind(category)
         .fetch_all(&self.pool)
@@ -44,29 +66,70 @@ impl ProjectRepository {
     /// Get featured projects
     pub async fn get_featured(&self) -> Result<Vec<Project>, sqlx::Error> {
         sqlx::query_as::<_, Project>(
-            "SELECT id, title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at FROM projects WHERE featured = true ORDER BY created_at DESC"
+            &format!("SELECT {} FROM projects WHERE featured = true ORDER BY created_at DESC", PROJECT_COLUMNS)
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get projects using a technology, matched by slug
+    pub async fn get_by_technology(&self, slug: &str) -> Result<Vec<Project>, sqlx::Error> {
+        sqlx::query_as::<_, Project>(
+            &format!(
+                r#"
+                SELECT {} FROM projects
+                WHERE id IN (
+                    SELECT pt.project_id FROM project_technologies pt
+                    JOIN technologies t ON t.id = pt.technology_id
+                    WHERE t.slug = ?
+                )
+                ORDER BY created_at DESC
+                "#,
+                PROJECT_COLUMNS
+            )
         )
+        .bind(slug)
         .fetch_all(&self.pool)
         .await
     }
 
     /// Create a new project
     pub async fn create(&self, project: &CreateProject) -> Result<Project, sqlx::Error> {
-        let technologies_json = project.technologies_as_json()
-            .map_err(|e| sqlx::Error::decode(e))?;
+        let mut tx = self.pool.begin().await?;
+        let id = Self::insert(&mut tx, project).await?;
+        tx.commit().await?;
         
+        // Fetch the created project
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Update an existing project
+    pub async fn update(&self, id: i32, project: &UpdateProject) -> Result<Option<Project>, sqlx::Error> {
+        // Check if project exists first
+        if self.get_by_id(id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        Self::update_on(&mut tx, id, project).await?;
+        tx.commit().await?;
+
+        self.get_by_id(id).await
+    }
+
+    /// Insert a project and its technologies on an open connection; returns the new ID
+    pub async fn insert(conn: &mut SqliteConnection, project: &CreateProject) -> Result<i32, sqlx::Error> {
         let now = Utc::now();
         
         let result = sqlx::query(
             r#"
-            INSERT INTO projects (title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO projects (title, description, long_description, github_url, demo_url, image_url, category, featured, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#
         )
         .bind(&project.title)
         .bind(&project.description)
         .bind(&project.long_description)
-        .bind(&technologies_json)
         .bind(&project.github_url)
         .bind(&project.demo_url)
         .bind(&project.image_url)
@@ -74,38 +137,24 @@ impl ProjectRepository {
         .bind(project.featured.unwrap_or(false))
         .bind(now)
         .bind(now)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         let id = result.last_insert_rowid() as i32;
-        
-        // Fetch the created project
-        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+        TechnologyRepository::replace_for_project(conn, id, &project.technologies).await?;
+        ProjectSkillRepository::suggest_for_project(conn, id).await?;
+        Ok(id)
     }
 
-    /// Update an existing project
-    pub async fn update(&self, id: i32, project: &UpdateProject) -> Result<Option<Project>, sqlx::Error> {
-        // Check if project exists first
-        if self.get_by_id(id).await?.is_none() {
-            return Ok(None);
-        }
-
-        let now = Utc::now();
-        let technologies_json = if project.technologies.is_some() {
-            Some(project.technologies_as_json()
-                .map_err(|e| sqlx::Error::decode(e))?)
-        } else {
-            None
-        };
-
+    /// Update a project on an open connection; returns whether the project exists
+    pub async fn update_on(conn: &mut SqliteConnection, id: i32, project: &UpdateProject) -> Result<bool, sqlx::Error> {
         // Use a comprehensive update query with COALESCE to keep existing values
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE projects SET 
                 title = COALESCE(?, title),
                 description = COALESCE(?, description),
                 long_description = COALESCE(?, long_description),
-                technologies = COALESCE(?, technologies),
                 github_url = COALESCE(?, github_url),
                 demo_url = COALESCE(?, demo_url),
                 image_url = COALESCE(?, image_url),
@@ -118,18 +167,24 @@ impl ProjectRepository {
         .bind(&project.title)
         .bind(&project.description)
         .bind(&project.long_description)
-        .bind(technologies_json.flatten())
         .bind(&project.github_url)
         .bind(&project.demo_url)
         .bind(&project.image_url)
         .bind(&project.category)
         .bind(project.featured)
-        .bind(now)
+        .bind(Utc::now())
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
-        self.get_by_id(id).await
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+        if let Some(technologies) = &project.technologies {
+            TechnologyRepository::replace_for_project(conn, id, technologies).await?;
+            ProjectSkillRepository::suggest_for_project(conn, id).await?;
+        }
+        Ok(true)
     }
 
     /// Delete a project
@@ -142,17 +197,6 @@ impl ProjectRepository {
         Ok(result.rows_affected() > 0)
     }
 
-    /// Get projects with pagination
-    pub async fn get_paginated(&self, limit: i64, offset: i64) -> Result<Vec<Project>, sqlx::Error> {
-        sqlx::query_as::<_, Project>(
-            "SELECT id, title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at FROM projects ORDER BY created_at DESC LIMIT ? OFFSET ?"
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await
-    }
-
     /// Count total projects
     pub async fn count(&self) -> Result<i64, sqlx::Error> {
         sqlx::query_scalar("SELECT COUNT(*) FROM projects")
@@ -160,18 +204,124 @@ impl ProjectRepository {
             .await
     }
 
-    /// Search projects by title or description
-    pub async fn search(&self, query: &str) -> Result<Vec<Project>, sqlx::Error> {
-        let search_pattern = format!("%{}%", query);
-        
-        sqlx::query_as::<_, Project>(
-            "SELECT id, title, description, long_description, technologies, github_url, demo_url, image_url, category, featured, created_at, updated_at FROM projects WHERE title LIKE ? OR description LIKE ? ORDER BY created_at DESC"
-        )
-        .bind(&search_pattern)
-        .bind(&search_pattern)
+    /// List projects matching every set filter, in the given order.
+    /// `after` is the `(sort key, id)` of the last project of the previous page, as returned by `sort_key`.
+    pub async fn find(
+        &self,
+        filter: &ProjectFilter,
+        sort: ProjectSort,
+        after: Option<(String, i32)>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Project>, sqlx::Error> {
+        let mut builder = QueryBuilder::<Sqlite>::new(format!("SELECT {} FROM projects WHERE 1 = 1", PROJECT_COLUMNS));
+        push_filters(&mut builder, filter);
+
+        let sort_key = sort_key_expression(sort.field);
+        let (comparison, direction) = if sort.descending { ("<", "DESC") } else { (">", "ASC") };
+
+        if let Some((key, id)) = after {
+            builder
+                .push(format!(" AND ({}, id) {} (", sort_key, comparison))
+                .push_bind(key)
+                .push(", ")
+                .push_bind(id)
+                .push(")");
+        }
+
+        builder
+            .push(format!(" ORDER BY {} {}, id {} LIMIT ", sort_key, direction, direction))
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        builder.build_query_as::<Project>().fetch_all(&self.pool).await
+    }
+
+    /// Count projects matching every set filter
+    pub async fn count_matching(&self, filter: &ProjectFilter) -> Result<i64, sqlx::Error> {
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT COUNT(*) FROM projects WHERE 1 = 1");
+        push_filters(&mut builder, filter);
+
+        builder.build_query_scalar().fetch_one(&self.pool).await
+    }
+
+    /// Get the stored sort key of a project, used to build pagination cursors
+    pub async fn sort_key(&self, id: i32, field: ProjectSortField) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar(&format!("SELECT CAST({} AS TEXT) FROM projects WHERE id = ?", sort_key_expression(field)))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+    }
+
+    /// Run an FTS5 match expression against the indexed projects, best matches first.
+    /// Titles, long descriptions and technologies are all searched.
+    pub async fn search(&self, match_expression: &str) -> Result<Vec<Project>, sqlx::Error> {
+        sqlx::query_as::<_, Project>(&format!(
+            "SELECT {} FROM projects JOIN (\
+                 SELECT CAST(ref_id AS INTEGER) AS ref_id, bm25(search_index, 0.0, 0.0, 10.0, 2.0, 5.0) AS rank \
+                 FROM search_index WHERE search_index MATCH ? AND kind = 'project'\
+             ) hits ON hits.ref_id = projects.id ORDER BY hits.rank, projects.id",
+            PROJECT_COLUMNS
+        ))
+        .bind(match_expression)
         .fetch_all(&self.pool)
         .await
     }
+
+    /// Whether a project already has this title, ignoring case
+    pub async fn title_exists(&self, title: &str) -> Result<bool, sqlx::Error> {
+        let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM projects").fetch_all(&self.pool).await?;
+        let title = title.to_lowercase();
+
+        Ok(titles.iter().any(|existing| existing.to_lowercase() == title))
+    }
+}
+
+/// SQL expression ordering projects by a sort field
+fn sort_key_expression(field: ProjectSortField) -> &'static str {
+    match field {
+        ProjectSortField::CreatedAt => "CAST(created_at AS TEXT)",
+        ProjectSortField::UpdatedAt => "CAST(updated_at AS TEXT)",
+        ProjectSortField::Title => "LOWER(title)",
+    }
+}
+
+/// Append a `WHERE` condition for each set filter
+fn push_filters(builder: &mut QueryBuilder<'_, Sqlite>, filter: &ProjectFilter) {
+    if let Some(category) = &filter.category {
+        builder.push(" AND category = ").push_bind(category.clone());
+    }
+    if let Some(featured) = filter.featured {
+        builder.push(" AND featured = ").push_bind(featured);
+    }
+    if let Some(technology) = &filter.technology {
+        builder
+            .push(
+                " AND id IN (SELECT pt.project_id FROM project_technologies pt \
+                 JOIN technologies t ON t.id = pt.technology_id WHERE t.slug = ",
+            )
+            .push_bind(technology.clone())
+            .push(")");
+    }
+    // Timestamps are stored in more than one text format, so compare them as julian days
+    if let Some(created_after) = filter.created_after {
+        builder.push(" AND julianday(created_at) >= julianday(").push_bind(sqlite_timestamp(created_after)).push(")");
+    }
+    if let Some(created_before) = filter.created_before {
+        builder.push(" AND julianday(created_at) < julianday(").push_bind(sqlite_timestamp(created_before)).push(")");
+    }
+    if let Some(search) = &filter.search {
+        builder
+            .push(" AND id IN (SELECT ref_id FROM search_index WHERE kind = 'project' AND search_index MATCH ")
+            .push_bind(search.clone())
+            .push(")");
+    }
+}
+
+/// Format a timestamp the way SQLite date functions expect it
+fn sqlite_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M:%S%.f").to_string()
 }
 
 #[cfg(test)]
@@ -184,28 +334,7 @@ mod tests {
             .await
             .unwrap();
 
-        // Create tables manually for testing
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS projects (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                long_description TEXT,
-                technologies TEXT NOT NULL,
-                github_url TEXT,
-                demo_url TEXT,
-                image_url TEXT,
-                category TEXT NOT NULL,
-                featured BOOLEAN DEFAULT FALSE,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
 
         ProjectRepository::new(pool)
     }
@@ -232,7 +361,7 @@ mod tests {
         let created = repo.create(&project_data).await.unwrap();
         assert_eq!(created.title, project_data.title);
         assert_eq!(created.description, project_data.description);
-        assert_eq!(created.featured, true);
+        assert!(created.featured);
 
         let retrieved = repo.get_by_id(created.id).await.unwrap().unwrap();
         assert_eq!(retrieved.id, created.id);
@@ -247,7 +376,7 @@ mod tests {
         repo.create(&project_data).await.unwrap();
         
         let projects = repo.get_all().await.unwrap();
-        assert!(projects.len() >= 1);
+        assert!(!projects.is_empty());
     }
 
     #[tokio::test]
@@ -258,7 +387,7 @@ mod tests {
         repo.create(&project_data).await.unwrap();
         
         let projects = repo.get_by_category("web").await.unwrap();
-        assert!(projects.len() >= 1);
+        assert!(!projects.is_empty());
         assert!(projects.iter().all(|p| p.category == "web"));
     }
 
@@ -270,7 +399,7 @@ mod tests {
         repo.create(&project_data).await.unwrap();
         
         let featured = repo.get_featured().await.unwrap();
-        assert!(featured.len() >= 1);
+        assert!(!featured.is_empty());
         assert!(featured.iter().all(|p| p.featured));
     }
 
@@ -291,7 +420,7 @@ mod tests {
         let updated = repo.update(created.id, &update_data).await.unwrap().unwrap();
         assert_eq!(updated.title, "Updated Title");
         assert_eq!(updated.description, "Updated description");
-        assert_eq!(updated.featured, false);
+        assert!(!updated.featured);
     }
 
     #[tokio::test]
@@ -313,11 +442,22 @@ mod tests {
         let repo = create_test_repository().await;
         let project_data = create_test_project();
         
-        repo.create(&project_data).await.unwrap();
+        let created = repo.create(&project_data).await.unwrap();
+        let mut other = create_test_project();
+        other.title = "Weather Station".to_string();
+        other.description = "Reads a test sensor".to_string();
+        repo.create(&other).await.unwrap();
         
-        let results = repo.search("Test").await.unwrap();
-        assert!(results.len() >= 1);
-        assert!(results.iter().any(|p| p.title.contains("Test")));
+        // Title matches rank first; long descriptions and technologies are searched too
+        let results = repo.search("\"test\"").await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, created.id);
+        assert_eq!(repo.search("\"longer\"").await.unwrap().len(), 2);
+        assert_eq!(repo.search("\"sqlite\"").await.unwrap().len(), 2);
+        assert!(repo.search("\"missing\"").await.unwrap().is_empty());
+
+        assert!(repo.title_exists("test PROJECT").await.unwrap());
+        assert!(!repo.title_exists("Test").await.unwrap());
     }
 
     #[tokio::test]
@@ -331,20 +471,4 @@ mod tests {
         
         assert_eq!(new_count, initial_count + 1);
     }
-}
-
-impl Default for UpdateProject {
-    fn default() -> Self {
-        Self {
-            title: None,
-            description: None,
-            long_description: None,
-            technologies: None,
-            github_url: None,
-            demo_url: None,
-            image_url: None,
-            category: None,
-            featured: None,
-        }
-    }
 }
\ No newline at end of file
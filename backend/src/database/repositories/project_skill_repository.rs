@@ -0,0 +1,150 @@
+use sqlx::{SqliteConnection, SqlitePool};
+use crate::models::{LinkSource, LinkedProject, LinkedSkill, Technology};
+
+/// Repository for links between projects and skills
+pub struct ProjectSkillRepository {
+    pool: SqlitePool,
+}
+
+impl ProjectSkillRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the skills linked to a project, strongest first
+    pub async fn get_skills_for_project(&self, project_id: i32) -> Result<Vec<LinkedSkill>, sqlx::Error> {
+        sqlx::query_as::<_, LinkedSkill>(
+            r#"
+            SELECT s.id, s.name, s.category, s.level, ps.source
+            FROM project_skills ps
+            JOIN skills s ON s.id = ps.skill_id
+            WHERE ps.project_id = ?
+            ORDER BY s.level DESC, s.name
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Get the projects linked to a skill, most recent first
+    pub async fn get_projects_for_skill(&self, skill_id: i32) -> Result<Vec<LinkedProject>, sqlx::Error> {
+        sqlx::query_as::<_, LinkedProject>(
+            r#"
+            SELECT p.id, p.title, p.category, p.featured, p.created_at, ps.source
+            FROM project_skills ps
+            JOIN projects p ON p.id = ps.project_id
+            WHERE ps.skill_id = ?
+            ORDER BY p.created_at DESC
+            "#
+        )
+        .bind(skill_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Return the IDs among `skill_ids` that do not match any skill
+    pub async fn find_missing_skills(&self, skill_ids: &[i32]) -> Result<Vec<i32>, sqlx::Error> {
+        let ids_json = serde_json::to_string(skill_ids).map_err(sqlx::Error::decode)?;
+
+        sqlx::query_scalar(
+            "SELECT DISTINCT value FROM json_each(?) WHERE value NOT IN (SELECT id FROM skills) ORDER BY value"
+        )
+        .bind(ids_json)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Replace the links of a project with manual links to the given skills
+    pub async fn set_for_project(&self, project_id: i32, skill_ids: &[i32]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM project_skills WHERE project_id = ?")
+            .bind(project_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for skill_id in skill_ids {
+            sqlx::query("INSERT OR IGNORE INTO project_skills (project_id, skill_id, source) VALUES (?, ?, ?)")
+                .bind(project_id)
+                .bind(skill_id)
+                .bind(LinkSource::Manual.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Recompute suggested links of a project from its technologies; manual links are kept
+    pub async fn suggest_for_project(conn: &mut SqliteConnection, project_id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM project_skills WHERE project_id = ? AND source = ?")
+            .bind(project_id)
+            .bind(LinkSource::Suggested.as_str())
+            .execute(&mut *conn)
+            .await?;
+
+        // Skill names are slugged in Rust, as technologies are: SQLite only lowercases ASCII
+        let slugs: Vec<String> = sqlx::query_scalar(
+            r#"
+            SELECT t.slug
+            FROM project_technologies pt
+            JOIN technologies t ON t.id = pt.technology_id
+            WHERE pt.project_id = ?
+            "#
+        )
+        .bind(project_id)
+        .fetch_all(&mut *conn)
+        .await?;
+        let skills: Vec<(i32, String)> = sqlx::query_as("SELECT id, name FROM skills")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        for (skill_id, name) in skills {
+            if slugs.contains(&Technology::slug_for(&name)) {
+                sqlx::query("INSERT OR IGNORE INTO project_skills (project_id, skill_id, source) VALUES (?, ?, ?)")
+                    .bind(project_id)
+                    .bind(skill_id)
+                    .bind(LinkSource::Suggested.as_str())
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Recompute suggested links of a skill from project technologies; manual links are kept
+    pub async fn suggest_for_skill(conn: &mut SqliteConnection, skill_id: i32) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM project_skills WHERE skill_id = ? AND source = ?")
+            .bind(skill_id)
+            .bind(LinkSource::Suggested.as_str())
+            .execute(&mut *conn)
+            .await?;
+
+        let name: Option<String> = sqlx::query_scalar("SELECT name FROM skills WHERE id = ?")
+            .bind(skill_id)
+            .fetch_optional(&mut *conn)
+            .await?;
+        let Some(name) = name else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO project_skills (project_id, skill_id, source)
+            SELECT pt.project_id, ?, ?
+            FROM project_technologies pt
+            JOIN technologies t ON t.id = pt.technology_id
+            WHERE t.slug = ?
+            "#
+        )
+        .bind(skill_id)
+        .bind(LinkSource::Suggested.as_str())
+        .bind(Technology::slug_for(&name))
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use sqlx::SqlitePool;
+
+/// Repository for persisted rate limit token buckets
+pub struct RateLimitRepository {
+    pool: SqlitePool,
+}
+
+impl RateLimitRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Refill a bucket for the time elapsed since its last use and take a token if one is
+    /// available, in a single statement so concurrent requests cannot both take the last one.
+    /// Returns the tokens left and whether a token was taken.
+    pub async fn take(&self, key: &str, capacity: f64, tokens_per_ms: f64, now_ms: i64) -> Result<(f64, bool), sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            INSERT INTO rate_limit_buckets (key, tokens, allowed, updated_at)
+            VALUES (?1, ?2 - 1, 1, ?4)
+            ON CONFLICT(key) DO UPDATE SET
+                tokens = CASE
+                    WHEN MIN(?2, tokens + MAX(?4 - updated_at, 0) * ?3) >= 1
+                    THEN MIN(?2, tokens + MAX(?4 - updated_at, 0) * ?3) - 1
+                    ELSE MIN(?2, tokens + MAX(?4 - updated_at, 0) * ?3)
+                END,
+                allowed = MIN(?2, tokens + MAX(?4 - updated_at, 0) * ?3) >= 1,
+                updated_at = ?4
+            RETURNING tokens, allowed
+            "#
+        )
+        .bind(key)
+        .bind(capacity)
+        .bind(tokens_per_ms)
+        .bind(now_ms)
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Add a token back to a bucket, without exceeding its capacity
+    pub async fn give_back(&self, key: &str, capacity: f64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE rate_limit_buckets SET tokens = MIN(?, tokens + 1) WHERE key = ?")
+            .bind(capacity)
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Delete buckets untouched since the given time, which have refilled completely
+    pub async fn prune(&self, before_ms: i64) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM rate_limit_buckets WHERE updated_at < ?")
+            .bind(before_ms)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_take_and_refill() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let repo = RateLimitRepository::new(pool);
+
+        // Two tokens, refilling one per second
+        assert_eq!(repo.take("login:a", 2.0, 0.001, 0).await.unwrap(), (1.0, true));
+        assert_eq!(repo.take("login:a", 2.0, 0.001, 0).await.unwrap(), (0.0, true));
+        assert_eq!(repo.take("login:a", 2.0, 0.001, 500).await.unwrap(), (0.5, false));
+        assert_eq!(repo.take("login:b", 2.0, 0.001, 500).await.unwrap(), (1.0, true));
+
+        let (tokens, allowed) = repo.take("login:a", 2.0, 0.001, 1000).await.unwrap();
+        assert!(allowed);
+        assert!(tokens.abs() < 1e-9);
+
+        assert_eq!(repo.prune(800).await.unwrap(), 1);
+    }
+}
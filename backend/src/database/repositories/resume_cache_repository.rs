@@ -0,0 +1,61 @@
+use sqlx::SqlitePool;
+
+/// Repository for rendered PDF résumés
+pub struct ResumeCacheRepository {
+    pool: SqlitePool,
+}
+
+impl ResumeCacheRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the cached PDF for these options if it was rendered from content with this fingerprint
+    pub async fn get(
+        &self,
+        template: &str,
+        page_size: &str,
+        language: &str,
+        fingerprint: &str,
+    ) -> Result<Option<Vec<u8>>, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT content FROM resume_cache WHERE template = ? AND page_size = ? AND language = ? AND fingerprint = ?",
+        )
+        .bind(template)
+        .bind(page_size)
+        .bind(language)
+        .bind(fingerprint)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// Store a rendered PDF, replacing the one previously cached for these options
+    pub async fn put(
+        &self,
+        template: &str,
+        page_size: &str,
+        language: &str,
+        fingerprint: &str,
+        content: &[u8],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO resume_cache (template, page_size, language, fingerprint, content, created_at)
+            VALUES (?, ?, ?, ?, ?, CURRENT_TIMESTAMP)
+            ON CONFLICT (template, page_size, language) DO UPDATE SET
+                fingerprint = excluded.fingerprint,
+                content = excluded.content,
+                created_at = excluded.created_at
+            "#,
+        )
+        .bind(template)
+        .bind(page_size)
+        .bind(language)
+        .bind(fingerprint)
+        .bind(content)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use sqlx::{FromRow, SqlitePool};
+
+/// Marker SQLite places before each matched term in highlights and snippets
+pub const MATCH_START: char = '\u{2}';
+/// Marker SQLite places after each matched term in highlights and snippets
+pub const MATCH_END: char = '\u{3}';
+
+/// Search hit as returned by the full-text index
+#[derive(Debug, Clone, FromRow)]
+pub struct SearchRow {
+    pub kind: String,
+    pub ref_id: i64,
+    /// Title with matches between `MATCH_START` and `MATCH_END`
+    pub title: String,
+    /// Body excerpt with matches between `MATCH_START` and `MATCH_END`
+    pub snippet: String,
+    /// BM25 rank, lower is better
+    pub rank: f64,
+}
+
+/// Repository for the full-text search index
+pub struct SearchRepository {
+    pool: SqlitePool,
+}
+
+impl SearchRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Run an FTS5 match expression against the given record kinds, best matches first.
+    /// Title matches weigh more than tags, which weigh more than body text.
+    pub async fn search(&self, match_expression: &str, kinds: &[&str], limit: i64) -> Result<Vec<SearchRow>, sqlx::Error> {
+        let kinds_json = serde_json::to_string(kinds).map_err(sqlx::Error::decode)?;
+
+        sqlx::query_as::<_, SearchRow>(
+            r#"
+            SELECT
+                kind,
+                CAST(ref_id AS INTEGER) AS ref_id,
+                highlight(search_index, 2, char(2), char(3)) AS title,
+                snippet(search_index, 3, char(2), char(3), '…', 24) AS snippet,
+                bm25(search_index, 0.0, 0.0, 10.0, 2.0, 5.0) AS rank
+            FROM search_index
+            WHERE search_index MATCH ? AND kind IN (SELECT value FROM json_each(?))
+            ORDER BY rank
+            LIMIT ?
+            "#
+        )
+        .bind(match_expression)
+        .bind(kinds_json)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+}
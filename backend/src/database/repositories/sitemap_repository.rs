@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+
+use super::post_repository::sqlite_timestamp;
+
+/// Repository for the pages the sitemap lists; when sections last changed comes from
+/// `ContentChangeRepository`
+pub struct SitemapRepository {
+    pool: SqlitePool,
+}
+
+impl SitemapRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// ID and last update of every project, newest first
+    pub async fn projects(&self) -> Result<Vec<(i32, DateTime<Utc>)>, sqlx::Error> {
+        sqlx::query_as("SELECT id, updated_at FROM projects ORDER BY created_at DESC, id DESC")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Slug, last update and publication date of the posts readers can see at `now`
+    pub async fn public_posts(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<(String, DateTime<Utc>, DateTime<Utc>)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT slug, updated_at, published_at FROM posts
+            WHERE status != 'draft' AND published_at IS NOT NULL AND julianday(published_at) <= julianday(?)
+            ORDER BY julianday(published_at) DESC, id DESC
+            "#,
+        )
+        .bind(sqlite_timestamp(now))
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// When the next scheduled post goes public after `now`
+    pub async fn next_publication(&self, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+            SELECT published_at FROM posts
+            WHERE status = 'scheduled' AND julianday(published_at) > julianday(?)
+            ORDER BY julianday(published_at)
+            LIMIT 1
+            "#,
+        )
+        .bind(sqlite_timestamp(now))
+        .fetch_optional(&self.pool)
+        .await
+    }
+}
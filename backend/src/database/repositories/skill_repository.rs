@@ -1,6 +1,16 @@
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 use chrono::Utc;
-use crate::models::{Skill, CreateSkill, UpdateSkill};
+use crate::{
+    database::ProjectSkillRepository,
+    models::{Skill, CreateSkill, UpdateSkill},
+};
+
+/// Columns selected for a `Skill`, including usage derived from project links
+const SKILL_COLUMNS: &str = r#"id, name, category, level, years_experience, description, created_at,
+    (SELECT COUNT(*) FROM project_skills ps WHERE ps.skill_id = skills.id) AS project_count,
+    (SELECT MAX(p.created_at) FROM project_skills ps
+        JOIN projects p ON p.id = ps.project_id
+        WHERE ps.skill_id = skills.id) AS last_used_at"#;
 
 /// Repository for skill database operations
 pub struct SkillRepository {
@@ -15,16 +25,25 @@ impl SkillRepository {
     /// Get all skills
     pub async fn get_all(&self) -> Result<Vec<Skill>, sqlx::Error> {
         sqlx::query_as::<_, Skill>(
-            "SELECT id, name, category, level, years_experience, description, created_at FROM skills ORDER BY category, name"
+            &format!("SELECT {} FROM skills ORDER BY category, name", SKILL_COLUMNS)
         )
         .fetch_all(&self.pool)
         .await
     }
 
+    /// Get all skills, on a connection that may be inside a transaction
+    pub async fn get_all_on(conn: &mut SqliteConnection) -> Result<Vec<Skill>, sqlx::Error> {
+        sqlx::query_as::<_, Skill>(
+            &format!("SELECT {} FROM skills ORDER BY category, name", SKILL_COLUMNS)
+        )
+        .fetch_all(conn)
+        .await
+    }
+
     /// Get skill by ID
     pub async fn get_by_id(&self, id: i32) -> Result<Option<Skill>, sqlx::Error> {
         sqlx::query_as::<_, Skill>(
-            "SELECT id, name, category, level, years_experience, description, created_at FROM skills WHERE id = ?"
+            &format!("SELECT {} FROM skills WHERE id = ?", SKILL_COLUMNS)
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -34,7 +53,7 @@ impl SkillRepository {
     /// Get skills by category
     pub async fn get_by_category(&self, category: &str) -> Result<Vec<Skill>, sqlx::Error> {
         sqlx::query_as::<_, Skill>(
-            "SELECT id, name, category, level, years_experience, description, created_at FROM skills WHERE category = ? ORDER BY level DESC, name"
+            &format!("SELECT {} FROM skills WHERE category = ? ORDER BY level DESC, name", SKILL_COLUMNS)
         )
         .bind(category)
         .fetch_all(&self.pool)
@@ -44,7 +63,7 @@ impl SkillRepository {
     /// Get skills by minimum level
     pub async fn get_by_min_level(&self, min_level: i32) -> Result<Vec<Skill>, sqlx::Error> {
         sqlx::query_as::<_, Skill>(
-            "SELECT id, name, category, level, years_experience, description, created_at FROM skills WHERE level >= ? ORDER BY level DESC, name"
+            &format!("SELECT {} FROM skills WHERE level >= ? ORDER BY level DESC, name", SKILL_COLUMNS)
         )
         .bind(min_level)
         .fetch_all(&self.pool)
@@ -53,8 +72,30 @@ impl SkillRepository {
 
     /// Create a new skill
     pub async fn create(&self, skill: &CreateSkill) -> Result<Skill, sqlx::Error> {
-        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        let id = Self::insert(&mut tx, skill).await?;
+        tx.commit().await?;
         
+        // Fetch the created skill
+        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+    }
+
+    /// Update an existing skill
+    pub async fn update(&self, id: i32, skill: &UpdateSkill) -> Result<Option<Skill>, sqlx::Error> {
+        // Check if skill exists first
+        if self.get_by_id(id).await?.is_none() {
+            return Ok(None);
+        }
+
+        let mut tx = self.pool.begin().await?;
+        Self::update_on(&mut tx, id, skill).await?;
+        tx.commit().await?;
+        
+        self.get_by_id(id).await
+    }
+
+    /// Insert a skill on an open connection, suggesting its project links; returns the new ID
+    pub async fn insert(conn: &mut SqliteConnection, skill: &CreateSkill) -> Result<i32, sqlx::Error> {
         let result = sqlx::query(
             "INSERT INTO skills (name, category, level, years_experience, description, created_at) VALUES (?, ?, ?, ?, ?, ?)"
         )
@@ -63,25 +104,19 @@ impl SkillRepository {
         .bind(skill.level)
         .bind(skill.years_experience)
         .bind(&skill.description)
-        .bind(now)
-        .execute(&self.pool)
+        .bind(Utc::now())
+        .execute(&mut *conn)
         .await?;
 
         let id = result.last_insert_rowid() as i32;
-        
-        // Fetch the created skill
-        self.get_by_id(id).await?.ok_or(sqlx::Error::RowNotFound)
+        ProjectSkillRepository::suggest_for_skill(conn, id).await?;
+        Ok(id)
     }
 
-    /// Update an existing skill
-    pub async fn update(&self, id: i32, skill: &UpdateSkill) -> Result<Option<Skill>, sqlx::Error> {
-        // Check if skill exists first
-        if self.get_by_id(id).await?.is_none() {
-            return Ok(None);
-        }
-
+    /// Update a skill on an open connection; returns whether the skill exists
+    pub async fn update_on(conn: &mut SqliteConnection, id: i32, skill: &UpdateSkill) -> Result<bool, sqlx::Error> {
         // Use COALESCE to keep existing values for fields that are None
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE skills SET 
                 name = COALESCE(?, name),
@@ -98,10 +133,14 @@ impl SkillRepository {
         .bind(skill.years_experience)
         .bind(&skill.description)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
-        
-        self.get_by_id(id).await
+
+        // A renamed skill may match different technologies
+        if skill.name.is_some() {
+            ProjectSkillRepository::suggest_for_skill(conn, id).await?;
+        }
+        Ok(result.rows_affected() > 0)
     }
 
     /// Delete a skill
@@ -140,23 +179,7 @@ mod tests {
             .await
             .unwrap();
 
-        // Create tables manually for testing
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS skills (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                category TEXT NOT NULL,
-                level INTEGER NOT NULL CHECK (level >= 1 AND level <= 5),
-                years_experience INTEGER,
-                description TEXT,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
 
         SkillRepository::new(pool)
     }
@@ -194,7 +217,7 @@ mod tests {
         repo.create(&skill_data).await.unwrap();
         
         let skills = repo.get_all().await.unwrap();
-        assert!(skills.len() >= 1);
+        assert!(!skills.is_empty());
     }
 
     #[tokio::test]
@@ -205,7 +228,7 @@ mod tests {
         repo.create(&skill_data).await.unwrap();
         
         let skills = repo.get_by_category("Backend").await.unwrap();
-        assert!(skills.len() >= 1);
+        assert!(!skills.is_empty());
         assert!(skills.iter().all(|s| s.category == "Backend"));
     }
 
@@ -217,7 +240,7 @@ mod tests {
         repo.create(&skill_data).await.unwrap();
         
         let skills = repo.get_by_min_level(3).await.unwrap();
-        assert!(skills.len() >= 1);
+        assert!(!skills.is_empty());
         assert!(skills.iter().all(|s| s.level >= 3));
     }
 
@@ -265,16 +288,4 @@ mod tests {
         let categories = repo.get_categories().await.unwrap();
         assert!(categories.contains(&"Backend".to_string()));
     }
-}
-
-impl Default for UpdateSkill {
-    fn default() -> Self {
-        Self {
-            name: None,
-            category: None,
-            level: None,
-            years_experience: None,
-            description: None,
-        }
-    }
 }
\ No newline at end of file
@@ -0,0 +1,162 @@
+use sqlx::SqlitePool;
+use crate::models::SpamLabel;
+
+/// How often a token was seen in trained spam and ham
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct TokenCounts {
+    pub token: String,
+    pub spam_count: i64,
+    pub ham_count: i64,
+}
+
+/// Repository for the naive Bayes spam model
+pub struct SpamRepository {
+    pool: SqlitePool,
+}
+
+impl SpamRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Number of messages trained as spam and as ham
+    pub async fn corpus_sizes(&self) -> Result<(i64, i64), sqlx::Error> {
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT label, messages FROM spam_corpus")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let count = |label: SpamLabel| {
+            rows.iter()
+                .find(|(name, _)| name == label.as_str())
+                .map(|(_, messages)| *messages)
+                .unwrap_or(0)
+        };
+        Ok((count(SpamLabel::Spam), count(SpamLabel::Ham)))
+    }
+
+    /// Counts of the given tokens; tokens never trained are left out
+    pub async fn token_counts(&self, tokens: &[String]) -> Result<Vec<TokenCounts>, sqlx::Error> {
+        sqlx::query_as::<_, TokenCounts>(
+            "SELECT token, spam_count, ham_count FROM spam_tokens WHERE token IN (SELECT value FROM json_each(?))"
+        )
+        .bind(serde_json::to_string(tokens).unwrap_or_default())
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Train the model with a message's tokens, undoing any earlier training with the other label.
+    /// Returns false when the message was already trained with this label or does not exist.
+    pub async fn train(&self, message_id: i32, tokens: &[String], label: SpamLabel) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let trained: Option<Option<String>> = sqlx::query_scalar("SELECT spam_trained FROM contact_messages WHERE id = ?")
+            .bind(message_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let previous = match trained {
+            None => return Ok(false),
+            Some(previous) => previous.as_deref().and_then(SpamLabel::from_str),
+        };
+        if previous == Some(label) {
+            return Ok(false);
+        }
+
+        let tokens_json = serde_json::to_string(tokens).unwrap_or_default();
+
+        if let Some(previous) = previous {
+            let column = count_column(previous);
+            sqlx::query(&format!(
+                "UPDATE spam_tokens SET {column} = MAX({column} - 1, 0) WHERE token IN (SELECT value FROM json_each(?))"
+            ))
+            .bind(&tokens_json)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("UPDATE spam_corpus SET messages = MAX(messages - 1, 0) WHERE label = ?")
+                .bind(previous.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        let column = count_column(label);
+        sqlx::query(&format!(
+            r#"
+            INSERT INTO spam_tokens (token, {column})
+            SELECT DISTINCT value, 1 FROM json_each(?)
+            WHERE true
+            ON CONFLICT(token) DO UPDATE SET {column} = {column} + 1
+            "#
+        ))
+        .bind(&tokens_json)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE spam_corpus SET messages = messages + 1 WHERE label = ?")
+            .bind(label.as_str())
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE contact_messages SET spam_trained = ? WHERE id = ?")
+            .bind(label.as_str())
+            .bind(message_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+fn count_column(label: SpamLabel) -> &'static str {
+    match label {
+        SpamLabel::Spam => "spam_count",
+        SpamLabel::Ham => "ham_count",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database::ContactRepository, models::{CreateContactMessage, SpamVerdict}};
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_train_and_retrain() {
+        let pool = create_test_pool().await;
+        let message = ContactRepository::new(pool.clone())
+            .create(
+                &CreateContactMessage {
+                    name: "Jane".to_string(),
+                    email: "jane@example.com".to_string(),
+                    subject: "Cheap pills".to_string(),
+                    message: "Buy cheap pills".to_string(),
+                    ..Default::default()
+                },
+                &SpamVerdict::default(),
+            )
+            .await
+            .unwrap();
+        let repo = SpamRepository::new(pool);
+        let tokens = vec!["cheap".to_string(), "pills".to_string()];
+
+        assert!(repo.train(message.id, &tokens, SpamLabel::Spam).await.unwrap());
+        assert!(!repo.train(message.id, &tokens, SpamLabel::Spam).await.unwrap());
+        assert_eq!(repo.corpus_sizes().await.unwrap(), (1, 0));
+
+        let counts = repo.token_counts(&["cheap".to_string(), "unknown".to_string()]).await.unwrap();
+        assert_eq!(counts, vec![TokenCounts { token: "cheap".to_string(), spam_count: 1, ham_count: 0 }]);
+
+        // Changing the label moves the message to the other side of the model
+        assert!(repo.train(message.id, &tokens, SpamLabel::Ham).await.unwrap());
+        assert_eq!(repo.corpus_sizes().await.unwrap(), (0, 1));
+        let counts = repo.token_counts(&tokens).await.unwrap();
+        assert!(counts.iter().all(|counts| counts.spam_count == 0 && counts.ham_count == 1));
+
+        assert!(!repo.train(999, &tokens, SpamLabel::Spam).await.unwrap());
+    }
+}
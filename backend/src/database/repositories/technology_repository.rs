@@ -0,0 +1,201 @@
+use sqlx::{SqliteConnection, SqlitePool};
+use crate::models::Technology;
+
+/// Repository for technology database operations
+pub struct TechnologyRepository {
+    pool: SqlitePool,
+}
+
+impl TechnologyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Get technologies used by at least one project, most used first
+    pub async fn get_all_with_counts(&self) -> Result<Vec<Technology>, sqlx::Error> {
+        sqlx::query_as::<_, Technology>(
+            r#"
+            SELECT t.id, t.name, t.slug, COUNT(pt.project_id) AS project_count
+            FROM technologies t
+            JOIN project_technologies pt ON pt.technology_id = t.id
+            GROUP BY t.id
+            ORDER BY project_count DESC, t.name COLLATE NOCASE
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Replace the technologies linked to a project, creating missing ones.
+    /// Names must already be normalized with `Technology::normalize_names`.
+    pub async fn replace_for_project(
+        conn: &mut SqliteConnection,
+        project_id: i32,
+        names: &[String],
+    ) -> Result<(), sqlx::Error> {
+        replace_links(conn, "project_technologies", "project_id", project_id, names).await
+    }
+
+    /// Replace the technologies linked to a work experience, creating missing ones.
+    /// Names must already be normalized with `Technology::normalize_names`.
+    pub async fn replace_for_experience(
+        conn: &mut SqliteConnection,
+        experience_id: i32,
+        names: &[String],
+    ) -> Result<(), sqlx::Error> {
+        replace_links(conn, "experience_technologies", "experience_id", experience_id, names).await
+    }
+
+    /// Recompute every slug with `Technology::slug_for`. Technologies that end up sharing a slug
+    /// are merged into the oldest one, which keeps its spelling and takes over the links.
+    /// Returns the number of technologies merged away.
+    pub async fn reslug_all(conn: &mut SqliteConnection) -> Result<usize, sqlx::Error> {
+        let technologies: Vec<(i32, String, String)> =
+            sqlx::query_as("SELECT id, name, slug FROM technologies ORDER BY id")
+                .fetch_all(&mut *conn)
+                .await?;
+
+        let mut kept: Vec<(i32, String)> = Vec::new();
+        let mut merged = 0;
+        for (id, name, slug) in technologies {
+            let new_slug = Technology::slug_for(&name);
+            match kept.iter().find(|(_, slug)| *slug == new_slug) {
+                Some((keeper, _)) => {
+                    for table in LINK_TABLES {
+                        sqlx::query(&format!("UPDATE OR IGNORE {} SET technology_id = ? WHERE technology_id = ?", table))
+                            .bind(keeper)
+                            .bind(id)
+                            .execute(&mut *conn)
+                            .await?;
+                        // Links left behind already existed for the keeper
+                        sqlx::query(&format!("DELETE FROM {} WHERE technology_id = ?", table))
+                            .bind(id)
+                            .execute(&mut *conn)
+                            .await?;
+                    }
+                    sqlx::query("DELETE FROM technologies WHERE id = ?")
+                        .bind(id)
+                        .execute(&mut *conn)
+                        .await?;
+                    merged += 1;
+                }
+                None => {
+                    if new_slug != slug {
+                        // Out of the way first, in case another technology still holds the new slug
+                        sqlx::query("UPDATE technologies SET slug = '#' || id WHERE id = ?")
+                            .bind(id)
+                            .execute(&mut *conn)
+                            .await?;
+                    }
+                    kept.push((id, new_slug));
+                }
+            }
+        }
+
+        for (id, slug) in kept {
+            sqlx::query("UPDATE technologies SET slug = ? WHERE id = ? AND slug <> ?")
+                .bind(&slug)
+                .bind(id)
+                .bind(&slug)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        Ok(merged)
+    }
+}
+
+/// Tables linking records to technologies
+const LINK_TABLES: [&str; 2] = ["project_technologies", "experience_technologies"];
+
+/// Replace the rows of a link table to technologies, keeping the order of the names
+async fn replace_links(
+    conn: &mut SqliteConnection,
+    table: &str,
+    owner_column: &str,
+    owner_id: i32,
+    names: &[String],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!("DELETE FROM {} WHERE {} = ?", table, owner_column))
+        .bind(owner_id)
+        .execute(&mut *conn)
+        .await?;
+
+    for (position, name) in names.iter().enumerate() {
+        let slug = Technology::slug_for(name);
+
+        sqlx::query("INSERT INTO technologies (name, slug) VALUES (?, ?) ON CONFLICT(slug) DO NOTHING")
+            .bind(name)
+            .bind(&slug)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query(&format!(
+            "INSERT OR IGNORE INTO {} ({}, technology_id, position) SELECT ?, id, ? FROM technologies WHERE slug = ?",
+            table, owner_column
+        ))
+        .bind(owner_id)
+        .bind(position as i64)
+        .bind(&slug)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::migrations::initialize_database;
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        initialize_database(pool.clone()).await.unwrap();
+        pool
+    }
+
+    async fn insert_project(pool: &SqlitePool, title: &str) -> i32 {
+        sqlx::query("INSERT INTO projects (title, description, category) VALUES (?, 'Description', 'web')")
+            .bind(title)
+            .execute(pool)
+            .await
+            .unwrap()
+            .last_insert_rowid() as i32
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_count() {
+        let pool = create_test_pool().await;
+        let first = insert_project(&pool, "First").await;
+        let second = insert_project(&pool, "Second").await;
+
+        let mut conn = pool.acquire().await.unwrap();
+        TechnologyRepository::replace_for_project(&mut conn, first, &["Rust".to_string(), "SQLite".to_string()])
+            .await
+            .unwrap();
+        TechnologyRepository::replace_for_project(&mut conn, second, &["rust".to_string()])
+            .await
+            .unwrap();
+        drop(conn);
+
+        let technologies = TechnologyRepository::new(pool.clone()).get_all_with_counts().await.unwrap();
+        assert_eq!(technologies.len(), 2);
+        assert_eq!(technologies[0].name, "Rust");
+        assert_eq!(technologies[0].slug, "rust");
+        assert_eq!(technologies[0].project_count, 2);
+        assert_eq!(technologies[1].name, "SQLite");
+        assert_eq!(technologies[1].project_count, 1);
+
+        // Replacing drops links, and unused technologies disappear from the listing
+        let mut conn = pool.acquire().await.unwrap();
+        TechnologyRepository::replace_for_project(&mut conn, first, &["Rust".to_string()])
+            .await
+            .unwrap();
+        drop(conn);
+
+        let technologies = TechnologyRepository::new(pool).get_all_with_counts().await.unwrap();
+        assert_eq!(technologies.len(), 1);
+        assert_eq!(technologies[0].project_count, 2);
+    }
+}
@@ -1,6 +1,6 @@
 use sqlx::SqlitePool;
-use tracing::{info, error};
-use serde_json::json;
+use tracing::info;
+use crate::database::{ProjectSkillRepository, TechnologyRepository};
 
 /// Seed data error types
 #[derive(Debug, thiserror::Error)]
@@ -124,7 +124,7 @@ async fn seed_projects(pool: &SqlitePool) -> Result<(), SeedError> {
             "Portfolio Website",
             "Modern portfolio website built with Rust and Svelte",
             "A full-stack portfolio application showcasing modern web development practices. Built with Rust backend using Axum framework and Svelte frontend with TypeScript. Features include project management, skills showcase, and contact form.",
-            json!(["Rust", "Svelte", "TypeScript", "SQLite", "Docker"]).to_string(),
+            vec!["Rust", "Svelte", "TypeScript", "SQLite", "Docker"],
             Some("https://github.com/johndoe/portfolio"),
             Some("https://johndoe.dev"),
             "web",
@@ -134,7 +134,7 @@ async fn seed_projects(pool: &SqlitePool) -> Result<(), SeedError> {
             "Task Management API",
             "RESTful API for task management with authentication",
             "A robust REST API built with Rust and Axum for managing tasks and projects. Features JWT authentication, role-based access control, and comprehensive error handling.",
-            json!(["Rust", "Axum", "PostgreSQL", "JWT", "Docker"]).to_string(),
+            vec!["Rust", "Axum", "PostgreSQL", "JWT", "Docker"],
             Some("https://github.com/johndoe/task-api"),
             None,
             "backend",
@@ -144,7 +144,7 @@ async fn seed_projects(pool: &SqlitePool) -> Result<(), SeedError> {
             "Weather Dashboard",
             "Real-time weather dashboard with interactive maps",
             "Interactive weather dashboard built with React and TypeScript. Integrates with multiple weather APIs to provide real-time weather data, forecasts, and interactive maps.",
-            json!(["React", "TypeScript", "Node.js", "Express", "MongoDB"]).to_string(),
+            vec!["React", "TypeScript", "Node.js", "Express", "MongoDB"],
             Some("https://github.com/johndoe/weather-dashboard"),
             Some("https://weather.johndoe.dev"),
             "frontend",
@@ -153,24 +153,31 @@ async fn seed_projects(pool: &SqlitePool) -> Result<(), SeedError> {
     ];
 
     for (title, description, long_description, technologies, github_url, demo_url, category, featured) in projects {
-        sqlx::query(
+        let mut tx = pool.begin().await?;
+
+        let project_id = sqlx::query(
             r#"
             INSERT INTO projects (
-                title, description, long_description, technologies,
+                title, description, long_description,
                 github_url, demo_url, category, featured
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(title)
         .bind(description)
         .bind(long_description)
-        .bind(technologies)
         .bind(github_url)
         .bind(demo_url)
         .bind(category)
         .bind(featured)
-        .execute(pool)
-        .await?;
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid() as i32;
+
+        let technologies: Vec<String> = technologies.into_iter().map(String::from).collect();
+        TechnologyRepository::replace_for_project(&mut tx, project_id, &technologies).await?;
+        ProjectSkillRepository::suggest_for_project(&mut tx, project_id).await?;
+        tx.commit().await?;
     }
 
     info!("Projects data seeded successfully");
@@ -187,12 +194,12 @@ mod tests {
             .await
             .unwrap();
 
-        // Create tables
-        let schema = include_str!("../../migrations/001_initial_schema.sql");
-        for statement in schema.split(';') {
-            let statement = statement.trim();
-            if !statement.is_empty() && !statement.starts_with("INSERT") {
-                sqlx::query(statement).execute(&pool).await.unwrap();
+        // Create tables, skipping the default profile insert
+        for migration in crate::database::migrations::MIGRATIONS {
+            for statement in crate::database::migrations::split_statements(migration.up) {
+                if !statement.starts_with("INSERT") {
+                    sqlx::query(&statement).execute(&pool).await.unwrap();
+                }
             }
         }
 
@@ -256,6 +263,16 @@ mod tests {
             .unwrap();
         
         assert!(count > 0);
+
+        // Technologies are shared between seeded projects
+        let rust_projects = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM project_technologies pt JOIN technologies t ON t.id = pt.technology_id WHERE t.slug = 'rust'"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(rust_projects, 2);
     }
 
     #[tokio::test]
@@ -284,5 +301,15 @@ mod tests {
         assert_eq!(profile_count, 1);
         assert!(skills_count > 0);
         assert!(projects_count > 0);
+
+        // Seeded projects are linked to the seeded skills matching their technologies
+        let rust_links = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM project_skills ps JOIN skills s ON s.id = ps.skill_id WHERE s.name = 'Rust'"
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(rust_links, 2);
     }
 }
\ No newline at end of file
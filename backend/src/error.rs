@@ -1,25 +1,136 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 
+/// Machine-readable error codes, stable across releases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    BadRequest,
+    ValidationFailed,
+    /// The body is not valid JSON or does not have the expected shape
+    InvalidBody,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    /// No route matches the path
+    RouteNotFound,
+    MethodNotAllowed,
+    Conflict,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    RateLimited,
+    DatabaseError,
+    InternalError,
+    ProjectNotFound,
+    SkillNotFound,
+    ProfileNotFound,
+    ContactMessageNotFound,
+    PostNotFound,
+    MediaNotFound,
+    ExperienceNotFound,
+    EducationNotFound,
+    CertificationNotFound,
+    ApiKeyNotFound,
+    AdminNotFound,
+    ProjectAlreadyExists,
+    SkillAlreadyExists,
+    PostSlugTaken,
+    AdminAlreadyExists,
+    /// The media is still shown in a project gallery
+    MediaInUse,
+    /// The contact message status cannot change that way
+    InvalidStatusTransition,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::ValidationFailed => "VALIDATION_FAILED",
+            ErrorCode::InvalidBody => "INVALID_BODY",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::RouteNotFound => "ROUTE_NOT_FOUND",
+            ErrorCode::MethodNotAllowed => "METHOD_NOT_ALLOWED",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::PayloadTooLarge => "PAYLOAD_TOO_LARGE",
+            ErrorCode::UnsupportedMediaType => "UNSUPPORTED_MEDIA_TYPE",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::DatabaseError => "DATABASE_ERROR",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::ProjectNotFound => "PROJECT_NOT_FOUND",
+            ErrorCode::SkillNotFound => "SKILL_NOT_FOUND",
+            ErrorCode::ProfileNotFound => "PROFILE_NOT_FOUND",
+            ErrorCode::ContactMessageNotFound => "CONTACT_MESSAGE_NOT_FOUND",
+            ErrorCode::PostNotFound => "POST_NOT_FOUND",
+            ErrorCode::MediaNotFound => "MEDIA_NOT_FOUND",
+            ErrorCode::ExperienceNotFound => "EXPERIENCE_NOT_FOUND",
+            ErrorCode::EducationNotFound => "EDUCATION_NOT_FOUND",
+            ErrorCode::CertificationNotFound => "CERTIFICATION_NOT_FOUND",
+            ErrorCode::ApiKeyNotFound => "API_KEY_NOT_FOUND",
+            ErrorCode::AdminNotFound => "ADMIN_NOT_FOUND",
+            ErrorCode::ProjectAlreadyExists => "PROJECT_ALREADY_EXISTS",
+            ErrorCode::SkillAlreadyExists => "SKILL_ALREADY_EXISTS",
+            ErrorCode::PostSlugTaken => "POST_SLUG_TAKEN",
+            ErrorCode::AdminAlreadyExists => "ADMIN_ALREADY_EXISTS",
+            ErrorCode::MediaInUse => "MEDIA_IN_USE",
+            ErrorCode::InvalidStatusTransition => "INVALID_STATUS_TRANSITION",
+        }
+    }
+
+    /// Code of an error response that did not come from an `ApiError`, such as an extractor rejection
+    pub fn for_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => ErrorCode::Unauthorized,
+            StatusCode::FORBIDDEN => ErrorCode::Forbidden,
+            StatusCode::NOT_FOUND => ErrorCode::RouteNotFound,
+            StatusCode::METHOD_NOT_ALLOWED => ErrorCode::MethodNotAllowed,
+            StatusCode::CONFLICT => ErrorCode::Conflict,
+            StatusCode::PAYLOAD_TOO_LARGE => ErrorCode::PayloadTooLarge,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => ErrorCode::UnsupportedMediaType,
+            StatusCode::UNPROCESSABLE_ENTITY => ErrorCode::InvalidBody,
+            StatusCode::TOO_MANY_REQUESTS => ErrorCode::RateLimited,
+            status if status.is_server_error() => ErrorCode::InternalError,
+            _ => ErrorCode::BadRequest,
+        }
+    }
+}
+
+/// One failed constraint on one field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    /// Name of the field, as sent in the request
+    pub field: String,
+    /// Constraint that failed, e.g. `length`, `range`, `email` or `url`
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    /// Describe a failed check that the validator derive cannot express
+    pub fn new(field: impl Into<String>, code: &str, message: impl Into<String>) -> Self {
+        Self { field: field.into(), code: code.to_string(), message: message.into() }
+    }
+}
+
 /// API error types for the portfolio application
 #[derive(Debug, thiserror::Error)]
 pub enum ApiError {
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
     
-    #[error("Validation error: {0}")]
-    Validation(String),
-    
     #[error("Validation errors: {0:?}")]
-    ValidationErrors(Vec<String>),
+    ValidationErrors(Vec<FieldError>),
     
-    #[error("Not found: {0}")]
-    NotFound(String),
+    #[error("Not found: {1}")]
+    NotFound(ErrorCode, String),
     
     #[error("Unauthorized access")]
     Unauthorized,
@@ -27,11 +138,20 @@ pub enum ApiError {
     #[error("Forbidden access")]
     Forbidden,
     
-    #[error("Conflict: {0}")]
-    Conflict(String),
+    #[error("Conflict: {1}")]
+    Conflict(ErrorCode, String),
     
     #[error("Bad request: {0}")]
     BadRequest(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Too many requests, retry in {retry_after_secs}s")]
+    TooManyRequests { retry_after_secs: u64 },
     
     #[error("Internal server error: {0}")]
     InternalServerError(String),
@@ -41,60 +161,87 @@ pub enum ApiError {
 }
 
 impl ApiError {
-    /// Create a validation error from validator errors
+    /// Create a validation error from validator errors, one entry per failed constraint
     pub fn from_validation_errors(errors: validator::ValidationErrors) -> Self {
-        let error_messages: Vec<String> = errors
+        let mut fields: Vec<FieldError> = errors
             .field_errors()
             .iter()
             .flat_map(|(field, errors)| {
-                errors.iter().map(move |error| {
-                    let message = error.message.as_ref()
+                errors.iter().map(move |error| FieldError {
+                    field: field.to_string(),
+                    code: error.code.to_string(),
+                    message: error.message.as_ref()
                         .map(|m| m.to_string())
-                        .unwrap_or_else(|| format!("Invalid value for field '{}'", field));
-                    format!("{}: {}", field, message)
+                        .unwrap_or_else(|| format!("Invalid value for field '{}'", field)),
                 })
             })
             .collect();
+        // Fields come out of a hash map
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
 
-        if error_messages.len() == 1 {
-            ApiError::Validation(error_messages[0].clone())
-        } else {
-            ApiError::ValidationErrors(error_messages)
-        }
+        ApiError::ValidationErrors(fields)
     }
 
     /// Get the HTTP status code for this error
     pub fn status_code(&self) -> StatusCode {
         match self {
             ApiError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ApiError::Validation(_) | ApiError::ValidationErrors(_) => StatusCode::BAD_REQUEST,
-            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::ValidationErrors(_) => StatusCode::BAD_REQUEST,
+            ApiError::NotFound(..) => StatusCode::NOT_FOUND,
             ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
             ApiError::Forbidden => StatusCode::FORBIDDEN,
-            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Conflict(..) => StatusCode::CONFLICT,
             ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ApiError::TooManyRequests { .. } => StatusCode::TOO_MANY_REQUESTS,
             ApiError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             ApiError::Serialization(_) => StatusCode::BAD_REQUEST,
         }
     }
 
+    /// Get the machine-readable code for this error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::Database(_) => ErrorCode::DatabaseError,
+            ApiError::ValidationErrors(_) => ErrorCode::ValidationFailed,
+            ApiError::NotFound(code, _) | ApiError::Conflict(code, _) => *code,
+            ApiError::Unauthorized => ErrorCode::Unauthorized,
+            ApiError::Forbidden => ErrorCode::Forbidden,
+            ApiError::BadRequest(_) => ErrorCode::BadRequest,
+            ApiError::PayloadTooLarge(_) => ErrorCode::PayloadTooLarge,
+            ApiError::UnsupportedMediaType(_) => ErrorCode::UnsupportedMediaType,
+            ApiError::TooManyRequests { .. } => ErrorCode::RateLimited,
+            ApiError::InternalServerError(_) => ErrorCode::InternalError,
+            ApiError::Serialization(_) => ErrorCode::InvalidBody,
+        }
+    }
+
     /// Get the error message for the response
     pub fn message(&self) -> String {
         match self {
             ApiError::Database(_) => "A database error occurred".to_string(),
-            ApiError::Validation(msg) => msg.clone(),
-            ApiError::ValidationErrors(errors) => errors.join(", "),
-            ApiError::NotFound(msg) => msg.clone(),
+            ApiError::ValidationErrors(errors) => errors
+                .iter()
+                .map(|error| format!("{}: {}", error.field, error.message))
+                .collect::<Vec<_>>()
+                .join(", "),
+            ApiError::NotFound(_, msg) => msg.clone(),
             ApiError::Unauthorized => "Unauthorized access".to_string(),
             ApiError::Forbidden => "Forbidden access".to_string(),
-            ApiError::Conflict(msg) => msg.clone(),
+            ApiError::Conflict(_, msg) => msg.clone(),
             ApiError::BadRequest(msg) => msg.clone(),
+            ApiError::PayloadTooLarge(msg) => msg.clone(),
+            ApiError::UnsupportedMediaType(msg) => msg.clone(),
+            ApiError::TooManyRequests { retry_after_secs } => {
+                format!("Too many requests, please retry in {} seconds", retry_after_secs)
+            }
             ApiError::InternalServerError(_) => "An internal server error occurred".to_string(),
             ApiError::Serialization(_) => "Invalid data format".to_string(),
         }
     }
 
-    /// Get detailed error information (for development/debugging)
+    /// Get the underlying cause, which may reveal internals: logged, and only shown in dev mode or to admins
     pub fn details(&self) -> Option<String> {
         match self {
             ApiError::Database(e) => Some(e.to_string()),
@@ -103,72 +250,159 @@ impl ApiError {
             _ => None,
         }
     }
+
+    /// Describe this error for a response body
+    pub fn body(&self) -> ErrorBody {
+        ErrorBody {
+            code: self.code(),
+            status: self.status_code().as_u16(),
+            message: self.message(),
+            details: self.details(),
+            fields: match self {
+                ApiError::ValidationErrors(errors) => Some(errors.clone()),
+                _ => None,
+            },
+            request_id: None,
+        }
+    }
+}
+
+impl From<FieldError> for ApiError {
+    /// A single failed check made outside the validator derive
+    fn from(error: FieldError) -> Self {
+        ApiError::ValidationErrors(vec![error])
+    }
+}
+
+/// Body of every error response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Always `false`
+    pub success: bool,
+    pub error: ErrorBody,
+}
+
+/// What went wrong, in an `ErrorResponse`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErrorBody {
+    pub code: ErrorCode,
+    /// HTTP status code
+    pub status: u16,
+    pub message: String,
+    /// Underlying cause of database, serialization and internal errors, in dev mode or for admins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    /// Every failed constraint, for validation errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<FieldError>>,
+    /// `X-Request-Id` of the request, to find the error in the server logs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl ErrorBody {
+    /// Render as the JSON envelope without the details, keeping the complete body for
+    /// `problem::negotiate_errors` to log and show to those allowed to see it
+    pub fn into_response(self, status: StatusCode) -> Response {
+        let public = ErrorBody { details: None, ..self.clone() };
+        let mut response = (status, Json(ErrorResponse { success: false, error: public })).into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let status = self.status_code();
-        let message = self.message();
-        
-        let mut response_body = json!({
-            "success": false,
-            "error": {
-                "code": status.as_u16(),
-                "message": message
-            }
-        });
+        let mut response = self.body().into_response(self.status_code());
 
-        // Add details in development mode (you might want to make this configurable)
-        if let Some(details) = self.details() {
-            if let Some(error_obj) = response_body.get_mut("error") {
-                error_obj["details"] = json!(details);
+        match &self {
+            // Tell clients which authentication scheme is expected
+            ApiError::Unauthorized => {
+                response.headers_mut().insert(
+                    header::WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Bearer"),
+                );
             }
-        }
-
-        // Add validation errors as a separate field for better client handling
-        if let ApiError::ValidationErrors(errors) = &self {
-            if let Some(error_obj) = response_body.get_mut("error") {
-                error_obj["validation_errors"] = json!(errors);
+            ApiError::TooManyRequests { retry_after_secs } => {
+                response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(*retry_after_secs));
             }
+            _ => {}
         }
 
-        (status, Json(response_body)).into_response()
+        response
     }
 }
 
 /// Result type alias for API operations
 pub type ApiResult<T> = Result<T, ApiError>;
 
-/// Success response wrapper
-#[derive(Debug, serde::Serialize)]
+/// Body of every successful JSON response
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub success: bool,
-    pub data: T,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
     pub message: Option<String>,
+    pub pagination: Option<PaginationInfo>,
+}
+
+/// Pagination information
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PaginationInfo {
+    /// Page number, absent when the page was requested with a cursor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    pub page_size: u32,
+    pub total_count: u64,
+    pub total_pages: u64,
+    /// Cursor for the following page, absent on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl PaginationInfo {
+    pub fn new(page: Option<u32>, page_size: u32, total_count: u64, next_cursor: Option<String>) -> Self {
+        Self {
+            page,
+            page_size,
+            total_count,
+            total_pages: total_count.div_ceil(page_size.max(1) as u64),
+            next_cursor,
+        }
+    }
 }
 
 impl<T> ApiResponse<T> {
-    pub fn new(data: T) -> Self {
+    pub fn success(data: T) -> Self {
         Self {
             success: true,
-            data,
+            data: Some(data),
             message: None,
+            pagination: None,
         }
     }
 
-    pub fn with_message(data: T, message: String) -> Self {
+    pub fn success_with_message(data: T, message: String) -> Self {
         Self {
             success: true,
-            data,
+            data: Some(data),
             message: Some(message),
+            pagination: None,
+        }
+    }
+
+    pub fn success_with_pagination(data: T, pagination: PaginationInfo) -> Self {
+        Self {
+            success: true,
+            data: Some(data),
+            message: None,
+            pagination: Some(pagination),
         }
     }
 }
 
 impl<T> IntoResponse for ApiResponse<T>
 where
-    T: serde::Serialize,
+    T: Serialize,
 {
     fn into_response(self) -> Response {
         Json(self).into_response()
@@ -190,22 +424,32 @@ mod tests {
 
     #[test]
     fn test_api_error_status_codes() {
-        assert_eq!(ApiError::NotFound("test".to_string()).status_code(), StatusCode::NOT_FOUND);
+        assert_eq!(ApiError::NotFound(ErrorCode::NotFound, "test".to_string()).status_code(), StatusCode::NOT_FOUND);
         assert_eq!(ApiError::Unauthorized.status_code(), StatusCode::UNAUTHORIZED);
-        assert_eq!(ApiError::Validation("test".to_string()).status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(ApiError::from(FieldError::new("name", "required", "test")).status_code(), StatusCode::BAD_REQUEST);
         assert_eq!(ApiError::Database(sqlx::Error::RowNotFound).status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(ApiError::TooManyRequests { retry_after_secs: 5 }.status_code(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(ApiError::PayloadTooLarge("too big".to_string()).status_code(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(ApiError::UnsupportedMediaType("text".to_string()).status_code(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
     }
 
     #[test]
     fn test_api_error_messages() {
-        let error = ApiError::NotFound("User not found".to_string());
+        let error = ApiError::NotFound(ErrorCode::AdminNotFound, "User not found".to_string());
         assert_eq!(error.message(), "User not found");
+        assert_eq!(error.code(), ErrorCode::AdminNotFound);
 
         let error = ApiError::Unauthorized;
         assert_eq!(error.message(), "Unauthorized access");
 
-        let error = ApiError::ValidationErrors(vec!["Name is required".to_string(), "Invalid email".to_string()]);
-        assert_eq!(error.message(), "Name is required, Invalid email");
+        let field = |field: &str, message: &str| FieldError {
+            field: field.to_string(),
+            code: "length".to_string(),
+            message: message.to_string(),
+        };
+        let error = ApiError::ValidationErrors(vec![field("name", "Name is required"), field("email", "Invalid email")]);
+        assert_eq!(error.message(), "name: Name is required, email: Invalid email");
+        assert_eq!(error.code(), ErrorCode::ValidationFailed);
     }
 
     #[test]
@@ -223,8 +467,12 @@ mod tests {
 
         match api_error {
             ApiError::ValidationErrors(errors) => {
-                assert!(errors.len() >= 1);
-                assert!(errors.iter().any(|e| e.contains("name")));
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0].field, "email");
+                assert_eq!(errors[0].code, "email");
+                assert_eq!(errors[1].field, "name");
+                assert_eq!(errors[1].code, "length");
+                assert_eq!(errors[1].message, "Name is required");
             }
             _ => panic!("Expected ValidationErrors"),
         }
@@ -232,14 +480,57 @@ mod tests {
 
     #[test]
     fn test_api_response_creation() {
-        let response = ApiResponse::new("test data");
+        let response = ApiResponse::success("test data");
         assert!(response.success);
-        assert_eq!(response.data, "test data");
+        assert_eq!(response.data, Some("test data"));
         assert!(response.message.is_none());
 
-        let response = ApiResponse::with_message("test data", "Success message".to_string());
+        let response = ApiResponse::success_with_message("test data", "Success message".to_string());
         assert!(response.success);
-        assert_eq!(response.data, "test data");
+        assert_eq!(response.data, Some("test data"));
         assert_eq!(response.message, Some("Success message".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_error_response_body() {
+        let response = ApiError::NotFound(ErrorCode::ProjectNotFound, "Project with ID 7 not found".to_string())
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["code"], "PROJECT_NOT_FOUND");
+        assert_eq!(body["error"]["status"], 404);
+        assert!(body["error"].get("fields").is_none());
+
+        // Internal causes stay out of the body
+        let response = ApiError::Database(sqlx::Error::PoolTimedOut).into_response();
+        let logged = response.extensions().get::<ErrorBody>().unwrap().details.clone();
+        assert_eq!(logged, Some(sqlx::Error::PoolTimedOut.to_string()));
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "DATABASE_ERROR");
+        assert!(body["error"].get("details").is_none());
+
+        let errors = TestStruct { name: "".to_string(), email: "someone@example.com".to_string() }
+            .validate()
+            .unwrap_err();
+        let response = ApiError::from_validation_errors(errors).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "VALIDATION_FAILED");
+        assert_eq!(
+            body["error"]["fields"],
+            serde_json::json!([{ "field": "name", "code": "length", "message": "Name is required" }])
+        );
+    }
+
+    #[test]
+    fn test_error_code_strings_match_serde() {
+        for code in [ErrorCode::RateLimited, ErrorCode::ContactMessageNotFound, ErrorCode::ApiKeyNotFound, ErrorCode::InvalidBody] {
+            assert_eq!(serde_json::to_value(code).unwrap(), code.as_str());
+        }
+        assert_eq!(ErrorCode::for_status(StatusCode::NOT_FOUND), ErrorCode::RouteNotFound);
+        assert_eq!(ErrorCode::for_status(StatusCode::BAD_GATEWAY), ErrorCode::InternalError);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,97 @@
+//! JSON Feed 1.1 writer (https://jsonfeed.org/version/1.1)
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::{Feed, FeedContent, FeedFormat};
+
+const VERSION: &str = "https://jsonfeed.org/version/1.1";
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    description: &'a str,
+    home_page_url: &'a str,
+    feed_url: String,
+    authors: [Author<'a>; 1],
+    items: Vec<Item<'a>>,
+}
+
+#[derive(Serialize)]
+struct Author<'a> {
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+struct Item<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    summary: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<&'a str>,
+    date_published: DateTime<Utc>,
+    date_modified: DateTime<Utc>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    tags: &'a [String],
+}
+
+/// Render a JSON Feed
+pub fn json_feed(feed: &Feed) -> String {
+    let document = JsonFeed {
+        version: VERSION,
+        title: &feed.title,
+        description: &feed.description,
+        home_page_url: &feed.home_url,
+        feed_url: feed.url(FeedFormat::Json),
+        authors: [Author { name: &feed.author }],
+        items: feed
+            .entries
+            .iter()
+            .map(|entry| {
+                let (content_html, content_text) = match &entry.content {
+                    FeedContent::Html(html) => (Some(html.as_str()), None),
+                    FeedContent::Text(text) => (None, Some(text.as_str())),
+                };
+                Item {
+                    id: &entry.url,
+                    url: &entry.url,
+                    title: &entry.title,
+                    summary: &entry.summary,
+                    content_html,
+                    content_text,
+                    date_published: entry.published,
+                    date_modified: entry.updated,
+                    tags: &entry.categories,
+                }
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&document).expect("feeds only hold strings and dates")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::sample_feed;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_json_feed() {
+        let feed: Value = serde_json::from_str(&json_feed(&sample_feed())).unwrap();
+        assert_eq!(feed["version"], VERSION);
+        assert_eq!(feed["feed_url"], "https://api.example.com/feeds/projects.json");
+        assert_eq!(feed["authors"], json!([{ "name": "Jane Doe" }]));
+
+        let item = &feed["items"][0];
+        assert_eq!(item["id"], "https://example.com/projects/1");
+        assert_eq!(item["content_html"], "<p>Uses <code>FTS5</code></p>");
+        assert!(item.get("content_text").is_none());
+        assert_eq!(item["date_published"], "2024-03-01T09:30:00Z");
+        assert_eq!(item["tags"], json!(["Web", "Rust"]));
+    }
+}
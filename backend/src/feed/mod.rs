@@ -0,0 +1,117 @@
+//! Syndication feeds in Atom, RSS 2.0 and JSON Feed 1.1, written from one description
+pub mod json;
+pub mod xml;
+
+use chrono::{DateTime, Utc};
+
+/// Format of a rendered feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Atom,
+    Rss,
+    Json,
+}
+
+impl FeedFormat {
+    /// File extension in the feed URL
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "atom",
+            FeedFormat::Rss => "rss",
+            FeedFormat::Json => "json",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+            FeedFormat::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "atom" => Some(FeedFormat::Atom),
+            "rss" => Some(FeedFormat::Rss),
+            "json" => Some(FeedFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A feed, whatever its format
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    /// Page the feed mirrors
+    pub home_url: String,
+    /// Address of the feed itself, without the format extension
+    pub feed_url: String,
+    pub author: String,
+    /// Latest change to the feed or any of its entries
+    pub updated: DateTime<Utc>,
+    /// Newest first
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    /// Address of the feed in the given format
+    pub fn url(&self, format: FeedFormat) -> String {
+        format!("{}.{}", self.feed_url, format.extension())
+    }
+
+    /// Render the feed
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Atom => xml::atom(self),
+            FeedFormat::Rss => xml::rss(self),
+            FeedFormat::Json => json::json_feed(self),
+        }
+    }
+}
+
+/// An entry of a feed
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedEntry {
+    /// Permanent address of the entry, also used as its identifier
+    pub url: String,
+    pub title: String,
+    pub summary: String,
+    pub content: FeedContent,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    pub categories: Vec<String>,
+}
+
+/// Full content of an entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedContent {
+    Text(String),
+    Html(String),
+}
+
+#[cfg(test)]
+pub(crate) fn sample_feed() -> Feed {
+    use chrono::TimeZone;
+
+    let published = Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap();
+    Feed {
+        title: "Jane Doe — Projects".to_string(),
+        description: "New projects & updates".to_string(),
+        home_url: "https://example.com/projects".to_string(),
+        feed_url: "https://api.example.com/feeds/projects".to_string(),
+        author: "Jane Doe".to_string(),
+        updated: published,
+        entries: vec![FeedEntry {
+            url: "https://example.com/projects/1".to_string(),
+            title: "Search <engine>".to_string(),
+            summary: "Full-text search".to_string(),
+            content: FeedContent::Html("<p>Uses <code>FTS5</code></p>".to_string()),
+            published,
+            updated: published,
+            categories: vec!["Web".to_string(), "Rust".to_string()],
+        }],
+    }
+}
@@ -0,0 +1,135 @@
+//! Atom (RFC 4287) and RSS 2.0 writers
+
+use std::fmt::Write;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use super::{Feed, FeedContent, FeedFormat};
+
+/// Render an Atom feed
+pub fn atom(feed: &Feed) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    element(&mut out, 1, "title", &feed.title);
+    element(&mut out, 1, "subtitle", &feed.description);
+    element(&mut out, 1, "id", &feed.url(FeedFormat::Atom));
+    let _ = writeln!(
+        out,
+        "  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>",
+        escape(&feed.url(FeedFormat::Atom))
+    );
+    let _ = writeln!(out, "  <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>", escape(&feed.home_url));
+    element(&mut out, 1, "updated", &rfc3339(feed.updated));
+    let _ = writeln!(out, "  <author><name>{}</name></author>", escape(&feed.author));
+
+    for entry in &feed.entries {
+        out.push_str("  <entry>\n");
+        element(&mut out, 2, "id", &entry.url);
+        element(&mut out, 2, "title", &entry.title);
+        let _ = writeln!(out, "    <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>", escape(&entry.url));
+        element(&mut out, 2, "published", &rfc3339(entry.published));
+        element(&mut out, 2, "updated", &rfc3339(entry.updated));
+        element(&mut out, 2, "summary", &entry.summary);
+        let (kind, content) = match &entry.content {
+            FeedContent::Text(text) => ("text", text),
+            FeedContent::Html(html) => ("html", html),
+        };
+        let _ = writeln!(out, "    <content type=\"{}\">{}</content>", kind, escape(content));
+        for category in &entry.categories {
+            let _ = writeln!(out, "    <category term=\"{}\"/>", escape(category));
+        }
+        out.push_str("  </entry>\n");
+    }
+
+    out.push_str("</feed>\n");
+    out
+}
+
+/// Render an RSS 2.0 feed
+pub fn rss(feed: &Feed) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n<channel>\n");
+    element(&mut out, 1, "title", &feed.title);
+    element(&mut out, 1, "link", &feed.home_url);
+    element(&mut out, 1, "description", &feed.description);
+    let _ = writeln!(
+        out,
+        "  <atom:link rel=\"self\" type=\"application/rss+xml\" href=\"{}\"/>",
+        escape(&feed.url(FeedFormat::Rss))
+    );
+    element(&mut out, 1, "lastBuildDate", &feed.updated.to_rfc2822());
+
+    for entry in &feed.entries {
+        out.push_str("  <item>\n");
+        element(&mut out, 2, "title", &entry.title);
+        element(&mut out, 2, "link", &entry.url);
+        let _ = writeln!(out, "    <guid isPermaLink=\"true\">{}</guid>", escape(&entry.url));
+        element(&mut out, 2, "pubDate", &entry.published.to_rfc2822());
+        // RSS descriptions are read as HTML
+        let description = match &entry.content {
+            FeedContent::Text(text) => escape(text),
+            FeedContent::Html(html) => html.clone(),
+        };
+        element(&mut out, 2, "description", &description);
+        for category in &entry.categories {
+            element(&mut out, 2, "category", category);
+        }
+        out.push_str("  </item>\n");
+    }
+
+    out.push_str("</channel>\n</rss>\n");
+    out
+}
+
+fn element(out: &mut String, depth: usize, name: &str, text: &str) {
+    let _ = writeln!(out, "{}<{}>{}</{}>", "  ".repeat(depth), name, escape(text), name);
+}
+
+pub(crate) fn rfc3339(date: DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Escape text for XML content and attribute values, dropping characters XML cannot carry
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch.is_control() => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feed::sample_feed;
+
+    #[test]
+    fn test_atom() {
+        let atom = atom(&sample_feed());
+        assert!(atom.contains("<id>https://api.example.com/feeds/projects.atom</id>"));
+        assert!(atom.contains("<updated>2024-03-01T09:30:00Z</updated>"));
+        assert!(atom.contains("<title>Search &lt;engine&gt;</title>"));
+        assert!(atom.contains("<content type=\"html\">&lt;p&gt;Uses &lt;code&gt;FTS5&lt;/code&gt;&lt;/p&gt;</content>"));
+        assert!(atom.contains("<category term=\"Rust\"/>"));
+        assert!(atom.contains("<subtitle>New projects &amp; updates</subtitle>"));
+    }
+
+    #[test]
+    fn test_rss() {
+        let rss = rss(&sample_feed());
+        assert!(rss.contains("<pubDate>Fri, 1 Mar 2024 09:30:00 +0000</pubDate>"));
+        assert!(rss.contains("<guid isPermaLink=\"true\">https://example.com/projects/1</guid>"));
+        assert!(rss.contains("<description>&lt;p&gt;Uses &lt;code&gt;FTS5&lt;/code&gt;&lt;/p&gt;</description>"));
+        assert!(rss.contains("<category>Web</category>"));
+        assert_eq!(escape("a\u{0}b & c"), "ab &amp; c");
+    }
+}
@@ -3,4 +3,19 @@ pub mod database;
 pub mod models;
 pub mod services;
 pub mod error;
-pub mod routes;
\ No newline at end of file
+pub mod routes;
+pub mod auth;
+pub mod server;
+pub mod config;
+pub mod notifier;
+pub mod spam;
+pub mod rate_limit;
+pub mod challenge;
+pub mod media;
+pub mod markdown;
+pub mod feed;
+pub mod sitemap;
+pub mod resume;
+pub mod openapi;
+pub mod problem;
+pub mod request_id;
\ No newline at end of file
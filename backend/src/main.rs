@@ -1,80 +1,63 @@
-use axum::{
-    http::{HeaderValue, Method},
-    routing::get,
-    Router,
+use clap::Parser;
+use portfolio_backend::{
+    auth::AuthConfig,
+    config::AppConfig,
+    database::{self, DatabaseConfig},
+    server,
 };
-use portfolio_backend::{database, routes};
-use sqlx::SqlitePool;
-use std::env;
-use tower_http::cors::CorsLayer;
-use tracing_subscriber;
+use std::path::PathBuf;
+
+/// Portfolio backend API server
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    /// TOML configuration file (defaults to PORTFOLIO_CONFIG, then ./portfolio.toml if present)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Print the effective configuration with secrets redacted, then exit
+    #[arg(long)]
+    print_config: bool,
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    // Load and validate configuration before anything else
+    let config = match AppConfig::load(args.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if args.print_config {
+        print!("{}", config.to_redacted_toml());
+        return Ok(());
+    }
+
     // Initialize tracing
     tracing_subscriber::fmt::init();
 
-    // Get database URL from environment or use default
-    let database_url = env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "sqlite:data/portfolio.db".to_string());
-
-    // Create database connection pool
-    let pool = SqlitePool::connect(&database_url).await?;
-
-    // Run database migrations
-    database::migrations::initialize_database(pool.clone()).await?;
+    // Connect to the database and run migrations
+    let pool = database::open_database(&DatabaseConfig::from(&config.database)).await?;
 
     // Seed database if needed
-    if env::var("SEED_DATABASE").unwrap_or_default() == "true" {
+    if config.database.seed {
         database::seed::seed_database(&pool).await?;
     }
 
-    // Configure CORS
-    let cors = CorsLayer::new()
-        .allow_origin([
-            "http://localhost:3000".parse::<HeaderValue>()?,
-            "http://localhost:5173".parse::<HeaderValue>()?,
-            "http://localhost:5174".parse::<HeaderValue>()?,
-            "http://localhost:5175".parse::<HeaderValue>()?,
-            "http://localhost:5176".parse::<HeaderValue>()?,
-            "http://localhost:5177".parse::<HeaderValue>()?,
-            "http://127.0.0.1:3000".parse::<HeaderValue>()?,
-            "http://127.0.0.1:5173".parse::<HeaderValue>()?,
-            "http://127.0.0.1:5174".parse::<HeaderValue>()?,
-            "http://127.0.0.1:5175".parse::<HeaderValue>()?,
-            "http://127.0.0.1:5176".parse::<HeaderValue>()?,
-            "http://127.0.0.1:5177".parse::<HeaderValue>()?,
-        ])
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
-        .allow_headers([
-            axum::http::header::CONTENT_TYPE,
-            axum::http::header::AUTHORIZATION,
-            axum::http::header::ACCEPT,
-        ])
-        .allow_credentials(false);
+    // Configure admin authentication
+    let auth_config = AuthConfig::from_settings(&config.auth);
 
-    // Build our application with routes
-    let app = Router::new()
-        .route("/", get(|| async { "Portfolio Backend API v1.0" }))
-        .route("/health", get(health_check))
-        .merge(routes::create_router(pool))
-        .layer(cors);
-
-    // Get port from environment or use default
-    let port = env::var("PORT").unwrap_or_else(|_| "3001".to_string());
-    let addr = format!("0.0.0.0:{}", port);
+    // Create the first admin account if requested and none exists yet
+    if let Some(admin) = server::bootstrap_admin(&pool, &auth_config, &config.auth).await? {
+        println!("👤 Created admin account '{}'", admin.username);
+    }
 
     // Run the server
-    let listener = tokio::net::TcpListener::bind(&addr).await?;
-    println!("🚀 Portfolio Backend API running on http://{}", addr);
-    println!("📊 Health check available at http://{}/health", addr);
-    
-    axum::serve(listener, app).await?;
-    
+    server::serve(pool, auth_config, &config).await?;
+
     Ok(())
 }
-
-/// Health check endpoint
-async fn health_check() -> &'static str {
-    "OK"
-}
\ No newline at end of file
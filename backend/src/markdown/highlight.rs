@@ -0,0 +1,76 @@
+use std::sync::OnceLock;
+
+use syntect::{
+    highlighting::ThemeSet,
+    html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator},
+    parsing::SyntaxSet,
+    util::LinesWithEndings,
+};
+
+/// Highlighted code is marked up with classes rather than inline styles, so the site
+/// stylesheet decides the colors
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+/// Theme of the stylesheet served when none is asked for
+pub const DEFAULT_THEME: &str = "InspiredGitHub";
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn themes() -> &'static ThemeSet {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+/// HTML of a code block highlighted for the given language (a name or file extension such
+/// as `rust` or `rs`), or `None` when the language is unknown
+pub fn code_block(language: &str, code: &str) -> Option<String> {
+    let syntax = syntaxes().find_syntax_by_token(language)?;
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, syntaxes(), CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        generator.parse_html_for_line_which_includes_newline(line).ok()?;
+    }
+
+    // Found by name, so the language only holds characters safe in an attribute
+    let class: String = language
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '#' | '-' | '_'))
+        .collect();
+    Some(format!(
+        "<pre class=\"hl-code\"><code class=\"language-{}\">{}</code></pre>\n",
+        class,
+        generator.finalize()
+    ))
+}
+
+/// Stylesheet coloring highlighted code with one of the bundled themes, or `None` when
+/// there is no such theme
+pub fn stylesheet(theme: &str) -> Option<String> {
+    let theme = themes().themes.get(theme)?;
+    css_for_theme_with_class_style(theme, CLASS_STYLE).ok()
+}
+
+/// Names of the bundled themes
+pub fn theme_names() -> Vec<&'static str> {
+    themes().themes.keys().map(String::as_str).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_block() {
+        let html = code_block("rs", "fn main() {}\n").unwrap();
+        assert!(html.starts_with("<pre class=\"hl-code\"><code class=\"language-rs\">"));
+        assert!(html.contains("<span class=\"hl-"));
+        assert!(html.contains("main"));
+
+        assert!(code_block("not-a-language", "x").is_none());
+        assert!(stylesheet(DEFAULT_THEME).unwrap().contains(".hl-"));
+        assert!(stylesheet("missing").is_none());
+        assert!(theme_names().contains(&DEFAULT_THEME));
+    }
+}
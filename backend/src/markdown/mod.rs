@@ -0,0 +1,6 @@
+//! Markdown rendering to sanitized HTML, with syntax-highlighted code blocks
+pub mod highlight;
+pub mod render;
+
+pub use highlight::stylesheet;
+pub use render::{render, Rendered, WORDS_PER_MINUTE};
\ No newline at end of file
@@ -0,0 +1,167 @@
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+use super::highlight;
+
+/// Reading speed used to estimate reading times
+pub const WORDS_PER_MINUTE: usize = 200;
+
+/// Link schemes kept in rendered HTML; relative links are always kept
+const SAFE_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Rendered Markdown document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub html: String,
+    pub word_count: usize,
+}
+
+impl Rendered {
+    /// Minutes needed to read the document, at least one
+    pub fn reading_time_minutes(&self) -> i32 {
+        self.word_count.div_ceil(WORDS_PER_MINUTE).max(1) as i32
+    }
+}
+
+/// Render Markdown (CommonMark with tables, footnotes, strikethrough and task lists) to HTML
+/// safe to insert in a page. Raw HTML in the source is shown as text rather than
+/// interpreted, links and images may only point to http(s), mailto or relative URLs, and
+/// fenced code blocks naming a known language are highlighted.
+pub fn render(markdown: &str) -> Rendered {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_SMART_PUNCTUATION;
+
+    let mut events = Vec::new();
+    let mut word_count = 0;
+    // Code block being read, with its language
+    let mut code_block: Option<(CodeBlockKind, String)> = None;
+
+    for event in Parser::new_ext(markdown, options) {
+        if let Some((_, code)) = &mut code_block {
+            match event {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => {
+                    if let Some((kind, code)) = code_block.take() {
+                        word_count += code.split_whitespace().count();
+                        events.extend(code_block_events(kind, code));
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => code_block = Some((kind, String::new())),
+            Event::Text(text) | Event::Code(text) => {
+                word_count += text.split_whitespace().count();
+                events.push(Event::Text(text));
+            }
+            // Raw HTML is escaped like any other text
+            Event::Html(text) | Event::InlineHtml(text) => events.push(Event::Text(text)),
+            Event::Start(Tag::Link { link_type, dest_url, title, id }) => events.push(Event::Start(Tag::Link {
+                link_type,
+                dest_url: safe_url(dest_url),
+                title,
+                id,
+            })),
+            Event::Start(Tag::Image { link_type, dest_url, title, id }) => events.push(Event::Start(Tag::Image {
+                link_type,
+                dest_url: safe_url(dest_url),
+                title,
+                id,
+            })),
+            event => events.push(event),
+        }
+    }
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut html, events.into_iter());
+    Rendered { html, word_count }
+}
+
+fn code_block_events(kind: CodeBlockKind<'_>, code: String) -> Vec<Event<'_>> {
+    let language = match &kind {
+        CodeBlockKind::Fenced(info) => info.split_whitespace().next(),
+        CodeBlockKind::Indented => None,
+    };
+
+    match language.and_then(|language| highlight::code_block(language, &code)) {
+        Some(highlighted) => vec![Event::Html(highlighted.into())],
+        // Left to the HTML writer, which escapes the code
+        None => vec![
+            Event::Start(Tag::CodeBlock(kind)),
+            Event::Text(code.into()),
+            Event::End(TagEnd::CodeBlock),
+        ],
+    }
+}
+
+/// The URL if its scheme is safe, `#` otherwise. Browsers ignore whitespace and control
+/// characters in schemes, so `java\tscript:` is caught too.
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let normalized: String = url
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let safe = match normalized.find([':', '/', '?', '#']) {
+        Some(end) if normalized[end..].starts_with(':') => SAFE_SCHEMES.contains(&&normalized[..end]),
+        _ => true,
+    };
+    if safe {
+        url
+    } else {
+        CowStr::Borrowed("#")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_markdown() {
+        let rendered = render("# Hello\n\nSome *emphasis* and a [link](https://example.com \"Example\").\n\n| a | b |\n|---|---|\n| 1 | 2 |\n");
+        assert!(rendered.html.contains("<h1>Hello</h1>"));
+        assert!(rendered.html.contains("<em>emphasis</em>"));
+        assert!(rendered.html.contains("<a href=\"https://example.com\" title=\"Example\">link</a>"));
+        assert!(rendered.html.contains("<table>"));
+        assert_eq!(rendered.reading_time_minutes(), 1);
+    }
+
+    #[test]
+    fn test_render_sanitizes() {
+        let rendered = render(
+            "<script>alert(1)</script>\n\nHi <img src=x onerror=alert(1)>\n\n[a](javascript:alert(1)) [b]( JaVa\tScRiPt:alert(1)) ![c](data:image/png;base64,AAAA) [d](/posts/other#top) [e](mailto:me@example.com)\n",
+        );
+        assert!(!rendered.html.contains("<script"));
+        assert!(!rendered.html.contains("<img src=x"));
+        assert!(rendered.html.contains("&lt;script&gt;"));
+        assert!(!rendered.html.to_lowercase().contains("javascript:"));
+        assert!(!rendered.html.contains("data:"));
+        assert!(rendered.html.contains("href=\"/posts/other#top\""));
+        assert!(rendered.html.contains("href=\"mailto:me@example.com\""));
+    }
+
+    #[test]
+    fn test_render_code_blocks() {
+        let rendered = render("```rust\nfn main() { println!(\"<b>\"); }\n```\n\n```\n<b>plain</b>\n```\n\n```nope\nx < y\n```\n");
+        assert!(rendered.html.contains("<pre class=\"hl-code\"><code class=\"language-rust\">"));
+        assert!(!rendered.html.contains("<b>"));
+        assert!(rendered.html.contains("<pre><code>&lt;b&gt;plain&lt;/b&gt;"));
+        assert!(rendered.html.contains("<code class=\"language-nope\">x &lt; y"));
+    }
+
+    #[test]
+    fn test_reading_time() {
+        let words = "word ".repeat(WORDS_PER_MINUTE * 2 + 1);
+        let rendered = render(&words);
+        assert_eq!(rendered.word_count, WORDS_PER_MINUTE * 2 + 1);
+        assert_eq!(rendered.reading_time_minutes(), 3);
+        assert_eq!(render("").reading_time_minutes(), 1);
+    }
+}
@@ -0,0 +1,208 @@
+use std::io::Cursor;
+
+use image::{
+    codecs::{avif::AvifEncoder, jpeg::JpegEncoder, png::PngEncoder, webp::WebPEncoder},
+    imageops::FilterType,
+    metadata::Orientation,
+    DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, RgbImage,
+};
+
+use crate::models::{MediaType, VariantFormat};
+
+/// Largest side, in pixels, of an image that is decoded
+pub const MAX_DIMENSION: u32 = 16_384;
+
+const JPEG_QUALITY: u8 = 82;
+const AVIF_SPEED: u8 = 8;
+const AVIF_QUALITY: u8 = 70;
+
+/// Image processing error types
+#[derive(Debug, thiserror::Error)]
+pub enum DerivativeError {
+    #[error("Cannot read image: {0}")]
+    Decode(String),
+    #[error("Cannot encode image: {0}")]
+    Encode(String),
+}
+
+/// What the headers of an image say about it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// Width as displayed, once the orientation is applied
+    pub width: u32,
+    pub height: u32,
+    /// Exif orientation, 1 when the image is stored upright
+    pub orientation: u8,
+}
+
+/// Rendered derivative
+#[derive(Debug, Clone)]
+pub struct Rendered {
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Read the dimensions and orientation of an image without decoding its pixels
+pub fn probe(bytes: &[u8], media_type: MediaType) -> Result<ImageInfo, DerivativeError> {
+    let mut decoder = reader(bytes, media_type).into_decoder().map_err(decode_error)?;
+    let orientation = decoder.orientation().map_err(decode_error)?;
+    let (width, height) = decoder.dimensions();
+
+    let (width, height) = if swaps_sides(orientation) {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    Ok(ImageInfo {
+        width,
+        height,
+        orientation: orientation.to_exif(),
+    })
+}
+
+/// Render an image upright, scaled down to `max_width` if wider, in the given format.
+/// Encoders write no metadata, so derivatives carry no Exif or GPS data. Only the first
+/// frame of an animation is kept.
+pub fn render(
+    bytes: &[u8],
+    media_type: MediaType,
+    max_width: u32,
+    format: VariantFormat,
+) -> Result<Rendered, DerivativeError> {
+    let mut decoder = reader(bytes, media_type).into_decoder().map_err(decode_error)?;
+    let orientation = decoder.orientation().map_err(decode_error)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(decode_error)?;
+    image.apply_orientation(orientation);
+
+    if max_width < image.width() {
+        let height = (u64::from(image.height()) * u64::from(max_width) + u64::from(image.width()) / 2)
+            / u64::from(image.width());
+        image = image.resize_exact(max_width, height.max(1) as u32, FilterType::Lanczos3);
+    }
+
+    Ok(Rendered {
+        bytes: encode(&image, format)?,
+        width: image.width(),
+        height: image.height(),
+    })
+}
+
+fn encode(image: &DynamicImage, format: VariantFormat) -> Result<Vec<u8>, DerivativeError> {
+    // 8 bits per channel is all browsers show; alpha is only kept when the image has some
+    let image = match (format, image.color().has_alpha()) {
+        (VariantFormat::Jpeg, true) => DynamicImage::ImageRgb8(flatten(image)),
+        (_, true) => DynamicImage::ImageRgba8(image.to_rgba8()),
+        (_, false) => DynamicImage::ImageRgb8(image.to_rgb8()),
+    };
+
+    let mut bytes = Vec::new();
+    let result = match format {
+        VariantFormat::Jpeg => image.write_with_encoder(JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)),
+        VariantFormat::Png => image.write_with_encoder(PngEncoder::new(&mut bytes)),
+        VariantFormat::Webp => image.write_with_encoder(WebPEncoder::new_lossless(&mut bytes)),
+        VariantFormat::Avif => {
+            image.write_with_encoder(AvifEncoder::new_with_speed_quality(&mut bytes, AVIF_SPEED, AVIF_QUALITY))
+        }
+    };
+
+    result.map_err(|e| DerivativeError::Encode(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// Drop the alpha channel of an image for formats without one, over a white background
+fn flatten(image: &DynamicImage) -> RgbImage {
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |channel: u8| ((u16::from(channel) * u16::from(a) + 255 * (255 - u16::from(a))) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+fn reader(bytes: &[u8], media_type: MediaType) -> ImageReader<Cursor<&[u8]>> {
+    let format = match media_type {
+        MediaType::Png => ImageFormat::Png,
+        MediaType::Jpeg => ImageFormat::Jpeg,
+        MediaType::Webp => ImageFormat::WebP,
+        MediaType::Gif => ImageFormat::Gif,
+    };
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+    reader
+}
+
+fn swaps_sides(orientation: Orientation) -> bool {
+    matches!(
+        orientation,
+        Orientation::Rotate90 | Orientation::Rotate270 | Orientation::Rotate90FlipH | Orientation::Rotate270FlipH
+    )
+}
+
+fn decode_error(error: image::ImageError) -> DerivativeError {
+    DerivativeError::Decode(error.to_string())
+}
+
+/// Encoded gradient of the given size, for tests
+#[cfg(test)]
+pub fn test_image(media_type: MediaType, width: u32, height: u32) -> Vec<u8> {
+    let image = RgbImage::from_fn(width, height, |x, y| image::Rgb([(x * 255 / width) as u8, (y * 255 / height) as u8, 128]));
+    let format = match media_type {
+        MediaType::Png => VariantFormat::Png,
+        MediaType::Jpeg => VariantFormat::Jpeg,
+        MediaType::Webp => VariantFormat::Webp,
+        MediaType::Gif => {
+            let mut bytes = Vec::new();
+            DynamicImage::ImageRgb8(image)
+                .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Gif)
+                .unwrap();
+            return bytes;
+        }
+    };
+    encode(&DynamicImage::ImageRgb8(image), format).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_scales_down_only() {
+        let png = test_image(MediaType::Png, 200, 100);
+        let info = probe(&png, MediaType::Png).unwrap();
+        assert_eq!((info.width, info.height, info.orientation), (200, 100, 1));
+
+        let small = render(&png, MediaType::Png, 64, VariantFormat::Webp).unwrap();
+        assert_eq!((small.width, small.height), (64, 32));
+        assert_eq!(MediaType::sniff(&small.bytes), Some(MediaType::Webp));
+
+        let full = render(&png, MediaType::Png, 1280, VariantFormat::Jpeg).unwrap();
+        assert_eq!((full.width, full.height), (200, 100));
+        assert_eq!(MediaType::sniff(&full.bytes), Some(MediaType::Jpeg));
+
+        let gif = test_image(MediaType::Gif, 20, 20);
+        let frame = render(&gif, MediaType::Gif, 10, VariantFormat::Png).unwrap();
+        assert_eq!((frame.width, frame.height), (10, 10));
+    }
+
+    #[test]
+    fn test_render_avif() {
+        let jpeg = test_image(MediaType::Jpeg, 32, 16);
+        let avif = render(&jpeg, MediaType::Jpeg, 16, VariantFormat::Avif).unwrap();
+        assert_eq!((avif.width, avif.height), (16, 8));
+        assert_eq!(&avif.bytes[4..12], b"ftypavif");
+    }
+
+    #[test]
+    fn test_unreadable_image() {
+        let mut png = test_image(MediaType::Png, 20, 20);
+        png.truncate(30);
+        assert!(matches!(probe(&png, MediaType::Png), Err(DerivativeError::Decode(_))));
+        assert!(render(b"\x89PNG\r\n\x1a\n-fake", MediaType::Png, 10, VariantFormat::Png).is_err());
+    }
+}
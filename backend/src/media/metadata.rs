@@ -0,0 +1,223 @@
+use crate::models::MediaType;
+
+/// VP8X flags announcing Exif and XMP chunks
+const WEBP_EXIF_FLAG: u8 = 0x08;
+const WEBP_XMP_FLAG: u8 = 0x04;
+
+/// Copy of an image without the metadata cameras and editors embed: Exif (with its GPS
+/// position), XMP, IPTC, text chunks and comments. The pixels are left untouched. A JPEG
+/// stored rotated keeps an Exif block holding only its orientation, so it still displays
+/// upright. GIFs carry no such metadata and are returned as they are.
+///
+/// Returns `None` when the file structure cannot be followed.
+pub fn strip(bytes: &[u8], media_type: MediaType, orientation: u8) -> Option<Vec<u8>> {
+    match media_type {
+        MediaType::Jpeg => strip_jpeg(bytes, orientation),
+        MediaType::Png => strip_png(bytes),
+        MediaType::Webp => strip_webp(bytes),
+        MediaType::Gif => Some(bytes.to_vec()),
+    }
+}
+
+fn strip_jpeg(bytes: &[u8], orientation: u8) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&bytes[..2]);
+    let mut orientation_written = orientation <= 1;
+    let mut pos = 2;
+
+    loop {
+        if bytes.get(pos) != Some(&0xff) {
+            return None;
+        }
+        // Markers may be preceded by fill bytes
+        while bytes.get(pos + 1) == Some(&0xff) {
+            pos += 1;
+        }
+        let marker = *bytes.get(pos + 1)?;
+
+        // Exif goes right after the JFIF header when there is one
+        if !orientation_written && marker != 0xe0 {
+            out.extend_from_slice(&orientation_segment(orientation));
+            orientation_written = true;
+        }
+
+        match marker {
+            // Start of scan: the compressed data and whatever follows is kept as is
+            0xda => {
+                out.extend_from_slice(&bytes[pos..]);
+                return Some(out);
+            }
+            0xd9 => {
+                out.extend_from_slice(&bytes[pos..pos + 2]);
+                return Some(out);
+            }
+            // Markers without a payload
+            0x01 | 0xd0..=0xd7 => {
+                out.extend_from_slice(&bytes[pos..pos + 2]);
+                pos += 2;
+                continue;
+            }
+            _ => {}
+        }
+
+        let length = u16::from_be_bytes([*bytes.get(pos + 2)?, *bytes.get(pos + 3)?]) as usize;
+        if length < 2 {
+            return None;
+        }
+        let end = pos + 2 + length;
+        let segment = bytes.get(pos..end)?;
+
+        // APP1 holds Exif and XMP, APP13 holds IPTC, COM holds comments
+        if !matches!(marker, 0xe1 | 0xed | 0xfe) {
+            out.extend_from_slice(segment);
+        }
+        pos = end;
+    }
+}
+
+/// APP1 segment with a big-endian Exif block whose only entry is the orientation
+fn orientation_segment(orientation: u8) -> Vec<u8> {
+    let mut segment = vec![0xff, 0xe1, 0x00, 0x22];
+    segment.extend_from_slice(b"Exif\0\0");
+    // TIFF header, then the first directory at offset 8
+    segment.extend_from_slice(b"MM\0\x2a\0\0\0\x08");
+    // One entry: tag 0x0112 (orientation), type SHORT, count 1
+    segment.extend_from_slice(&[0x00, 0x01, 0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+    segment.extend_from_slice(&[0x00, orientation, 0x00, 0x00]);
+    // No next directory
+    segment.extend_from_slice(&[0x00; 4]);
+    segment
+}
+
+fn strip_png(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut out = bytes.get(..8)?.to_vec();
+    let mut pos = 8;
+
+    while pos < bytes.len() {
+        let length = u32::from_be_bytes(bytes.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        // Length, type, data and CRC
+        let end = pos.checked_add(12)?.checked_add(length)?;
+        let chunk = bytes.get(pos..end)?;
+        let kind = &chunk[4..8];
+
+        if !matches!(kind, b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt" | b"tIME") {
+            out.extend_from_slice(chunk);
+        }
+        if kind == b"IEND" {
+            return Some(out);
+        }
+        pos = end;
+    }
+
+    None
+}
+
+fn strip_webp(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() < 12 || &bytes[..4] != b"RIFF" || &bytes[8..12] != b"WEBP" {
+        return None;
+    }
+
+    let mut out = bytes[..12].to_vec();
+    let mut pos = 12;
+
+    while pos < bytes.len() {
+        let header = bytes.get(pos..pos + 8)?;
+        let length = u32::from_le_bytes(header[4..8].try_into().ok()?) as usize;
+        let data_end = pos.checked_add(8)?.checked_add(length)?;
+        if data_end > bytes.len() {
+            return None;
+        }
+        // Chunks are padded to an even length; some writers drop the last padding byte
+        let end = (data_end + length % 2).min(bytes.len());
+        let chunk = &bytes[pos..end];
+
+        match &header[..4] {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" => {
+                let mut chunk = chunk.to_vec();
+                if let Some(flags) = chunk.get_mut(8) {
+                    *flags &= !(WEBP_EXIF_FLAG | WEBP_XMP_FLAG);
+                }
+                out.extend_from_slice(&chunk);
+            }
+            _ => out.extend_from_slice(chunk),
+        }
+        pos = end;
+    }
+
+    let riff_length = u32::try_from(out.len() - 8).ok()?;
+    out[4..8].copy_from_slice(&riff_length.to_le_bytes());
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::derivative::{probe, test_image};
+
+    /// Exif block with a GPS directory pointer, as phones write it
+    fn exif_segment() -> Vec<u8> {
+        let mut payload = b"Exif\0\0MM\0\x2a\0\0\0\x08".to_vec();
+        payload.extend_from_slice(&[0x00, 0x01, 0x88, 0x25, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x1a]);
+        payload.extend_from_slice(&[0x00; 4]);
+        payload.extend_from_slice(b"GPS 48.8584 N 2.2945 E");
+
+        let mut segment = vec![0xff, 0xe1];
+        segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(&payload);
+        segment
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|window| window == needle)
+    }
+
+    #[test]
+    fn test_strip_jpeg_keeps_orientation() {
+        let jpeg = test_image(MediaType::Jpeg, 40, 20);
+        // Phone picture with a GPS position and a comment, which the camera stored rotated
+        let mut tagged = jpeg[..2].to_vec();
+        tagged.extend_from_slice(&exif_segment());
+        tagged.extend_from_slice(&[0xff, 0xfe, 0x00, 0x07]);
+        tagged.extend_from_slice(b"hello");
+        tagged.extend_from_slice(&jpeg[2..]);
+
+        let stripped = strip(&tagged, MediaType::Jpeg, 6).unwrap();
+        assert!(!contains(&stripped, b"GPS"));
+        assert!(!contains(&stripped, b"hello"));
+        let info = probe(&stripped, MediaType::Jpeg).unwrap();
+        assert_eq!((info.width, info.height, info.orientation), (20, 40, 6));
+
+        // An upright picture needs no Exif at all
+        let upright = strip(&tagged, MediaType::Jpeg, 1).unwrap();
+        assert!(!contains(&upright, b"Exif"));
+        assert_eq!(upright.len(), jpeg.len());
+    }
+
+    #[test]
+    fn test_strip_png_and_webp() {
+        let png = test_image(MediaType::Png, 8, 8);
+        let mut tagged = png[..33].to_vec();
+        let text = b"tEXtComment\0Taken at home";
+        tagged.extend_from_slice(&((text.len() - 4) as u32).to_be_bytes());
+        tagged.extend_from_slice(text);
+        tagged.extend_from_slice(&[0; 4]);
+        tagged.extend_from_slice(&png[33..]);
+
+        let stripped = strip(&tagged, MediaType::Png, 1).unwrap();
+        assert_eq!(stripped, png);
+        assert!(strip(&png[..40], MediaType::Png, 1).is_none());
+
+        let webp = test_image(MediaType::Webp, 8, 8);
+        let mut tagged = webp.clone();
+        tagged.extend_from_slice(b"EXIF\x05\0\0\0GPS!!\0");
+        let riff_length = (tagged.len() - 8) as u32;
+        tagged[4..8].copy_from_slice(&riff_length.to_le_bytes());
+
+        assert_eq!(strip(&tagged, MediaType::Webp, 1).unwrap(), webp);
+    }
+}
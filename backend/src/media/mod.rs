@@ -0,0 +1,8 @@
+//! Content-addressed storage of uploaded media on local disk, and the derivatives rendered
+//! from them
+pub mod derivative;
+pub mod metadata;
+pub mod storage;
+
+pub use derivative::{DerivativeError, ImageInfo, Rendered};
+pub use storage::{content_hash, is_content_hash, MediaStorage};
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+use tokio::fs;
+
+use crate::models::{MediaType, VariantFormat};
+
+/// SHA-256 of a file's content, hex encoded, under which it is stored
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Whether a string can be a content hash, and so is safe to use in a path
+pub fn is_content_hash(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Files stored under `<root>/<first two hash characters>/<hash>.<extension>`, so
+/// identical uploads share a single file. Derivatives sit next to their original as
+/// `<hash>-w<width>.<extension>`.
+#[derive(Debug, Clone)]
+pub struct MediaStorage {
+    root: PathBuf,
+}
+
+impl MediaStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where the file with the given hash is kept
+    pub fn path(&self, hash: &str, media_type: MediaType) -> PathBuf {
+        self.root
+            .join(&hash[..2])
+            .join(format!("{}.{}", hash, media_type.extension()))
+    }
+
+    /// Where a derivative of the file with the given hash is kept
+    pub fn variant_path(&self, hash: &str, width: u32, format: VariantFormat) -> PathBuf {
+        self.root
+            .join(&hash[..2])
+            .join(format!("{}-w{}.{}", hash, width, format.extension()))
+    }
+
+    /// Write a file unless the same content is already stored. The file is written under a
+    /// temporary name first, so a file at the final path is always complete.
+    pub async fn store(&self, hash: &str, media_type: MediaType, bytes: &[u8]) -> io::Result<PathBuf> {
+        let path = self.path(hash, media_type);
+        if fs::try_exists(&path).await? {
+            return Ok(path);
+        }
+
+        self.write(path, hash, bytes).await
+    }
+
+    /// Write a derivative, replacing any earlier rendering of it
+    pub async fn store_variant(
+        &self,
+        hash: &str,
+        width: u32,
+        format: VariantFormat,
+        bytes: &[u8],
+    ) -> io::Result<PathBuf> {
+        self.write(self.variant_path(hash, width, format), hash, bytes).await
+    }
+
+    async fn write(&self, path: PathBuf, hash: &str, bytes: &[u8]) -> io::Result<PathBuf> {
+        let dir = path.parent().unwrap_or(&self.root);
+        fs::create_dir_all(dir).await?;
+
+        let temp_path = dir.join(format!(".{}.{}.tmp", hash, uuid::Uuid::new_v4()));
+        if let Err(e) = fs::write(&temp_path, bytes).await {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(e);
+        }
+        fs::rename(&temp_path, &path).await?;
+
+        Ok(path)
+    }
+
+    /// Delete a stored file; a file already gone is not an error
+    pub async fn remove(&self, hash: &str, media_type: MediaType) -> io::Result<()> {
+        remove_file(self.path(hash, media_type)).await
+    }
+
+    /// Delete a stored derivative; a file already gone is not an error
+    pub async fn remove_variant(&self, hash: &str, width: u32, format: VariantFormat) -> io::Result<()> {
+        remove_file(self.variant_path(hash, width, format)).await
+    }
+}
+
+async fn remove_file(path: PathBuf) -> io::Result<()> {
+    match fs::remove_file(path).await {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_by_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MediaStorage::new(dir.path());
+        let bytes = b"GIF89a-not-really";
+        let hash = content_hash(bytes);
+        assert!(is_content_hash(&hash));
+        assert!(!is_content_hash("../../etc/passwd"));
+
+        let path = storage.store(&hash, MediaType::Gif, bytes).await.unwrap();
+        assert_eq!(path, dir.path().join(&hash[..2]).join(format!("{}.gif", hash)));
+        assert_eq!(fs::read(&path).await.unwrap(), bytes);
+
+        // Storing the same content again leaves the file alone
+        assert_eq!(storage.store(&hash, MediaType::Gif, bytes).await.unwrap(), path);
+        let entries = std::fs::read_dir(path.parent().unwrap()).unwrap().count();
+        assert_eq!(entries, 1);
+
+        let variant = storage.store_variant(&hash, 320, VariantFormat::Webp, b"RIFF").await.unwrap();
+        assert_eq!(variant, path.with_file_name(format!("{}-w320.webp", hash)));
+
+        storage.remove(&hash, MediaType::Gif).await.unwrap();
+        assert!(!path.exists());
+        storage.remove(&hash, MediaType::Gif).await.unwrap();
+        storage.remove_variant(&hash, 320, VariantFormat::Webp).await.unwrap();
+        assert!(!variant.exists());
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Admin user model representing an account allowed to manage content
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct AdminUser {
+    pub id: i32,
+    pub username: String,
+    #[serde(skip)]
+    pub password_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+/// Create admin user request model
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateAdminUser {
+    #[validate(length(min = 3, max = 50, message = "Username must be between 3 and 50 characters"))]
+    pub username: String,
+
+    #[validate(length(min = 12, max = 1024, message = "Password must be between 12 and 1024 characters"))]
+    pub password: String,
+}
+
+/// Login request model
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct LoginRequest {
+    #[validate(length(min = 1, max = 50, message = "Username must be between 1 and 50 characters"))]
+    #[schema(min_length = 1, max_length = 50)]
+    pub username: String,
+
+    #[validate(length(min = 1, max = 1024, message = "Password must be between 1 and 1024 characters"))]
+    #[schema(min_length = 1, max_length = 1024)]
+    pub password: String,
+}
+
+/// Login response model carrying the signed session token
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LoginResponse {
+    pub token: String,
+    pub token_type: String,
+    pub expires_at: DateTime<Utc>,
+    pub admin: AdminUser,
+}
+
+impl CreateAdminUser {
+    /// Normalize the username (trimmed, lowercase)
+    pub fn sanitize(&mut self) {
+        self.username = self.username.trim().to_lowercase();
+    }
+}
+
+impl LoginRequest {
+    /// Normalize the username the same way it is stored
+    pub fn sanitize(&mut self) {
+        self.username = self.username.trim().to_lowercase();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[test]
+    fn test_create_admin_user_validation() {
+        let admin = CreateAdminUser {
+            username: "admin".to_string(),
+            password: "a-long-enough-password".to_string(),
+        };
+        assert!(admin.validate().is_ok());
+
+        let short_password = CreateAdminUser {
+            username: "admin".to_string(),
+            password: "short".to_string(),
+        };
+        assert!(short_password.validate().is_err());
+    }
+
+    #[test]
+    fn test_login_request_sanitize() {
+        let mut request = LoginRequest {
+            username: "  Admin  ".to_string(),
+            password: "secret".to_string(),
+        };
+        request.sanitize();
+        assert_eq!(request.username, "admin");
+    }
+
+    #[test]
+    fn test_admin_user_hides_password_hash() {
+        let admin = AdminUser {
+            id: 1,
+            username: "admin".to_string(),
+            password_hash: "$argon2id$hash".to_string(),
+            created_at: Utc::now(),
+            last_login_at: None,
+        };
+
+        let json = serde_json::to_value(&admin).unwrap();
+        assert!(json.get("password_hash").is_none());
+        assert_eq!(json["username"], "admin");
+    }
+}
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Prefix identifying API keys (as opposed to admin session tokens)
+pub const API_KEY_PREFIX: &str = "pfk_";
+
+/// API key model as stored in the database
+#[derive(Debug, Clone, Deserialize, FromRow)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    pub key_prefix: String,
+    pub key_hash: String,
+    pub scopes: String, // JSON array as string
+    pub created_by: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// API key model for API responses (never exposes the hash)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: i32,
+    pub name: String,
+    pub key_prefix: String,
+    #[schema(value_type = Vec<ApiScope>)]
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub active: bool,
+}
+
+impl From<ApiKey> for ApiKeyResponse {
+    fn from(key: ApiKey) -> Self {
+        let active = key.is_active();
+        let scopes = key.get_scopes().unwrap_or_default();
+        Self {
+            id: key.id,
+            name: key.name,
+            key_prefix: key.key_prefix,
+            scopes,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            revoked_at: key.revoked_at,
+            created_at: key.created_at,
+            active,
+        }
+    }
+}
+
+/// Create API key request model
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateApiKey {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub name: String,
+
+    #[validate(length(min = 1, message = "At least one scope must be specified"))]
+    #[schema(value_type = Vec<ApiScope>, min_items = 1)]
+    pub scopes: Vec<String>,
+
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Response for a newly created API key; the plaintext key is only ever shown here
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatedApiKey {
+    pub key: String,
+    pub api_key: ApiKeyResponse,
+}
+
+/// Permissions that can be granted to an API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ApiScope {
+    #[serde(rename = "projects:write")]
+    ProjectsWrite,
+    #[serde(rename = "skills:write")]
+    SkillsWrite,
+    #[serde(rename = "profile:write")]
+    ProfileWrite,
+    #[serde(rename = "contact:read")]
+    ContactRead,
+    #[serde(rename = "contact:write")]
+    ContactWrite,
+    #[serde(rename = "contact:delete")]
+    ContactDelete,
+    #[serde(rename = "posts:write")]
+    PostsWrite,
+}
+
+impl ApiScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::ProjectsWrite => "projects:write",
+            ApiScope::SkillsWrite => "skills:write",
+            ApiScope::ProfileWrite => "profile:write",
+            ApiScope::ContactRead => "contact:read",
+            ApiScope::ContactWrite => "contact:write",
+            ApiScope::ContactDelete => "contact:delete",
+            ApiScope::PostsWrite => "posts:write",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "projects:write" => Some(ApiScope::ProjectsWrite),
+            "skills:write" => Some(ApiScope::SkillsWrite),
+            "profile:write" => Some(ApiScope::ProfileWrite),
+            "contact:read" => Some(ApiScope::ContactRead),
+            "contact:write" => Some(ApiScope::ContactWrite),
+            "contact:delete" => Some(ApiScope::ContactDelete),
+            "posts:write" => Some(ApiScope::PostsWrite),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["projects:write", "skills:write", "profile:write", "contact:read", "contact:write", "contact:delete", "posts:write"]
+    }
+}
+
+impl ApiKey {
+    /// Parse scopes from JSON string
+    pub fn get_scopes(&self) -> Result<Vec<String>, serde_json::Error> {
+        serde_json::from_str(&self.scopes)
+    }
+
+    /// Check whether the key grants a scope
+    pub fn has_scope(&self, scope: ApiScope) -> bool {
+        self.get_scopes()
+            .map(|scopes| scopes.iter().any(|s| s == scope.as_str()))
+            .unwrap_or(false)
+    }
+
+    /// Check if the key has expired
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= Utc::now())
+    }
+
+    /// Check if the key can still be used (not revoked, not expired)
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none() && !self.is_expired()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    fn create_test_api_key() -> ApiKey {
+        ApiKey {
+            id: 1,
+            name: "CI".to_string(),
+            key_prefix: "pfk_abcdefgh".to_string(),
+            key_hash: "hash".to_string(),
+            scopes: r#"["projects:write","skills:write"]"#.to_string(),
+            created_by: Some(1),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_api_key_scopes() {
+        let key = create_test_api_key();
+        assert!(key.has_scope(ApiScope::ProjectsWrite));
+        assert!(key.has_scope(ApiScope::SkillsWrite));
+        assert!(!key.has_scope(ApiScope::ContactRead));
+    }
+
+    #[test]
+    fn test_api_key_active() {
+        let key = create_test_api_key();
+        assert!(key.is_active());
+
+        let expired = ApiKey {
+            expires_at: Some(Utc::now() - chrono::Duration::minutes(1)),
+            ..create_test_api_key()
+        };
+        assert!(expired.is_expired());
+        assert!(!expired.is_active());
+
+        let revoked = ApiKey {
+            revoked_at: Some(Utc::now()),
+            ..create_test_api_key()
+        };
+        assert!(!revoked.is_active());
+    }
+
+    #[test]
+    fn test_api_scope_roundtrip() {
+        for scope in ApiScope::all() {
+            assert_eq!(ApiScope::from_str(scope).unwrap().as_str(), scope);
+        }
+        assert!(ApiScope::from_str("projects:delete").is_none());
+    }
+
+    #[test]
+    fn test_create_api_key_validation() {
+        let request = CreateApiKey {
+            name: "CI".to_string(),
+            scopes: vec![],
+            expires_at: None,
+        };
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_api_key_response_hides_hash() {
+        let response = ApiKeyResponse::from(create_test_api_key());
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("key_hash").is_none());
+        assert_eq!(response.scopes, vec!["projects:write", "skills:write"]);
+        assert!(response.active);
+    }
+}
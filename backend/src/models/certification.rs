@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::models::timeline::validate_month;
+
+/// Professional certification
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Certification {
+    pub id: i32,
+    pub name: String,
+    pub issuer: String,
+    /// `YYYY-MM`
+    pub issued_month: String,
+    /// `YYYY-MM`, absent when the certification does not expire
+    pub expires_month: Option<String>,
+    pub credential_id: Option<String>,
+    pub credential_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create certification request model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateCertification {
+    #[validate(length(min = 1, max = 150, message = "Name must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 150, message = "Issuer must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub issuer: String,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub issued_month: String,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub expires_month: Option<String>,
+
+    #[validate(length(max = 100, message = "Credential ID must be less than 100 characters"))]
+    #[schema(max_length = 100)]
+    pub credential_id: Option<String>,
+
+    #[validate(url(message = "Credential URL must be a valid URL"))]
+    #[schema(format = "uri")]
+    pub credential_url: Option<String>,
+}
+
+/// Update certification request model
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateCertification {
+    #[validate(length(min = 1, max = 150, message = "Name must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub name: Option<String>,
+
+    #[validate(length(min = 1, max = 150, message = "Issuer must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub issuer: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub issued_month: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub expires_month: Option<String>,
+
+    #[validate(length(max = 100, message = "Credential ID must be less than 100 characters"))]
+    #[schema(max_length = 100)]
+    pub credential_id: Option<String>,
+
+    #[validate(url(message = "Credential URL must be a valid URL"))]
+    #[schema(format = "uri")]
+    pub credential_url: Option<String>,
+}
+
+impl UpdateCertification {
+    /// Check if any field is being updated
+    pub fn has_updates(&self) -> bool {
+        self.name.is_some()
+            || self.issuer.is_some()
+            || self.issued_month.is_some()
+            || self.expires_month.is_some()
+            || self.credential_id.is_some()
+            || self.credential_url.is_some()
+    }
+}
+
+impl From<Certification> for CreateCertification {
+    fn from(certification: Certification) -> Self {
+        Self {
+            name: certification.name,
+            issuer: certification.issuer,
+            issued_month: certification.issued_month,
+            expires_month: certification.expires_month,
+            credential_id: certification.credential_id,
+            credential_url: certification.credential_url,
+        }
+    }
+}
+
+impl From<CreateCertification> for UpdateCertification {
+    fn from(certification: CreateCertification) -> Self {
+        Self {
+            name: Some(certification.name),
+            issuer: Some(certification.issuer),
+            issued_month: Some(certification.issued_month),
+            expires_month: certification.expires_month,
+            credential_id: certification.credential_id,
+            credential_url: certification.credential_url,
+        }
+    }
+}
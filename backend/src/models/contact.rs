@@ -1,33 +1,192 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use utoipa::ToSchema;
 use validator::Validate;
 
+use super::SpamSignal;
+
 /// Contact message model representing messages from the contact form
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ContactMessage {
     pub id: i32,
     pub name: String,
     pub email: String,
     pub subject: String,
     pub message: String,
+    #[schema(value_type = MessageStatus)]
+    pub status: String,
+    /// Whether the spam filters flagged the message when it was submitted
+    pub is_spam: bool,
+    /// Total score given by the spam filters, unset for messages received before scoring
+    pub spam_score: Option<f64>,
+    /// Signals behind the score
+    #[schema(value_type = Option<Vec<SpamSignal>>)]
+    pub spam_reasons: Option<Json<Vec<SpamSignal>>>,
+    /// Label the message last trained the spam model with (`spam` or `ham`)
+    pub spam_trained: Option<String>,
+    pub starred: bool,
+    /// Internal notes, never shown to the sender
+    pub notes: Option<String>,
+    pub status_changed_at: Option<DateTime<Utc>>,
+    pub replied_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Moderation state of a contact message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageStatus {
+    New,
+    Read,
+    Replied,
+    Archived,
+    /// Quarantined: left out of the inbox unless asked for
+    Spam,
+}
+
+impl MessageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageStatus::New => "new",
+            MessageStatus::Read => "read",
+            MessageStatus::Replied => "replied",
+            MessageStatus::Archived => "archived",
+            MessageStatus::Spam => "spam",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(status: &str) -> Option<Self> {
+        match status {
+            "new" => Some(MessageStatus::New),
+            "read" => Some(MessageStatus::Read),
+            "replied" => Some(MessageStatus::Replied),
+            "archived" => Some(MessageStatus::Archived),
+            "spam" => Some(MessageStatus::Spam),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["new", "read", "replied", "archived", "spam"]
+    }
+
+    /// Whether a message may move from this status to another.
+    /// Replied messages can only be archived (or reported as spam), archived ones restored
+    /// as read or replied, and spam either released to the inbox as new or archived.
+    pub fn can_transition_to(&self, to: MessageStatus) -> bool {
+        use MessageStatus::*;
+
+        *self == to
+            || match self {
+                New | Read => true,
+                Replied => matches!(to, Archived | Spam),
+                Archived => matches!(to, Read | Replied),
+                Spam => matches!(to, New | Archived),
+            }
+    }
+}
+
+/// Reply recorded against a contact message
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct ContactReply {
+    pub id: i32,
+    pub message_id: i32,
+    pub body: String,
+    /// Admin or API key that recorded the reply
+    pub replied_by: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
+/// Contact message with the replies recorded for it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ContactMessageDetail {
+    #[serde(flatten)]
+    pub message: ContactMessage,
+    pub replies: Vec<ContactReply>,
+}
+
+/// Update contact message request model (admin only)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateContactMessage {
+    #[schema(value_type = Option<MessageStatus>)]
+    pub status: Option<String>,
+
+    pub starred: Option<bool>,
+
+    /// Empty notes clear them
+    #[validate(length(max = 2000, message = "Notes must be less than 2000 characters"))]
+    #[schema(max_length = 2000)]
+    pub notes: Option<String>,
+}
+
+/// Move several messages to a status at once
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct BulkStatusUpdate {
+    #[validate(length(min = 1, max = 100, message = "Between 1 and 100 message IDs must be given"))]
+    #[schema(min_items = 1, max_items = 100)]
+    pub ids: Vec<i32>,
+
+    #[schema(value_type = MessageStatus)]
+    pub status: String,
+}
+
+/// Record a reply request model
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateContactReply {
+    #[validate(length(min = 1, max = 5000, message = "Reply must be between 1 and 5000 characters"))]
+    #[schema(min_length = 1, max_length = 5000)]
+    pub body: String,
+}
+
+/// Criteria for listing contact messages; all given criteria must match
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    /// Exact status; without one, everything but spam
+    pub status: Option<MessageStatus>,
+    pub starred: Option<bool>,
+    /// FTS5 match expression run against the search index (see `search::to_match_expression`)
+    pub search: Option<String>,
+    /// Only messages received since
+    pub since: Option<DateTime<Utc>>,
+}
+
 /// Create contact message request model
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateContactMessage {
     #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
     pub name: String,
     
     #[validate(email(message = "Email must be a valid email address"))]
+    #[schema(format = "email")]
     pub email: String,
     
     #[validate(length(min = 1, max = 200, message = "Subject must be between 1 and 200 characters"))]
+    #[schema(min_length = 1, max_length = 200)]
     pub subject: String,
     
     #[validate(length(min = 1, max = 2000, message = "Message must be between 1 and 2000 characters"))]
+    #[schema(min_length = 1, max_length = 2000)]
     pub message: String,
+
+    /// Honeypot: the form hides this field from people, so anything in it came from a bot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+
+    /// Token from `GET /api/contact/form-token`, fetched when the form was displayed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form_token: Option<String>,
+
+    /// Challenge from `GET /api/contact/challenge`, when challenges are enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+
+    /// Nonce solving `challenge`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge_nonce: Option<String>,
 }
 
 impl ContactMessage {
@@ -52,43 +211,9 @@ impl ContactMessage {
         self.created_at.format("%Y-%m-%d %H:%M UTC").to_string()
     }
 
-    /// Check if message appears to be spam (basic heuristics)
-    pub fn is_likely_spam(&self) -> bool {
-        let spam_keywords = [
-            "viagra", "casino", "lottery", "winner", "congratulations",
-            "click here", "free money", "urgent", "act now", "limited time"
-        ];
-        
-        let message_lower = self.message.to_lowercase();
-        let subject_lower = self.subject.to_lowercase();
-        
-        // Check for spam keywords
-        let has_spam_keywords = spam_keywords.iter().any(|&keyword| {
-            message_lower.contains(keyword) || subject_lower.contains(keyword)
-        });
-        
-        // Check for excessive capitalization
-        let caps_ratio = self.message.chars()
-            .filter(|c| c.is_alphabetic())
-            .fold((0, 0), |(caps, total), c| {
-                if c.is_uppercase() {
-                    (caps + 1, total + 1)
-                } else {
-                    (caps, total + 1)
-                }
-            });
-        
-        let excessive_caps = if caps_ratio.1 > 0 {
-            (caps_ratio.0 as f32 / caps_ratio.1 as f32) > 0.5
-        } else {
-            false
-        };
-        
-        // Check for excessive exclamation marks
-        let exclamation_count = self.message.matches('!').count();
-        let excessive_exclamations = exclamation_count > 5;
-        
-        has_spam_keywords || excessive_caps || excessive_exclamations
+    /// Current moderation status (messages are only ever stored with a known one)
+    pub fn message_status(&self) -> MessageStatus {
+        MessageStatus::from_str(&self.status).unwrap_or(MessageStatus::New)
     }
 }
 
@@ -126,6 +251,15 @@ mod tests {
             email: "john.doe@example.com".to_string(),
             subject: "Inquiry about services".to_string(),
             message: "Hello, I'm interested in your web development services. Could you please provide more information about your rates and availability?".to_string(),
+            status: "new".to_string(),
+            is_spam: false,
+            spam_score: None,
+            spam_reasons: None,
+            spam_trained: None,
+            starred: false,
+            notes: None,
+            status_changed_at: None,
+            replied_at: None,
             created_at: Utc::now(),
         }
     }
@@ -137,6 +271,7 @@ mod tests {
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message with sufficient content.".to_string(),
+            ..Default::default()
         };
 
         assert!(message.validate().is_ok());
@@ -149,6 +284,7 @@ mod tests {
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message.".to_string(),
+            ..Default::default()
         };
 
         assert!(message.validate().is_err());
@@ -161,6 +297,7 @@ mod tests {
             email: "invalid-email".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message.".to_string(),
+            ..Default::default()
         };
 
         assert!(message.validate().is_err());
@@ -202,16 +339,22 @@ mod tests {
     }
 
     #[test]
-    fn test_contact_message_spam_detection() {
-        let spam_message = ContactMessage {
-            subject: "URGENT: You won the lottery!".to_string(),
-            message: "CONGRATULATIONS!!! Click here to claim your FREE MONEY!!!".to_string(),
-            ..create_test_contact_message()
-        };
-        assert!(spam_message.is_likely_spam());
+    fn test_message_status_transitions() {
+        use MessageStatus::*;
+
+        assert!(New.can_transition_to(Spam));
+        assert!(Read.can_transition_to(New));
+        assert!(Replied.can_transition_to(Archived));
+        assert!(!Replied.can_transition_to(New));
+        assert!(Archived.can_transition_to(Read));
+        assert!(!Archived.can_transition_to(Spam));
+        assert!(Spam.can_transition_to(New));
+        assert!(!Spam.can_transition_to(Replied));
+        assert!(Spam.can_transition_to(Spam));
 
-        let normal_message = create_test_contact_message();
-        assert!(!normal_message.is_likely_spam());
+        for status in MessageStatus::all() {
+            assert_eq!(MessageStatus::from_str(status).unwrap().as_str(), status);
+        }
     }
 
     #[test]
@@ -221,6 +364,7 @@ mod tests {
             email: "  JOHN.DOE@EXAMPLE.COM  ".to_string(),
             subject: "  Test Subject  ".to_string(),
             message: "  This is a test message.  ".to_string(),
+            ..Default::default()
         };
 
         message.sanitize();
@@ -238,6 +382,7 @@ mod tests {
             email: "john@example.com".to_string(),
             subject: "Test".to_string(),
             message: "This is a valid message with multiple words.".to_string(),
+            ..Default::default()
         };
         assert!(valid_message.is_valid_content());
 
@@ -246,6 +391,7 @@ mod tests {
             email: "john@example.com".to_string(),
             subject: "Test".to_string(),
             message: "123".to_string(),
+            ..Default::default()
         };
         assert!(!invalid_message.is_valid_content());
     }
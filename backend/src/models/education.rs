@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::models::timeline::validate_month;
+
+/// Degree or course of study
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Education {
+    pub id: i32,
+    pub institution: String,
+    pub degree: String,
+    pub field_of_study: Option<String>,
+    pub location: Option<String>,
+    /// `YYYY-MM`
+    pub start_month: String,
+    /// `YYYY-MM`, absent while studying
+    pub end_month: Option<String>,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create education request model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateEducation {
+    #[validate(length(min = 1, max = 150, message = "Institution must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub institution: String,
+
+    #[validate(length(min = 1, max = 150, message = "Degree must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub degree: String,
+
+    #[validate(length(max = 150, message = "Field of study must be less than 150 characters"))]
+    #[schema(max_length = 150)]
+    pub field_of_study: Option<String>,
+
+    #[validate(length(max = 100, message = "Location must be less than 100 characters"))]
+    #[schema(max_length = 100)]
+    pub location: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub start_month: String,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub end_month: Option<String>,
+
+    #[validate(length(max = 1000, message = "Description must be less than 1000 characters"))]
+    #[schema(max_length = 1000)]
+    pub description: Option<String>,
+}
+
+/// Update education request model
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateEducation {
+    #[validate(length(min = 1, max = 150, message = "Institution must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub institution: Option<String>,
+
+    #[validate(length(min = 1, max = 150, message = "Degree must be between 1 and 150 characters"))]
+    #[schema(min_length = 1, max_length = 150)]
+    pub degree: Option<String>,
+
+    #[validate(length(max = 150, message = "Field of study must be less than 150 characters"))]
+    #[schema(max_length = 150)]
+    pub field_of_study: Option<String>,
+
+    #[validate(length(max = 100, message = "Location must be less than 100 characters"))]
+    #[schema(max_length = 100)]
+    pub location: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub start_month: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub end_month: Option<String>,
+
+    #[validate(length(max = 1000, message = "Description must be less than 1000 characters"))]
+    #[schema(max_length = 1000)]
+    pub description: Option<String>,
+}
+
+impl UpdateEducation {
+    /// Check if any field is being updated
+    pub fn has_updates(&self) -> bool {
+        self.institution.is_some()
+            || self.degree.is_some()
+            || self.field_of_study.is_some()
+            || self.location.is_some()
+            || self.start_month.is_some()
+            || self.end_month.is_some()
+            || self.description.is_some()
+    }
+}
+
+impl From<Education> for CreateEducation {
+    fn from(education: Education) -> Self {
+        Self {
+            institution: education.institution,
+            degree: education.degree,
+            field_of_study: education.field_of_study,
+            location: education.location,
+            start_month: education.start_month,
+            end_month: education.end_month,
+            description: education.description,
+        }
+    }
+}
+
+impl From<CreateEducation> for UpdateEducation {
+    fn from(education: CreateEducation) -> Self {
+        Self {
+            institution: Some(education.institution),
+            degree: Some(education.degree),
+            field_of_study: education.field_of_study,
+            location: education.location,
+            start_month: Some(education.start_month),
+            end_month: education.end_month,
+            description: education.description,
+        }
+    }
+}
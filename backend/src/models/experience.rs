@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::models::timeline::validate_month;
+
+/// Longest bullet point of an experience
+pub const MAX_HIGHLIGHT_LEN: usize = 300;
+
+/// Position held, as listed on the CV
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Experience {
+    pub id: i32,
+    pub company: String,
+    pub role: String,
+    pub location: Option<String>,
+    /// `YYYY-MM`
+    pub start_month: String,
+    /// `YYYY-MM`, absent while the position is held
+    pub end_month: Option<String>,
+    pub is_current: bool,
+    /// Bullet points, in display order
+    #[schema(value_type = Vec<String>)]
+    pub highlights: Json<Vec<String>>,
+    #[schema(value_type = Vec<String>)]
+    pub technologies: Json<Vec<String>>, // aggregated from experience_technologies
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create experience request model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateExperience {
+    #[validate(length(min = 1, max = 100, message = "Company must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub company: String,
+
+    #[validate(length(min = 1, max = 100, message = "Role must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub role: String,
+
+    #[validate(length(max = 100, message = "Location must be less than 100 characters"))]
+    #[schema(max_length = 100)]
+    pub location: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub start_month: String,
+
+    /// Required unless the position is current
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub end_month: Option<String>,
+
+    #[serde(default)]
+    pub is_current: bool,
+
+    #[validate(length(max = 20, message = "An experience can have at most 20 highlights"))]
+    #[schema(max_items = 20)]
+    #[serde(default)]
+    pub highlights: Vec<String>,
+
+    #[validate(length(max = 30, message = "An experience can have at most 30 technologies"))]
+    #[schema(max_items = 30)]
+    #[serde(default)]
+    pub technologies: Vec<String>,
+}
+
+/// Update experience request model. Setting `is_current` clears the end month, and setting
+/// an end month ends a current position.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateExperience {
+    #[validate(length(min = 1, max = 100, message = "Company must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub company: Option<String>,
+
+    #[validate(length(min = 1, max = 100, message = "Role must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub role: Option<String>,
+
+    #[validate(length(max = 100, message = "Location must be less than 100 characters"))]
+    #[schema(max_length = 100)]
+    pub location: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub start_month: Option<String>,
+
+    #[validate(custom(function = "validate_month"))]
+    #[schema(pattern = r"^\d{4}-(0[1-9]|1[0-2])$")]
+    pub end_month: Option<String>,
+
+    pub is_current: Option<bool>,
+
+    #[validate(length(max = 20, message = "An experience can have at most 20 highlights"))]
+    #[schema(max_items = 20)]
+    pub highlights: Option<Vec<String>>,
+
+    #[validate(length(max = 30, message = "An experience can have at most 30 technologies"))]
+    #[schema(max_items = 30)]
+    pub technologies: Option<Vec<String>>,
+}
+
+impl UpdateExperience {
+    /// Check if any field is being updated
+    pub fn has_updates(&self) -> bool {
+        self.company.is_some()
+            || self.role.is_some()
+            || self.location.is_some()
+            || self.start_month.is_some()
+            || self.end_month.is_some()
+            || self.is_current.is_some()
+            || self.highlights.is_some()
+            || self.technologies.is_some()
+    }
+
+    /// The experience once the update is applied
+    pub fn apply_to(self, experience: Experience) -> CreateExperience {
+        let is_current = self.is_current.unwrap_or(experience.is_current && self.end_month.is_none());
+        let end_month = match (self.is_current, self.end_month) {
+            (Some(true), None) => None,
+            (_, Some(end_month)) => Some(end_month),
+            (_, None) => experience.end_month,
+        };
+
+        CreateExperience {
+            company: self.company.unwrap_or(experience.company),
+            role: self.role.unwrap_or(experience.role),
+            location: self.location.or(experience.location),
+            start_month: self.start_month.unwrap_or(experience.start_month),
+            end_month,
+            is_current,
+            highlights: self.highlights.unwrap_or(experience.highlights.0),
+            technologies: self.technologies.unwrap_or(experience.technologies.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_experience() -> Experience {
+        let now = Utc::now();
+        Experience {
+            id: 1,
+            company: "Acme".to_string(),
+            role: "Developer".to_string(),
+            location: Some("Lyon".to_string()),
+            start_month: "2021-09".to_string(),
+            end_month: None,
+            is_current: true,
+            highlights: Json(vec!["Shipped things".to_string()]),
+            technologies: Json(vec!["Rust".to_string()]),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_month_validation() {
+        let update = UpdateExperience {
+            start_month: Some("2021-9".to_string()),
+            ..Default::default()
+        };
+        assert!(update.validate().is_err());
+
+        let update = UpdateExperience {
+            end_month: Some("2023-02".to_string()),
+            ..Default::default()
+        };
+        assert!(update.validate().is_ok());
+    }
+
+    #[test]
+    fn test_apply_update() {
+        let ended = UpdateExperience {
+            end_month: Some("2023-02".to_string()),
+            ..Default::default()
+        }
+        .apply_to(create_test_experience());
+        assert_eq!(ended.end_month.as_deref(), Some("2023-02"));
+        assert!(!ended.is_current);
+        assert_eq!(ended.highlights, vec!["Shipped things"]);
+
+        let mut experience = create_test_experience();
+        experience.end_month = Some("2023-02".to_string());
+        experience.is_current = false;
+        let resumed = UpdateExperience {
+            is_current: Some(true),
+            ..Default::default()
+        }
+        .apply_to(experience);
+        assert!(resumed.is_current);
+        assert_eq!(resumed.end_month, None);
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::models::{CreateProject, CreateSkill, Profile, ProjectResponse, Skill, UpdateProfile, UpdateProject, UpdateSkill};
+
+/// Current version of the export format
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Portable snapshot of the portfolio content (contact messages are not included)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioExport {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub profile: Option<Profile>,
+    #[serde(default)]
+    pub projects: Vec<ProjectResponse>,
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+}
+
+/// Outcome of an import
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub profile_updated: bool,
+    pub projects_created: usize,
+    pub projects_updated: usize,
+    pub skills_created: usize,
+    pub skills_updated: usize,
+}
+
+impl From<Profile> for UpdateProfile {
+    fn from(profile: Profile) -> Self {
+        Self {
+            name: Some(profile.name),
+            title: Some(profile.title),
+            bio: Some(profile.bio),
+            email: Some(profile.email),
+            phone: profile.phone,
+            location: Some(profile.location),
+            linkedin_url: profile.linkedin_url,
+            github_url: profile.github_url,
+            twitter_url: profile.twitter_url,
+        }
+    }
+}
+
+impl From<ProjectResponse> for CreateProject {
+    fn from(project: ProjectResponse) -> Self {
+        Self {
+            title: project.title,
+            description: project.description,
+            long_description: project.long_description,
+            technologies: project.technologies,
+            github_url: project.github_url,
+            demo_url: project.demo_url,
+            image_url: project.image_url,
+            category: project.category,
+            featured: Some(project.featured),
+        }
+    }
+}
+
+impl From<Skill> for CreateSkill {
+    fn from(skill: Skill) -> Self {
+        Self {
+            name: skill.name,
+            category: skill.category,
+            level: skill.level,
+            years_experience: skill.years_experience,
+            description: skill.description,
+        }
+    }
+}
+
+impl From<CreateProject> for UpdateProject {
+    fn from(project: CreateProject) -> Self {
+        Self {
+            title: Some(project.title),
+            description: Some(project.description),
+            long_description: project.long_description,
+            technologies: Some(project.technologies),
+            github_url: project.github_url,
+            demo_url: project.demo_url,
+            image_url: project.image_url,
+            category: Some(project.category),
+            featured: project.featured,
+        }
+    }
+}
+
+impl From<CreateSkill> for UpdateSkill {
+    fn from(skill: CreateSkill) -> Self {
+        Self {
+            name: Some(skill.name),
+            category: Some(skill.category),
+            level: Some(skill.level),
+            years_experience: skill.years_experience,
+            description: skill.description,
+        }
+    }
+}
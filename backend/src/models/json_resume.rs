@@ -0,0 +1,625 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use crate::models::{
+    Certification, Education, Experience, Profile, ProjectResponse, Skill, UpdateProfile,
+    skill::SkillCategory, timeline::is_valid_month,
+};
+
+/// Schema the exported documents declare
+pub const JSON_RESUME_SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// Version of the JSON Resume schema the exported documents follow
+pub const JSON_RESUME_VERSION: &str = "v1.0.0";
+
+/// A JSON Resume (https://jsonresume.org/schema) document. Sections the portfolio has no
+/// equivalent for (volunteering, awards, languages...) are ignored on import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct JsonResume {
+    #[serde(rename = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub basics: Option<ResumeBasics>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub work: Vec<ResumeWork>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub education: Vec<ResumeEducation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub certificates: Vec<ResumeCertificate>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skills: Vec<ResumeSkill>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<ResumeProject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ResumeMeta>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResumeBasics {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<ResumeLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<ResumeProfile>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResumeLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub postal_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+/// Social network profile
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct ResumeProfile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Work experience; `name` is the company
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResumeWork {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+}
+
+/// Education entry; `studyType` is the degree and `area` the field of study
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResumeEducation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub institution: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub area: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub study_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub courses: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct ResumeCertificate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Group of skills; each keyword becomes a portfolio skill
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct ResumeSkill {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+/// Project; `keywords` are its technologies and `type` its category
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResumeProject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ResumeMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canonical: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+/// What an import did, or would do in a dry run, to a record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    Create,
+    Update,
+    Unchanged,
+}
+
+/// A record touched by an import, named by its matching key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ImportChange {
+    pub name: String,
+    pub action: ImportAction,
+}
+
+/// Outcome of a JSON Resume import
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct JsonResumeImportReport {
+    pub dry_run: bool,
+    /// `None` when the document has no `basics` section
+    pub profile: Option<ImportAction>,
+    pub skills: Vec<ImportChange>,
+    pub projects: Vec<ImportChange>,
+    pub experiences: Vec<ImportChange>,
+    pub education: Vec<ImportChange>,
+    pub certifications: Vec<ImportChange>,
+}
+
+impl JsonResumeImportReport {
+    /// Whether the import creates or updates anything
+    pub fn has_changes(&self) -> bool {
+        let changed = |action: &ImportAction| *action != ImportAction::Unchanged;
+        self.profile.as_ref().is_some_and(changed)
+            || [&self.skills, &self.projects, &self.experiences, &self.education, &self.certifications]
+                .into_iter()
+                .flatten()
+                .any(|change| changed(&change.action))
+    }
+}
+
+/// Everything a JSON Resume document is built from
+#[derive(Debug, Clone, Default)]
+pub struct ResumeContent {
+    pub profile: Option<Profile>,
+    pub skills: Vec<Skill>,
+    pub projects: Vec<ProjectResponse>,
+    pub experiences: Vec<Experience>,
+    pub education: Vec<Education>,
+    pub certifications: Vec<Certification>,
+}
+
+impl JsonResume {
+    /// Build the document for the portfolio content. Skills are grouped by category, with
+    /// the group level only given when every skill of the group shares it.
+    pub fn from_content(content: ResumeContent, exported_at: DateTime<Utc>) -> Self {
+        let skills = SkillCategory::all()
+            .into_iter()
+            .filter_map(|category| {
+                let group: Vec<&Skill> = content.skills.iter().filter(|skill| skill.category == category).collect();
+                let first = group.first()?;
+                let level = group
+                    .iter()
+                    .all(|skill| skill.level == first.level)
+                    .then(|| first.level_description().to_string());
+                Some(ResumeSkill {
+                    name: Some(category.to_string()),
+                    level,
+                    keywords: group.iter().map(|skill| skill.name.clone()).collect(),
+                })
+            })
+            .collect();
+
+        Self {
+            schema: Some(JSON_RESUME_SCHEMA_URL.to_string()),
+            basics: content.profile.map(ResumeBasics::from),
+            work: content.experiences.into_iter().map(ResumeWork::from).collect(),
+            education: content.education.into_iter().map(ResumeEducation::from).collect(),
+            certificates: content.certifications.into_iter().map(ResumeCertificate::from).collect(),
+            skills,
+            projects: content.projects.into_iter().map(ResumeProject::from).collect(),
+            meta: Some(ResumeMeta {
+                canonical: None,
+                version: Some(JSON_RESUME_VERSION.to_string()),
+                last_modified: Some(exported_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            }),
+        }
+    }
+}
+
+impl From<Profile> for ResumeBasics {
+    fn from(profile: Profile) -> Self {
+        let profiles = [
+            ("LinkedIn", profile.linkedin_url),
+            ("GitHub", profile.github_url),
+            ("Twitter", profile.twitter_url),
+        ]
+        .into_iter()
+        .filter_map(|(network, url)| {
+            let url = url?;
+            Some(ResumeProfile {
+                network: Some(network.to_string()),
+                username: username_from_url(&url),
+                url: Some(url),
+            })
+        })
+        .collect();
+
+        Self {
+            name: Some(profile.name),
+            label: Some(profile.title),
+            email: Some(profile.email),
+            phone: profile.phone,
+            url: None,
+            summary: Some(profile.bio),
+            location: Some(ResumeLocation {
+                city: Some(profile.location),
+                ..Default::default()
+            }),
+            profiles,
+        }
+    }
+}
+
+impl ResumeBasics {
+    /// Profile fields carried by the document; social links are matched by network name
+    pub fn to_update_profile(&self) -> UpdateProfile {
+        let location = self.location.as_ref().and_then(|location| {
+            let parts: Vec<&str> = [&location.city, &location.region, &location.country_code]
+                .into_iter()
+                .flatten()
+                .map(|part| part.trim())
+                .filter(|part| !part.is_empty())
+                .collect();
+            (!parts.is_empty()).then(|| parts.join(", "))
+        });
+
+        let mut update = UpdateProfile {
+            name: non_empty(&self.name),
+            title: non_empty(&self.label),
+            bio: non_empty(&self.summary),
+            email: non_empty(&self.email),
+            phone: non_empty(&self.phone),
+            location,
+            ..Default::default()
+        };
+
+        for profile in &self.profiles {
+            let network = profile.network.as_deref().unwrap_or_default().trim().to_lowercase();
+            let (slot, base_url) = match network.as_str() {
+                "linkedin" => (&mut update.linkedin_url, "https://www.linkedin.com/in/"),
+                "github" => (&mut update.github_url, "https://github.com/"),
+                "twitter" | "x" => (&mut update.twitter_url, "https://twitter.com/"),
+                _ => continue,
+            };
+            *slot = non_empty(&profile.url)
+                .or_else(|| non_empty(&profile.username).map(|username| format!("{}{}", base_url, username)));
+        }
+
+        update
+    }
+}
+
+impl From<Experience> for ResumeWork {
+    fn from(experience: Experience) -> Self {
+        Self {
+            name: Some(experience.company),
+            position: Some(experience.role),
+            location: experience.location,
+            url: None,
+            start_date: Some(experience.start_month),
+            end_date: experience.end_month,
+            summary: None,
+            highlights: experience.highlights.0,
+        }
+    }
+}
+
+impl From<Education> for ResumeEducation {
+    fn from(education: Education) -> Self {
+        Self {
+            institution: Some(education.institution),
+            url: None,
+            area: education.field_of_study,
+            study_type: Some(education.degree),
+            start_date: Some(education.start_month),
+            end_date: education.end_month,
+            score: None,
+            courses: Vec::new(),
+        }
+    }
+}
+
+impl From<Certification> for ResumeCertificate {
+    fn from(certification: Certification) -> Self {
+        Self {
+            name: Some(certification.name),
+            date: Some(certification.issued_month),
+            issuer: Some(certification.issuer),
+            url: certification.credential_url,
+        }
+    }
+}
+
+impl From<ProjectResponse> for ResumeProject {
+    fn from(project: ProjectResponse) -> Self {
+        Self {
+            name: Some(project.title),
+            description: Some(project.description),
+            highlights: project
+                .long_description
+                .iter()
+                .flat_map(|text| text.lines())
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            keywords: project.technologies,
+            start_date: Some(project.created_at.format("%Y-%m-%d").to_string()),
+            end_date: None,
+            url: project.demo_url.or(project.github_url),
+            kind: Some(project.category),
+        }
+    }
+}
+
+/// Convert a JSON Resume date (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`) to a `YYYY-MM` month.
+/// A bare year stands for its January.
+pub fn month_from_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    let month = match date.len() {
+        4 => format!("{}-01", date),
+        7 | 10 => date.get(..7)?.to_string(),
+        _ => return None,
+    };
+    is_valid_month(&month).then_some(month)
+}
+
+/// Skill level (1 to 5) for a free-form JSON Resume level such as "Advanced" or "4"
+pub fn level_from_label(label: &str) -> Option<i32> {
+    let label = label.trim().to_lowercase();
+    if let Ok(level) = label.parse::<i32>() {
+        return (1..=5).contains(&level).then_some(level);
+    }
+
+    match label.as_str() {
+        "beginner" | "basic" | "elementary" => Some(1),
+        "novice" => Some(2),
+        "intermediate" | "competent" | "proficient" => Some(3),
+        "advanced" | "fluent" => Some(4),
+        "expert" | "master" | "native" => Some(5),
+        _ => None,
+    }
+}
+
+/// Well-known technologies and the category they belong to
+const KNOWN_KEYWORDS: &[(&str, SkillCategory)] = &[
+    ("html", SkillCategory::Frontend),
+    ("css", SkillCategory::Frontend),
+    ("sass", SkillCategory::Frontend),
+    ("javascript", SkillCategory::Frontend),
+    ("typescript", SkillCategory::Frontend),
+    ("react", SkillCategory::Frontend),
+    ("vue", SkillCategory::Frontend),
+    ("vue.js", SkillCategory::Frontend),
+    ("angular", SkillCategory::Frontend),
+    ("svelte", SkillCategory::Frontend),
+    ("next.js", SkillCategory::Frontend),
+    ("tailwind", SkillCategory::Frontend),
+    ("tailwind css", SkillCategory::Frontend),
+    ("rust", SkillCategory::Backend),
+    ("go", SkillCategory::Backend),
+    ("golang", SkillCategory::Backend),
+    ("python", SkillCategory::Backend),
+    ("java", SkillCategory::Backend),
+    ("node.js", SkillCategory::Backend),
+    ("nodejs", SkillCategory::Backend),
+    ("php", SkillCategory::Backend),
+    ("ruby", SkillCategory::Backend),
+    ("c#", SkillCategory::Backend),
+    (".net", SkillCategory::Backend),
+    ("django", SkillCategory::Backend),
+    ("flask", SkillCategory::Backend),
+    ("spring", SkillCategory::Backend),
+    ("express", SkillCategory::Backend),
+    ("axum", SkillCategory::Backend),
+    ("ruby on rails", SkillCategory::Backend),
+    ("graphql", SkillCategory::Backend),
+    ("sql", SkillCategory::Database),
+    ("postgresql", SkillCategory::Database),
+    ("postgres", SkillCategory::Database),
+    ("mysql", SkillCategory::Database),
+    ("mariadb", SkillCategory::Database),
+    ("sqlite", SkillCategory::Database),
+    ("mongodb", SkillCategory::Database),
+    ("redis", SkillCategory::Database),
+    ("elasticsearch", SkillCategory::Database),
+    ("docker", SkillCategory::DevOps),
+    ("kubernetes", SkillCategory::DevOps),
+    ("terraform", SkillCategory::DevOps),
+    ("ansible", SkillCategory::DevOps),
+    ("aws", SkillCategory::DevOps),
+    ("gcp", SkillCategory::DevOps),
+    ("azure", SkillCategory::DevOps),
+    ("ci/cd", SkillCategory::DevOps),
+    ("github actions", SkillCategory::DevOps),
+    ("jenkins", SkillCategory::DevOps),
+    ("nginx", SkillCategory::DevOps),
+    ("linux", SkillCategory::DevOps),
+    ("git", SkillCategory::Tools),
+    ("vim", SkillCategory::Tools),
+    ("vs code", SkillCategory::Tools),
+    ("figma", SkillCategory::Tools),
+    ("jira", SkillCategory::Tools),
+    ("swift", SkillCategory::Mobile),
+    ("kotlin", SkillCategory::Mobile),
+    ("flutter", SkillCategory::Mobile),
+    ("dart", SkillCategory::Mobile),
+    ("react native", SkillCategory::Mobile),
+    ("android", SkillCategory::Mobile),
+    ("ios", SkillCategory::Mobile),
+];
+
+/// Words hinting at a category in a skill group name, checked in order
+const GROUP_HINTS: &[(&str, SkillCategory)] = &[
+    ("front", SkillCategory::Frontend),
+    ("mobile", SkillCategory::Mobile),
+    ("back", SkillCategory::Backend),
+    ("server", SkillCategory::Backend),
+    ("data", SkillCategory::Database),
+    ("devops", SkillCategory::DevOps),
+    ("cloud", SkillCategory::DevOps),
+    ("infra", SkillCategory::DevOps),
+    ("tool", SkillCategory::Tools),
+    ("web", SkillCategory::Frontend),
+];
+
+/// Category a skill group is named after, ignoring case
+pub fn category_from_group(group: &str) -> Option<SkillCategory> {
+    let group = group.trim();
+    SkillCategory::all()
+        .into_iter()
+        .find(|category| category.eq_ignore_ascii_case(group))
+        .and_then(SkillCategory::from_str)
+}
+
+/// Infer the category of a skill keyword: a group named after a category wins, then
+/// well-known technologies, then hints in the group name
+pub fn infer_skill_category(group: &str, keyword: &str) -> Option<SkillCategory> {
+    if let Some(category) = category_from_group(group) {
+        return Some(category);
+    }
+
+    let keyword = keyword.trim().to_lowercase();
+    if let Some((_, category)) = KNOWN_KEYWORDS.iter().find(|(known, _)| *known == keyword) {
+        return Some(category.clone());
+    }
+
+    let group = group.trim().to_lowercase();
+    GROUP_HINTS
+        .iter()
+        .find(|(hint, _)| group.contains(hint))
+        .map(|(_, category)| category.clone())
+}
+
+/// Trimmed value, `None` when missing or blank
+pub fn non_empty(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Last path segment of a profile URL, taken as the username
+fn username_from_url(url: &str) -> Option<String> {
+    url.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .filter(|segment| !segment.is_empty() && !segment.contains(':') && !segment.contains('.'))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_from_date() {
+        assert_eq!(month_from_date("2020-03-15").as_deref(), Some("2020-03"));
+        assert_eq!(month_from_date("2020-03").as_deref(), Some("2020-03"));
+        assert_eq!(month_from_date("2020").as_deref(), Some("2020-01"));
+        assert_eq!(month_from_date("2020-13"), None);
+        assert_eq!(month_from_date("March 2020"), None);
+    }
+
+    #[test]
+    fn test_level_from_label() {
+        assert_eq!(level_from_label("Master"), Some(5));
+        assert_eq!(level_from_label(" advanced "), Some(4));
+        assert_eq!(level_from_label("3"), Some(3));
+        assert_eq!(level_from_label("9"), None);
+        assert_eq!(level_from_label("Ninja"), None);
+    }
+
+    #[test]
+    fn test_infer_skill_category() {
+        let infer = |group: &str, keyword: &str| infer_skill_category(group, keyword).map(|category| category.as_str());
+
+        assert_eq!(infer("devops", "Rust"), Some("DevOps"));
+        assert_eq!(infer("Web Development", "Node.js"), Some("Backend"));
+        assert_eq!(infer("Web Development", "Elm"), Some("Frontend"));
+        assert_eq!(infer("Data engineering", "dbt"), Some("Database"));
+        assert_eq!(infer("Languages", "Haskell"), None);
+    }
+
+    #[test]
+    fn test_basics_to_update_profile() {
+        let basics: ResumeBasics = serde_json::from_value(serde_json::json!({
+            "name": "Jane Doe",
+            "label": "  ",
+            "location": { "city": "Lyon", "countryCode": "FR" },
+            "profiles": [
+                { "network": "GitHub", "username": "jdoe" },
+                { "network": "Mastodon", "url": "https://mastodon.social/@jdoe" }
+            ]
+        }))
+        .unwrap();
+
+        let update = basics.to_update_profile();
+        assert_eq!(update.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(update.title, None);
+        assert_eq!(update.location.as_deref(), Some("Lyon, FR"));
+        assert_eq!(update.github_url.as_deref(), Some("https://github.com/jdoe"));
+        assert_eq!(update.twitter_url, None);
+    }
+}
@@ -0,0 +1,326 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Image formats accepted for upload, recognised from their content rather than their name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    Png,
+    Jpeg,
+    Webp,
+    Gif,
+}
+
+impl MediaType {
+    /// MIME type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaType::Png => "image/png",
+            MediaType::Jpeg => "image/jpeg",
+            MediaType::Webp => "image/webp",
+            MediaType::Gif => "image/gif",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "image/png" => Some(MediaType::Png),
+            "image/jpeg" => Some(MediaType::Jpeg),
+            "image/webp" => Some(MediaType::Webp),
+            "image/gif" => Some(MediaType::Gif),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["image/png", "image/jpeg", "image/webp", "image/gif"]
+    }
+
+    /// File extension used on disk, which also tells the file server the content type
+    pub fn extension(&self) -> &'static str {
+        match self {
+            MediaType::Png => "png",
+            MediaType::Jpeg => "jpg",
+            MediaType::Webp => "webp",
+            MediaType::Gif => "gif",
+        }
+    }
+
+    /// Recognise a format from the first bytes of a file
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(MediaType::Png)
+        } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(MediaType::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(MediaType::Gif)
+        } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+            Some(MediaType::Webp)
+        } else {
+            None
+        }
+    }
+
+    /// Format derivatives are rendered in when none is requested. Animated GIFs are not
+    /// resized, so they become PNGs of their first frame.
+    pub fn variant_format(&self) -> VariantFormat {
+        match self {
+            MediaType::Png | MediaType::Gif => VariantFormat::Png,
+            MediaType::Jpeg => VariantFormat::Jpeg,
+            MediaType::Webp => VariantFormat::Webp,
+        }
+    }
+}
+
+/// Formats derivatives can be rendered in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VariantFormat {
+    Jpeg,
+    Png,
+    Webp,
+    Avif,
+}
+
+impl VariantFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariantFormat::Jpeg => "jpeg",
+            VariantFormat::Png => "png",
+            VariantFormat::Webp => "webp",
+            VariantFormat::Avif => "avif",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "jpeg" | "jpg" => Some(VariantFormat::Jpeg),
+            "png" => Some(VariantFormat::Png),
+            "webp" => Some(VariantFormat::Webp),
+            "avif" => Some(VariantFormat::Avif),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["jpeg", "png", "webp", "avif"]
+    }
+
+    /// MIME type
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            VariantFormat::Jpeg => "image/jpeg",
+            VariantFormat::Png => "image/png",
+            VariantFormat::Webp => "image/webp",
+            VariantFormat::Avif => "image/avif",
+        }
+    }
+
+    /// File extension used on disk
+    pub fn extension(&self) -> &'static str {
+        match self {
+            VariantFormat::Jpeg => "jpg",
+            other => other.as_str(),
+        }
+    }
+}
+
+/// Uploaded file, stored once per distinct content
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Media {
+    pub id: i32,
+    /// SHA-256 of the content, hex encoded
+    pub hash: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// File name given by the first uploader
+    pub original_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Dimensions as displayed, once the image orientation is applied
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// When the configured derivatives were rendered
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl Media {
+    /// Where the file is served
+    pub fn url(&self) -> String {
+        format!("/api/media/{}", self.hash)
+    }
+
+    /// Format of the file (media are only ever stored with a known one)
+    pub fn media_type(&self) -> MediaType {
+        MediaType::from_str(&self.content_type).unwrap_or(MediaType::Png)
+    }
+}
+
+/// Resized or converted copy of an uploaded image
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MediaVariant {
+    pub media_id: i32,
+    pub width: i32,
+    pub height: i32,
+    pub format: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MediaVariant {
+    /// Format of the variant (variants are only ever stored with a known one)
+    pub fn variant_format(&self) -> VariantFormat {
+        VariantFormat::from_str(&self.format).unwrap_or(VariantFormat::Png)
+    }
+}
+
+/// Derivative of a media, as listed in API responses so clients can build a `srcset`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MediaVariantResponse {
+    pub url: String,
+    pub width: i32,
+    pub height: i32,
+    pub format: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+/// Media model for API responses, with the URL it is served at and its derivatives
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MediaResponse {
+    pub id: i32,
+    pub hash: String,
+    pub url: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub original_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Rendered derivatives, by format then width
+    #[serde(default)]
+    pub variants: Vec<MediaVariantResponse>,
+}
+
+impl MediaResponse {
+    /// Response for a media and the derivatives rendered from it
+    pub fn new(media: Media, mut variants: Vec<MediaVariant>) -> Self {
+        variants.retain(|variant| variant.media_id == media.id);
+        variants.sort_by_key(|variant| (variant.variant_format(), variant.width));
+
+        let url = media.url();
+        Self {
+            variants: variants
+                .into_iter()
+                .map(|variant| MediaVariantResponse {
+                    url: format!("{}?w={}&fmt={}", url, variant.width, variant.format),
+                    content_type: variant.variant_format().content_type().to_string(),
+                    width: variant.width,
+                    height: variant.height,
+                    format: variant.format,
+                    size_bytes: variant.size_bytes,
+                })
+                .collect(),
+            url,
+            id: media.id,
+            hash: media.hash,
+            content_type: media.content_type,
+            size_bytes: media.size_bytes,
+            width: media.width,
+            height: media.height,
+            original_name: media.original_name,
+            created_at: media.created_at,
+        }
+    }
+}
+
+impl From<Media> for MediaResponse {
+    fn from(media: Media) -> Self {
+        Self::new(media, Vec::new())
+    }
+}
+
+/// Media item of a project gallery, in gallery order
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GalleryItem {
+    pub position: i32,
+    pub alt_text: String,
+    pub media: MediaResponse,
+}
+
+/// Media item to place in a project gallery
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GalleryItemInput {
+    pub media_id: i32,
+    /// Description of the image for screen readers
+    #[serde(default)]
+    #[schema(max_length = 300)]
+    pub alt_text: String,
+}
+
+/// Longest alt text of a gallery item
+pub const MAX_ALT_TEXT_LEN: usize = 300;
+
+/// Request to replace a project gallery; items are shown in the given order
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct SetProjectGallery {
+    #[validate(length(max = 50, message = "A gallery can hold at most 50 items"))]
+    #[schema(max_items = 50)]
+    pub items: Vec<GalleryItemInput>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(MediaType::sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some(MediaType::Png));
+        assert_eq!(MediaType::sniff(&[0xff, 0xd8, 0xff, 0xe0, 0, 0x10]), Some(MediaType::Jpeg));
+        assert_eq!(MediaType::sniff(b"GIF89a\x01\0\x01\0"), Some(MediaType::Gif));
+        assert_eq!(MediaType::sniff(b"RIFF\x24\0\0\0WEBPVP8 "), Some(MediaType::Webp));
+
+        // The name or declared type of a file does not matter, only its content
+        assert_eq!(MediaType::sniff(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"), None);
+        assert_eq!(MediaType::sniff(b"RIFF\x24\0\0\0WAVEfmt "), None);
+        assert_eq!(MediaType::sniff(b""), None);
+    }
+
+    #[test]
+    fn test_variants_listed_in_order() {
+        let now = Utc::now();
+        let media = Media {
+            id: 1,
+            hash: "abc".to_string(),
+            content_type: "image/gif".to_string(),
+            size_bytes: 100,
+            original_name: None,
+            created_at: now,
+            width: Some(800),
+            height: Some(600),
+            processed_at: Some(now),
+        };
+        let variant = |media_id, width, format: &str| MediaVariant {
+            media_id,
+            width,
+            height: width * 3 / 4,
+            format: format.to_string(),
+            size_bytes: 10,
+            created_at: now,
+        };
+
+        let response = MediaResponse::new(
+            media,
+            vec![variant(1, 640, "webp"), variant(1, 320, "webp"), variant(2, 320, "png"), variant(1, 320, "avif")],
+        );
+        let urls: Vec<&str> = response.variants.iter().map(|v| v.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec!["/api/media/abc?w=320&fmt=webp", "/api/media/abc?w=640&fmt=webp", "/api/media/abc?w=320&fmt=avif"]
+        );
+        assert_eq!(response.variants[2].content_type, "image/avif");
+        assert_eq!(MediaType::Gif.variant_format(), VariantFormat::Png);
+    }
+}
@@ -3,11 +3,41 @@ pub mod project;
 pub mod skill;
 pub mod profile;
 pub mod contact;
+pub mod admin;
+pub mod api_key;
+pub mod export;
+pub mod technology;
+pub mod project_skill;
+pub mod search;
+pub mod notification;
+pub mod spam;
+pub mod media;
+pub mod post;
+pub mod experience;
+pub mod education;
+pub mod certification;
+pub mod timeline;
+pub mod json_resume;
 
 #[cfg(test)]
 mod tests;
 
-pub use project::{Project, ProjectResponse, CreateProject, UpdateProject};
+pub use project::{Project, ProjectResponse, CreateProject, UpdateProject, ProjectFilter, ProjectSort, ProjectSortField, ProjectCursor, ProjectPage};
 pub use skill::{Skill, CreateSkill, UpdateSkill};
 pub use profile::{Profile, UpdateProfile};
-pub use contact::{ContactMessage, CreateContactMessage};
\ No newline at end of file
+pub use contact::{ContactMessage, ContactMessageDetail, ContactReply, CreateContactMessage, CreateContactReply, UpdateContactMessage, BulkStatusUpdate, MessageFilter, MessageStatus};
+pub use admin::{AdminUser, CreateAdminUser, LoginRequest, LoginResponse};
+pub use api_key::{ApiKey, ApiKeyResponse, ApiScope, CreateApiKey, CreatedApiKey};
+pub use export::{PortfolioExport, ImportSummary, EXPORT_FORMAT_VERSION};
+pub use technology::Technology;
+pub use project_skill::{LinkSource, LinkedSkill, LinkedProject, ProjectDetail, SkillDetail, SetProjectSkills};
+pub use search::{SearchHit, SearchResultType};
+pub use notification::{Notification, NotificationJob, NotificationKind, NotificationStatus};
+pub use spam::{SpamDecision, SpamLabel, SpamSignal, SpamVerdict};
+pub use media::{Media, MediaResponse, MediaType, MediaVariant, MediaVariantResponse, VariantFormat, GalleryItem, GalleryItemInput, SetProjectGallery};
+pub use post::{Post, PostFilter, PostRecord, PostResponse, PostStatus, PostSummary, CreatePost, UpdatePost};
+pub use experience::{Experience, CreateExperience, UpdateExperience};
+pub use education::{Education, CreateEducation, UpdateEducation};
+pub use certification::{Certification, CreateCertification, UpdateCertification};
+pub use timeline::TimelineEntry;
+pub use json_resume::{JsonResume, JsonResumeImportReport, ImportAction, ImportChange};
\ No newline at end of file
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+
+/// Who a notification about a contact message is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// The site owner, about a new message
+    Owner,
+    /// The site owner, about a message that looks like spam
+    Spam,
+    /// The sender, acknowledging their message
+    AutoReply,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::Owner => "owner",
+            NotificationKind::Spam => "spam",
+            NotificationKind::AutoReply => "auto_reply",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(kind: &str) -> Option<Self> {
+        match kind {
+            "owner" => Some(NotificationKind::Owner),
+            "spam" => Some(NotificationKind::Spam),
+            "auto_reply" => Some(NotificationKind::AutoReply),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["owner", "spam", "auto_reply"]
+    }
+}
+
+/// Delivery state of a queued notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationStatus {
+    Pending,
+    Sent,
+    /// Given up after too many attempts, or undeliverable
+    Failed,
+}
+
+impl NotificationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationStatus::Pending => "pending",
+            NotificationStatus::Sent => "sent",
+            NotificationStatus::Failed => "failed",
+        }
+    }
+}
+
+/// A rendered notification, ready to hand to a notifier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub contact_message_id: Option<i32>,
+    /// Email address of the recipient, for channels that need one
+    pub to: Option<String>,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Notification queue entry as stored in the database
+#[derive(Debug, Clone, FromRow)]
+pub struct NotificationJob {
+    pub id: i32,
+    pub kind: String,
+    pub channel: String,
+    pub contact_message_id: Option<i32>,
+    pub recipient: Option<String>,
+    pub reply_to: Option<String>,
+    pub subject: String,
+    pub body: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NotificationJob {
+    /// The notification to send, or None if the stored kind is unknown
+    pub fn notification(&self) -> Option<Notification> {
+        Some(Notification {
+            kind: NotificationKind::from_str(&self.kind)?,
+            contact_message_id: self.contact_message_id,
+            to: self.recipient.clone(),
+            reply_to: self.reply_to.clone(),
+            subject: self.subject.clone(),
+            body: self.body.clone(),
+        })
+    }
+}
@@ -0,0 +1,317 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// Longest slug of a post
+pub const MAX_SLUG_LEN: usize = 100;
+
+/// Longest tag of a post
+pub const MAX_TAG_LEN: usize = 50;
+
+/// Slugs that would shadow other post routes
+pub const RESERVED_SLUGS: &[&str] = &["admin"];
+
+/// Publication status of a post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    Draft,
+    Published,
+    /// Published automatically once `published_at` is reached
+    Scheduled,
+}
+
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+            PostStatus::Scheduled => "scheduled",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "draft" => Some(PostStatus::Draft),
+            "published" => Some(PostStatus::Published),
+            "scheduled" => Some(PostStatus::Scheduled),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["draft", "published", "scheduled"]
+    }
+}
+
+/// Blog post
+#[derive(Debug, Clone, Deserialize, FromRow)]
+pub struct Post {
+    pub id: i32,
+    pub slug: String,
+    pub title: String,
+    pub summary: String,
+    pub body_markdown: String,
+    pub body_html: String,
+    pub tags: Json<Vec<String>>, // aggregated from post_tags
+    pub reading_time_minutes: i32,
+    pub status: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Post {
+    /// Status as stored (posts are only ever stored with a known one)
+    pub fn stored_status(&self) -> PostStatus {
+        PostStatus::from_str(&self.status).unwrap_or(PostStatus::Draft)
+    }
+
+    /// Status at the given time: a scheduled post whose date has come is published
+    pub fn status_at(&self, now: DateTime<Utc>) -> PostStatus {
+        match self.stored_status() {
+            PostStatus::Scheduled if self.is_public_at(now) => PostStatus::Published,
+            status => status,
+        }
+    }
+
+    /// Whether readers can see the post at the given time
+    pub fn is_public_at(&self, now: DateTime<Utc>) -> bool {
+        self.stored_status() != PostStatus::Draft && self.published_at.is_some_and(|published_at| published_at <= now)
+    }
+
+    /// URL-safe identifier derived from a title: "Été à Paris !" gives "ete-a-paris"
+    pub fn slugify(title: &str) -> String {
+        let mut slug = String::with_capacity(title.len());
+        for c in title.chars().flat_map(char::to_lowercase) {
+            let folded = match c {
+                'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+                'ç' => "c",
+                'è' | 'é' | 'ê' | 'ë' => "e",
+                'ì' | 'í' | 'î' | 'ï' => "i",
+                'ñ' => "n",
+                'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+                'ù' | 'ú' | 'û' | 'ü' => "u",
+                'ý' | 'ÿ' => "y",
+                'æ' => "ae",
+                'œ' => "oe",
+                'ß' => "ss",
+                c if c.is_ascii_alphanumeric() => {
+                    slug.push(c);
+                    continue;
+                }
+                _ => "-",
+            };
+            slug.push_str(folded);
+        }
+
+        let slug = slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+        match slug.char_indices().nth(MAX_SLUG_LEN) {
+            Some((end, _)) => slug[..end].trim_end_matches('-').to_string(),
+            None => slug,
+        }
+    }
+
+    /// Whether a slug is lowercase words of letters and digits joined by single dashes
+    pub fn is_valid_slug(slug: &str) -> bool {
+        !slug.is_empty()
+            && slug.len() <= MAX_SLUG_LEN
+            && slug.split('-').all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()))
+            && !RESERVED_SLUGS.contains(&slug)
+    }
+}
+
+/// Post listed without its body
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostSummary {
+    pub id: i32,
+    pub slug: String,
+    pub title: String,
+    pub summary: String,
+    pub tags: Vec<String>,
+    pub reading_time_minutes: i32,
+    /// Status when the post was read; a scheduled post whose date has come is `published`
+    #[schema(value_type = PostStatus)]
+    pub status: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Post model for API responses, with its Markdown source and rendered HTML
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostResponse {
+    #[serde(flatten)]
+    pub post: PostSummary,
+    pub body_markdown: String,
+    pub body_html: String,
+}
+
+impl PostSummary {
+    /// Summary of a post, with its status at the given time
+    pub fn at(post: Post, now: DateTime<Utc>) -> Self {
+        Self {
+            status: post.status_at(now).as_str().to_string(),
+            id: post.id,
+            slug: post.slug,
+            title: post.title,
+            summary: post.summary,
+            tags: post.tags.0,
+            reading_time_minutes: post.reading_time_minutes,
+            published_at: post.published_at,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
+    }
+}
+
+impl PostResponse {
+    /// Full post, with its status at the given time
+    pub fn at(mut post: Post, now: DateTime<Utc>) -> Self {
+        let body_markdown = std::mem::take(&mut post.body_markdown);
+        let body_html = std::mem::take(&mut post.body_html);
+        Self {
+            post: PostSummary::at(post, now),
+            body_markdown,
+            body_html,
+        }
+    }
+}
+
+/// Create post request model
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreatePost {
+    /// Derived from the title when not given
+    #[validate(length(min = 1, max = 100, message = "Slug must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub slug: Option<String>,
+
+    #[validate(length(min = 1, max = 200, message = "Title must be between 1 and 200 characters"))]
+    #[schema(min_length = 1, max_length = 200)]
+    pub title: String,
+
+    #[validate(length(min = 1, max = 500, message = "Summary must be between 1 and 500 characters"))]
+    #[schema(min_length = 1, max_length = 500)]
+    pub summary: String,
+
+    /// Markdown source
+    #[validate(length(min = 1, max = 100000, message = "Body must be between 1 and 100000 characters"))]
+    #[schema(min_length = 1, max_length = 100000)]
+    pub body: String,
+
+    #[validate(length(max = 20, message = "A post can have at most 20 tags"))]
+    #[schema(max_items = 20)]
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// `draft` (default), `published` or `scheduled`
+    #[schema(value_type = Option<PostStatus>)]
+    pub status: Option<String>,
+
+    /// Defaults to now when publishing; required, and in the future, when scheduling
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Update post request model
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdatePost {
+    #[validate(length(min = 1, max = 100, message = "Slug must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub slug: Option<String>,
+
+    #[validate(length(min = 1, max = 200, message = "Title must be between 1 and 200 characters"))]
+    #[schema(min_length = 1, max_length = 200)]
+    pub title: Option<String>,
+
+    #[validate(length(min = 1, max = 500, message = "Summary must be between 1 and 500 characters"))]
+    #[schema(min_length = 1, max_length = 500)]
+    pub summary: Option<String>,
+
+    #[validate(length(min = 1, max = 100000, message = "Body must be between 1 and 100000 characters"))]
+    #[schema(min_length = 1, max_length = 100000)]
+    pub body: Option<String>,
+
+    #[validate(length(max = 20, message = "A post can have at most 20 tags"))]
+    #[schema(max_items = 20)]
+    pub tags: Option<Vec<String>>,
+
+    #[schema(value_type = Option<PostStatus>)]
+    pub status: Option<String>,
+
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Post fields as saved, once the service has rendered and checked them
+#[derive(Debug, Clone)]
+pub struct PostRecord {
+    pub slug: String,
+    pub title: String,
+    pub summary: String,
+    pub body_markdown: String,
+    pub body_html: String,
+    pub reading_time_minutes: i32,
+    pub tags: Vec<String>,
+    pub status: PostStatus,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Filters of a post listing
+#[derive(Debug, Clone, Default)]
+pub struct PostFilter {
+    /// Only posts readers can see at this time
+    pub public_at: Option<DateTime<Utc>>,
+    /// Stored status
+    pub status: Option<PostStatus>,
+    pub tag: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(Post::slugify("Été à Paris !"), "ete-a-paris");
+        assert_eq!(Post::slugify("  Rust & SQLite: 2 ans après  "), "rust-sqlite-2-ans-apres");
+        assert_eq!(Post::slugify("C'est l'œuvre"), "c-est-l-oeuvre");
+        assert_eq!(Post::slugify(&"long ".repeat(40)).len(), 99);
+        assert_eq!(Post::slugify("!!!"), "");
+
+        assert!(Post::is_valid_slug("rust-sqlite-2"));
+        assert!(!Post::is_valid_slug("Rust"));
+        assert!(!Post::is_valid_slug("double--dash"));
+        assert!(!Post::is_valid_slug("-leading"));
+        assert!(!Post::is_valid_slug("admin"));
+    }
+
+    #[test]
+    fn test_status_over_time() {
+        let now = Utc::now();
+        let mut post = Post {
+            id: 1,
+            slug: "hello".to_string(),
+            title: "Hello".to_string(),
+            summary: "First post".to_string(),
+            body_markdown: "Hello".to_string(),
+            body_html: "<p>Hello</p>".to_string(),
+            tags: Json(vec![]),
+            reading_time_minutes: 1,
+            status: "scheduled".to_string(),
+            published_at: Some(now + Duration::hours(1)),
+            created_at: now,
+            updated_at: now,
+        };
+
+        assert!(!post.is_public_at(now));
+        assert_eq!(post.status_at(now), PostStatus::Scheduled);
+        assert!(post.is_public_at(now + Duration::hours(2)));
+        assert_eq!(post.status_at(now + Duration::hours(2)), PostStatus::Published);
+
+        post.status = "draft".to_string();
+        assert!(!post.is_public_at(now + Duration::hours(2)));
+    }
+}
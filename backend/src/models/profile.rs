@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 use validator::Validate;
 
 /// Profile model representing the developer's profile information
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Profile {
     pub id: i32,
     pub name: String,
@@ -20,33 +21,42 @@ pub struct Profile {
 }
 
 /// Update profile request model
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, Validate, ToSchema)]
 pub struct UpdateProfile {
     #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
     pub name: Option<String>,
     
     #[validate(length(min = 1, max = 200, message = "Title must be between 1 and 200 characters"))]
+    #[schema(min_length = 1, max_length = 200)]
     pub title: Option<String>,
     
     #[validate(length(min = 1, max = 1000, message = "Bio must be between 1 and 1000 characters"))]
+    #[schema(min_length = 1, max_length = 1000)]
     pub bio: Option<String>,
     
     #[validate(email(message = "Email must be a valid email address"))]
+    #[schema(format = "email")]
     pub email: Option<String>,
     
     #[validate(length(max = 20, message = "Phone number must be less than 20 characters"))]
+    #[schema(max_length = 20)]
     pub phone: Option<String>,
     
     #[validate(length(min = 1, max = 100, message = "Location must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
     pub location: Option<String>,
     
     #[validate(url(message = "LinkedIn URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub linkedin_url: Option<String>,
     
     #[validate(url(message = "GitHub URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub github_url: Option<String>,
     
     #[validate(url(message = "Twitter URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub twitter_url: Option<String>,
 }
 
@@ -151,7 +161,6 @@ mod tests {
             email: Some("invalid-email".to_string()),
             phone: None,
             location: None,
-            avatar_url: None,
             linkedin_url: None,
             github_url: None,
             twitter_url: None,
@@ -169,7 +178,7 @@ mod tests {
             email: None,
             phone: None,
             location: None,
-            linkedin_url: None,
+            linkedin_url: Some("not-a-url".to_string()),
             github_url: None,
             twitter_url: None,
         };
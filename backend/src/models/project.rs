@@ -1,16 +1,18 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{types::Json, FromRow};
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 use validator::Validate;
 
 /// Project model representing a portfolio project
-#[derive(Debug, Clone, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Project {
     pub id: i32,
     pub title: String,
     pub description: String,
     pub long_description: Option<String>,
-    pub technologies: String, // JSON array as string
+    pub technologies: Json<Vec<String>>, // aggregated from project_technologies
     pub github_url: Option<String>,
     pub demo_url: Option<String>,
     pub image_url: Option<String>,
@@ -21,7 +23,7 @@ pub struct Project {
 }
 
 /// Project model for API responses with parsed technologies
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProjectResponse {
     pub id: i32,
     pub title: String,
@@ -38,13 +40,12 @@ pub struct ProjectResponse {
 
 impl From<Project> for ProjectResponse {
     fn from(project: Project) -> Self {
-        let technologies = project.get_technologies().unwrap_or_default();
         Self {
             id: project.id,
             title: project.title,
             description: project.description,
             long_description: project.long_description,
-            technologies,
+            technologies: project.technologies.0,
             github_url: project.github_url,
             demo_url: project.demo_url,
             image_url: project.image_url,
@@ -56,86 +57,188 @@ impl From<Project> for ProjectResponse {
 }
 
 /// Create project request model
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateProject {
     #[validate(length(min = 1, max = 200, message = "Title must be between 1 and 200 characters"))]
+    #[schema(min_length = 1, max_length = 200)]
     pub title: String,
     
     #[validate(length(min = 1, max = 500, message = "Description must be between 1 and 500 characters"))]
+    #[schema(min_length = 1, max_length = 500)]
     pub description: String,
     
     #[validate(length(max = 2000, message = "Long description must be less than 2000 characters"))]
+    #[schema(max_length = 2000)]
     pub long_description: Option<String>,
     
     #[validate(length(min = 1, message = "At least one technology must be specified"))]
+    #[schema(min_items = 1)]
     pub technologies: Vec<String>,
     
     #[validate(url(message = "GitHub URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub github_url: Option<String>,
     
     #[validate(url(message = "Demo URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub demo_url: Option<String>,
     
     #[validate(url(message = "Image URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub image_url: Option<String>,
     
     #[validate(length(min = 1, max = 50, message = "Category must be between 1 and 50 characters"))]
+    #[schema(min_length = 1, max_length = 50)]
     pub category: String,
     
     pub featured: Option<bool>,
 }
 
 /// Update project request model
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
 pub struct UpdateProject {
     #[validate(length(min = 1, max = 200, message = "Title must be between 1 and 200 characters"))]
+    #[schema(min_length = 1, max_length = 200)]
     pub title: Option<String>,
     
     #[validate(length(min = 1, max = 500, message = "Description must be between 1 and 500 characters"))]
+    #[schema(min_length = 1, max_length = 500)]
     pub description: Option<String>,
     
     #[validate(length(max = 2000, message = "Long description must be less than 2000 characters"))]
+    #[schema(max_length = 2000)]
     pub long_description: Option<String>,
     
     pub technologies: Option<Vec<String>>,
     
     #[validate(url(message = "GitHub URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub github_url: Option<String>,
     
     #[validate(url(message = "Demo URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub demo_url: Option<String>,
     
     #[validate(url(message = "Image URL must be a valid URL"))]
+    #[schema(format = "uri")]
     pub image_url: Option<String>,
     
     #[validate(length(min = 1, max = 50, message = "Category must be between 1 and 50 characters"))]
+    #[schema(min_length = 1, max_length = 50)]
     pub category: Option<String>,
     
     pub featured: Option<bool>,
 }
 
-impl Project {
-    /// Parse technologies from JSON string
-    pub fn get_technologies(&self) -> Result<Vec<String>, serde_json::Error> {
-        serde_json::from_str(&self.technologies)
-    }
+/// Field used to order project listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectSortField {
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+/// Order of a project listing; a leading `-` in the query value means descending
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProjectSort {
+    pub field: ProjectSortField,
+    pub descending: bool,
 }
 
-impl CreateProject {
-    /// Convert technologies to JSON string
-    pub fn technologies_as_json(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string(&self.technologies)
+impl Default for ProjectSort {
+    /// Newest projects first
+    fn default() -> Self {
+        Self {
+            field: ProjectSortField::CreatedAt,
+            descending: true,
+        }
     }
 }
 
-impl UpdateProject {
-    /// Convert technologies to JSON string if present
-    pub fn technologies_as_json(&self) -> Result<Option<String>, serde_json::Error> {
-        match &self.technologies {
-            Some(techs) => Ok(Some(serde_json::to_string(techs)?)),
-            None => Ok(None),
+impl ProjectSort {
+    pub fn as_str(&self) -> &'static str {
+        match (self.field, self.descending) {
+            (ProjectSortField::CreatedAt, false) => "created_at",
+            (ProjectSortField::CreatedAt, true) => "-created_at",
+            (ProjectSortField::UpdatedAt, false) => "updated_at",
+            (ProjectSortField::UpdatedAt, true) => "-updated_at",
+            (ProjectSortField::Title, false) => "title",
+            (ProjectSortField::Title, true) => "-title",
         }
     }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        let (descending, field) = match s.strip_prefix('-') {
+            Some(field) => (true, field),
+            None => (false, s),
+        };
+        let field = match field {
+            "created_at" => ProjectSortField::CreatedAt,
+            "updated_at" => ProjectSortField::UpdatedAt,
+            "title" => ProjectSortField::Title,
+            _ => return None,
+        };
+        Some(Self { field, descending })
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["created_at", "-created_at", "updated_at", "-updated_at", "title", "-title"]
+    }
+}
+
+/// Filters for project listings; every filter that is set must match
+#[derive(Debug, Clone, Default)]
+pub struct ProjectFilter {
+    pub category: Option<String>,
+    pub featured: Option<bool>,
+    /// Technology slug (see `Technology::slug_for`)
+    pub technology: Option<String>,
+    /// Inclusive lower bound on `created_at`
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`
+    pub created_before: Option<DateTime<Utc>>,
+    /// FTS5 match expression run against the search index (see `search::to_match_expression`)
+    pub search: Option<String>,
+}
+
+/// Position after the last project of a page, handed to clients as an opaque string
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectCursor {
+    /// Sort the cursor was issued for
+    pub sort: String,
+    /// Sort key of the last project, as stored
+    pub key: String,
+    /// ID of the last project, breaking ties between equal keys
+    pub id: i32,
+}
+
+impl ProjectCursor {
+    /// Encode the cursor as URL-safe base64 JSON
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Decode a cursor produced by `encode`
+    pub fn decode(cursor: &str) -> Option<Self> {
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// One page of a project listing
+#[derive(Debug, Clone)]
+pub struct ProjectPage {
+    pub projects: Vec<Project>,
+    pub total_count: u64,
+    pub next_cursor: Option<String>,
+}
+
+impl Project {
+    /// Technology names in display order
+    pub fn technology_names(&self) -> &[String] {
+        &self.technologies
+    }
 }
 
 #[cfg(test)]
@@ -195,31 +298,13 @@ mod tests {
     }
 
     #[test]
-    fn test_create_project_technologies_json() {
-        let project = CreateProject {
-            title: "Test Project".to_string(),
-            description: "A test project description".to_string(),
-            long_description: None,
-            technologies: vec!["Rust".to_string(), "SQLite".to_string()],
-            github_url: None,
-            demo_url: None,
-            image_url: None,
-            category: "web".to_string(),
-            featured: None,
-        };
-
-        let json = project.technologies_as_json().unwrap();
-        assert_eq!(json, r#"["Rust","SQLite"]"#);
-    }
-
-    #[test]
-    fn test_project_get_technologies() {
+    fn test_project_technology_names() {
         let project = Project {
             id: 1,
             title: "Test Project".to_string(),
             description: "A test project".to_string(),
             long_description: None,
-            technologies: r#"["Rust","SQLite"]"#.to_string(),
+            technologies: Json(vec!["Rust".to_string(), "SQLite".to_string()]),
             github_url: None,
             demo_url: None,
             image_url: None,
@@ -229,7 +314,35 @@ mod tests {
             updated_at: Utc::now(),
         };
 
-        let techs = project.get_technologies().unwrap();
-        assert_eq!(techs, vec!["Rust", "SQLite"]);
+        assert_eq!(project.technology_names(), ["Rust", "SQLite"]);
+
+        let response = ProjectResponse::from(project);
+        assert_eq!(response.technologies, vec!["Rust", "SQLite"]);
+    }
+
+    #[test]
+    fn test_project_sort_parsing() {
+        assert_eq!(ProjectSort::from_str("-updated_at"), Some(ProjectSort { field: ProjectSortField::UpdatedAt, descending: true }));
+        assert_eq!(ProjectSort::from_str("title"), Some(ProjectSort { field: ProjectSortField::Title, descending: false }));
+        assert_eq!(ProjectSort::from_str("featured"), None);
+        assert_eq!(ProjectSort::default().as_str(), "-created_at");
+
+        for sort in ProjectSort::all() {
+            assert_eq!(ProjectSort::from_str(sort).unwrap().as_str(), sort);
+        }
+    }
+
+    #[test]
+    fn test_project_cursor_roundtrip() {
+        let cursor = ProjectCursor {
+            sort: "-created_at".to_string(),
+            key: "2024-01-01 12:00:00".to_string(),
+            id: 42,
+        };
+
+        let encoded = cursor.encode();
+        assert!(!encoded.contains('='));
+        assert_eq!(ProjectCursor::decode(&encoded), Some(cursor));
+        assert_eq!(ProjectCursor::decode("not a cursor"), None);
     }
 }
\ No newline at end of file
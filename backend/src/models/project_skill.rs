@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+use validator::Validate;
+use crate::models::{GalleryItem, ProjectResponse, Skill};
+
+/// How a link between a project and a skill was created
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkSource {
+    /// Set explicitly by an admin
+    Manual,
+    /// Derived from a project technology matching the skill name
+    Suggested,
+}
+
+impl LinkSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LinkSource::Manual => "manual",
+            LinkSource::Suggested => "suggested",
+        }
+    }
+}
+
+/// Skill linked to a project, as listed on the project
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LinkedSkill {
+    pub id: i32,
+    pub name: String,
+    pub category: String,
+    pub level: i32,
+    pub source: String,
+}
+
+/// Project linked to a skill, as listed on the skill
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LinkedProject {
+    pub id: i32,
+    pub title: String,
+    pub category: String,
+    pub featured: bool,
+    pub created_at: DateTime<Utc>,
+    pub source: String,
+}
+
+/// Project with the skills it demonstrates and its gallery
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProjectDetail {
+    #[serde(flatten)]
+    pub project: ProjectResponse,
+    pub skills: Vec<LinkedSkill>,
+    #[serde(default)]
+    pub gallery: Vec<GalleryItem>,
+}
+
+/// Skill with the projects demonstrating it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkillDetail {
+    #[serde(flatten)]
+    pub skill: Skill,
+    pub projects: Vec<LinkedProject>,
+}
+
+/// Request to replace the skills linked to a project
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct SetProjectSkills {
+    #[validate(length(max = 100, message = "At most 100 skills can be linked to a project"))]
+    #[schema(max_items = 100)]
+    pub skill_ids: Vec<i32>,
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Kind of record a search hit refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchResultType {
+    Project,
+    Skill,
+    ContactMessage,
+    Profile,
+}
+
+impl SearchResultType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchResultType::Project => "project",
+            SearchResultType::Skill => "skill",
+            SearchResultType::ContactMessage => "contact_message",
+            SearchResultType::Profile => "profile",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "project" => Some(SearchResultType::Project),
+            "skill" => Some(SearchResultType::Skill),
+            "contact_message" => Some(SearchResultType::ContactMessage),
+            "profile" => Some(SearchResultType::Profile),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["project", "skill", "contact_message", "profile"]
+    }
+
+    /// Whether hits of this type may only be shown to admins
+    pub fn is_private(&self) -> bool {
+        matches!(self, SearchResultType::ContactMessage)
+    }
+}
+
+/// Ranked search hit; `title` and `snippet` are HTML-escaped with matches wrapped in `<mark>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SearchHit {
+    #[serde(rename = "type")]
+    pub result_type: SearchResultType,
+    pub id: i32,
+    pub title: String,
+    pub snippet: String,
+    /// Relevance, higher is better
+    pub score: f64,
+}
+
+/// Build an FTS5 match expression from user input.
+///
+/// Words must all match; `"quoted text"` matches a phrase and a trailing `*` matches a prefix
+/// (`rus*`). Everything else is quoted so user input cannot inject FTS5 operators.
+/// Returns `None` when the input contains nothing searchable.
+pub fn to_match_expression(query: &str) -> Option<String> {
+    let mut terms = Vec::new();
+
+    for (index, part) in query.split('"').enumerate() {
+        // Odd parts sit between double quotes
+        if index % 2 == 1 {
+            let phrase = words(part).join(" ");
+            if !phrase.is_empty() {
+                terms.push(format!("\"{}\"", phrase));
+            }
+            continue;
+        }
+
+        for token in part.split_whitespace() {
+            let token_words = words(token);
+            let last = token_words.len().saturating_sub(1);
+            for (position, word) in token_words.into_iter().enumerate() {
+                // Only the last word of `foo.ba*` is a prefix
+                let prefix = if position == last && token.ends_with('*') { "*" } else { "" };
+                terms.push(format!("\"{}\"{}", word, prefix));
+            }
+        }
+    }
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Split text into words the way the FTS5 tokenizer does (letters and digits)
+fn words(text: &str) -> Vec<&str> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_expression_words_and_prefix() {
+        assert_eq!(to_match_expression("rust web").as_deref(), Some(r#""rust" "web""#));
+        assert_eq!(to_match_expression("sve*").as_deref(), Some(r#""sve"*"#));
+        assert_eq!(to_match_expression("node.js").as_deref(), Some(r#""node" "js""#));
+    }
+
+    #[test]
+    fn test_match_expression_phrase() {
+        assert_eq!(
+            to_match_expression(r#"api "task management" rust*"#).as_deref(),
+            Some(r#""api" "task management" "rust"*"#)
+        );
+        // An unterminated quote still yields a phrase
+        assert_eq!(to_match_expression(r#""full stack"#).as_deref(), Some(r#""full stack""#));
+    }
+
+    #[test]
+    fn test_match_expression_neutralizes_operators() {
+        assert_eq!(to_match_expression("title:rust OR NEAR(x)").as_deref(), Some(r#""title" "rust" "OR" "NEAR" "x""#));
+        assert_eq!(to_match_expression("  * \"\" -- ").as_deref(), None);
+        assert_eq!(to_match_expression("rust *").as_deref(), Some(r#""rust""#));
+    }
+
+    #[test]
+    fn test_result_type_privacy() {
+        assert!(SearchResultType::ContactMessage.is_private());
+        assert!(!SearchResultType::Project.is_private());
+        for result_type in SearchResultType::all() {
+            assert_eq!(SearchResultType::from_str(result_type).unwrap().as_str(), result_type);
+        }
+    }
+}
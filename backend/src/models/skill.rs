@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 use validator::Validate;
 
 /// Skill model representing a technical skill
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Skill {
     pub id: i32,
     pub name: String,
@@ -13,43 +14,59 @@ pub struct Skill {
     pub years_experience: Option<i32>,
     pub description: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Number of projects demonstrating the skill (derived from project links)
+    #[serde(default)]
+    pub project_count: i64,
+    /// Creation date of the most recent linked project
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 /// Create skill request model
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateSkill {
     #[validate(length(min = 1, max = 100, message = "Skill name must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
     pub name: String,
     
     #[validate(length(min = 1, max = 50, message = "Category must be between 1 and 50 characters"))]
+    #[schema(min_length = 1, max_length = 50)]
     pub category: String,
     
     #[validate(range(min = 1, max = 5, message = "Level must be between 1 and 5"))]
+    #[schema(minimum = 1, maximum = 5)]
     pub level: i32,
     
     #[validate(range(min = 0, max = 50, message = "Years of experience must be between 0 and 50"))]
+    #[schema(minimum = 0, maximum = 50)]
     pub years_experience: Option<i32>,
     
     #[validate(length(max = 500, message = "Description must be less than 500 characters"))]
+    #[schema(max_length = 500)]
     pub description: Option<String>,
 }
 
 /// Update skill request model
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
 pub struct UpdateSkill {
     #[validate(length(min = 1, max = 100, message = "Skill name must be between 1 and 100 characters"))]
+    #[schema(min_length = 1, max_length = 100)]
     pub name: Option<String>,
     
     #[validate(length(min = 1, max = 50, message = "Category must be between 1 and 50 characters"))]
+    #[schema(min_length = 1, max_length = 50)]
     pub category: Option<String>,
     
     #[validate(range(min = 1, max = 5, message = "Level must be between 1 and 5"))]
+    #[schema(minimum = 1, maximum = 5)]
     pub level: Option<i32>,
     
     #[validate(range(min = 0, max = 50, message = "Years of experience must be between 0 and 50"))]
+    #[schema(minimum = 0, maximum = 50)]
     pub years_experience: Option<i32>,
     
     #[validate(length(max = 500, message = "Description must be less than 500 characters"))]
+    #[schema(max_length = 500)]
     pub description: Option<String>,
 }
 
@@ -78,6 +95,7 @@ impl SkillCategory {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "Frontend" => Some(SkillCategory::Frontend),
@@ -182,6 +200,8 @@ mod tests {
             years_experience: Some(3),
             description: None,
             created_at: Utc::now(),
+            project_count: 0,
+            last_used_at: None,
         };
 
         assert_eq!(skill.level_description(), "Advanced");
@@ -197,6 +217,8 @@ mod tests {
             years_experience: Some(3),
             description: None,
             created_at: Utc::now(),
+            project_count: 0,
+            last_used_at: None,
         };
 
         assert!(skill.is_valid_category());
@@ -209,6 +231,8 @@ mod tests {
             years_experience: None,
             description: None,
             created_at: Utc::now(),
+            project_count: 0,
+            last_used_at: None,
         };
 
         assert!(!invalid_skill.is_valid_category());
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// One piece of evidence for or against a submission being spam
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SpamSignal {
+    /// Name of the filter that raised the signal
+    pub filter: String,
+    /// Points added to the total; negative when the filter vouches for the message
+    pub score: f64,
+    pub reason: String,
+}
+
+impl SpamSignal {
+    pub fn new(filter: &str, score: f64, reason: impl Into<String>) -> Self {
+        Self {
+            filter: filter.to_string(),
+            score,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// What happens to a submission once it is scored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpamDecision {
+    #[default]
+    Accept,
+    /// Stored with the `spam` status, out of the inbox
+    Quarantine,
+    /// Not stored at all
+    Reject,
+}
+
+impl SpamDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpamDecision::Accept => "accept",
+            SpamDecision::Quarantine => "quarantine",
+            SpamDecision::Reject => "reject",
+        }
+    }
+}
+
+/// Outcome of running a submission through the spam filters
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpamVerdict {
+    pub score: f64,
+    pub decision: SpamDecision,
+    pub signals: Vec<SpamSignal>,
+}
+
+impl SpamVerdict {
+    pub fn is_spam(&self) -> bool {
+        self.decision != SpamDecision::Accept
+    }
+}
+
+/// Label an admin gives a message when training the spam model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpamLabel {
+    Spam,
+    Ham,
+}
+
+impl SpamLabel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpamLabel::Spam => "spam",
+            SpamLabel::Ham => "ham",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(label: &str) -> Option<Self> {
+        match label {
+            "spam" => Some(SpamLabel::Spam),
+            "ham" => Some(SpamLabel::Ham),
+            _ => None,
+        }
+    }
+}
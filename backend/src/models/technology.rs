@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+
+/// Maximum length of a technology name
+pub const MAX_TECHNOLOGY_NAME_LEN: usize = 50;
+
+/// Technology model with the number of projects using it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Technology {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub project_count: i64,
+}
+
+impl Technology {
+    /// Canonical key of a technology name; "rust" and " Rust " share the same slug
+    pub fn slug_for(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+
+    /// Trim names and drop empty entries and case-insensitive duplicates, keeping the first spelling
+    pub fn normalize_names(names: &[String]) -> Vec<String> {
+        let mut seen = Vec::new();
+        let mut normalized = Vec::new();
+
+        for name in names {
+            let name = name.trim();
+            let slug = Self::slug_for(name);
+            if name.is_empty() || seen.contains(&slug) {
+                continue;
+            }
+            seen.push(slug);
+            normalized.push(name.to_string());
+        }
+
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slug_for() {
+        assert_eq!(Technology::slug_for("Rust"), "rust");
+        assert_eq!(Technology::slug_for("  Node.js "), "node.js");
+        assert_eq!(Technology::slug_for("rust"), Technology::slug_for("RUST"));
+    }
+
+    #[test]
+    fn test_normalize_names() {
+        let names = vec![
+            " Rust".to_string(),
+            "SQLite".to_string(),
+            "rust".to_string(),
+            "   ".to_string(),
+            "sqlite ".to_string(),
+            "Axum".to_string(),
+        ];
+
+        assert_eq!(Technology::normalize_names(&names), vec!["Rust", "SQLite", "Axum"]);
+    }
+}
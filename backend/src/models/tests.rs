@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod project_tests {
-    use super::*;
+    use crate::models::{CreateProject, UpdateProject};
 
     #[test]
     fn test_create_project_validation() {
@@ -44,7 +44,7 @@ mod project_tests {
 
 #[cfg(test)]
 mod skill_tests {
-    use super::*;
+    use crate::models::CreateSkill;
 
     #[test]
     fn test_create_skill_validation() {
@@ -63,7 +63,7 @@ mod skill_tests {
 
 #[cfg(test)]
 mod contact_tests {
-    use super::*;
+    use crate::models::CreateContactMessage;
 
     #[test]
     fn test_create_contact_message_validation() {
@@ -72,6 +72,7 @@ mod contact_tests {
             email: "test@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "Test message content".to_string(),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&valid_message).expect("Valid message should serialize");
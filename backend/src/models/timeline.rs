@@ -0,0 +1,156 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::ValidationError;
+use crate::{
+    error::FieldError,
+    models::{Certification, Education, Experience},
+};
+
+/// Whether a month is written `YYYY-MM`, as timeline dates are
+pub fn is_valid_month(month: &str) -> bool {
+    let bytes = month.as_bytes();
+    bytes.len() == 7
+        && bytes[4] == b'-'
+        && bytes[..4].iter().chain(&bytes[5..]).all(u8::is_ascii_digit)
+        && matches!(&month[5..], "01" | "02" | "03" | "04" | "05" | "06" | "07" | "08" | "09" | "10" | "11" | "12")
+}
+
+/// Validator for `YYYY-MM` month fields
+pub fn validate_month(month: &str) -> Result<(), ValidationError> {
+    if is_valid_month(month) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("month").with_message(Cow::Borrowed("Month must be written YYYY-MM")))
+    }
+}
+
+/// Check that a period does not end before it starts
+pub fn check_period(start_month: &str, end_month: Option<&str>) -> Result<(), FieldError> {
+    match end_month {
+        // Months are zero-padded, so they compare as text
+        Some(end_month) if end_month < start_month => Err(FieldError::new(
+            "end_month",
+            "period",
+            format!("Period cannot end ({}) before it starts ({})", end_month, start_month),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Entry of the CV timeline, tagged with its kind
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TimelineEntry {
+    Experience(Experience),
+    Education(Education),
+    Certification(Certification),
+}
+
+impl TimelineEntry {
+    /// Month the entry starts, or the certification was issued
+    pub fn start_month(&self) -> &str {
+        match self {
+            TimelineEntry::Experience(experience) => &experience.start_month,
+            TimelineEntry::Education(education) => &education.start_month,
+            TimelineEntry::Certification(certification) => &certification.issued_month,
+        }
+    }
+
+    /// Month the entry ends, `None` while it is ongoing. Certifications are single events.
+    pub fn end_month(&self) -> Option<&str> {
+        match self {
+            TimelineEntry::Experience(experience) => experience.end_month.as_deref(),
+            TimelineEntry::Education(education) => education.end_month.as_deref(),
+            TimelineEntry::Certification(certification) => Some(&certification.issued_month),
+        }
+    }
+
+    /// Sort entries newest first: by start month, then ongoing entries before those that
+    /// ended, then by end month
+    pub fn sort_newest_first(entries: &mut [TimelineEntry]) {
+        entries.sort_by(|a, b| {
+            b.start_month()
+                .cmp(a.start_month())
+                .then_with(|| match (a.end_month(), b.end_month()) {
+                    (None, None) => std::cmp::Ordering::Equal,
+                    (None, Some(_)) => std::cmp::Ordering::Less,
+                    (Some(_), None) => std::cmp::Ordering::Greater,
+                    (Some(a), Some(b)) => b.cmp(a),
+                })
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::types::Json;
+
+    #[test]
+    fn test_month_format() {
+        assert!(is_valid_month("2024-01"));
+        assert!(is_valid_month("1999-12"));
+        assert!(!is_valid_month("2024-13"));
+        assert!(!is_valid_month("2024-1"));
+        assert!(!is_valid_month("24-01-01"));
+        assert!(!is_valid_month("2024/01"));
+        assert!(!is_valid_month("２０２４-01"));
+
+        assert!(check_period("2020-05", Some("2020-05")).is_ok());
+        assert!(check_period("2020-05", None).is_ok());
+        assert!(check_period("2020-05", Some("2019-12")).is_err());
+    }
+
+    #[test]
+    fn test_sort_newest_first() {
+        let now = Utc::now();
+        let experience = |id: i32, start: &str, end: Option<&str>| {
+            TimelineEntry::Experience(Experience {
+                id,
+                company: "Company".to_string(),
+                role: "Developer".to_string(),
+                location: None,
+                start_month: start.to_string(),
+                end_month: end.map(str::to_string),
+                is_current: end.is_none(),
+                highlights: Json(vec![]),
+                technologies: Json(vec![]),
+                created_at: now,
+                updated_at: now,
+            })
+        };
+        let certification = TimelineEntry::Certification(Certification {
+            id: 4,
+            name: "Certified".to_string(),
+            issuer: "Issuer".to_string(),
+            issued_month: "2021-06".to_string(),
+            expires_month: None,
+            credential_id: None,
+            credential_url: None,
+            created_at: now,
+            updated_at: now,
+        });
+
+        let mut entries = vec![
+            experience(1, "2018-01", Some("2020-12")),
+            certification,
+            experience(2, "2021-01", Some("2022-03")),
+            experience(3, "2021-01", None),
+        ];
+        TimelineEntry::sort_newest_first(&mut entries);
+
+        let order: Vec<(&str, Option<&str>)> = entries.iter().map(|e| (e.start_month(), e.end_month())).collect();
+        assert_eq!(
+            order,
+            vec![
+                ("2021-06", Some("2021-06")),
+                ("2021-01", None),
+                ("2021-01", Some("2022-03")),
+                ("2018-01", Some("2020-12")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Delivery of notifications through pluggable channels
+pub mod smtp;
+pub mod webhook;
+pub mod sink;
+pub mod template;
+
+use std::{collections::HashMap, sync::Arc};
+
+use axum::async_trait;
+
+use crate::{config::NotificationConfig, models::Notification};
+
+pub use sink::SinkNotifier;
+pub use smtp::SmtpNotifier;
+pub use webhook::WebhookNotifier;
+
+/// Notification delivery error types
+#[derive(Debug, thiserror::Error)]
+pub enum NotifyError {
+    /// The notification can never be delivered (bad address, rejected payload...)
+    #[error("Notification rejected: {0}")]
+    Rejected(String),
+    /// Delivery failed but may succeed later
+    #[error("Delivery failed: {0}")]
+    Unavailable(String),
+    #[error("Invalid notifier configuration: {0}")]
+    Config(String),
+}
+
+impl NotifyError {
+    /// Whether retrying the same notification is pointless
+    pub fn is_permanent(&self) -> bool {
+        !matches!(self, NotifyError::Unavailable(_))
+    }
+}
+
+/// A channel notifications can be sent through
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError>;
+}
+
+/// Notifiers by channel name
+#[derive(Clone, Default)]
+pub struct Notifiers {
+    channels: HashMap<String, Arc<dyn Notifier>>,
+}
+
+impl Notifiers {
+    /// Build the notifiers for every channel the configuration uses
+    pub fn from_config(config: &NotificationConfig) -> Result<Self, NotifyError> {
+        let mut notifiers = Self::default();
+
+        for channel in config.channels() {
+            let notifier: Arc<dyn Notifier> = match channel {
+                "smtp" => Arc::new(SmtpNotifier::new(&config.smtp, &config.from_email)?),
+                "webhook" => {
+                    let url = config
+                        .webhook
+                        .url
+                        .as_deref()
+                        .ok_or_else(|| NotifyError::Config("the webhook channel needs a URL".to_string()))?;
+                    Arc::new(WebhookNotifier::new(url, &config.webhook)?)
+                }
+                "file" => Arc::new(SinkNotifier::file(&config.file.path)),
+                "stdout" => Arc::new(SinkNotifier::stdout()),
+                other => return Err(NotifyError::Config(format!("unknown channel '{}'", other))),
+            };
+            notifiers.channels.insert(channel.to_string(), notifier);
+        }
+
+        Ok(notifiers)
+    }
+
+    /// Add or replace the notifier of a channel
+    pub fn with(mut self, channel: &str, notifier: impl Notifier + 'static) -> Self {
+        self.channels.insert(channel.to_string(), Arc::new(notifier));
+        self
+    }
+
+    pub fn get(&self, channel: &str) -> Option<&Arc<dyn Notifier>> {
+        self.channels.get(channel)
+    }
+}
@@ -0,0 +1,85 @@
+//! Notifications written as JSON lines to a file or standard output
+use std::path::{Path, PathBuf};
+
+use axum::async_trait;
+use tokio::io::AsyncWriteExt;
+
+use super::{Notifier, NotifyError};
+use crate::models::Notification;
+
+/// Writes each notification as one line of JSON, for development and tests
+pub struct SinkNotifier {
+    /// Output file, or standard output when None
+    path: Option<PathBuf>,
+}
+
+impl SinkNotifier {
+    /// Append notifications to a file, creating it and its directory as needed
+    pub fn file(path: &Path) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    /// Print notifications to standard output
+    pub fn stdout() -> Self {
+        Self { path: None }
+    }
+}
+
+#[async_trait]
+impl Notifier for SinkNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let mut line = serde_json::to_string(notification).map_err(|e| NotifyError::Rejected(e.to_string()))?;
+        line.push('\n');
+
+        let Some(path) = &self.path else {
+            print!("{}", line);
+            return Ok(());
+        };
+
+        let unavailable = |e: std::io::Error| NotifyError::Unavailable(format!("{}: {}", path.display(), e));
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent).await.map_err(unavailable)?;
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(unavailable)?;
+        file.write_all(line.as_bytes()).await.map_err(unavailable)?;
+        file.flush().await.map_err(unavailable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NotificationKind;
+
+    #[tokio::test]
+    async fn test_file_sink_appends_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("notifications.log");
+        let notifier = SinkNotifier::file(&path);
+
+        for subject in ["First", "Second"] {
+            let notification = Notification {
+                kind: NotificationKind::Owner,
+                contact_message_id: Some(1),
+                to: None,
+                reply_to: None,
+                subject: subject.to_string(),
+                body: "Body".to_string(),
+            };
+            notifier.send(&notification).await.unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<Notification> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].subject, "Second");
+    }
+}
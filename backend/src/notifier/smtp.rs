@@ -0,0 +1,189 @@
+//! Email delivery through an SMTP server
+use std::time::Duration;
+
+use axum::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+
+use super::{Notifier, NotifyError};
+use crate::{config::SmtpConfig, models::Notification};
+
+/// Sends notifications as plain-text emails
+pub struct SmtpNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpNotifier {
+    pub fn new(config: &SmtpConfig, from: &str) -> Result<Self, NotifyError> {
+        let from = from
+            .parse()
+            .map_err(|e| NotifyError::Config(format!("invalid sender '{}': {}", from, e)))?;
+
+        let builder = match config.tls.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host),
+            "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host),
+            _ => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)),
+        }
+        .map_err(|e| NotifyError::Config(format!("invalid SMTP server '{}': {}", config.host, e)))?;
+
+        let mut builder = builder
+            .port(config.port)
+            .timeout(Some(Duration::from_secs(config.timeout_secs)));
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for SmtpNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let rejected = |e: &dyn std::fmt::Display| NotifyError::Rejected(e.to_string());
+
+        let to: Mailbox = notification
+            .to
+            .as_deref()
+            .ok_or_else(|| NotifyError::Rejected("no recipient address".to_string()))?
+            .parse()
+            .map_err(|e| rejected(&e))?;
+
+        let mut message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(notification.subject.as_str())
+            .header(ContentType::TEXT_PLAIN);
+        if let Some(reply_to) = &notification.reply_to {
+            message = message.reply_to(reply_to.parse().map_err(|e| rejected(&e))?);
+        }
+        let message = message.body(notification.body.clone()).map_err(|e| rejected(&e))?;
+
+        match self.transport.send(message).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.is_permanent() => Err(NotifyError::Rejected(e.to_string())),
+            Err(e) => Err(NotifyError::Unavailable(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    use crate::models::NotificationKind;
+
+    /// Minimal SMTP server accepting one session; returns the commands and message data it received.
+    /// Recipients containing "reject" get a permanent failure.
+    async fn start_smtp_server() -> (u16, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let mut received = Vec::new();
+
+            writer.write_all(b"220 localhost ESMTP test\r\n").await.unwrap();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                let command = line.to_uppercase();
+                let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                    b"250 localhost\r\n"
+                } else if command.starts_with("RCPT") && command.contains("REJECT") {
+                    b"550 No such user\r\n"
+                } else if command.starts_with("DATA") {
+                    writer.write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n").await.unwrap();
+                    let mut data = Vec::new();
+                    while let Some(line) = lines.next_line().await.unwrap() {
+                        if line == "." {
+                            break;
+                        }
+                        data.push(line);
+                    }
+                    received.push(data.join("\n"));
+                    b"250 Queued\r\n"
+                } else if command.starts_with("QUIT") {
+                    writer.write_all(b"221 Bye\r\n").await.unwrap();
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                received.push(line);
+                writer.write_all(reply).await.unwrap();
+            }
+            received
+        });
+
+        (port, handle)
+    }
+
+    fn create_test_notifier(port: u16) -> SmtpNotifier {
+        let config = SmtpConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            ..SmtpConfig::default()
+        };
+        SmtpNotifier::new(&config, "portfolio@example.com").unwrap()
+    }
+
+    fn create_test_notification(to: &str) -> Notification {
+        Notification {
+            kind: NotificationKind::Owner,
+            contact_message_id: Some(1),
+            to: Some(to.to_string()),
+            reply_to: Some("john.doe@example.com".to_string()),
+            subject: "New contact message: Hello".to_string(),
+            body: "John Doe wrote:\n\nHello there".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_email() {
+        let (port, server) = start_smtp_server().await;
+        let notifier = create_test_notifier(port);
+
+        notifier.send(&create_test_notification("owner@example.com")).await.unwrap();
+        drop(notifier);
+
+        let received = server.await.unwrap();
+        assert!(received.iter().any(|line| line == "MAIL FROM:<portfolio@example.com>"));
+        assert!(received.iter().any(|line| line == "RCPT TO:<owner@example.com>"));
+
+        let data = received.iter().find(|line| line.contains("Subject:")).unwrap();
+        assert!(data.contains("Subject: New contact message: Hello"));
+        assert!(data.contains("Reply-To: john.doe@example.com"));
+        assert!(data.contains("Hello there"));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_recipient_is_permanent() {
+        let (port, _server) = start_smtp_server().await;
+        let notifier = create_test_notifier(port);
+
+        let error = notifier.send(&create_test_notification("reject@example.com")).await.unwrap_err();
+        assert!(error.is_permanent(), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_server_is_transient() {
+        // Bind then drop a listener to get a port nothing listens on
+        let port = TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let notifier = create_test_notifier(port);
+
+        let error = notifier.send(&create_test_notification("owner@example.com")).await.unwrap_err();
+        assert!(!error.is_permanent(), "{}", error);
+    }
+}
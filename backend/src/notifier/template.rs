@@ -0,0 +1,96 @@
+//! `{{placeholder}}` substitution in notification templates
+use crate::models::ContactMessage;
+
+/// Placeholders templates may use
+pub const PLACEHOLDERS: &[&str] = &["name", "email", "subject", "message", "date", "id"];
+
+/// Check a template only uses known placeholders, returning the first unknown one
+pub fn check(template: &str) -> Result<(), String> {
+    match placeholders(template).find(|name| !PLACEHOLDERS.contains(name)) {
+        Some(name) => Err(name.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Fill a template with a contact message's values (unknown placeholders are left as they are)
+pub fn render(template: &str, message: &ContactMessage) -> String {
+    let mut output = String::with_capacity(template.len() + message.message.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(length) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+
+        let name = rest[start + 2..start + 2 + length].trim();
+        match value(name, message) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(&rest[start..start + length + 4]),
+        }
+        rest = &rest[start + length + 4..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn value(name: &str, message: &ContactMessage) -> Option<String> {
+    Some(match name {
+        "name" => message.name.clone(),
+        "email" => message.email.clone(),
+        "subject" => message.subject.clone(),
+        "message" => message.message.clone(),
+        "date" => message.formatted_date(),
+        "id" => message.id.to_string(),
+        _ => return None,
+    })
+}
+
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split("{{").skip(1).filter_map(|part| part.split_once("}}")).map(|(name, _)| name.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn create_test_message() -> ContactMessage {
+        ContactMessage {
+            id: 42,
+            name: "John Doe".to_string(),
+            email: "john.doe@example.com".to_string(),
+            subject: "Project inquiry".to_string(),
+            message: "Could we talk about {{a project}}?".to_string(),
+            status: "new".to_string(),
+            is_spam: false,
+            spam_score: None,
+            spam_reasons: None,
+            spam_trained: None,
+            starred: false,
+            notes: None,
+            status_changed_at: None,
+            replied_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let message = create_test_message();
+        let rendered = render("#{{id}} from {{ name }} <{{email}}>: {{message}} {{unknown}} {{", &message);
+        // Values are inserted once, never expanded again
+        assert_eq!(
+            rendered,
+            "#42 from John Doe <john.doe@example.com>: Could we talk about {{a project}}? {{unknown}} {{"
+        );
+    }
+
+    #[test]
+    fn test_check_placeholders() {
+        assert!(check("{{name}} wrote {{ subject }} on {{date}}").is_ok());
+        assert!(check("no placeholders {{").is_ok());
+        assert_eq!(check("Hi {{name}}, {{phone}}"), Err("phone".to_string()));
+    }
+}
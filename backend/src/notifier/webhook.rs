@@ -0,0 +1,123 @@
+//! Notifications posted as JSON to an HTTP endpoint
+use std::time::Duration;
+
+use axum::async_trait;
+use reqwest::StatusCode;
+use serde_json::json;
+
+use super::{Notifier, NotifyError};
+use crate::{config::WebhookConfig, models::Notification};
+
+/// Posts `{"event": "contact_message", "notification": {...}}` to a URL
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: &str, config: &WebhookConfig) -> Result<Self, NotifyError> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| NotifyError::Config(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            url: url.to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn send(&self, notification: &Notification) -> Result<(), NotifyError> {
+        let payload = json!({
+            "event": "contact_message",
+            "notification": notification,
+        });
+
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| NotifyError::Unavailable(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_client_error() && status != StatusCode::REQUEST_TIMEOUT && status != StatusCode::TOO_MANY_REQUESTS {
+            Err(NotifyError::Rejected(format!("webhook answered {}", status)))
+        } else {
+            Err(NotifyError::Unavailable(format!("webhook answered {}", status)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use axum::{extract::State, http::StatusCode as HttpStatus, routing::post, Json, Router};
+    use serde_json::Value;
+
+    use crate::models::NotificationKind;
+
+    /// Start a webhook receiver answering with the given status and recording the payloads
+    async fn start_receiver(status: HttpStatus) -> (String, Arc<Mutex<Vec<Value>>>) {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let app = Router::new()
+            .route(
+                "/hook",
+                post(move |State(received): State<Arc<Mutex<Vec<Value>>>>, Json(payload): Json<Value>| async move {
+                    received.lock().unwrap().push(payload);
+                    status
+                }),
+            )
+            .with_state(received.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (url, received)
+    }
+
+    fn create_test_notification() -> Notification {
+        Notification {
+            kind: NotificationKind::Spam,
+            contact_message_id: Some(7),
+            to: None,
+            reply_to: None,
+            subject: "[Likely spam] You won".to_string(),
+            body: "Click here".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_posts_json_payload() {
+        let (url, received) = start_receiver(HttpStatus::NO_CONTENT).await;
+        let notifier = WebhookNotifier::new(&url, &WebhookConfig::default()).unwrap();
+
+        notifier.send(&create_test_notification()).await.unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0]["event"], "contact_message");
+        assert_eq!(received[0]["notification"]["kind"], "spam");
+        assert_eq!(received[0]["notification"]["contact_message_id"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses() {
+        let (url, _) = start_receiver(HttpStatus::BAD_REQUEST).await;
+        let notifier = WebhookNotifier::new(&url, &WebhookConfig::default()).unwrap();
+        assert!(notifier.send(&create_test_notification()).await.unwrap_err().is_permanent());
+
+        let (url, _) = start_receiver(HttpStatus::SERVICE_UNAVAILABLE).await;
+        let notifier = WebhookNotifier::new(&url, &WebhookConfig::default()).unwrap();
+        assert!(!notifier.send(&create_test_notification()).await.unwrap_err().is_permanent());
+    }
+}
@@ -0,0 +1,505 @@
+//! OpenAPI 3.1 description of the API, generated from the handlers and the models they exchange
+
+use utoipa::{
+    openapi::{
+        path::{Operation, PathItem},
+        response::{Response, ResponseBuilder},
+        security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+        schema::{ObjectBuilder, Type},
+        ContentBuilder, Header, OpenApi as Document, Ref, RefOr, ServerBuilder,
+    },
+    Modify, OpenApi,
+};
+
+use crate::{
+    auth::API_KEY_HEADER,
+    config::AppConfig,
+    error::{ErrorBody, ErrorResponse},
+    problem::{Problem, PROBLEM_JSON},
+    routes::{
+        api_keys, auth, certifications, contact, education, experience, export, feeds, media, posts, profile, projects,
+        search, skills, technologies,
+    },
+};
+
+/// Paths served only when the public contact form is enabled
+const CONTACT_FORM_PATHS: [&str; 3] = ["/api/contact", "/api/contact/form-token", "/api/contact/challenge"];
+
+/// Paths served only when API keys are enabled
+const API_KEY_PATHS: [&str; 2] = ["/api/keys", "/api/keys/{id}"];
+
+/// Specification of every endpoint the API router nests, from authentication to the feeds
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Portfolio API",
+        description = "Projects, skills, profile, CV, blog, media, feeds and contact form of the portfolio. \
+                       Successful responses are wrapped in `{success, data, message, pagination}`, errors in \
+                       `ErrorResponse`, or in RFC 7807 `Problem` details when `Accept` prefers \
+                       `application/problem+json`."
+    ),
+    paths(
+        auth::login,
+        auth::get_current_admin,
+        api_keys::get_api_keys,
+        api_keys::create_api_key,
+        api_keys::revoke_api_key,
+        projects::get_projects,
+        projects::get_project_by_id,
+        projects::create_project,
+        projects::update_project,
+        projects::set_project_skills,
+        projects::set_project_gallery,
+        projects::delete_project,
+        skills::get_skills,
+        skills::get_skill_by_id,
+        skills::get_categories,
+        skills::create_skill,
+        skills::update_skill,
+        skills::delete_skill,
+        profile::get_profile,
+        profile::update_profile,
+        profile::get_profile_summary,
+        profile::check_profile_exists,
+        profile::get_timeline,
+        profile::get_resume_pdf,
+        contact::submit_contact_message,
+        contact::issue_form_token,
+        contact::issue_challenge,
+        contact::get_contact_messages,
+        contact::get_contact_message_by_id,
+        contact::update_contact_message,
+        contact::update_messages_status,
+        contact::record_contact_reply,
+        contact::delete_contact_message,
+        contact::get_message_stats,
+        contact::cleanup_old_messages,
+        technologies::get_technologies,
+        search::search,
+        media::upload_media,
+        media::list_media,
+        media::serve_media,
+        media::delete_media,
+        posts::get_posts,
+        posts::get_post,
+        posts::get_stylesheet,
+        posts::get_all_posts,
+        posts::get_any_post,
+        posts::create_post,
+        posts::update_post,
+        posts::delete_post,
+        experience::get_experiences,
+        experience::get_experience_by_id,
+        experience::create_experience,
+        experience::update_experience,
+        experience::delete_experience,
+        education::get_education,
+        education::get_education_by_id,
+        education::create_education,
+        education::update_education,
+        education::delete_education,
+        certifications::get_certifications,
+        certifications::get_certification_by_id,
+        certifications::create_certification,
+        certifications::update_certification,
+        certifications::delete_certification,
+        export::export_json_resume,
+        export::import_json_resume,
+        feeds::get_feed,
+    ),
+    components(schemas(ErrorResponse, ErrorBody, Problem)),
+    modifiers(&SharedResponses),
+    tags(
+        (name = "auth", description = "Admin sessions"),
+        (name = "keys", description = "Scoped API keys for automation"),
+        (name = "projects", description = "Portfolio projects, their skills and galleries"),
+        (name = "skills", description = "Technical skills"),
+        (name = "profile", description = "Profile, CV timeline and PDF résumé"),
+        (name = "contact", description = "Contact form and message moderation"),
+        (name = "technologies", description = "Technologies used by the projects"),
+        (name = "search", description = "Full-text search across the portfolio"),
+        (name = "media", description = "Uploaded images and their resized variants"),
+        (name = "posts", description = "Blog posts written in Markdown"),
+        (name = "experience", description = "Work experience on the CV"),
+        (name = "education", description = "Education on the CV"),
+        (name = "certifications", description = "Certifications on the CV"),
+        (name = "export", description = "JSON Resume export and import"),
+        (name = "feeds", description = "Atom, RSS and JSON feeds of the projects and posts"),
+    )
+)]
+pub struct ApiDoc;
+
+/// The specification as served by this deployment: its base URL, without disabled features
+pub fn document(config: &AppConfig) -> Document {
+    let mut document = ApiDoc::openapi();
+    document.servers = Some(vec![ServerBuilder::new().url(config.site.api_url.clone()).build()]);
+    if !config.features.contact_form {
+        for path in CONTACT_FORM_PATHS {
+            document.paths.paths.remove(path);
+        }
+    }
+    if !config.features.api_keys {
+        for path in API_KEY_PATHS {
+            document.paths.paths.remove(path);
+        }
+    }
+    document
+}
+
+/// Declares the authentication schemes and the error responses every operation shares, offers
+/// every error as problem details too, and drops the `METHOD /path - ` prefix of handler doc comments from the summaries
+struct SharedResponses;
+
+impl Modify for SharedResponses {
+    fn modify(&self, openapi: &mut Document) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some(
+                        "Admin session token from `POST /api/auth/login`: a base64url payload and its \
+                         HMAC-SHA256 signature, joined by a dot. An API key is accepted here too.",
+                    ))
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                API_KEY_HEADER,
+                "API key with the scopes listed by the operation",
+            ))),
+        );
+
+        for item in openapi.paths.paths.values_mut() {
+            for operation in operations_mut(item) {
+                if let Some(summary) = operation.summary.take() {
+                    let summary = match summary.split_once(" - ") {
+                        Some((route, summary)) if route.contains(" /") => summary.to_string(),
+                        _ => summary,
+                    };
+                    // Lines after the first go to the description
+                    match summary.split_once('\n') {
+                        Some((first, rest)) => {
+                            operation.summary = Some(first.to_string());
+                            operation.description.get_or_insert_with(|| rest.to_string());
+                        }
+                        None => operation.summary = Some(summary),
+                    }
+                }
+
+                let mut shared = vec![
+                    ("429", error_response("Too many requests", true)),
+                    ("500", error_response("Unexpected server error", false)),
+                ];
+                if operation.security.is_some() {
+                    shared.push(("401", error_response("Missing or invalid credentials", false)));
+                    shared.push(("403", error_response("The API key lacks the required scope", false)));
+                }
+                for (status, response) in shared {
+                    operation.responses.responses.entry(status.to_string()).or_insert(response);
+                }
+                for (status, response) in operation.responses.responses.iter_mut() {
+                    if let (true, RefOr::T(response)) = (status.starts_with(['4', '5']), response) {
+                        response.content.entry(PROBLEM_JSON.to_string()).or_insert_with(|| {
+                            ContentBuilder::new().schema(Some(Ref::from_schema_name("Problem"))).build()
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Every operation of a path
+fn operations_mut(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [&mut item.get, &mut item.put, &mut item.post, &mut item.delete, &mut item.patch]
+        .into_iter()
+        .filter_map(Option::as_mut)
+}
+
+fn error_response(description: &str, retry_after: bool) -> RefOr<Response> {
+    let mut response = ResponseBuilder::new().description(description).content(
+        "application/json",
+        ContentBuilder::new().schema(Some(Ref::from_schema_name("ErrorResponse"))).build(),
+    );
+    if retry_after {
+        response = response.header("Retry-After", Header::new(ObjectBuilder::new().schema_type(Type::Integer)));
+    }
+    RefOr::T(response.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use axum::{body::Body, http::{header, Method, Request, StatusCode}};
+    use regex::Regex;
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+    use sqlx::SqlitePool;
+    use tower::ServiceExt;
+    use validator::Validate;
+
+    use crate::{
+        auth::AuthConfig,
+        models::{
+            BulkStatusUpdate, CreateApiKey, CreateCertification, CreateContactMessage, CreateContactReply, CreateEducation,
+            CreateExperience, CreatePost, CreateProject, CreateSkill, LoginRequest, SetProjectGallery, SetProjectSkills,
+            UpdateCertification, UpdateContactMessage, UpdateEducation, UpdateExperience, UpdatePost, UpdateProfile,
+            UpdateProject, UpdateSkill,
+        },
+        routes::create_router_with_config,
+    };
+
+    /// `METHOD /path` of every operation in the specification
+    fn documented_operations() -> BTreeSet<String> {
+        let mut document = ApiDoc::openapi();
+        document
+            .paths
+            .paths
+            .iter_mut()
+            .flat_map(|(path, item)| {
+                let methods = [
+                    ("GET", item.get.is_some()),
+                    ("PUT", item.put.is_some()),
+                    ("POST", item.post.is_some()),
+                    ("DELETE", item.delete.is_some()),
+                    ("PATCH", item.patch.is_some()),
+                ];
+                methods
+                    .into_iter()
+                    .filter(|(_, present)| *present)
+                    .map(|(method, _)| format!("{} {}", method, path))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Every documented path is routed, with exactly the documented methods. The router is
+    /// asked, as an admin to get past the guards, with a method no route accepts, and lists the
+    /// methods it serves in `Allow`.
+    #[tokio::test]
+    async fn test_documented_operations_match_the_router() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+        let app = create_router_with_config(pool, auth, &AppConfig::default());
+        let param = Regex::new(r"\{\w+\}").unwrap();
+
+        let documented = documented_operations();
+        assert!(documented.len() >= 60, "only found {:?}", documented);
+        let paths: BTreeSet<&str> = documented.iter().map(|operation| operation.split_once(' ').unwrap().1).collect();
+
+        let mut routed = BTreeSet::new();
+        for path in paths {
+            let request = Request::builder()
+                .method(Method::TRACE)
+                .uri(param.replace_all(path, "1").as_ref())
+                .header(header::AUTHORIZATION, format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED, "{} is not routed", path);
+
+            let allowed = response.headers()[header::ALLOW].to_str().unwrap();
+            for method in allowed.split(',').map(str::trim).filter(|method| *method != "HEAD") {
+                routed.insert(format!("{} {}", method, path));
+            }
+        }
+
+        let undocumented: Vec<_> = routed.difference(&documented).collect();
+        let unrouted: Vec<_> = documented.difference(&routed).collect();
+        assert!(
+            undocumented.is_empty() && unrouted.is_empty(),
+            "routes missing from the specification: {:?}; documented operations without a route: {:?}",
+            undocumented,
+            unrouted
+        );
+    }
+
+    #[test]
+    fn test_contact_form_paths_follow_the_feature() {
+        let mut config = AppConfig::default();
+        assert!(document(&config).paths.paths.contains_key("/api/contact"));
+
+        config.features.contact_form = false;
+        let document = document(&config);
+        assert!(!document.paths.paths.contains_key("/api/contact/form-token"));
+        assert!(document.paths.paths.contains_key("/api/contact/messages"));
+    }
+
+    #[test]
+    fn test_api_key_paths_follow_the_feature() {
+        let mut config = AppConfig::default();
+        assert!(document(&config).paths.paths.contains_key("/api/keys/{id}"));
+
+        config.features.api_keys = false;
+        let document = document(&config);
+        assert!(API_KEY_PATHS.iter().all(|path| !document.paths.paths.contains_key(*path)));
+        assert!(document.paths.paths.contains_key("/api/auth/login"));
+    }
+
+    /// Whether a request body deserializes and passes validation
+    type Check = fn(&Value) -> bool;
+
+    fn validates<T: DeserializeOwned + Validate>(value: &Value) -> bool {
+        serde_json::from_value::<T>(value.clone()).is_ok_and(|request| request.validate().is_ok())
+    }
+
+    /// Every constraint the specification documents on a request is the one its validator enforces
+    #[test]
+    fn test_documented_constraints_are_enforced() {
+        let cases: Vec<(&str, Value, Check)> = vec![
+            (
+                "CreateProject",
+                json!({
+                    "title": "Portfolio", "description": "This site", "technologies": ["Rust"],
+                    "category": "web", "github_url": "https://github.com/a/b",
+                }),
+                validates::<CreateProject>,
+            ),
+            ("UpdateProject", json!({ "title": "Portfolio" }), validates::<UpdateProject>),
+            ("CreateSkill", json!({ "name": "Rust", "category": "Backend", "level": 4 }), validates::<CreateSkill>),
+            ("UpdateSkill", json!({ "level": 3 }), validates::<UpdateSkill>),
+            ("UpdateProfile", json!({ "email": "me@example.com" }), validates::<UpdateProfile>),
+            (
+                "CreateContactMessage",
+                json!({ "name": "Ada", "email": "ada@example.com", "subject": "Hello", "message": "Nice portfolio" }),
+                validates::<CreateContactMessage>,
+            ),
+            ("CreateContactReply", json!({ "body": "Thanks!" }), validates::<CreateContactReply>),
+            ("UpdateContactMessage", json!({ "notes": "Call back" }), validates::<UpdateContactMessage>),
+            ("BulkStatusUpdate", json!({ "ids": [1], "status": "read" }), validates::<BulkStatusUpdate>),
+            ("SetProjectSkills", json!({ "skill_ids": [1] }), validates::<SetProjectSkills>),
+            ("SetProjectGallery", json!({ "items": [{ "media_id": 1 }] }), validates::<SetProjectGallery>),
+            ("LoginRequest", json!({ "username": "admin", "password": "secret" }), validates::<LoginRequest>),
+            ("CreateApiKey", json!({ "name": "CI", "scopes": ["projects:write"] }), validates::<CreateApiKey>),
+            (
+                "CreatePost",
+                json!({ "title": "Hello", "summary": "First post", "body": "# Hello", "tags": ["rust"] }),
+                validates::<CreatePost>,
+            ),
+            ("UpdatePost", json!({ "title": "Hello", "tags": ["rust"] }), validates::<UpdatePost>),
+            (
+                "CreateExperience",
+                json!({
+                    "company": "Acme", "role": "Engineer", "start_month": "2020-01",
+                    "highlights": ["Shipped"], "technologies": ["Rust"],
+                }),
+                validates::<CreateExperience>,
+            ),
+            (
+                "UpdateExperience",
+                json!({ "role": "Lead", "highlights": ["Shipped"], "technologies": ["Rust"] }),
+                validates::<UpdateExperience>,
+            ),
+            (
+                "CreateEducation",
+                json!({ "institution": "University", "degree": "BSc", "start_month": "2015-09" }),
+                validates::<CreateEducation>,
+            ),
+            ("UpdateEducation", json!({ "degree": "MSc" }), validates::<UpdateEducation>),
+            (
+                "CreateCertification",
+                json!({ "name": "CKA", "issuer": "CNCF", "issued_month": "2023-05" }),
+                validates::<CreateCertification>,
+            ),
+            ("UpdateCertification", json!({ "name": "CKAD" }), validates::<UpdateCertification>),
+        ];
+
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let mut checked = 0;
+        for (name, sample, is_valid) in cases {
+            assert!(is_valid(&sample), "{} sample is invalid", name);
+            let properties = spec["components"]["schemas"][name]["properties"].as_object().unwrap();
+
+            for (field, schema) in properties {
+                let with = |value: Value| {
+                    let mut request = sample.clone();
+                    request[field] = value;
+                    is_valid(&request)
+                };
+                let item = sample[field].get(0).cloned().unwrap_or(json!(1));
+                let items = |count: u64| Value::Array(vec![item.clone(); count as usize]);
+                let text = |count: u64| Value::String("a".repeat(count as usize));
+                let limit = |key: &str| schema[key].as_u64();
+
+                let mut expectations = Vec::new();
+                if let Some(max) = limit("maxLength") {
+                    expectations.extend([(text(max), true), (text(max + 1), false)]);
+                }
+                if let Some(min) = limit("minLength").filter(|min| *min > 0) {
+                    expectations.extend([(text(min), true), (text(min - 1), false)]);
+                }
+                if let Some(max) = limit("maxItems") {
+                    expectations.extend([(items(max), true), (items(max + 1), false)]);
+                }
+                if let Some(min) = limit("minItems").filter(|min| *min > 0) {
+                    expectations.extend([(items(min), true), (items(min - 1), false)]);
+                }
+                if let Some(max) = schema["maximum"].as_i64() {
+                    expectations.extend([(json!(max), true), (json!(max + 1), false)]);
+                }
+                if let Some(min) = schema["minimum"].as_i64() {
+                    expectations.extend([(json!(min), true), (json!(min - 1), false)]);
+                }
+                match schema["format"].as_str() {
+                    Some("uri") => expectations.extend([(json!("https://example.com/a"), true), (json!("not a url"), false)]),
+                    Some("email") => expectations.extend([(json!("a@example.com"), true), (json!("not an email"), false)]),
+                    _ => {}
+                }
+                if let Some(pattern) = schema["pattern"].as_str() {
+                    let pattern = Regex::new(pattern).unwrap();
+                    for value in ["2024-01", "2024-12", "2024-13", "2024-1", "24-01"] {
+                        expectations.push((json!(value), pattern.is_match(value)));
+                    }
+                }
+
+                for (value, expected) in expectations {
+                    assert_eq!(with(value.clone()), expected, "{}.{} = {}", name, field, value);
+                    checked += 1;
+                }
+            }
+        }
+        assert!(checked > 50, "only {} constraints checked", checked);
+    }
+
+    #[test]
+    fn test_shared_responses() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        assert_eq!(spec["openapi"], "3.1.0");
+
+        let create = &spec["paths"]["/api/projects"]["post"];
+        assert_eq!(create["summary"], "Create a new project");
+        assert_eq!(create["security"][1]["api_key"][0], "projects:write");
+        for status in ["400", "401", "403", "429", "500"] {
+            assert_eq!(
+                create["responses"][status]["content"]["application/json"]["schema"]["$ref"],
+                "#/components/schemas/ErrorResponse",
+                "{}",
+                status
+            );
+            assert_eq!(
+                create["responses"][status]["content"][PROBLEM_JSON]["schema"]["$ref"],
+                "#/components/schemas/Problem",
+                "{}",
+                status
+            );
+        }
+        assert!(spec["components"]["schemas"]["ErrorCode"]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("PROJECT_NOT_FOUND")));
+
+        // Public operations cannot fail authentication
+        assert!(spec["paths"]["/api/projects"]["get"]["responses"]["401"].is_null());
+        assert_eq!(spec["components"]["securitySchemes"]["api_key"]["name"], API_KEY_HEADER);
+    }
+}
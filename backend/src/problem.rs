@@ -0,0 +1,281 @@
+//! Error responses in the shape the client asked for: the JSON envelope by default, RFC 7807
+//! problem details when `Accept` prefers `application/problem+json`
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    auth::{authenticates_admin, AuthState},
+    error::{ErrorBody, ErrorCode, ErrorResponse, FieldError},
+    request_id::RequestId,
+};
+
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// Longest plain-text error body read back from an extractor rejection
+const MAX_REJECTION_BYTES: usize = 16 * 1024;
+
+/// RFC 7807 problem details
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Problem {
+    /// `urn:portfolio:error:` followed by the error code in kebab case
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// Reason phrase of the status
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    /// Path of the request
+    pub instance: String,
+    pub code: ErrorCode,
+    /// Underlying cause of database, serialization and internal errors, in dev mode or for admins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    /// Every failed constraint, for validation errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<FieldError>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Problem {
+    pub fn new(error: ErrorBody, instance: String) -> Self {
+        let title = StatusCode::from_u16(error.status)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+            .unwrap_or("Error");
+        Self {
+            problem_type: format!("urn:portfolio:error:{}", error.code.as_str().to_lowercase().replace('_', "-")),
+            title: title.to_string(),
+            status: error.status,
+            detail: error.message,
+            instance,
+            code: error.code,
+            details: error.details,
+            errors: error.fields,
+            request_id: error.request_id,
+        }
+    }
+}
+
+/// Whether `Accept` names `application/problem+json` and ranks it at least as high as `application/json`
+///
+/// Wildcards alone keep the default envelope.
+pub fn prefers_problem_json(headers: &HeaderMap) -> bool {
+    let mut problem: Option<f32> = None;
+    let mut json: f32 = 0.0;
+    for accept in headers.get_all(header::ACCEPT) {
+        let Ok(accept) = accept.to_str() else { continue };
+        for range in accept.split(',') {
+            let mut params = range.split(';');
+            let media = params.next().unwrap_or("").trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            match media.as_str() {
+                PROBLEM_JSON => problem = Some(problem.map_or(quality, |q| q.max(quality))),
+                "application/json" => json = json.max(quality),
+                _ => {}
+            }
+        }
+    }
+    problem.is_some_and(|q| q > 0.0 && q >= json)
+}
+
+/// Who may see the internal cause of errors
+#[derive(Debug, Clone)]
+pub struct ErrorExposure {
+    /// Everyone, as set by `server.dev_mode`
+    pub dev_mode: bool,
+    /// Admins, checked only when an error has details to show
+    pub auth: AuthState,
+}
+
+/// Render error responses as the client prefers, and give the JSON envelope to those that
+/// did not come from an `ApiError`: extractor rejections, unmatched routes and methods.
+///
+/// Every error carries the request ID; its details are logged, and shown only in dev mode or to admins.
+pub async fn negotiate_errors(State(exposure): State<ErrorExposure>, request: Request, next: Next) -> Response {
+    let problem = prefers_problem_json(request.headers());
+    let method = request.method().clone();
+    let instance = request.uri().path().to_string();
+    let request_id = request.extensions().get::<RequestId>().map(|id| id.as_str().to_string());
+    let headers = request.headers().clone();
+
+    let response = next.run(request).await;
+    let status = response.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let mut error = match parts.extensions.remove::<ErrorBody>() {
+        Some(error) => error,
+        None => {
+            let plain = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .is_none_or(|content_type| content_type.as_bytes().starts_with(b"text/plain"));
+            if !plain {
+                return Response::from_parts(parts, body);
+            }
+            let text = to_bytes(body, MAX_REJECTION_BYTES).await.unwrap_or_default();
+            let code = ErrorCode::for_status(status);
+            let message = match String::from_utf8_lossy(&text).trim() {
+                "" if code == ErrorCode::RouteNotFound => format!("No route for {} {}", method, instance),
+                "" => status.canonical_reason().unwrap_or("Error").to_string(),
+                message => message.to_string(),
+            };
+            ErrorBody { code, status: status.as_u16(), message, details: None, fields: None, request_id: None }
+        }
+    };
+
+    // Within the request span, so the log line carries the request ID
+    let cause = error.details.as_deref().unwrap_or(&error.message);
+    if status.is_server_error() {
+        error!("{} {} failed with {}: {}", method, instance, error.code.as_str(), cause);
+    } else if error.details.is_some() {
+        warn!("{} {} rejected with {}: {}", method, instance, error.code.as_str(), cause);
+    }
+    if error.details.is_some() && !exposure.dev_mode && !authenticates_admin(&exposure.auth, &headers).await {
+        error.details = None;
+    }
+    error.request_id = request_id;
+
+    let (content_type, body) = if problem {
+        (PROBLEM_JSON, serde_json::to_vec(&Problem::new(error, instance)))
+    } else {
+        ("application/json", serde_json::to_vec(&ErrorResponse { success: false, error }))
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    Response::from_parts(parts, Body::from(body.unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        extract::Path,
+        middleware,
+        response::IntoResponse,
+        routing::get,
+        Router,
+    };
+    use sqlx::SqlitePool;
+    use tower::ServiceExt;
+
+    use crate::{auth::AuthConfig, error::ApiError, request_id::request_id};
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_prefers_problem_json() {
+        assert!(prefers_problem_json(&accept("application/problem+json")));
+        assert!(prefers_problem_json(&accept("application/problem+json, application/json")));
+        assert!(prefers_problem_json(&accept("application/json;q=0.5, application/problem+json")));
+        assert!(!prefers_problem_json(&accept("application/json, application/problem+json;q=0.9")));
+        assert!(!prefers_problem_json(&accept("application/problem+json;q=0")));
+        assert!(!prefers_problem_json(&accept("*/*")));
+        assert!(!prefers_problem_json(&HeaderMap::new()));
+    }
+
+    async fn send(app: &Router, uri: &str, headers: &[(&str, &str)]) -> (StatusCode, HeaderMap, serde_json::Value) {
+        let mut request = axum::http::Request::builder().uri(uri);
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, usize::MAX).await.unwrap();
+        (parts.status, parts.headers, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn create_test_app(dev_mode: bool) -> (Router, String) {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+        let exposure = ErrorExposure { dev_mode, auth: AuthState::new(pool, auth) };
+
+        let app = Router::new()
+            .route("/limited", get(|| async { ApiError::TooManyRequests { retry_after_secs: 9 }.into_response() }))
+            .route("/broken", get(|| async { ApiError::Database(sqlx::Error::PoolTimedOut).into_response() }))
+            .route("/items/:id", get(|Path(id): Path<u32>| async move { id.to_string() }))
+            .layer(middleware::from_fn_with_state(exposure, negotiate_errors))
+            .layer(middleware::from_fn(request_id));
+        (app, token)
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_errors() {
+        let (app, _) = create_test_app(false).await;
+        let problem = [("accept", PROBLEM_JSON)];
+
+        let (status, headers, body) = send(&app, "/limited", &[("x-request-id", "abc-123")]).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+        assert_eq!(headers["x-request-id"], "abc-123");
+        assert_eq!(body["error"]["code"], "RATE_LIMITED");
+        assert_eq!(body["error"]["request_id"], "abc-123");
+
+        let (_, headers, body) = send(&app, "/limited", &problem).await;
+        assert_eq!(headers[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(headers[header::RETRY_AFTER], "9");
+        assert_eq!(body["type"], "urn:portfolio:error:rate-limited");
+        assert_eq!(body["title"], "Too Many Requests");
+        assert_eq!(body["status"], 429);
+        assert_eq!(body["instance"], "/limited");
+        assert_eq!(body["code"], "RATE_LIMITED");
+        assert_eq!(body["request_id"], headers["x-request-id"].to_str().unwrap());
+
+        // Rejections and unmatched routes get the envelope too
+        let (status, _, body) = send(&app, "/items/abc", &[]).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["code"], "BAD_REQUEST");
+        assert!(body["error"]["message"].as_str().unwrap().contains("Invalid URL"));
+
+        let (status, _, body) = send(&app, "/missing", &problem).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["code"], "ROUTE_NOT_FOUND");
+        assert_eq!(body["detail"], "No route for GET /missing");
+    }
+
+    #[tokio::test]
+    async fn test_details_shown_in_dev_mode_and_to_admins() {
+        let cause = sqlx::Error::PoolTimedOut.to_string();
+        let (app, token) = create_test_app(false).await;
+
+        let (status, _, body) = send(&app, "/broken", &[]).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"]["code"], "DATABASE_ERROR");
+        assert!(body["error"].get("details").is_none());
+
+        let (_, _, body) = send(&app, "/broken", &[("authorization", "Bearer forged.token")]).await;
+        assert!(body["error"].get("details").is_none());
+
+        let bearer = format!("Bearer {}", token);
+        let (_, _, body) = send(&app, "/broken", &[("authorization", &bearer), ("accept", PROBLEM_JSON)]).await;
+        assert_eq!(body["details"], cause);
+
+        let (app, _) = create_test_app(true).await;
+        let (_, _, body) = send(&app, "/broken", &[]).await;
+        assert_eq!(body["error"]["details"], cause);
+    }
+}
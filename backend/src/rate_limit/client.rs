@@ -0,0 +1,147 @@
+use std::net::IpAddr;
+
+use axum::http::{header, HeaderMap};
+use ipnet::IpNet;
+use sha2::{Digest, Sha256};
+
+/// Header listing the addresses a request went through, appended to by each proxy
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Works out who sent a request, believing `X-Forwarded-For` only from trusted proxies
+#[derive(Debug, Clone, Default)]
+pub struct ClientResolver {
+    trusted_proxies: Vec<IpNet>,
+    fingerprint: bool,
+}
+
+impl ClientResolver {
+    pub fn new(trusted_proxies: Vec<IpNet>, fingerprint: bool) -> Self {
+        Self {
+            trusted_proxies,
+            fingerprint,
+        }
+    }
+
+    /// Build the resolver from configured proxies; entries that do not parse, which
+    /// validation reports, are skipped
+    pub fn from_config(trusted_proxies: &[String], key: &str) -> Self {
+        let trusted_proxies = trusted_proxies
+            .iter()
+            .filter_map(|proxy| {
+                proxy
+                    .parse::<IpNet>()
+                    .ok()
+                    .or_else(|| proxy.parse::<IpAddr>().ok().map(IpNet::from))
+            })
+            .collect();
+
+        Self::new(trusted_proxies, key == "fingerprint")
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Address of the client. Behind trusted proxies this is the last address in
+    /// `X-Forwarded-For` that is not itself a trusted proxy; anything to its left could
+    /// have been written by the client.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.is_trusted(peer) {
+            return peer;
+        }
+
+        let hops: Vec<&str> = headers
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+
+        let mut client = peer;
+        for hop in hops.iter().rev() {
+            let Ok(ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+            client = ip;
+            if !self.is_trusted(ip) {
+                break;
+            }
+        }
+        client
+    }
+
+    /// Bucket key of the client: its address
+    pub fn key(&self, peer: IpAddr, headers: &HeaderMap) -> String {
+        self.client_ip(peer, headers).to_string()
+    }
+
+    /// With fingerprinting, the key of the browser's own bucket: the address and a hash of the
+    /// browser headers. Clients choose those headers, so this only ever tightens the address's limit.
+    pub fn fingerprint(&self, peer: IpAddr, headers: &HeaderMap) -> Option<String> {
+        if !self.fingerprint {
+            return None;
+        }
+
+        let mut hasher = Sha256::new();
+        for name in [header::USER_AGENT, header::ACCEPT_LANGUAGE] {
+            hasher.update(headers.get(&name).map(|value| value.as_bytes()).unwrap_or_default());
+            hasher.update(b"\n");
+        }
+        let digest = format!("{:x}", hasher.finalize());
+        Some(format!("{}#{}", self.key(peer, headers), &digest[..16]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    fn forwarded(value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(FORWARDED_FOR_HEADER, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn test_forwarded_for_only_from_trusted_proxies() {
+        let resolver = ClientResolver::from_config(&["10.0.0.0/8".to_string(), "192.0.2.1".to_string()], "ip");
+        let headers = forwarded("198.51.100.9, 203.0.113.7, 10.0.0.2");
+
+        // Untrusted peers cannot pick their address
+        assert_eq!(resolver.client_ip(ip("203.0.113.50"), &headers), ip("203.0.113.50"));
+
+        // The rightmost untrusted hop is the client; the spoofable entries before it are ignored
+        assert_eq!(resolver.client_ip(ip("10.0.0.1"), &headers), ip("203.0.113.7"));
+        assert_eq!(resolver.client_ip(ip("192.0.2.1"), &HeaderMap::new()), ip("192.0.2.1"));
+        assert_eq!(resolver.client_ip(ip("10.0.0.1"), &forwarded("10.0.0.3")), ip("10.0.0.3"));
+        assert_eq!(resolver.client_ip(ip("10.0.0.1"), &forwarded("garbage, 10.0.0.3")), ip("10.0.0.3"));
+    }
+
+    #[test]
+    fn test_fingerprint_key() {
+        let by_ip = ClientResolver::from_config(&[], "ip");
+        let by_fingerprint = ClientResolver::from_config(&[], "fingerprint");
+        let peer = ip("203.0.113.7");
+
+        let mut firefox = HeaderMap::new();
+        firefox.insert(header::USER_AGENT, HeaderValue::from_static("Firefox"));
+        let mut chrome = HeaderMap::new();
+        chrome.insert(header::USER_AGENT, HeaderValue::from_static("Chrome"));
+
+        assert_eq!(by_ip.key(peer, &firefox), "203.0.113.7");
+        assert_eq!(by_ip.fingerprint(peer, &firefox), None);
+
+        // The address stays the key; the fingerprint only names a browser's bucket within it
+        assert_eq!(by_fingerprint.key(peer, &firefox), by_fingerprint.key(peer, &chrome));
+        let fingerprint = by_fingerprint.fingerprint(peer, &firefox).unwrap();
+        assert!(fingerprint.starts_with("203.0.113.7#"));
+        assert_eq!(Some(&fingerprint), by_fingerprint.fingerprint(peer, &firefox).as_ref());
+        assert_ne!(Some(fingerprint), by_fingerprint.fingerprint(peer, &chrome));
+    }
+}
@@ -0,0 +1,471 @@
+//! Token-bucket rate limiting per client and route group
+pub mod client;
+pub mod store;
+
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use sqlx::SqlitePool;
+use tracing::{error, warn};
+
+use crate::{
+    config::{RateLimitConfig, RateLimitRule},
+    error::ApiError,
+};
+
+pub use client::ClientResolver;
+pub use store::{MemoryStore, RateLimitStore, SqliteStore};
+
+/// Stale buckets are swept after this many requests
+const PRUNE_EVERY: u64 = 1024;
+
+/// Routes sharing a limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteGroup {
+    /// `POST /api/contact`
+    Contact,
+    /// `POST /api/auth/login`
+    Login,
+    /// Any other `GET` or `HEAD`
+    Reads,
+    /// Everything else
+    Default,
+}
+
+impl RouteGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteGroup::Contact => "contact",
+            RouteGroup::Login => "login",
+            RouteGroup::Reads => "reads",
+            RouteGroup::Default => "default",
+        }
+    }
+
+    /// Group a request belongs to
+    pub fn classify(method: &Method, path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+
+        match (method, path) {
+            (&Method::POST, "/api/contact") => RouteGroup::Contact,
+            (&Method::POST, "/api/auth/login") => RouteGroup::Login,
+            (&Method::GET | &Method::HEAD, _) => RouteGroup::Reads,
+            _ => RouteGroup::Default,
+        }
+    }
+}
+
+/// Token bucket: `burst` requests at once, refilled at `requests_per_minute`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    pub requests_per_minute: u32,
+    pub burst: u32,
+}
+
+impl Rule {
+    pub fn new(requests_per_minute: u32, burst: u32) -> Self {
+        Self {
+            requests_per_minute: requests_per_minute.max(1),
+            burst: burst.max(1),
+        }
+    }
+
+    pub fn capacity(&self) -> f64 {
+        self.burst as f64
+    }
+
+    /// Tighter rule of a single browser's bucket within a client's: half its rate and burst
+    pub fn per_browser(&self) -> Self {
+        Self::new(self.requests_per_minute / 2, self.burst / 2)
+    }
+
+    pub fn tokens_per_ms(&self) -> f64 {
+        self.requests_per_minute as f64 / 60_000.0
+    }
+
+    /// Seconds for `tokens` tokens to be refilled
+    fn secs_to_refill(&self, tokens: f64) -> u64 {
+        (tokens.max(0.0) / self.tokens_per_ms() / 1000.0).ceil() as u64
+    }
+}
+
+impl From<RateLimitRule> for Rule {
+    fn from(rule: RateLimitRule) -> Self {
+        Self::new(rule.requests_per_minute, rule.burst)
+    }
+}
+
+/// Outcome of checking a request against its bucket
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub rule: Rule,
+    /// Whole requests left in the bucket
+    pub remaining: u32,
+    /// Seconds until the bucket is full again
+    pub reset_secs: u64,
+    /// Seconds until the next request will be allowed
+    pub retry_after_secs: u64,
+}
+
+impl RateLimitDecision {
+    fn new(rule: Rule, tokens: f64, allowed: bool) -> Self {
+        Self {
+            allowed,
+            rule,
+            remaining: tokens.max(0.0).floor() as u32,
+            reset_secs: rule.secs_to_refill(rule.capacity() - tokens),
+            retry_after_secs: if tokens >= 1.0 { 0 } else { rule.secs_to_refill(1.0 - tokens).max(1) },
+        }
+    }
+
+    /// Add the `RateLimit-*` headers describing the bucket
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        let window = self.rule.secs_to_refill(self.rule.capacity());
+        let values = [
+            ("ratelimit-limit", self.rule.burst.to_string()),
+            ("ratelimit-remaining", self.remaining.to_string()),
+            ("ratelimit-reset", self.reset_secs.to_string()),
+            ("ratelimit-policy", format!("{};w={}", self.rule.burst, window)),
+        ];
+
+        for (name, value) in values {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        }
+    }
+}
+
+/// Limits per route group, shared by every request
+#[derive(Clone)]
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    clients: ClientResolver,
+    default: Rule,
+    reads: Rule,
+    contact: Rule,
+    login: Rule,
+    checks: Arc<AtomicU64>,
+}
+
+impl RateLimiter {
+    /// A limiter applying `rule` to every group until other rules are set
+    pub fn new(store: impl RateLimitStore + 'static, clients: ClientResolver, rule: Rule) -> Self {
+        Self {
+            store: Arc::new(store),
+            clients,
+            default: rule,
+            reads: rule,
+            contact: rule,
+            login: rule,
+            checks: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Build the limiter as configured
+    pub fn from_config(pool: SqlitePool, config: &RateLimitConfig) -> Self {
+        let clients = ClientResolver::from_config(&config.trusted_proxies, &config.key);
+        let limiter = match config.store.as_str() {
+            "sqlite" => Self::new(SqliteStore::new(pool), clients, config.default_rule().into()),
+            _ => Self::new(MemoryStore::new(), clients, config.default_rule().into()),
+        };
+
+        limiter
+            .with_rule(RouteGroup::Reads, config.reads.into())
+            .with_rule(RouteGroup::Contact, config.contact.into())
+            .with_rule(RouteGroup::Login, config.login.into())
+    }
+
+    /// Set the limit of a route group
+    pub fn with_rule(mut self, group: RouteGroup, rule: Rule) -> Self {
+        *self.rule_mut(group) = rule;
+        self
+    }
+
+    fn rule_mut(&mut self, group: RouteGroup) -> &mut Rule {
+        match group {
+            RouteGroup::Contact => &mut self.contact,
+            RouteGroup::Login => &mut self.login,
+            RouteGroup::Reads => &mut self.reads,
+            RouteGroup::Default => &mut self.default,
+        }
+    }
+
+    pub fn rule(&self, group: RouteGroup) -> Rule {
+        match group {
+            RouteGroup::Contact => self.contact,
+            RouteGroup::Login => self.login,
+            RouteGroup::Reads => self.reads,
+            RouteGroup::Default => self.default,
+        }
+    }
+
+    /// Take a token from the client's bucket for the group
+    pub async fn check(&self, group: RouteGroup, client: &str) -> Result<RateLimitDecision, sqlx::Error> {
+        self.take(group, client, self.rule(group)).await
+    }
+
+    /// Take a token from the browser's tighter bucket, then from the client's. Browsers with new
+    /// headers get new buckets of their own, but still draw on the client's. When the client's
+    /// bucket refuses the request, the browser's token is given back.
+    pub async fn check_browser(
+        &self,
+        group: RouteGroup,
+        client: &str,
+        fingerprint: &str,
+    ) -> Result<RateLimitDecision, sqlx::Error> {
+        let browser_rule = self.rule(group).per_browser();
+        let browser = self.take(group, fingerprint, browser_rule).await?;
+        if !browser.allowed {
+            return Ok(browser);
+        }
+
+        let client = self.check(group, client).await?;
+        if !client.allowed {
+            self.store.give_back(&bucket_key(group, fingerprint), browser_rule).await?;
+            return Ok(client);
+        }
+        Ok(if browser.remaining < client.remaining { browser } else { client })
+    }
+
+    async fn take(&self, group: RouteGroup, client: &str, rule: Rule) -> Result<RateLimitDecision, sqlx::Error> {
+        let now_ms = Utc::now().timestamp_millis();
+        let (tokens, allowed) = self.store.take(&bucket_key(group, client), rule, now_ms).await?;
+
+        if self.checks.fetch_add(1, Ordering::Relaxed) % PRUNE_EVERY == PRUNE_EVERY - 1 {
+            self.prune(now_ms).await;
+        }
+
+        Ok(RateLimitDecision::new(rule, tokens, allowed))
+    }
+
+    /// Forget buckets idle long enough to have refilled completely
+    async fn prune(&self, now_ms: i64) {
+        let longest_refill_ms = [self.default, self.reads, self.contact, self.login]
+            .iter()
+            .map(|rule| rule.secs_to_refill(rule.capacity()) as i64 * 1000)
+            .max()
+            .unwrap_or_default();
+
+        if let Err(e) = self.store.prune(now_ms - longest_refill_ms).await {
+            error!("Failed to prune rate limit buckets: {}", e);
+        }
+    }
+}
+
+/// Key of a client's bucket for a route group
+fn bucket_key(group: RouteGroup, client: &str) -> String {
+    format!("{}:{}", group.as_str(), client)
+}
+
+/// Middleware limiting requests per client and route group, answering `429 Too Many Requests`
+/// with `Retry-After` once a bucket is empty. Requests are let through if the store fails.
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let group = RouteGroup::classify(request.method(), request.uri().path());
+    let peer = connect_info
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    let client = limiter.clients.key(peer, request.headers());
+    let checked = match limiter.clients.fingerprint(peer, request.headers()) {
+        Some(fingerprint) => limiter.check_browser(group, &client, &fingerprint).await,
+        None => limiter.check(group, &client).await,
+    };
+
+    let decision = match checked {
+        Ok(decision) => decision,
+        Err(e) => {
+            error!("Rate limit store failed, letting the request through: {}", e);
+            return next.run(request).await;
+        }
+    };
+
+    if !decision.allowed {
+        warn!("Rate limit of group '{}' exceeded by {}", group.as_str(), client);
+        let mut response = ApiError::TooManyRequests {
+            retry_after_secs: decision.retry_after_secs,
+        }
+        .into_response();
+        decision.apply(response.headers_mut());
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    decision.apply(response.headers_mut());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        extract::connect_info::MockConnectInfo,
+        http::{header, StatusCode},
+        middleware,
+        routing::{get, post},
+        Router,
+    };
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(RouteGroup::classify(&Method::POST, "/api/contact"), RouteGroup::Contact);
+        assert_eq!(RouteGroup::classify(&Method::POST, "/api/contact/"), RouteGroup::Contact);
+        assert_eq!(RouteGroup::classify(&Method::POST, "/api/auth/login"), RouteGroup::Login);
+        assert_eq!(RouteGroup::classify(&Method::GET, "/api/projects"), RouteGroup::Reads);
+        assert_eq!(RouteGroup::classify(&Method::POST, "/api/contact/cleanup"), RouteGroup::Default);
+        assert_eq!(RouteGroup::classify(&Method::DELETE, "/api/projects/1"), RouteGroup::Default);
+    }
+
+    #[test]
+    fn test_decision() {
+        let rule = Rule::new(6, 3);
+
+        let decision = RateLimitDecision::new(rule, 1.5, true);
+        assert_eq!(decision.remaining, 1);
+        assert_eq!(decision.reset_secs, 15);
+        assert_eq!(decision.retry_after_secs, 0);
+
+        let decision = RateLimitDecision::new(rule, 0.25, false);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after_secs, 8);
+    }
+
+    fn create_test_app(limiter: RateLimiter, peer: &str) -> Router {
+        Router::new()
+            .route("/api/contact", post(|| async { "sent" }))
+            .route("/api/projects", get(|| async { "projects" }))
+            .layer(middleware::from_fn_with_state(limiter, rate_limit))
+            .layer(MockConnectInfo(SocketAddr::new(peer.parse().unwrap(), 4000)))
+    }
+
+    async fn send(app: &Router, method: Method, uri: &str, forwarded_for: Option<&str>) -> Response {
+        send_as(app, method, uri, forwarded_for, None).await
+    }
+
+    async fn send_as(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        forwarded_for: Option<&str>,
+        user_agent: Option<&str>,
+    ) -> Response {
+        let mut request = axum::http::Request::builder().method(method).uri(uri);
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.header(client::FORWARDED_FOR_HEADER, forwarded_for);
+        }
+        if let Some(user_agent) = user_agent {
+            request = request.header(header::USER_AGENT, user_agent);
+        }
+        app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_groups_limited_separately() {
+        let limiter = RateLimiter::new(MemoryStore::new(), ClientResolver::default(), Rule::new(60, 5))
+            .with_rule(RouteGroup::Contact, Rule::new(1, 2));
+        let app = create_test_app(limiter, "203.0.113.7");
+
+        for remaining in ["1", "0"] {
+            let response = send(&app, Method::POST, "/api/contact", None).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["ratelimit-limit"], "2");
+            assert_eq!(response.headers()["ratelimit-remaining"], remaining);
+        }
+
+        let response = send(&app, Method::POST, "/api/contact", None).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "60");
+        assert_eq!(response.headers()["ratelimit-remaining"], "0");
+        assert_eq!(response.headers()["ratelimit-policy"], "2;w=120");
+
+        // Reads have their own bucket
+        let response = send(&app, Method::GET, "/api/projects", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["ratelimit-remaining"], "4");
+    }
+
+    #[tokio::test]
+    async fn test_clients_behind_trusted_proxy_limited_separately() {
+        let clients = ClientResolver::from_config(&["10.0.0.0/8".to_string()], "ip");
+        let limiter = RateLimiter::new(MemoryStore::new(), clients, Rule::new(1, 1));
+        let app = create_test_app(limiter, "10.0.0.1");
+
+        let response = send(&app, Method::GET, "/api/projects", Some("203.0.113.7")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = send(&app, Method::GET, "/api/projects", Some("203.0.113.7")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Another client through the same proxy still has its requests
+        let response = send(&app, Method::GET, "/api/projects", Some("203.0.113.7, 198.51.100.2")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Untrusted peers cannot escape their bucket by inventing addresses
+        let app = create_test_app(
+            RateLimiter::new(MemoryStore::new(), ClientResolver::default(), Rule::new(1, 1)),
+            "203.0.113.9",
+        );
+        send(&app, Method::GET, "/api/projects", Some("192.0.2.1")).await;
+        let response = send(&app, Method::GET, "/api/projects", Some("192.0.2.2")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+    #[tokio::test]
+    async fn test_fingerprint_tightens_the_address_limit() {
+        let clients = ClientResolver::from_config(&[], "fingerprint");
+        let limiter = RateLimiter::new(MemoryStore::new(), clients, Rule::new(1, 4));
+        let app = create_test_app(limiter, "203.0.113.7");
+
+        // A browser gets half of its address's requests
+        for remaining in ["1", "0"] {
+            let response = send_as(&app, Method::GET, "/api/projects", None, Some("Firefox")).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["ratelimit-remaining"], remaining);
+        }
+        let response = send_as(&app, Method::GET, "/api/projects", None, Some("Firefox")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Rotating the User-Agent gives a new browser bucket, but not a new address bucket
+        for user_agent in ["Chrome", "Safari"] {
+            let response = send_as(&app, Method::GET, "/api/projects", None, Some(user_agent)).await;
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        let response = send_as(&app, Method::GET, "/api/projects", None, Some("Edge")).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["ratelimit-limit"], "4");
+    }
+
+    #[tokio::test]
+    async fn test_refused_requests_keep_the_browser_token() {
+        let limiter = RateLimiter::new(MemoryStore::new(), ClientResolver::default(), Rule::new(2, 2));
+        for _ in 0..2 {
+            assert!(limiter.check(RouteGroup::Reads, "203.0.113.7").await.unwrap().allowed);
+        }
+
+        // The address is out of tokens, so the browser's is returned
+        for _ in 0..3 {
+            let decision = limiter.check_browser(RouteGroup::Reads, "203.0.113.7", "firefox").await.unwrap();
+            assert!(!decision.allowed);
+        }
+        let rule = limiter.rule(RouteGroup::Reads).per_browser();
+        let browser = limiter.take(RouteGroup::Reads, "firefox", rule).await.unwrap();
+        assert!(browser.allowed);
+    }
+}
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use axum::async_trait;
+use sqlx::SqlitePool;
+
+use crate::database::RateLimitRepository;
+
+use super::Rule;
+
+/// Where token buckets are kept
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Refill the bucket for `key` up to `now_ms` and take a token if one is available.
+    /// Returns the tokens left and whether a token was taken.
+    async fn take(&self, key: &str, rule: Rule, now_ms: i64) -> Result<(f64, bool), sqlx::Error>;
+
+    /// Return a token taken for a request that was refused afterwards, up to the bucket's capacity
+    async fn give_back(&self, key: &str, rule: Rule) -> Result<(), sqlx::Error>;
+
+    /// Forget buckets untouched since `before_ms`
+    async fn prune(&self, before_ms: i64) -> Result<u64, sqlx::Error>;
+}
+
+/// Buckets kept in memory: fast, but reset when the server restarts
+#[derive(Default)]
+pub struct MemoryStore {
+    buckets: Mutex<HashMap<String, (f64, i64)>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for MemoryStore {
+    async fn take(&self, key: &str, rule: Rule, now_ms: i64) -> Result<(f64, bool), sqlx::Error> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let capacity = rule.capacity();
+
+        let (tokens, updated_at) = buckets.entry(key.to_string()).or_insert((capacity, now_ms));
+        let elapsed = (now_ms - *updated_at).max(0) as f64;
+        let refilled = (*tokens + elapsed * rule.tokens_per_ms()).min(capacity);
+
+        let allowed = refilled >= 1.0;
+        *tokens = if allowed { refilled - 1.0 } else { refilled };
+        *updated_at = now_ms;
+
+        Ok((*tokens, allowed))
+    }
+
+    async fn give_back(&self, key: &str, rule: Rule) -> Result<(), sqlx::Error> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((tokens, _)) = buckets.get_mut(key) {
+            *tokens = (*tokens + 1.0).min(rule.capacity());
+        }
+        Ok(())
+    }
+
+    async fn prune(&self, before_ms: i64) -> Result<u64, sqlx::Error> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let before = buckets.len();
+        buckets.retain(|_, (_, updated_at)| *updated_at >= before_ms);
+        Ok((before - buckets.len()) as u64)
+    }
+}
+
+/// Buckets kept in the database, so limits survive restarts
+pub struct SqliteStore {
+    repository: RateLimitRepository,
+}
+
+impl SqliteStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: RateLimitRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for SqliteStore {
+    async fn take(&self, key: &str, rule: Rule, now_ms: i64) -> Result<(f64, bool), sqlx::Error> {
+        self.repository
+            .take(key, rule.capacity(), rule.tokens_per_ms(), now_ms)
+            .await
+    }
+
+    async fn give_back(&self, key: &str, rule: Rule) -> Result<(), sqlx::Error> {
+        self.repository.give_back(key, rule.capacity()).await
+    }
+
+    async fn prune(&self, before_ms: i64) -> Result<u64, sqlx::Error> {
+        self.repository.prune(before_ms).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_store_matches_sqlite_store() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let stores: [Box<dyn RateLimitStore>; 2] = [Box::new(MemoryStore::new()), Box::new(SqliteStore::new(pool))];
+        // Three requests at once, then one every 20 seconds
+        let rule = Rule::new(3, 3);
+
+        for store in stores {
+            let mut results = Vec::new();
+            for now_ms in [0, 0, 0, 0, 10_000, 20_000, 20_000] {
+                results.push(store.take("reads:a", rule, now_ms).await.unwrap().1);
+            }
+            assert_eq!(results, [true, true, true, false, false, true, false]);
+
+            // A token given back is taken again, but the bucket never holds more than its capacity
+            store.give_back("reads:a", rule).await.unwrap();
+            assert!(store.take("reads:a", rule, 20_000).await.unwrap().1);
+            for _ in 0..5 {
+                store.give_back("reads:a", rule).await.unwrap();
+            }
+            assert_eq!(store.take("reads:a", rule, 20_000).await.unwrap(), (2.0, true));
+
+            assert_eq!(store.prune(10_000).await.unwrap(), 0);
+            assert_eq!(store.prune(30_000).await.unwrap(), 1);
+        }
+    }
+}
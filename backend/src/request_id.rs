@@ -0,0 +1,94 @@
+//! `X-Request-Id`: taken from the client or a proxy when usable, generated otherwise, recorded
+//! on the request's tracing span and returned on the response
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{info_span, Instrument};
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest request ID accepted from a client
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// ID of the current request, in the request extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// The ID sent by the client if it is short and made of safe characters, a new UUID otherwise
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let sent = headers
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_REQUEST_ID_LEN
+                    && id.bytes().all(|b| b.is_ascii_alphanumeric() || b"-_.:".contains(&b))
+            });
+        match sent {
+            Some(id) => Self(id.to_string()),
+            None => Self(Uuid::new_v4().to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Middleware giving every request an ID and running it inside a span that carries it
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = RequestId::from_headers(request.headers());
+    let span = info_span!(
+        "request",
+        request_id = %id.as_str(),
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    request.extensions_mut().insert(id.clone());
+
+    let mut response = next.run(request).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(id.as_str()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, middleware, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_request_id() {
+        let app = Router::new()
+            .route("/", get(|Extension(id): Extension<RequestId>| async move { id.0 }))
+            .layer(middleware::from_fn(request_id));
+        let send = |id: Option<&str>| {
+            let mut request = axum::http::Request::builder().uri("/");
+            if let Some(id) = id {
+                request = request.header(REQUEST_ID_HEADER, id);
+            }
+            app.clone().oneshot(request.body(Body::empty()).unwrap())
+        };
+
+        let response = send(Some("edge-42:a.b_c")).await.unwrap();
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "edge-42:a.b_c");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "edge-42:a.b_c");
+
+        // Unsafe or missing IDs are replaced
+        for sent in [Some("<script>"), Some(&"x".repeat(200)[..]), None] {
+            let response = send(sent).await.unwrap();
+            let id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+            assert!(Uuid::parse_str(id).is_ok(), "{:?} gave {}", sent, id);
+        }
+    }
+}
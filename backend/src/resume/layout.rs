@@ -0,0 +1,368 @@
+//! Page layout: a cursor moving down the page that wraps text and starts new
+//! pages when the current one is full.
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str, TextStr};
+
+use super::fonts::{encode, StandardFont};
+
+/// Paper size of the rendered document
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageSize {
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PageSize::A4 => "a4",
+            PageSize::Letter => "letter",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "a4" => Some(PageSize::A4),
+            "letter" => Some(PageSize::Letter),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["a4", "letter"]
+    }
+
+    /// Width and height in points
+    pub fn dimensions(&self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (595.28, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+/// RGB color with components between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color(pub f32, pub f32, pub f32);
+
+impl Color {
+    pub const BLACK: Color = Color(0.0, 0.0, 0.0);
+    pub const WHITE: Color = Color(1.0, 1.0, 1.0);
+}
+
+/// Font, size and color of a run of text
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextStyle {
+    pub font: StandardFont,
+    pub size: f32,
+    pub color: Color,
+}
+
+impl TextStyle {
+    pub fn new(font: StandardFont, size: f32) -> Self {
+        Self { font, size, color: Color::BLACK }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.size * 1.35
+    }
+
+    pub fn width(&self, text: &str) -> f32 {
+        self.font.text_width(text, self.size)
+    }
+}
+
+/// Horizontal alignment of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Document being laid out, one page after the other
+pub struct Document {
+    width: f32,
+    height: f32,
+    margin: f32,
+    pages: Vec<Vec<u8>>,
+    content: Content,
+    /// Top of the free space on the current page
+    y: f32,
+}
+
+impl Document {
+    pub fn new(size: PageSize, margin: f32) -> Self {
+        let (width, height) = size.dimensions();
+        Self {
+            width,
+            height,
+            margin,
+            pages: Vec::new(),
+            content: Content::new(),
+            y: height - margin,
+        }
+    }
+
+    pub fn page_width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn page_height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn margin(&self) -> f32 {
+        self.margin
+    }
+
+    /// Width between the left and right margins
+    pub fn content_width(&self) -> f32 {
+        self.width - 2.0 * self.margin
+    }
+
+    /// Top of the free space on the current page
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+
+    /// Move the cursor to a position on the current page
+    pub fn set_y(&mut self, y: f32) {
+        self.y = y;
+    }
+
+    /// Number of pages laid out so far, the current one included
+    pub fn page_count(&self) -> usize {
+        self.pages.len() + 1
+    }
+
+    pub fn new_page(&mut self) {
+        let content = std::mem::replace(&mut self.content, Content::new());
+        self.pages.push(content.finish());
+        self.y = self.height - self.margin;
+    }
+
+    /// Start a new page unless `height` fits above the bottom margin
+    pub fn ensure_space(&mut self, height: f32) {
+        if self.y - height < self.margin && !self.at_page_top() {
+            self.new_page();
+        }
+    }
+
+    /// Vertical gap, dropped at the top of a page
+    pub fn space(&mut self, height: f32) {
+        if !self.at_page_top() {
+            self.y -= height;
+        }
+    }
+
+    fn at_page_top(&self) -> bool {
+        self.y >= self.height - self.margin
+    }
+
+    /// A single line of text, aligned between the margins
+    pub fn line(&mut self, text: &str, style: TextStyle, align: Align) {
+        self.ensure_space(style.line_height());
+        let x = match align {
+            Align::Left => self.margin,
+            Align::Center => (self.width - style.width(text)) / 2.0,
+            Align::Right => self.width - self.margin - style.width(text),
+        };
+        let baseline = self.y - style.size;
+        self.text_at(x, baseline, text, style);
+        self.y -= style.line_height();
+    }
+
+    /// Text on the left wrapped beside text aligned on the right margin, e.g. a title and its dates
+    pub fn line_pair(&mut self, left: &str, left_style: TextStyle, right: &str, right_style: TextStyle) {
+        let right_width = right_style.width(right);
+        let lines = wrap(left, left_style, self.content_width() - right_width - 12.0);
+        self.ensure_space(left_style.line_height().max(right_style.line_height()));
+
+        let baseline = self.y - left_style.size;
+        self.text_at(self.width - self.margin - right_width, baseline, right, right_style);
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                self.ensure_space(left_style.line_height());
+            }
+            let baseline = self.y - left_style.size;
+            self.text_at(self.margin, baseline, line, left_style);
+            self.y -= left_style.line_height();
+        }
+    }
+
+    /// Text wrapped between the margins, `indent` points from the left one
+    pub fn paragraph(&mut self, text: &str, style: TextStyle, indent: f32) {
+        for line in wrap(text, style, self.content_width() - indent) {
+            self.ensure_space(style.line_height());
+            let baseline = self.y - style.size;
+            self.text_at(self.margin + indent, baseline, &line, style);
+            self.y -= style.line_height();
+        }
+    }
+
+    /// Bulleted paragraph with a hanging indent
+    pub fn bullet(&mut self, text: &str, style: TextStyle, indent: f32) {
+        let hang = style.width("•  ");
+        for (i, line) in wrap(text, style, self.content_width() - indent - hang).into_iter().enumerate() {
+            self.ensure_space(style.line_height());
+            let baseline = self.y - style.size;
+            if i == 0 {
+                self.text_at(self.margin + indent, baseline, "•", style);
+            }
+            self.text_at(self.margin + indent + hang, baseline, &line, style);
+            self.y -= style.line_height();
+        }
+    }
+
+    /// Horizontal rule between the margins
+    pub fn rule(&mut self, color: Color, thickness: f32) {
+        self.ensure_space(thickness);
+        self.content
+            .set_stroke_rgb(color.0, color.1, color.2)
+            .set_line_width(thickness)
+            .move_to(self.margin, self.y)
+            .line_to(self.width - self.margin, self.y)
+            .stroke();
+        self.y -= thickness;
+    }
+
+    /// Filled rectangle, in page coordinates from the bottom left corner
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        self.content
+            .set_fill_rgb(color.0, color.1, color.2)
+            .rect(x, y, width, height)
+            .fill_nonzero();
+    }
+
+    /// Text at a position on the page, without moving the cursor
+    pub fn text_at(&mut self, x: f32, baseline: f32, text: &str, style: TextStyle) {
+        let TextStyle { font, size, color } = style;
+        self.content
+            .begin_text()
+            .set_font(Name(font.resource_name().as_bytes()), size)
+            .set_fill_rgb(color.0, color.1, color.2)
+            .next_line(x, baseline)
+            .show(Str(&encode(text)))
+            .end_text();
+    }
+
+    /// Write out the PDF file
+    pub fn finish(mut self, title: &str, author: &str, lang: &str) -> Vec<u8> {
+        self.new_page();
+
+        let catalog_id = Ref::new(1);
+        let tree_id = Ref::new(2);
+        let info_id = Ref::new(3);
+        let fonts: Vec<(StandardFont, Ref)> = StandardFont::all()
+            .into_iter()
+            .zip(4..)
+            .map(|(font, id)| (font, Ref::new(id)))
+            .collect();
+        let first_page = 4 + fonts.len() as i32;
+        let page_ids: Vec<Ref> = (0..self.pages.len() as i32).map(|i| Ref::new(first_page + 2 * i)).collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(tree_id).lang(TextStr(lang));
+        pdf.pages(tree_id).kids(page_ids.iter().copied()).count(page_ids.len() as i32);
+
+        for (page_id, content) in page_ids.iter().zip(&self.pages) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut page = pdf.page(*page_id);
+            page.parent(tree_id)
+                .media_box(Rect::new(0.0, 0.0, self.width, self.height))
+                .contents(content_id);
+            let mut resources = page.resources();
+            let mut font_resources = resources.fonts();
+            for (font, id) in &fonts {
+                font_resources.pair(Name(font.resource_name().as_bytes()), *id);
+            }
+            font_resources.finish();
+            resources.finish();
+            page.finish();
+            pdf.stream(content_id, content);
+        }
+
+        for (font, id) in &fonts {
+            pdf.type1_font(*id)
+                .base_font(Name(font.base_font().as_bytes()))
+                .encoding_predefined(Name(b"WinAnsiEncoding"));
+        }
+
+        pdf.document_info(info_id)
+            .title(TextStr(title))
+            .author(TextStr(author))
+            .producer(TextStr("portfolio-backend"));
+
+        pdf.finish()
+    }
+}
+
+/// Break text into lines no wider than `width`, splitting words that do not fit on a line of their own
+pub fn wrap(text: &str, style: TextStyle, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+        if style.width(&candidate) <= width {
+            line = candidate;
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for ch in word.chars() {
+            line.push(ch);
+            if style.width(&line) > width && line.chars().count() > 1 {
+                line.pop();
+                lines.push(std::mem::replace(&mut line, ch.to_string()));
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        let style = TextStyle::new(StandardFont::Helvetica, 10.0);
+        let lines = wrap("The quick brown fox jumps over the lazy dog", style, 100.0);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|line| style.width(line) <= 100.0));
+        assert_eq!(lines.join(" "), "The quick brown fox jumps over the lazy dog");
+
+        let lines = wrap("Supercalifragilisticexpialidocious", style, 50.0);
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat(), "Supercalifragilisticexpialidocious");
+
+        assert!(wrap("  ", style, 100.0).is_empty());
+    }
+
+    #[test]
+    fn test_pages() {
+        let style = TextStyle::new(StandardFont::TimesRoman, 11.0);
+        let mut document = Document::new(PageSize::Letter, 50.0);
+        document.space(20.0);
+        assert_eq!(document.y(), 742.0);
+
+        for i in 0..100 {
+            document.line(&format!("Line {}", i), style, Align::Left);
+        }
+        assert_eq!(document.page_count(), 3);
+
+        let pdf = document.finish("Title", "Author", "en");
+        assert!(pdf.starts_with(b"%PDF-1.7"));
+    }
+}
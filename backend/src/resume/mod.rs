@@ -0,0 +1,7 @@
+//! Résumé rendering to PDF, with the standard fonts and no browser involved
+pub mod fonts;
+pub mod layout;
+pub mod template;
+
+pub use layout::PageSize;
+pub use template::{render, ResumeData, ResumeLanguage, ResumeTemplate};
@@ -0,0 +1,388 @@
+//! Résumé templates: what goes on the page and how each template styles it
+
+use serde::Serialize;
+
+use super::fonts::StandardFont;
+use super::layout::{Align, Color, Document, PageSize, TextStyle};
+use crate::models::{skill::SkillCategory, Profile, Project, Skill};
+
+/// Built-in résumé template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeTemplate {
+    /// Serif, centered header, sections separated by rules
+    Classic,
+    /// Sans-serif, colored header band and headings
+    Modern,
+}
+
+impl ResumeTemplate {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResumeTemplate::Classic => "classic",
+            ResumeTemplate::Modern => "modern",
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "classic" => Some(ResumeTemplate::Classic),
+            "modern" => Some(ResumeTemplate::Modern),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["classic", "modern"]
+    }
+
+    fn theme(&self) -> Theme {
+        match self {
+            ResumeTemplate::Classic => Theme {
+                regular: StandardFont::TimesRoman,
+                bold: StandardFont::TimesBold,
+                italic: StandardFont::TimesItalic,
+                accent: Color(0.2, 0.2, 0.2),
+                muted: Color(0.35, 0.35, 0.35),
+            },
+            ResumeTemplate::Modern => Theme {
+                regular: StandardFont::Helvetica,
+                bold: StandardFont::HelveticaBold,
+                italic: StandardFont::HelveticaOblique,
+                accent: Color(0.11, 0.36, 0.62),
+                muted: Color(0.4, 0.4, 0.4),
+            },
+        }
+    }
+}
+
+/// Language of the résumé headings and labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeLanguage {
+    En,
+    Fr,
+}
+
+impl ResumeLanguage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResumeLanguage::En => "en",
+            ResumeLanguage::Fr => "fr",
+        }
+    }
+
+    /// Parse a language tag, ignoring its region (`fr-CA` is French)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.split(['-', '_']).next().unwrap_or_default().to_lowercase().as_str() {
+            "en" => Some(ResumeLanguage::En),
+            "fr" => Some(ResumeLanguage::Fr),
+            _ => None,
+        }
+    }
+
+    pub fn all() -> Vec<&'static str> {
+        vec!["en", "fr"]
+    }
+
+    fn labels(&self) -> &'static Labels {
+        match self {
+            ResumeLanguage::En => &EN,
+            ResumeLanguage::Fr => &FR,
+        }
+    }
+}
+
+struct Labels {
+    resume: &'static str,
+    summary: &'static str,
+    projects: &'static str,
+    skills: &'static str,
+    /// Prefix of a project's technology list
+    technologies: &'static str,
+    /// Skill levels 1 to 5
+    levels: [&'static str; 5],
+    /// Headings for `SkillCategory::all()`, in the same order
+    categories: [&'static str; 7],
+}
+
+const EN: Labels = Labels {
+    resume: "Resume",
+    summary: "Summary",
+    projects: "Featured projects",
+    skills: "Skills",
+    technologies: "Technologies: ",
+    levels: ["Beginner", "Novice", "Intermediate", "Advanced", "Expert"],
+    categories: ["Frontend", "Backend", "Databases", "DevOps", "Tools", "Mobile", "Other"],
+};
+
+const FR: Labels = Labels {
+    resume: "CV",
+    summary: "Profil",
+    projects: "Projets phares",
+    skills: "Compétences",
+    technologies: "Technologies : ",
+    levels: ["Débutant", "Novice", "Intermédiaire", "Avancé", "Expert"],
+    categories: ["Frontend", "Backend", "Bases de données", "DevOps", "Outils", "Mobile", "Autres"],
+};
+
+impl Labels {
+    fn level(&self, level: i32) -> Option<&'static str> {
+        usize::try_from(level - 1).ok().and_then(|i| self.levels.get(i).copied())
+    }
+
+    fn category<'a>(&self, category: &'a str) -> &'a str {
+        SkillCategory::all()
+            .iter()
+            .position(|name| *name == category)
+            .map_or(category, |i| self.categories[i])
+    }
+}
+
+struct Theme {
+    regular: StandardFont,
+    bold: StandardFont,
+    italic: StandardFont,
+    accent: Color,
+    muted: Color,
+}
+
+/// Content of the résumé
+#[derive(Debug, Clone, Serialize)]
+pub struct ResumeData {
+    pub profile: Profile,
+    /// Featured projects, in display order
+    pub projects: Vec<Project>,
+    pub skills: Vec<Skill>,
+}
+
+const MARGIN: f32 = 50.0;
+
+/// Render the résumé as a PDF file
+pub fn render(data: &ResumeData, template: ResumeTemplate, size: PageSize, lang: ResumeLanguage) -> Vec<u8> {
+    let theme = template.theme();
+    let labels = lang.labels();
+    let profile = &data.profile;
+    let mut document = Document::new(size, MARGIN);
+
+    let regular = TextStyle::new(theme.regular, 10.0);
+    let muted = TextStyle::new(theme.regular, 9.5).color(theme.muted);
+    let contact = [Some(profile.email.as_str()), profile.phone.as_deref(), Some(profile.location.as_str())]
+        .into_iter()
+        .flatten()
+        .filter(|value| !value.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("  •  ");
+    let links = [&profile.linkedin_url, &profile.github_url, &profile.twitter_url]
+        .into_iter()
+        .flatten()
+        .map(|url| url.trim_start_matches("https://").trim_start_matches("http://"))
+        .collect::<Vec<_>>()
+        .join("  •  ");
+
+    match template {
+        ResumeTemplate::Classic => {
+            document.line(&profile.name, TextStyle::new(theme.bold, 22.0), Align::Center);
+            document.line(&profile.title, TextStyle::new(theme.italic, 12.0), Align::Center);
+            document.space(4.0);
+            document.line(&contact, muted, Align::Center);
+            if !links.is_empty() {
+                document.line(&links, muted, Align::Center);
+            }
+        }
+        ResumeTemplate::Modern => {
+            let band = 82.0;
+            let top = document.page_height();
+            document.fill_rect(0.0, top - band, document.page_width(), band, theme.accent);
+            let name = TextStyle::new(theme.bold, 24.0).color(Color::WHITE);
+            let title = TextStyle::new(theme.regular, 12.0).color(Color::WHITE);
+            document.text_at(MARGIN, top - 42.0, &profile.name, name);
+            document.text_at(MARGIN, top - 62.0, &profile.title, title);
+            document.set_y(top - band - 14.0);
+            document.line(&contact, muted, Align::Left);
+            if !links.is_empty() {
+                document.line(&links, muted, Align::Left);
+            }
+        }
+    }
+
+    if !profile.bio.trim().is_empty() {
+        heading(&mut document, template, &theme, labels.summary);
+        document.paragraph(&profile.bio, regular, 0.0);
+    }
+
+    if !data.projects.is_empty() {
+        heading(&mut document, template, &theme, labels.projects);
+        for (i, project) in data.projects.iter().enumerate() {
+            if i > 0 {
+                document.space(6.0);
+            }
+            // Keep the project title with at least the first line of its description
+            document.ensure_space(30.0);
+            document.line_pair(
+                &project.title,
+                TextStyle::new(theme.bold, 10.5),
+                &project.category,
+                TextStyle::new(theme.italic, 9.5).color(theme.muted),
+            );
+            document.paragraph(&project.description, regular, 0.0);
+            if !project.technologies.is_empty() {
+                let technologies = format!("{}{}", labels.technologies, project.technologies.join(", "));
+                document.paragraph(&technologies, TextStyle::new(theme.italic, 9.5).color(theme.muted), 0.0);
+            }
+        }
+    }
+
+    let groups = group_skills(&data.skills);
+    if !groups.is_empty() {
+        heading(&mut document, template, &theme, labels.skills);
+        for (category, skills) in groups {
+            let list = skills
+                .iter()
+                .map(|skill| match labels.level(skill.level) {
+                    Some(level) => format!("{} ({})", skill.name, level),
+                    None => skill.name.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            document.ensure_space(26.0);
+            document.line(labels.category(category), TextStyle::new(theme.bold, 10.0), Align::Left);
+            document.paragraph(&list, regular, 10.0);
+            document.space(3.0);
+        }
+    }
+
+    let title = format!("{} — {}", profile.name, labels.resume);
+    document.finish(&title, &profile.name, lang.as_str())
+}
+
+fn heading(document: &mut Document, template: ResumeTemplate, theme: &Theme, text: &str) {
+    document.space(14.0);
+    // Never leave a heading alone at the bottom of a page
+    document.ensure_space(40.0);
+    match template {
+        ResumeTemplate::Classic => {
+            document.line(&text.to_uppercase(), TextStyle::new(theme.bold, 11.5), Align::Left);
+            document.space(1.0);
+            document.rule(theme.muted, 0.6);
+        }
+        ResumeTemplate::Modern => {
+            document.line(text, TextStyle::new(theme.bold, 13.0).color(theme.accent), Align::Left);
+            document.space(1.0);
+            document.rule(theme.accent, 1.2);
+        }
+    }
+    document.space(5.0);
+}
+
+/// Skills grouped by category in `SkillCategory` order (unknown categories last), strongest first
+fn group_skills(skills: &[Skill]) -> Vec<(&str, Vec<&Skill>)> {
+    let mut groups: Vec<(&str, Vec<&Skill>)> = Vec::new();
+    for skill in skills {
+        match groups.iter_mut().find(|(category, _)| *category == skill.category) {
+            Some((_, group)) => group.push(skill),
+            None => groups.push((&skill.category, vec![skill])),
+        }
+    }
+
+    let order = SkillCategory::all();
+    groups.sort_by_key(|(category, _)| order.iter().position(|name| name == category).unwrap_or(order.len()));
+    for (_, group) in &mut groups {
+        group.sort_by(|a, b| b.level.cmp(&a.level).then_with(|| a.name.cmp(&b.name)));
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use sqlx::types::Json;
+
+    fn skill(name: &str, category: &str, level: i32) -> Skill {
+        Skill {
+            id: 0,
+            name: name.to_string(),
+            category: category.to_string(),
+            level,
+            years_experience: None,
+            description: None,
+            created_at: Utc::now(),
+            project_count: 0,
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn test_group_skills() {
+        let skills = vec![
+            skill("Docker", "DevOps", 3),
+            skill("Rust", "Backend", 4),
+            skill("Figma", "Design", 2),
+            skill("Go", "Backend", 5),
+            skill("React", "Frontend", 4),
+        ];
+        let groups: Vec<(&str, Vec<&str>)> = group_skills(&skills)
+            .into_iter()
+            .map(|(category, skills)| (category, skills.iter().map(|skill| skill.name.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                ("Frontend", vec!["React"]),
+                ("Backend", vec!["Go", "Rust"]),
+                ("DevOps", vec!["Docker"]),
+                ("Design", vec!["Figma"]),
+            ]
+        );
+
+        assert_eq!(FR.category("Database"), "Bases de données");
+        assert_eq!(FR.category("Design"), "Design");
+        assert_eq!(EN.level(5), Some("Expert"));
+        assert_eq!(EN.level(0), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let data = ResumeData {
+            profile: Profile {
+                id: 1,
+                name: "Jane Doe".to_string(),
+                title: "Développeuse Rust".to_string(),
+                bio: "Builds reliable backends. ".repeat(40),
+                email: "jane@example.com".to_string(),
+                phone: None,
+                location: "Lyon".to_string(),
+                linkedin_url: None,
+                github_url: Some("https://github.com/jane".to_string()),
+                twitter_url: None,
+                updated_at: Utc::now(),
+            },
+            projects: vec![Project {
+                id: 1,
+                title: "Portfolio".to_string(),
+                description: "A portfolio backend".to_string(),
+                long_description: None,
+                technologies: Json(vec!["Rust".to_string(), "SQLite".to_string()]),
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "Web".to_string(),
+                featured: true,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }],
+            skills: vec![skill("Rust", "Backend", 5)],
+        };
+
+        for template in [ResumeTemplate::Classic, ResumeTemplate::Modern] {
+            for size in [PageSize::A4, PageSize::Letter] {
+                let pdf = render(&data, template, size, ResumeLanguage::Fr);
+                assert!(pdf.starts_with(b"%PDF-"));
+                assert_eq!(pdf, render(&data, template, size, ResumeLanguage::Fr));
+            }
+        }
+        assert_eq!(ResumeLanguage::from_str("fr-CA"), Some(ResumeLanguage::Fr));
+        assert_eq!(ResumeTemplate::from_str("fancy"), None);
+    }
+}
@@ -0,0 +1,179 @@
+use axum::{
+    extract::{Path, State},
+    middleware,
+    response::Json,
+    routing::{delete, get},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use crate::{
+    auth::{require_admin, AuthConfig, AuthState, AuthenticatedAdmin},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiKeyResponse, CreateApiKey, CreatedApiKey},
+    services::ApiKeyService,
+};
+
+/// Create API key management routes (admin sessions only, keys cannot manage keys)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    Router::new()
+        .route("/", get(get_api_keys).post(create_api_key))
+        .route("/:id", delete(revoke_api_key))
+        .route_layer(middleware::from_fn_with_state(
+            AuthState::new(pool.clone(), auth),
+            require_admin,
+        ))
+        .with_state(pool)
+}
+
+/// GET /api/keys - List all API keys
+#[utoipa::path(
+    get,
+    path = "/api/keys",
+    tag = "keys",
+    responses(
+        (status = 200, description = "Every key, revoked and expired ones included", body = ApiResponse<Vec<ApiKeyResponse>>),
+    ),
+    security(("bearer" = [])),
+)]
+async fn get_api_keys(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ApiResponse<Vec<ApiKeyResponse>>>, ApiError> {
+    let service = ApiKeyService::new(pool);
+    let keys = service.get_all_keys().await?;
+    let key_responses: Vec<ApiKeyResponse> = keys.into_iter().map(ApiKeyResponse::from).collect();
+    Ok(Json(ApiResponse::success(key_responses)))
+}
+
+/// POST /api/keys - Create a new API key (the plaintext key is only returned here)
+#[utoipa::path(
+    post,
+    path = "/api/keys",
+    tag = "keys",
+    request_body = CreateApiKey,
+    responses(
+        (status = 200, description = "The key, in plain text for the only time", body = ApiResponse<CreatedApiKey>),
+        (status = 400, description = "Invalid name, scope or expiry", body = ErrorResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn create_api_key(
+    State(pool): State<SqlitePool>,
+    admin: AuthenticatedAdmin,
+    Json(key_data): Json<CreateApiKey>,
+) -> Result<Json<ApiResponse<CreatedApiKey>>, ApiError> {
+    let service = ApiKeyService::new(pool);
+    let created = service.create_key(key_data, Some(admin.id)).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        created,
+        "API key created successfully. Store it now, it will not be shown again".to_string(),
+    )))
+}
+
+/// DELETE /api/keys/:id - Revoke an API key
+#[utoipa::path(
+    delete,
+    path = "/api/keys/{id}",
+    tag = "keys",
+    params(("id" = i32, Path, description = "API key ID")),
+    responses(
+        (status = 200, description = "The key was revoked", body = ApiResponse<Value>),
+        (status = 404, description = "No such key", body = ErrorResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn revoke_api_key(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let service = ApiKeyService::new(pool);
+    service.revoke_key(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        json!({}),
+        "API key revoked successfully".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    async fn create_test_app() -> (Router, SqlitePool, String) {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+
+        let app = create_routes(pool.clone(), auth);
+        (app, pool, token)
+    }
+
+    #[tokio::test]
+    async fn test_create_list_and_revoke_key() {
+        let (app, _pool, token) = create_test_app().await;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"name":"CI","scopes":["projects:write"]}"#))
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<CreatedApiKey> = serde_json::from_slice(&body).unwrap();
+        let created = response_json.data.unwrap();
+        assert!(created.key.starts_with("pfk_"));
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let listed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(listed["data"].as_array().unwrap().len(), 1);
+        assert!(!body.windows(created.key.len()).any(|w| w == created.key.as_bytes()));
+
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/{}", created.api_key.id))
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_cannot_manage_keys() {
+        let (app, pool, _token) = create_test_app().await;
+        let key = crate::auth::test_api_key(&pool, &["projects:write"]).await;
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .header("x-api-key", key)
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+}
@@ -0,0 +1,178 @@
+use axum::{
+    extract::State,
+    middleware,
+    response::Json,
+    routing::{get, post},
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::{
+    auth::{require_admin, AuthConfig, AuthState, AuthenticatedAdmin},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{AdminUser, LoginRequest, LoginResponse},
+    services::AuthService,
+};
+
+/// Create authentication routes
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let state = AuthState::new(pool, auth);
+
+    Router::new()
+        .route("/login", post(login))
+        .route(
+            "/me",
+            get(get_current_admin)
+                .route_layer(middleware::from_fn_with_state(state.clone(), require_admin)),
+        )
+        .with_state(state)
+}
+
+/// POST /api/auth/login - Exchange admin credentials for a session token
+#[utoipa::path(
+    post,
+    path = "/api/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "A session token and the admin it belongs to", body = ApiResponse<LoginResponse>),
+        (status = 400, description = "Missing username or password", body = ErrorResponse),
+        (status = 401, description = "Wrong username or password", body = ErrorResponse),
+    )
+)]
+async fn login(
+    State(auth): State<AuthState>,
+    Json(credentials): Json<LoginRequest>,
+) -> Result<Json<ApiResponse<LoginResponse>>, ApiError> {
+    let service = AuthService::new(auth.pool, auth.config);
+    let response = service.login(credentials).await?;
+    Ok(Json(ApiResponse::success(response)))
+}
+
+/// GET /api/auth/me - Get the currently authenticated admin
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    tag = "auth",
+    responses(
+        (status = 200, description = "The admin", body = ApiResponse<AdminUser>),
+        (status = 404, description = "The admin was deleted since the token was issued", body = ErrorResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn get_current_admin(
+    State(auth): State<AuthState>,
+    admin: AuthenticatedAdmin,
+) -> Result<Json<ApiResponse<AdminUser>>, ApiError> {
+    let service = AuthService::new(auth.pool, auth.config);
+    let admin = service.get_admin_by_id(admin.id).await?;
+    Ok(Json(ApiResponse::success(admin)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Method, Request, StatusCode},
+    };
+    use chrono::Duration;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    use crate::models::CreateAdminUser;
+
+    async fn create_test_app() -> (Router, SqlitePool, AuthConfig) {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        // Create table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS admin_users (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                username TEXT NOT NULL UNIQUE,
+                password_hash TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                last_login_at DATETIME
+            );
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let config = AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::hours(1));
+        AuthService::new(pool.clone(), config.clone())
+            .create_admin(CreateAdminUser {
+                username: "admin".to_string(),
+                password: "correct horse battery staple".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let app = create_routes(pool.clone(), config.clone());
+        (app, pool, config)
+    }
+
+    fn login_request(password: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/login")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "username": "admin", "password": password }).to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_login_and_me() {
+        let (app, _pool, _config) = create_test_app().await;
+
+        let response = app.clone().oneshot(login_request("correct horse battery staple")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<LoginResponse> = serde_json::from_slice(&body).unwrap();
+        let login = response_json.data.unwrap();
+        assert_eq!(login.token_type, "Bearer");
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/me")
+            .header(header::AUTHORIZATION, format!("Bearer {}", login.token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response_json["data"]["username"], "admin");
+        assert!(response_json["data"].get("password_hash").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_login_wrong_password() {
+        let (app, _pool, _config) = create_test_app().await;
+
+        let response = app.oneshot(login_request("wrong password")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_me_requires_token() {
+        let (app, _pool, _config) = create_test_app().await;
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/me")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+    }
+}
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use crate::{
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiScope, CreateCertification, Certification, UpdateCertification},
+    services::CertificationService,
+};
+
+/// Create certification routes (reads are public, writes require an admin or a `profile:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", post(create_certification))
+        .route("/:id", put(update_certification).delete(delete_certification))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ProfileWrite),
+            require_scope,
+        ));
+
+    Router::new()
+        .route("/", get(get_certifications))
+        .route("/:id", get(get_certification_by_id))
+        .merge(admin_routes)
+        .with_state(pool)
+}
+
+/// GET /api/certifications - Get all certifications, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/certifications",
+    tag = "certifications",
+    responses(
+        (status = 200, description = "Every entry, most recent first", body = ApiResponse<Vec<Certification>>),
+    )
+)]
+async fn get_certifications(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ApiResponse<Vec<Certification>>>, ApiError> {
+    let service = CertificationService::new(pool);
+    let certifications = service.get_all_certifications().await?;
+    Ok(Json(ApiResponse::success(certifications)))
+}
+
+/// GET /api/certifications/:id - Get a specific certification by ID
+#[utoipa::path(
+    get,
+    path = "/api/certifications/{id}",
+    tag = "certifications",
+    params(("id" = i32, Path, description = "Certification ID")),
+    responses(
+        (status = 200, description = "The entry", body = ApiResponse<Certification>),
+        (status = 404, description = "No such certification", body = ErrorResponse),
+    )
+)]
+async fn get_certification_by_id(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Certification>>, ApiError> {
+    let service = CertificationService::new(pool);
+    let certification = service.get_certification_by_id(id).await?;
+    Ok(Json(ApiResponse::success(certification)))
+}
+
+/// POST /api/certifications - Create a new certification
+#[utoipa::path(
+    post,
+    path = "/api/certifications",
+    tag = "certifications",
+    request_body = CreateCertification,
+    responses(
+        (status = 200, description = "The created entry", body = ApiResponse<Certification>),
+        (status = 400, description = "Invalid entry or period", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn create_certification(
+    State(pool): State<SqlitePool>,
+    Json(certification_data): Json<CreateCertification>,
+) -> Result<Json<ApiResponse<Certification>>, ApiError> {
+    let service = CertificationService::new(pool);
+    let certification = service.create_certification(certification_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        certification,
+        "Certification created successfully".to_string(),
+    )))
+}
+
+/// PUT /api/certifications/:id - Update an existing certification
+#[utoipa::path(
+    put,
+    path = "/api/certifications/{id}",
+    tag = "certifications",
+    params(("id" = i32, Path, description = "Certification ID")),
+    request_body = UpdateCertification,
+    responses(
+        (status = 200, description = "The updated entry", body = ApiResponse<Certification>),
+        (status = 400, description = "Invalid or empty changes", body = ErrorResponse),
+        (status = 404, description = "No such certification", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn update_certification(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+    Json(certification_data): Json<UpdateCertification>,
+) -> Result<Json<ApiResponse<Certification>>, ApiError> {
+    let service = CertificationService::new(pool);
+    let certification = service.update_certification(id, certification_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        certification,
+        "Certification updated successfully".to_string(),
+    )))
+}
+
+/// DELETE /api/certifications/:id - Delete a certification
+#[utoipa::path(
+    delete,
+    path = "/api/certifications/{id}",
+    tag = "certifications",
+    params(("id" = i32, Path, description = "Certification ID")),
+    responses(
+        (status = 200, description = "The entry was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such certification", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn delete_certification(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let service = CertificationService::new(pool);
+    service.delete_certification(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        json!({}),
+        "Certification deleted successfully".to_string(),
+    )))
+}
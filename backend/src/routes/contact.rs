@@ -1,47 +1,142 @@
+use std::sync::Arc;
+
 use axum::{
     extract::{Path, Query, State},
     response::Json,
-    routing::{delete, get, post},
+    middleware,
+    routing::{delete, get, post, put},
     Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    error::ApiError,
-    models::{ContactMessage, CreateContactMessage},
-    routes::projects::{ApiResponse, PaginationInfo},
-    services::{ContactService, contact_service::MessageStats},
+    auth::{require_scope, AuthConfig, AuthState, Principal},
+    challenge::{Challenge, Challenges},
+    config::AppConfig,
+    error::{ApiError, ApiResponse, ErrorCode, ErrorResponse, PaginationInfo},
+    models::{
+        ApiScope, BulkStatusUpdate, ContactMessage, ContactMessageDetail, ContactReply, CreateContactMessage,
+        CreateContactReply, MessageFilter, MessageStatus, UpdateContactMessage,
+    },
+    services::{ContactService, NotificationService, contact_service::MessageStats},
+    spam::{FormTokens, SpamPipeline},
 };
 
 /// Query parameters for contact message listing (admin only)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ContactQuery {
+    /// One of new, read, replied, archived or spam; spam is hidden unless asked for
+    #[param(value_type = Option<MessageStatus>)]
+    pub status: Option<String>,
+    pub starred: Option<bool>,
+    /// Full-text search of subjects, bodies, names and emails; `"quoted phrases"` and `prefix*` are supported
     pub search: Option<String>,
+    /// Only messages received in the last `days` days
     pub days: Option<u32>,
     pub page: Option<u32>,
     pub page_size: Option<u32>,
 }
 
-/// Create contact routes
-pub fn create_routes(pool: SqlitePool) -> Router {
+/// Create contact routes (submission is public; reading, moderating and deleting messages require
+/// an admin or a `contact:read` / `contact:write` / `contact:delete` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig, config: &AppConfig) -> Router {
+    let form_tokens = FormTokens::from_config(&auth, &config.spam);
+    let challenges = config
+        .challenge
+        .enabled
+        .then(|| Challenges::from_config(pool.clone(), &auth, &config.challenge));
+
+    let mut service = ContactService::new(
+        pool.clone(),
+        Some(SpamPipeline::from_config(pool.clone(), &config.spam, form_tokens.clone())),
+    )
+    .with_notifications(NotificationService::new(pool.clone(), config.notifications.clone()));
+    if let Some(challenges) = &challenges {
+        service = service.with_challenges(challenges.clone());
+    }
+    let service = Arc::new(service);
+
     Router::new()
         .route("/", post(submit_contact_message))
+        .route("/form-token", get(issue_form_token))
+        .route("/challenge", get(issue_challenge))
+        .with_state(ContactFormState {
+            service: service.clone(),
+            form_tokens,
+            challenges,
+        })
+        .merge(admin_routes(service, pool, auth))
+}
+
+/// State shared by the public contact form routes
+#[derive(Clone)]
+struct ContactFormState {
+    service: Arc<ContactService>,
+    form_tokens: FormTokens,
+    /// Set when submissions must solve a proof-of-work challenge
+    challenges: Option<Challenges>,
+}
+
+/// Create only the message management routes, for deployments without a public contact form
+pub fn create_admin_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    admin_routes(Arc::new(ContactService::new(pool.clone(), None)), pool, auth)
+}
+
+/// Message management routes, sharing the service of the contact form when there is one
+fn admin_routes(service: Arc<ContactService>, pool: SqlitePool, auth: AuthConfig) -> Router {
+    let read_routes = Router::new()
         .route("/messages", get(get_contact_messages))
-        .route("/messages/:id", get(get_contact_message_by_id).delete(delete_contact_message))
+        .route("/messages/:id", get(get_contact_message_by_id))
         .route("/stats", get(get_message_stats))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth.clone()), ApiScope::ContactRead),
+            require_scope,
+        ));
+
+    let write_routes = Router::new()
+        .route("/messages/status", put(update_messages_status))
+        .route("/messages/:id", put(update_contact_message))
+        .route("/messages/:id/replies", post(record_contact_reply))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth.clone()), ApiScope::ContactWrite),
+            require_scope,
+        ));
+
+    let delete_routes = Router::new()
+        .route("/messages/:id", delete(delete_contact_message))
         .route("/cleanup", post(cleanup_old_messages))
-        .with_state(pool)
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ContactDelete),
+            require_scope,
+        ));
+
+    Router::new()
+        .merge(read_routes)
+        .merge(write_routes)
+        .merge(delete_routes)
+        .with_state(service)
 }
 
 /// POST /api/contact - Submit a contact message
+#[utoipa::path(
+    post,
+    path = "/api/contact",
+    tag = "contact",
+    request_body = CreateContactMessage,
+    responses(
+        (status = 200, description = "The message was received", body = ApiResponse<ContactSubmissionResponse>),
+        (status = 400, description = "Invalid message, missing or wrong challenge, rejected as spam or sent too often", body = ErrorResponse),
+    )
+)]
 async fn submit_contact_message(
-    State(pool): State<SqlitePool>,
+    State(state): State<ContactFormState>,
     Json(message_data): Json<CreateContactMessage>,
 ) -> Result<Json<ApiResponse<ContactSubmissionResponse>>, ApiError> {
-    let service = ContactService::new(pool);
-    let message = service.submit_message(message_data).await?;
+    let message = state.service.submit_message(message_data).await?;
     
     let response = ContactSubmissionResponse {
         id: message.id,
@@ -55,61 +150,204 @@ async fn submit_contact_message(
     )))
 }
 
-/// GET /api/contact/messages - Get all contact messages (admin only)
+/// GET /api/contact/form-token - Token to send back with the form, recording when it was displayed
+#[utoipa::path(
+    get,
+    path = "/api/contact/form-token",
+    tag = "contact",
+    responses(
+        (status = 200, description = "A form token", body = ApiResponse<FormTokenResponse>),
+    )
+)]
+async fn issue_form_token(
+    State(state): State<ContactFormState>,
+) -> Json<ApiResponse<FormTokenResponse>> {
+    Json(ApiResponse::success(FormTokenResponse {
+        token: state.form_tokens.issue(),
+    }))
+}
+
+/// GET /api/contact/challenge - Proof-of-work puzzle to solve before submitting the form
+#[utoipa::path(
+    get,
+    path = "/api/contact/challenge",
+    tag = "contact",
+    responses(
+        (status = 200, description = "A challenge", body = ApiResponse<Challenge>),
+        (status = 404, description = "Challenges are not enabled", body = ErrorResponse),
+    )
+)]
+async fn issue_challenge(
+    State(state): State<ContactFormState>,
+) -> Result<Json<ApiResponse<Challenge>>, ApiError> {
+    let challenges = state
+        .challenges
+        .ok_or_else(|| ApiError::NotFound(ErrorCode::NotFound, "Contact challenges are not enabled".to_string()))?;
+
+    Ok(Json(ApiResponse::success(challenges.issue())))
+}
+
+/// GET /api/contact/messages - List contact messages, optionally filtered and paginated (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/contact/messages",
+    tag = "contact",
+    params(ContactQuery),
+    responses(
+        (status = 200, description = "Messages, newest first; paginated when `page` or `page_size` is given", body = ApiResponse<Vec<ContactMessage>>),
+        (status = 400, description = "Unknown status", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:read"]), ("api_key" = ["contact:read"])),
+)]
 async fn get_contact_messages(
-    State(pool): State<SqlitePool>,
+    State(service): State<Arc<ContactService>>,
     Query(params): Query<ContactQuery>,
 ) -> Result<Json<ApiResponse<Vec<ContactMessage>>>, ApiError> {
-    let service = ContactService::new(pool);
+    let status = match params.status.as_deref() {
+        Some(status) => Some(MessageStatus::from_str(status).ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "Invalid status '{}'. Valid statuses: {}",
+                status,
+                MessageStatus::all().join(", ")
+            ))
+        })?),
+        None => None,
+    };
+    let filter = MessageFilter {
+        status,
+        starred: params.starred,
+        search: params.search,
+        since: params.days.map(|days| chrono::Utc::now() - chrono::Duration::days(days as i64)),
+    };
 
-    // Handle pagination
-    if let (Some(page), Some(page_size)) = (params.page, params.page_size) {
-        let (messages, total_count) = service.get_messages_paginated(page, page_size).await?;
-        let total_pages = (total_count as f64 / page_size as f64).ceil() as u64;
-        
-        let pagination = PaginationInfo {
-            page,
-            page_size,
-            total_count,
-            total_pages,
-        };
+    if params.page.is_some() || params.page_size.is_some() {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params.page_size.unwrap_or(20);
+        let (messages, total_count) = service.list_messages(filter, Some(page), page_size).await?;
+        let pagination = PaginationInfo::new(Some(page), page_size, total_count, None);
 
         return Ok(Json(ApiResponse::success_with_pagination(messages, pagination)));
     }
 
-    // Handle search
-    if let Some(search_query) = params.search {
-        let messages = service.search_messages(&search_query).await?;
-        return Ok(Json(ApiResponse::success(messages)));
-    }
-
-    // Handle recent messages
-    if let Some(days) = params.days {
-        let messages = service.get_recent_messages(days).await?;
-        return Ok(Json(ApiResponse::success(messages)));
-    }
-
-    // Default: get all messages
-    let messages = service.get_all_messages().await?;
+    let (messages, _) = service.list_messages(filter, None, 20).await?;
     Ok(Json(ApiResponse::success(messages)))
 }
 
-/// GET /api/contact/messages/:id - Get a specific contact message by ID (admin only)
+/// GET /api/contact/messages/:id - Get a contact message with its replies (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/contact/messages/{id}",
+    tag = "contact",
+    params(("id" = i32, Path, description = "Message ID")),
+    responses(
+        (status = 200, description = "The message and its replies", body = ApiResponse<ContactMessageDetail>),
+        (status = 404, description = "No such message", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:read"]), ("api_key" = ["contact:read"])),
+)]
 async fn get_contact_message_by_id(
-    State(pool): State<SqlitePool>,
+    State(service): State<Arc<ContactService>>,
     Path(id): Path<i32>,
-) -> Result<Json<ApiResponse<ContactMessage>>, ApiError> {
-    let service = ContactService::new(pool);
-    let message = service.get_message_by_id(id).await?;
+) -> Result<Json<ApiResponse<ContactMessageDetail>>, ApiError> {
+    let message = service.get_message_detail(id).await?;
     Ok(Json(ApiResponse::success(message)))
 }
 
+/// PUT /api/contact/messages/:id - Change a message's status, starred flag or notes (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/contact/messages/{id}",
+    tag = "contact",
+    params(("id" = i32, Path, description = "Message ID")),
+    request_body = UpdateContactMessage,
+    responses(
+        (status = 200, description = "The updated message", body = ApiResponse<ContactMessage>),
+        (status = 400, description = "Invalid changes or status transition", body = ErrorResponse),
+        (status = 404, description = "No such message", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:write"]), ("api_key" = ["contact:write"])),
+)]
+async fn update_contact_message(
+    State(service): State<Arc<ContactService>>,
+    Path(id): Path<i32>,
+    Json(update): Json<UpdateContactMessage>,
+) -> Result<Json<ApiResponse<ContactMessage>>, ApiError> {
+    let message = service.update_message(id, update).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        message,
+        "Message updated successfully".to_string(),
+    )))
+}
+
+/// PUT /api/contact/messages/status - Move several messages to a status at once (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/contact/messages/status",
+    tag = "contact",
+    request_body = BulkStatusUpdate,
+    responses(
+        (status = 200, description = "Number of messages updated, as `{\"updated\": n}`", body = ApiResponse<Value>),
+        (status = 400, description = "Invalid status or status transition", body = ErrorResponse),
+        (status = 404, description = "Some messages do not exist", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:write"]), ("api_key" = ["contact:write"])),
+)]
+async fn update_messages_status(
+    State(service): State<Arc<ContactService>>,
+    Json(update): Json<BulkStatusUpdate>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let updated = service.update_status_bulk(update).await?;
+    Ok(Json(ApiResponse::success(json!({ "updated": updated }))))
+}
+
+/// POST /api/contact/messages/:id/replies - Record a reply sent to the author (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/contact/messages/{id}/replies",
+    tag = "contact",
+    params(("id" = i32, Path, description = "Message ID")),
+    request_body = CreateContactReply,
+    responses(
+        (status = 200, description = "The recorded reply", body = ApiResponse<ContactReply>),
+        (status = 400, description = "Invalid reply", body = ErrorResponse),
+        (status = 404, description = "No such message", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:write"]), ("api_key" = ["contact:write"])),
+)]
+async fn record_contact_reply(
+    State(service): State<Arc<ContactService>>,
+    principal: Principal,
+    Path(id): Path<i32>,
+    Json(reply): Json<CreateContactReply>,
+) -> Result<Json<ApiResponse<ContactReply>>, ApiError> {
+    let replied_by = match principal {
+        Principal::Admin(admin) => admin.username,
+        Principal::ApiKey { name, .. } => name,
+    };
+
+    let reply = service.record_reply(id, reply, Some(&replied_by)).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        reply,
+        "Reply recorded successfully".to_string(),
+    )))
+}
+
 /// DELETE /api/contact/messages/:id - Delete a contact message (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/contact/messages/{id}",
+    tag = "contact",
+    params(("id" = i32, Path, description = "Message ID")),
+    responses(
+        (status = 200, description = "The message was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such message", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:delete"]), ("api_key" = ["contact:delete"])),
+)]
 async fn delete_contact_message(
-    State(pool): State<SqlitePool>,
+    State(service): State<Arc<ContactService>>,
     Path(id): Path<i32>,
 ) -> Result<Json<ApiResponse<Value>>, ApiError> {
-    let service = ContactService::new(pool);
     service.delete_message(id).await?;
     Ok(Json(ApiResponse::success_with_message(
         json!({}),
@@ -118,20 +356,38 @@ async fn delete_contact_message(
 }
 
 /// GET /api/contact/stats - Get message statistics (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/contact/stats",
+    tag = "contact",
+    responses(
+        (status = 200, description = "Message statistics", body = ApiResponse<MessageStats>),
+    ),
+    security(("bearer" = ["contact:read"]), ("api_key" = ["contact:read"])),
+)]
 async fn get_message_stats(
-    State(pool): State<SqlitePool>,
+    State(service): State<Arc<ContactService>>,
 ) -> Result<Json<ApiResponse<MessageStats>>, ApiError> {
-    let service = ContactService::new(pool);
     let stats = service.get_message_stats().await?;
     Ok(Json(ApiResponse::success(stats)))
 }
 
 /// POST /api/contact/cleanup - Clean up old messages (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/contact/cleanup",
+    tag = "contact",
+    request_body = CleanupRequest,
+    responses(
+        (status = 200, description = "Number of messages deleted", body = ApiResponse<CleanupResponse>),
+        (status = 400, description = "Fewer than 30 days", body = ErrorResponse),
+    ),
+    security(("bearer" = ["contact:delete"]), ("api_key" = ["contact:delete"])),
+)]
 async fn cleanup_old_messages(
-    State(pool): State<SqlitePool>,
+    State(service): State<Arc<ContactService>>,
     Json(cleanup_request): Json<CleanupRequest>,
 ) -> Result<Json<ApiResponse<CleanupResponse>>, ApiError> {
-    let service = ContactService::new(pool);
     let deleted_count = service.cleanup_old_messages(cleanup_request.days).await?;
     
     let response = CleanupResponse {
@@ -143,21 +399,29 @@ async fn cleanup_old_messages(
 }
 
 /// Response for contact form submission
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ContactSubmissionResponse {
     pub id: i32,
     pub submitted_at: chrono::DateTime<chrono::Utc>,
     pub message: String,
 }
 
+/// Response carrying a contact form token
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FormTokenResponse {
+    pub token: String,
+}
+
 /// Request for cleanup operation
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CleanupRequest {
+    /// Delete messages older than this many days, at least 30
+    #[schema(minimum = 30)]
     pub days: u32,
 }
 
 /// Response for cleanup operation
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CleanupResponse {
     pub deleted_count: u64,
     pub message: String,
@@ -174,30 +438,18 @@ mod tests {
     use sqlx::SqlitePool;
     use tower::ServiceExt;
 
-    async fn create_test_app() -> (Router, SqlitePool) {
+    async fn create_test_app() -> (Router, SqlitePool, String) {
         let pool = SqlitePool::connect("sqlite::memory:")
             .await
             .unwrap();
 
-        // Create table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS contact_messages (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                email TEXT NOT NULL,
-                subject TEXT NOT NULL,
-                message TEXT NOT NULL,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
-
-        let app = create_routes(pool.clone());
-        (app, pool)
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+
+        let app = create_routes(pool.clone(), auth, &AppConfig::default());
+        (app, pool, token)
     }
 
     fn create_test_contact_json() -> serde_json::Value {
@@ -211,7 +463,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_submit_contact_message() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, _token) = create_test_app().await;
         
         let request = Request::builder()
             .method(Method::POST)
@@ -236,7 +488,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_submit_contact_message_validation_error() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, _token) = create_test_app().await;
         
         let invalid_message = json!({
             "name": "",
@@ -258,21 +510,23 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_contact_messages() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First submit a message
-        let service = ContactService::new(pool);
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
         let message_data = CreateContactMessage {
             name: "John Doe".to_string(),
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message with sufficient content for testing purposes.".to_string(),
+            ..Default::default()
         };
         service.submit_message(message_data).await.unwrap();
 
         let request = Request::builder()
             .method(Method::GET)
             .uri("/messages")
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -292,21 +546,23 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_contact_message_by_id() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First submit a message
-        let service = ContactService::new(pool);
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
         let message_data = CreateContactMessage {
             name: "John Doe".to_string(),
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message with sufficient content for testing purposes.".to_string(),
+            ..Default::default()
         };
         let submitted_message = service.submit_message(message_data).await.unwrap();
 
         let request = Request::builder()
             .method(Method::GET)
-            .uri(&format!("/messages/{}", submitted_message.id))
+            .uri(format!("/messages/{}", submitted_message.id))
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -314,33 +570,36 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<ContactMessage> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<ContactMessageDetail> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
         
-        let message = response_json.data.unwrap();
-        assert_eq!(message.id, submitted_message.id);
-        assert_eq!(message.name, "John Doe");
+        let detail = response_json.data.unwrap();
+        assert_eq!(detail.message.id, submitted_message.id);
+        assert_eq!(detail.message.name, "John Doe");
+        assert!(detail.replies.is_empty());
     }
 
     #[tokio::test]
     async fn test_get_message_stats() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First submit a message
-        let service = ContactService::new(pool);
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
         let message_data = CreateContactMessage {
             name: "John Doe".to_string(),
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message with sufficient content for testing purposes.".to_string(),
+            ..Default::default()
         };
         service.submit_message(message_data).await.unwrap();
 
         let request = Request::builder()
             .method(Method::GET)
             .uri("/stats")
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -359,21 +618,23 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_contact_message() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First submit a message
-        let service = ContactService::new(pool);
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
         let message_data = CreateContactMessage {
             name: "John Doe".to_string(),
             email: "john.doe@example.com".to_string(),
             subject: "Test Subject".to_string(),
             message: "This is a test message with sufficient content for testing purposes.".to_string(),
+            ..Default::default()
         };
         let submitted_message = service.submit_message(message_data).await.unwrap();
 
         let request = Request::builder()
             .method(Method::DELETE)
-            .uri(&format!("/messages/{}", submitted_message.id))
+            .uri(format!("/messages/{}", submitted_message.id))
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -389,7 +650,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_cleanup_old_messages() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, token) = create_test_app().await;
 
         let cleanup_request = json!({
             "days": 365
@@ -398,6 +659,7 @@ mod tests {
         let request = Request::builder()
             .method(Method::POST)
             .uri("/cleanup")
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(cleanup_request.to_string()))
             .unwrap();
@@ -417,16 +679,17 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_messages_with_pagination() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // Create multiple messages
-        let service = ContactService::new(pool);
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
         for i in 0..5 {
             let message_data = CreateContactMessage {
                 name: format!("User {}", i),
                 email: format!("user{}@example.com", i),
                 subject: "Test Subject".to_string(),
                 message: "This is a test message with sufficient content for testing purposes.".to_string(),
+                ..Default::default()
             };
             service.submit_message(message_data).await.unwrap();
         }
@@ -434,6 +697,7 @@ mod tests {
         let request = Request::builder()
             .method(Method::GET)
             .uri("/messages?page=1&page_size=3")
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -452,7 +716,304 @@ mod tests {
         
         assert!(messages.len() <= 3);
         assert_eq!(pagination.total_count, 5);
-        assert_eq!(pagination.page, 1);
+        assert_eq!(pagination.page, Some(1));
         assert_eq!(pagination.page_size, 3);
     }
+
+    #[tokio::test]
+    async fn test_admin_only_routes_reject_submissions() {
+        let (_app, pool, token) = create_test_app().await;
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let app = create_admin_routes(pool, auth);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("content-type", "application/json")
+            .body(Body::from(create_test_contact_json().to_string()))
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/stats")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_require_admin() {
+        let (app, _pool, _token) = create_test_app().await;
+
+        for uri in ["/messages", "/messages/1", "/stats"] {
+            let request = Request::builder()
+                .method(Method::GET)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{} should require an admin", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contact_read_key_cannot_delete() {
+        let (app, pool, _token) = create_test_app().await;
+        let key = crate::auth::test_api_key(&pool, &["contact:read"]).await;
+
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
+        let message_data = CreateContactMessage {
+            name: "John Doe".to_string(),
+            email: "john.doe@example.com".to_string(),
+            subject: "Test Subject".to_string(),
+            message: "This is a test message with sufficient content.".to_string(),
+            ..Default::default()
+        };
+        let submitted_message = service.submit_message(message_data).await.unwrap();
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/messages/{}", submitted_message.id))
+            .header("x-api-key", key.clone())
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri(format!("/messages/{}", submitted_message.id))
+            .header("x-api-key", key)
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_moderation_workflow() {
+        let (app, pool, token) = create_test_app().await;
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
+        let mut ids = Vec::new();
+        for subject in ["First question", "Second question"] {
+            let message_data = CreateContactMessage {
+                name: "John Doe".to_string(),
+                email: "john.doe@example.com".to_string(),
+                subject: subject.to_string(),
+                message: "This is a test message with sufficient content.".to_string(),
+                ..Default::default()
+            };
+            ids.push(service.submit_message(message_data).await.unwrap().id);
+        }
+
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/messages/status")
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "ids": ids, "status": "read" }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<Value> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response_json.data.unwrap()["updated"], 2);
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/messages/{}/replies", ids[0]))
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "body": "Thanks for reaching out!" }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<ContactReply> = serde_json::from_slice(&body).unwrap();
+        assert_eq!(response_json.data.unwrap().replied_by.as_deref(), Some("test-admin"));
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/messages?status=replied")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<Vec<ContactMessage>> = serde_json::from_slice(&body).unwrap();
+        let messages = response_json.data.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, ids[0]);
+
+        // Replied messages cannot be reopened
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/messages/{}", ids[0]))
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "status": "new" }).to_string()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/messages?status=unknown")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_contact_read_key_cannot_moderate() {
+        let (app, pool, _token) = create_test_app().await;
+        let read_key = crate::auth::test_api_key(&pool, &["contact:read"]).await;
+        let write_key = crate::auth::test_api_key(&pool, &["contact:write"]).await;
+
+        let service = ContactService::new(pool, Some(SpamPipeline::new(5.0, 10.0)));
+        let message_data = CreateContactMessage {
+            name: "John Doe".to_string(),
+            email: "john.doe@example.com".to_string(),
+            subject: "Test Subject".to_string(),
+            message: "This is a test message with sufficient content.".to_string(),
+            ..Default::default()
+        };
+        let submitted_message = service.submit_message(message_data).await.unwrap();
+
+        for (key, expected) in [(read_key, StatusCode::FORBIDDEN), (write_key, StatusCode::OK)] {
+            let request = Request::builder()
+                .method(Method::PUT)
+                .uri(format!("/messages/{}", submitted_message.id))
+                .header("x-api-key", key)
+                .header("content-type", "application/json")
+                .body(Body::from(json!({ "starred": true }).to_string()))
+                .unwrap();
+
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_form_token_and_honeypot() {
+        let (app, _pool, _token) = create_test_app().await;
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/form-token")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<FormTokenResponse> = serde_json::from_slice(&body).unwrap();
+        let form_token = response_json.data.unwrap().token;
+
+        // A bot filling in the hidden field is turned away
+        let mut submission = create_test_contact_json();
+        submission["website"] = json!("https://seo.example");
+        submission["form_token"] = json!(form_token);
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("content-type", "application/json")
+            .body(Body::from(submission.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_submissions_scored_with_configured_spam_settings() {
+        let (_, pool, token) = create_test_app().await;
+        let mut config = AppConfig::default();
+        config.spam.min_submit_secs = 0;
+        config.spam.quarantine_threshold = 0.5;
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let app = create_routes(pool, auth, &config);
+
+        let request = Request::builder().uri("/form-token").body(Body::empty()).unwrap();
+        let body = axum::body::to_bytes(app.clone().oneshot(request).await.unwrap().into_body(), usize::MAX).await.unwrap();
+        let form_token = serde_json::from_slice::<Value>(&body).unwrap()["data"]["token"].clone();
+
+        // The token issued by the router verifies; a submission without one is quarantined
+        let mut with_token = create_test_contact_json();
+        with_token["form_token"] = form_token;
+        let mut without_token = create_test_contact_json();
+        without_token["subject"] = json!("Another inquiry");
+        without_token["message"] = json!("Hello again, are you available for a new web project this spring?");
+        for submission in [with_token, without_token] {
+            let request = Request::builder()
+                .method(Method::POST)
+                .uri("/")
+                .header("content-type", "application/json")
+                .body(Body::from(submission.to_string()))
+                .unwrap();
+            assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::OK);
+        }
+
+        let request = Request::builder()
+            .uri("/messages?status=spam")
+            .header("authorization", format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+        let body = axum::body::to_bytes(app.oneshot(request).await.unwrap().into_body(), usize::MAX).await.unwrap();
+        let quarantined: Value = serde_json::from_slice(&body).unwrap();
+        let quarantined = quarantined["data"].as_array().unwrap();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0]["subject"], "Another inquiry");
+    }
+
+    #[tokio::test]
+    async fn test_challenge_solved_before_submission() {
+        let (app, pool, _token) = create_test_app().await;
+        let request = Request::builder().uri("/challenge").body(Body::empty()).unwrap();
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::NOT_FOUND);
+
+        let mut config = AppConfig::default();
+        config.challenge.enabled = true;
+        config.challenge.difficulty = 8;
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let app = create_routes(pool, auth, &config);
+
+        let request = Request::builder().uri("/challenge").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: Value = serde_json::from_slice(&body).unwrap();
+        let challenge = response_json["data"]["challenge"].as_str().unwrap().to_string();
+        assert_eq!(response_json["data"]["algorithm"], "sha256");
+        assert_eq!(response_json["data"]["difficulty"], 8);
+
+        let mut submission = create_test_contact_json();
+        let submit = |submission: &serde_json::Value| {
+            Request::builder()
+                .method(Method::POST)
+                .uri("/")
+                .header("content-type", "application/json")
+                .body(Body::from(submission.to_string()))
+                .unwrap()
+        };
+        let response = app.clone().oneshot(submit(&submission)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        submission["challenge_nonce"] = json!(crate::challenge::pow::solve(&challenge, 8));
+        submission["challenge"] = json!(challenge);
+        let response = app.clone().oneshot(submit(&submission)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(submit(&submission)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
\ No newline at end of file
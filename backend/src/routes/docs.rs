@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Redirect, Response},
+    routing::get,
+    Router,
+};
+use utoipa_swagger_ui::Config;
+
+use crate::{config::AppConfig, error::{ApiError, ErrorCode}, openapi};
+
+/// Where Swagger UI loads the specification from
+const SPEC_PATH: &str = "/api/openapi.json";
+
+/// State shared by the documentation routes
+#[derive(Clone)]
+struct DocsState {
+    /// The specification, serialized once at startup
+    spec: Bytes,
+    swagger: Arc<Config<'static>>,
+}
+
+/// Create the `/api/openapi.json` and `/api/docs/` routes (public)
+pub fn create_routes(config: &AppConfig) -> Router {
+    let spec = openapi::document(config)
+        .to_pretty_json()
+        .expect("the OpenAPI document serializes to JSON");
+
+    Router::new()
+        .route(SPEC_PATH, get(get_spec))
+        .route("/api/docs", get(|| async { Redirect::permanent("/api/docs/") }))
+        .route("/api/docs/", get(get_swagger_index))
+        .route("/api/docs/*file", get(get_swagger_file))
+        .with_state(DocsState {
+            spec: Bytes::from(spec),
+            swagger: Arc::new(Config::new([SPEC_PATH]).persist_authorization(true)),
+        })
+}
+
+/// GET /api/openapi.json - OpenAPI 3.1 description of the API
+async fn get_spec(State(state): State<DocsState>) -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], state.spec).into_response()
+}
+
+/// GET /api/docs/ - Swagger UI, bundled with the server
+async fn get_swagger_index(State(state): State<DocsState>) -> Result<Response, ApiError> {
+    swagger_file("", state.swagger)
+}
+
+/// GET /api/docs/*file - Swagger UI scripts and stylesheets
+async fn get_swagger_file(
+    State(state): State<DocsState>,
+    Path(file): Path<String>,
+) -> Result<Response, ApiError> {
+    swagger_file(&file, state.swagger)
+}
+
+fn swagger_file(file: &str, config: Arc<Config<'static>>) -> Result<Response, ApiError> {
+    match utoipa_swagger_ui::serve(file, config) {
+        Ok(Some(file)) => Ok(([(header::CONTENT_TYPE, file.content_type)], file.bytes.into_owned()).into_response()),
+        Ok(None) => Err(ApiError::NotFound(ErrorCode::NotFound, format!("Documentation file '{}' not found", file))),
+        Err(e) => Err(ApiError::InternalServerError(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{Request, StatusCode}};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_docs_routes() {
+        let app = create_routes(&AppConfig::default());
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/api/openapi.json")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(spec["openapi"], "3.1.0");
+        assert_eq!(spec["servers"][0]["url"], "http://localhost:3001");
+
+        let response = app.clone().oneshot(get("/api/docs")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+
+        let response = app.clone().oneshot(get("/api/docs/")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/html"));
+
+        // The initializer points Swagger UI at our specification
+        let response = app.clone().oneshot(get("/api/docs/swagger-initializer.js")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains(SPEC_PATH));
+
+        let response = app.oneshot(get("/api/docs/missing.js")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
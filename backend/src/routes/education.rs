@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use crate::{
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiScope, CreateEducation, Education, UpdateEducation},
+    services::EducationService,
+};
+
+/// Create education entry routes (reads are public, writes require an admin or a `profile:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", post(create_education))
+        .route("/:id", put(update_education).delete(delete_education))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ProfileWrite),
+            require_scope,
+        ));
+
+    Router::new()
+        .route("/", get(get_education))
+        .route("/:id", get(get_education_by_id))
+        .merge(admin_routes)
+        .with_state(pool)
+}
+
+/// GET /api/education - Get all education entries, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/education",
+    tag = "education",
+    responses(
+        (status = 200, description = "Every entry, most recent first", body = ApiResponse<Vec<Education>>),
+    )
+)]
+async fn get_education(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ApiResponse<Vec<Education>>>, ApiError> {
+    let service = EducationService::new(pool);
+    let entries = service.get_all_education().await?;
+    Ok(Json(ApiResponse::success(entries)))
+}
+
+/// GET /api/education/:id - Get a specific education entry by ID
+#[utoipa::path(
+    get,
+    path = "/api/education/{id}",
+    tag = "education",
+    params(("id" = i32, Path, description = "Education entry ID")),
+    responses(
+        (status = 200, description = "The entry", body = ApiResponse<Education>),
+        (status = 404, description = "No such education entry", body = ErrorResponse),
+    )
+)]
+async fn get_education_by_id(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Education>>, ApiError> {
+    let service = EducationService::new(pool);
+    let education = service.get_education_by_id(id).await?;
+    Ok(Json(ApiResponse::success(education)))
+}
+
+/// POST /api/education - Create a new education entry
+#[utoipa::path(
+    post,
+    path = "/api/education",
+    tag = "education",
+    request_body = CreateEducation,
+    responses(
+        (status = 200, description = "The created entry", body = ApiResponse<Education>),
+        (status = 400, description = "Invalid entry or period", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn create_education(
+    State(pool): State<SqlitePool>,
+    Json(education_data): Json<CreateEducation>,
+) -> Result<Json<ApiResponse<Education>>, ApiError> {
+    let service = EducationService::new(pool);
+    let education = service.create_education(education_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        education,
+        "Education entry created successfully".to_string(),
+    )))
+}
+
+/// PUT /api/education/:id - Update an existing education entry
+#[utoipa::path(
+    put,
+    path = "/api/education/{id}",
+    tag = "education",
+    params(("id" = i32, Path, description = "Education entry ID")),
+    request_body = UpdateEducation,
+    responses(
+        (status = 200, description = "The updated entry", body = ApiResponse<Education>),
+        (status = 400, description = "Invalid or empty changes", body = ErrorResponse),
+        (status = 404, description = "No such education entry", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn update_education(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+    Json(education_data): Json<UpdateEducation>,
+) -> Result<Json<ApiResponse<Education>>, ApiError> {
+    let service = EducationService::new(pool);
+    let education = service.update_education(id, education_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        education,
+        "Education entry updated successfully".to_string(),
+    )))
+}
+
+/// DELETE /api/education/:id - Delete an education entry
+#[utoipa::path(
+    delete,
+    path = "/api/education/{id}",
+    tag = "education",
+    params(("id" = i32, Path, description = "Education entry ID")),
+    responses(
+        (status = 200, description = "The entry was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such education entry", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn delete_education(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let service = EducationService::new(pool);
+    service.delete_education(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        json!({}),
+        "Education entry deleted successfully".to_string(),
+    )))
+}
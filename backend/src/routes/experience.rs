@@ -0,0 +1,202 @@
+use axum::{
+    extract::{Path, State},
+    response::Json,
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+
+use crate::{
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiScope, CreateExperience, Experience, UpdateExperience},
+    services::ExperienceService,
+};
+
+/// Create work experience routes (reads are public, writes require an admin or a `profile:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", post(create_experience))
+        .route("/:id", put(update_experience).delete(delete_experience))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ProfileWrite),
+            require_scope,
+        ));
+
+    Router::new()
+        .route("/", get(get_experiences))
+        .route("/:id", get(get_experience_by_id))
+        .merge(admin_routes)
+        .with_state(pool)
+}
+
+/// GET /api/experience - Get all work experience, current and most recent first
+#[utoipa::path(
+    get,
+    path = "/api/experience",
+    tag = "experience",
+    responses(
+        (status = 200, description = "Every entry, most recent first", body = ApiResponse<Vec<Experience>>),
+    )
+)]
+async fn get_experiences(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ApiResponse<Vec<Experience>>>, ApiError> {
+    let service = ExperienceService::new(pool);
+    let experiences = service.get_all_experiences().await?;
+    Ok(Json(ApiResponse::success(experiences)))
+}
+
+/// GET /api/experience/:id - Get a specific experience by ID
+#[utoipa::path(
+    get,
+    path = "/api/experience/{id}",
+    tag = "experience",
+    params(("id" = i32, Path, description = "Experience ID")),
+    responses(
+        (status = 200, description = "The entry", body = ApiResponse<Experience>),
+        (status = 404, description = "No such experience", body = ErrorResponse),
+    )
+)]
+async fn get_experience_by_id(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Experience>>, ApiError> {
+    let service = ExperienceService::new(pool);
+    let experience = service.get_experience_by_id(id).await?;
+    Ok(Json(ApiResponse::success(experience)))
+}
+
+/// POST /api/experience - Create a new experience
+#[utoipa::path(
+    post,
+    path = "/api/experience",
+    tag = "experience",
+    request_body = CreateExperience,
+    responses(
+        (status = 200, description = "The created entry", body = ApiResponse<Experience>),
+        (status = 400, description = "Invalid entry or period", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn create_experience(
+    State(pool): State<SqlitePool>,
+    Json(experience_data): Json<CreateExperience>,
+) -> Result<Json<ApiResponse<Experience>>, ApiError> {
+    let service = ExperienceService::new(pool);
+    let experience = service.create_experience(experience_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        experience,
+        "Experience created successfully".to_string(),
+    )))
+}
+
+/// PUT /api/experience/:id - Update an existing experience
+#[utoipa::path(
+    put,
+    path = "/api/experience/{id}",
+    tag = "experience",
+    params(("id" = i32, Path, description = "Experience ID")),
+    request_body = UpdateExperience,
+    responses(
+        (status = 200, description = "The updated entry", body = ApiResponse<Experience>),
+        (status = 400, description = "Invalid or empty changes", body = ErrorResponse),
+        (status = 404, description = "No such experience", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn update_experience(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+    Json(experience_data): Json<UpdateExperience>,
+) -> Result<Json<ApiResponse<Experience>>, ApiError> {
+    let service = ExperienceService::new(pool);
+    let experience = service.update_experience(id, experience_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        experience,
+        "Experience updated successfully".to_string(),
+    )))
+}
+
+/// DELETE /api/experience/:id - Delete an experience
+#[utoipa::path(
+    delete,
+    path = "/api/experience/{id}",
+    tag = "experience",
+    params(("id" = i32, Path, description = "Experience ID")),
+    responses(
+        (status = 200, description = "The entry was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such experience", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
+async fn delete_experience(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let service = ExperienceService::new(pool);
+    service.delete_experience(id).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        json!({}),
+        "Experience deleted successfully".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Method, Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_experience_routes() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+        let app = create_routes(pool, auth);
+
+        let experience = json!({
+            "company": "Acme",
+            "role": "Developer",
+            "start_month": "2020-01",
+            "is_current": true,
+            "highlights": ["Rewrote the search"],
+            "technologies": ["Rust", "SQLite"]
+        });
+        let create = |token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method(Method::POST)
+                .uri("/")
+                .header(header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            builder.body(Body::from(experience.to_string())).unwrap()
+        };
+
+        let response = app.clone().oneshot(create(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.clone().oneshot(create(Some(&token))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["data"][0]["technologies"], json!(["Rust", "SQLite"]));
+        assert_eq!(json["data"][0]["highlights"], json!(["Rewrote the search"]));
+    }
+}
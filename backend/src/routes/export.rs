@@ -0,0 +1,154 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use utoipa::IntoParams;
+
+use crate::{
+    auth::{require_admin, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{JsonResume, JsonResumeImportReport},
+    services::JsonResumeService,
+};
+
+/// Query parameters for imports
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// Report what the import would change without writing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Create export routes (the exported content is public)
+pub fn create_export_routes(pool: SqlitePool) -> Router {
+    Router::new()
+        .route("/json-resume", get(export_json_resume))
+        .with_state(pool)
+}
+
+/// Create import routes; imports touch every section of the portfolio, so they require an admin
+pub fn create_import_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    Router::new()
+        .route("/json-resume", post(import_json_resume))
+        .route_layer(middleware::from_fn_with_state(
+            AuthState::new(pool.clone(), auth),
+            require_admin,
+        ))
+        .with_state(pool)
+}
+
+/// GET /api/export/json-resume - Export the portfolio as a JSON Resume document
+#[utoipa::path(
+    get,
+    path = "/api/export/json-resume",
+    tag = "export",
+    responses(
+        (status = 200, description = "The portfolio as a JSON Resume document", body = JsonResume),
+    )
+)]
+async fn export_json_resume(State(pool): State<SqlitePool>) -> Result<Json<JsonResume>, ApiError> {
+    let service = JsonResumeService::new(pool);
+    let resume = service.export().await?;
+    Ok(Json(resume))
+}
+
+/// POST /api/import/json-resume - Import a JSON Resume document (`?dry_run=true` to preview)
+#[utoipa::path(
+    post,
+    path = "/api/import/json-resume",
+    tag = "export",
+    params(ImportQuery),
+    request_body = JsonResume,
+    responses(
+        (status = 200, description = "What the import changed, or would change", body = ApiResponse<JsonResumeImportReport>),
+        (status = 400, description = "The document holds an invalid entry", body = ErrorResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn import_json_resume(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<ImportQuery>,
+    Json(resume): Json<JsonResume>,
+) -> Result<Json<ApiResponse<JsonResumeImportReport>>, ApiError> {
+    let service = JsonResumeService::new(pool);
+    let report = service.import(resume, params.dry_run).await?;
+    let message = if report.dry_run {
+        "Dry run completed, nothing was written"
+    } else {
+        "Resume imported successfully"
+    };
+    Ok(Json(ApiResponse::success_with_message(report, message.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Method, Request, StatusCode},
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_json_resume_routes() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+        let app = Router::new()
+            .nest("/export", create_export_routes(pool.clone()))
+            .nest("/import", create_import_routes(pool, auth));
+
+        let resume = json!({
+            "basics": { "name": "Jane Doe", "email": "jane@example.com" },
+            "skills": [{ "name": "Backend", "level": "Expert", "keywords": ["Rust"] }]
+        });
+        let import = |uri: &str, token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method(Method::POST)
+                .uri(uri)
+                .header(header::CONTENT_TYPE, "application/json");
+            if let Some(token) = token {
+                builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+            }
+            builder.body(Body::from(resume.to_string())).unwrap()
+        };
+
+        let response = app.clone().oneshot(import("/import/json-resume", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(import("/import/json-resume?dry_run=true", Some(&token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["data"]["dry_run"], true);
+        assert_eq!(json["data"]["skills"][0], json!({ "name": "Rust", "action": "create" }));
+
+        let response = app.clone().oneshot(import("/import/json-resume", Some(&token))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .oneshot(Request::builder().uri("/export/json-resume").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["basics"]["name"], "Jane Doe");
+        assert_eq!(json["skills"][0], json!({ "name": "Backend", "level": "Expert", "keywords": ["Rust"] }));
+    }
+}
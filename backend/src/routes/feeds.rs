@@ -0,0 +1,229 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+
+use crate::{
+    config::SiteConfig,
+    error::{ApiError, ErrorCode, ErrorResponse},
+    feed::FeedFormat,
+    routes::media::etag_matches,
+    services::FeedService,
+};
+
+/// Feed readers poll often; let shared caches absorb a few minutes of it
+const CACHE_CONTROL: &str = "public, max-age=300";
+
+/// State shared by the feed routes
+#[derive(Clone)]
+struct FeedState {
+    pool: SqlitePool,
+    site: SiteConfig,
+}
+
+/// Create feed routes: `/projects` and `/posts`, each as `.atom`, `.rss` and `.json` (public)
+pub fn create_routes(pool: SqlitePool, site: &SiteConfig) -> Router {
+    Router::new()
+        .route("/:file", get(get_feed))
+        .with_state(FeedState {
+            pool,
+            site: site.clone(),
+        })
+}
+
+/// GET /feeds/:name.:format - Atom, RSS or JSON Feed of the projects or posts, answering
+/// conditional requests with 304
+#[utoipa::path(
+    get,
+    path = "/feeds/{file}",
+    tag = "feeds",
+    params(
+        ("file" = String, Path, description = "`projects` or `posts`, then `.atom`, `.rss` or `.json`"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a feed already fetched"),
+        ("If-Modified-Since" = Option<String>, Header, description = "Last-Modified of a feed already fetched"),
+    ),
+    responses(
+        (status = 200, description = "The feed",
+            content(
+                (String = "application/atom+xml"),
+                (String = "application/rss+xml"),
+                (String = "application/feed+json"),
+            ),
+            headers(
+                ("ETag" = String, description = "Fingerprint of the feed"),
+                ("Last-Modified" = String, description = "Latest change to the feed's content"),
+            )),
+        (status = 304, description = "The feed did not change"),
+        (status = 404, description = "No such feed or format", body = ErrorResponse),
+    )
+)]
+async fn get_feed(
+    State(state): State<FeedState>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let not_found = || ApiError::NotFound(ErrorCode::NotFound, format!("Feed '{}' not found", file));
+    let (name, extension) = file.rsplit_once('.').ok_or_else(not_found)?;
+    let format = FeedFormat::from_extension(extension).ok_or_else(not_found)?;
+
+    let service = FeedService::new(state.pool, &state.site);
+    let feed = match name {
+        "projects" => service.projects_feed().await?,
+        "posts" => service.posts_feed().await?,
+        _ => return Err(not_found()),
+    };
+
+    let body = feed.render(format);
+    let etag = format!("\"{:x}\"", Sha256::digest(body.as_bytes()));
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::LAST_MODIFIED, http_date(feed.updated)),
+        (header::CACHE_CONTROL, CACHE_CONTROL.to_string()),
+    ];
+
+    if is_fresh(&headers, &etag, feed.updated) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+    Ok((cache_headers, [(header::CONTENT_TYPE, format.content_type())], body).into_response())
+}
+
+/// Whether the client's copy is current. `If-None-Match` takes precedence over
+/// `If-Modified-Since`, which is only precise to the second.
+fn is_fresh(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if headers.contains_key(header::IF_NONE_MATCH) {
+        return etag_matches(headers, etag);
+    }
+
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+fn http_date(date: DateTime<Utc>) -> String {
+    date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_feed_routes() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let app = create_routes(pool, &SiteConfig::default());
+
+        let get = |uri: &str, condition: Option<(header::HeaderName, &str)>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some((name, value)) = condition {
+                builder = builder.header(name, value);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        for (uri, content_type) in [
+            ("/projects.atom", "application/atom+xml; charset=utf-8"),
+            ("/projects.rss", "application/rss+xml; charset=utf-8"),
+            ("/posts.json", "application/feed+json; charset=utf-8"),
+        ] {
+            let response = app.clone().oneshot(get(uri, None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[header::CONTENT_TYPE], content_type);
+        }
+
+        let response = app.clone().oneshot(get("/projects.atom", None)).await.unwrap();
+        let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+        let last_modified = response.headers()[header::LAST_MODIFIED].to_str().unwrap().to_string();
+
+        let response = app
+            .clone()
+            .oneshot(get("/projects.atom", Some((header::IF_NONE_MATCH, &etag))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = app
+            .clone()
+            .oneshot(get("/projects.atom", Some((header::IF_MODIFIED_SINCE, &last_modified))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let response = app
+            .clone()
+            .oneshot(get("/projects.atom", Some((header::IF_MODIFIED_SINCE, "Mon, 01 Jan 2001 00:00:00 GMT"))))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for uri in ["/projects.xml", "/comments.rss", "/projects"] {
+            let response = app.clone().oneshot(get(uri, None)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+    }
+    #[tokio::test]
+    async fn test_deletion_changes_last_modified() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let projects = crate::services::ProjectService::new(pool.clone());
+        let mut ids = Vec::new();
+        for title in ["Older project", "Newer project"] {
+            let project = projects
+                .create_project(crate::models::CreateProject {
+                    title: title.to_string(),
+                    description: "A project in the feed".to_string(),
+                    long_description: None,
+                    technologies: vec!["Rust".to_string()],
+                    github_url: None,
+                    demo_url: None,
+                    image_url: None,
+                    category: "web".to_string(),
+                    featured: None,
+                })
+                .await
+                .unwrap();
+            ids.push(project.id);
+        }
+
+        // Everything so far happened an hour ago
+        for statement in [
+            "UPDATE projects SET updated_at = datetime('now', '-1 hour')",
+            "UPDATE content_changes SET changed_at = datetime('now', '-1 hour')",
+        ] {
+            sqlx::query(statement).execute(&pool).await.unwrap();
+        }
+        let app = create_routes(pool, &SiteConfig::default());
+        let get = |last_modified: Option<&str>| {
+            let mut builder = Request::builder().uri("/projects.atom");
+            if let Some(last_modified) = last_modified {
+                builder = builder.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(get(None)).await.unwrap();
+        let last_modified = response.headers()[header::LAST_MODIFIED].to_str().unwrap().to_string();
+        let response = app.clone().oneshot(get(Some(&last_modified))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // Deleting a project leaves the newest entry as it was, yet the feed changed
+        projects.delete_project(ids[0]).await.unwrap();
+        let response = app.clone().oneshot(get(Some(&last_modified))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::LAST_MODIFIED], last_modified.as_str());
+    }
+}
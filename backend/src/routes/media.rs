@@ -0,0 +1,428 @@
+use axum::{
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware,
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use tower::ServiceExt;
+use tower_http::services::ServeFile;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    auth::{require_scope, AuthConfig, AuthState},
+    config::UploadConfig,
+    error::{ApiError, ApiResponse, ErrorCode, ErrorResponse, PaginationInfo},
+    models::{ApiScope, MediaResponse, VariantFormat},
+    services::{media_service::MAX_PAGE_SIZE, MediaService},
+};
+
+/// Media never change under a given hash, so clients may keep them for a year
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Room left in the request body for the multipart framing around the file
+const MULTIPART_OVERHEAD_BYTES: u64 = 64 * 1024;
+
+/// Query parameters for media listing
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MediaQuery {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+/// Derivative to serve instead of the original file
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VariantQuery {
+    /// Width in pixels, among the configured ones
+    pub w: Option<u32>,
+    /// `jpeg`, `png`, `webp` or `avif`
+    pub fmt: Option<String>,
+}
+
+/// Multipart form of an upload, as documented; the handler reads the fields itself
+#[derive(ToSchema)]
+#[allow(dead_code)]
+struct MediaUpload {
+    /// PNG, JPEG, WebP or GIF image
+    #[schema(value_type = String, format = Binary)]
+    file: Vec<u8>,
+}
+
+/// State shared by the media routes
+#[derive(Clone)]
+struct MediaState {
+    pool: SqlitePool,
+    uploads: UploadConfig,
+}
+
+impl MediaState {
+    fn service(&self) -> MediaService {
+        MediaService::new(self.pool.clone(), &self.uploads)
+    }
+}
+
+/// Create media routes (files are public; uploading, listing and deleting require an admin or
+/// a `projects:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig, uploads: &UploadConfig) -> Router {
+    let body_limit = uploads.max_file_size_bytes.saturating_add(MULTIPART_OVERHEAD_BYTES);
+    let admin_routes = Router::new()
+        .route("/", get(list_media).post(upload_media))
+        .route("/:hash", delete(delete_media))
+        .layer(DefaultBodyLimit::max(usize::try_from(body_limit).unwrap_or(usize::MAX)))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ProjectsWrite),
+            require_scope,
+        ));
+
+    Router::new()
+        .route("/:hash", get(serve_media))
+        .merge(admin_routes)
+        .with_state(MediaState {
+            pool,
+            uploads: uploads.clone(),
+        })
+}
+
+/// POST /api/media - Upload an image as the `file` field of a multipart form
+#[utoipa::path(
+    post,
+    path = "/api/media",
+    tag = "media",
+    request_body(content = MediaUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 201, description = "The stored image", body = ApiResponse<MediaResponse>),
+        (status = 200, description = "The same image was already stored", body = ApiResponse<MediaResponse>),
+        (status = 400, description = "Missing or empty `file` field", body = ErrorResponse),
+        (status = 413, description = "The file is larger than `uploads.max_file_size_bytes`", body = ErrorResponse),
+        (status = 415, description = "Not a readable PNG, JPEG, WebP or GIF image", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
+async fn upload_media(
+    State(state): State<MediaState>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ApiResponse<MediaResponse>>), ApiError> {
+    while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.file_name().map(str::to_string);
+        let bytes = field.bytes().await.map_err(multipart_error)?;
+        let (media, created) = state.service().upload(&bytes, file_name.as_deref()).await?;
+
+        let (status, message) = if created {
+            (StatusCode::CREATED, "Media uploaded successfully")
+        } else {
+            (StatusCode::OK, "Media already stored")
+        };
+        return Ok((
+            status,
+            Json(ApiResponse::success_with_message(MediaResponse::from(media), message.to_string())),
+        ));
+    }
+
+    Err(ApiError::BadRequest("Missing multipart field 'file'".to_string()))
+}
+
+fn multipart_error(error: axum::extract::multipart::MultipartError) -> ApiError {
+    if error.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        ApiError::PayloadTooLarge("The uploaded file is too large".to_string())
+    } else {
+        ApiError::BadRequest(format!("Invalid multipart body: {}", error.body_text()))
+    }
+}
+
+/// GET /api/media - List uploaded media, newest first
+#[utoipa::path(
+    get,
+    path = "/api/media",
+    tag = "media",
+    params(MediaQuery),
+    responses(
+        (status = 200, description = "A page of media", body = ApiResponse<Vec<MediaResponse>>),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
+async fn list_media(
+    State(state): State<MediaState>,
+    Query(params): Query<MediaQuery>,
+) -> Result<Json<ApiResponse<Vec<MediaResponse>>>, ApiError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(MAX_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let (media, total_count) = state.service().list_media(page, page_size).await?;
+    let pagination = PaginationInfo::new(Some(page), page_size, total_count, None);
+    Ok(Json(ApiResponse::success_with_pagination(media, pagination)))
+}
+
+/// GET /api/media/:hash - The stored file, cacheable forever and revalidated by ETag.
+/// With `w` and/or `fmt`, a derivative of it, rendered on first request.
+#[utoipa::path(
+    get,
+    path = "/api/media/{hash}",
+    tag = "media",
+    params(
+        ("hash" = String, Path, description = "SHA-256 of the original file"),
+        VariantQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a copy already downloaded"),
+    ),
+    responses(
+        (status = 200, description = "The image", content_type = "image/*",
+            headers(("ETag" = String, description = "Version of the file"))),
+        (status = 206, description = "The requested range of the image", content_type = "image/*"),
+        (status = 304, description = "The copy is current"),
+        (status = 400, description = "Unknown format or width", body = ErrorResponse),
+        (status = 404, description = "No such media", body = ErrorResponse),
+    )
+)]
+async fn serve_media(
+    State(state): State<MediaState>,
+    Path(hash): Path<String>,
+    Query(params): Query<VariantQuery>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    let service = state.service();
+    let media = service.get_by_hash(&hash).await?;
+
+    if params.w.is_some() || params.fmt.is_some() {
+        let format = match params.fmt.as_deref() {
+            Some(name) => Some(VariantFormat::from_str(name).ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "Unknown format '{}', use one of: {}",
+                    name,
+                    VariantFormat::all().join(", ")
+                ))
+            })?),
+            None => None,
+        };
+        let (variant, path) = service.variant(&media, params.w, format).await?;
+        let etag = format!("\"{}-w{}.{}\"", media.hash, variant.width, variant.format);
+        let content_type = HeaderValue::from_static(variant.variant_format().content_type());
+        return serve_file(path, etag, Some(content_type), request).await;
+    }
+
+    let etag = format!("\"{}\"", media.hash);
+    serve_file(service.file_path(&media), etag, None, request).await
+}
+
+/// Answer with a stored file, or with 304 when the client already has this version
+async fn serve_file(
+    path: std::path::PathBuf,
+    etag: String,
+    content_type: Option<HeaderValue>,
+    request: Request,
+) -> Result<Response, ApiError> {
+    if etag_matches(request.headers(), &etag) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        add_cache_headers(response.headers_mut(), &etag);
+        return Ok(response);
+    }
+
+    if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        return Err(ApiError::NotFound(ErrorCode::MediaNotFound, "Media file not found".to_string()));
+    }
+
+    // Range requests and HEAD are answered by the file service
+    let mut response = match ServeFile::new(path).oneshot(request).await {
+        Ok(response) => response.map(Body::new),
+        Err(infallible) => match infallible {},
+    };
+    if response.status().is_success() {
+        add_cache_headers(response.headers_mut(), &etag);
+        if let Some(content_type) = content_type {
+            response.headers_mut().insert(header::CONTENT_TYPE, content_type);
+        }
+    }
+    Ok(response)
+}
+
+/// Whether `If-None-Match` names the current version
+pub(crate) fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+fn add_cache_headers(headers: &mut HeaderMap, etag: &str) {
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static(CACHE_CONTROL));
+    if let Ok(etag) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, etag);
+    }
+}
+
+/// DELETE /api/media/:hash - Delete media that no project gallery shows
+#[utoipa::path(
+    delete,
+    path = "/api/media/{hash}",
+    tag = "media",
+    params(("hash" = String, Path, description = "SHA-256 of the original file")),
+    responses(
+        (status = 200, description = "The media was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such media", body = ErrorResponse),
+        (status = 409, description = "A project gallery shows the media", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
+async fn delete_media(
+    State(state): State<MediaState>,
+    Path(hash): Path<String>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    state.service().delete_media(&hash).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        json!({}),
+        "Media deleted successfully".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Method;
+
+    use crate::{media::derivative::test_image, models::MediaType};
+
+    const BOUNDARY: &str = "portfolio-test-boundary";
+
+    async fn create_test_app(dir: &std::path::Path) -> (Router, String) {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+        let uploads = UploadConfig {
+            dir: dir.to_path_buf(),
+            max_file_size_bytes: 1024,
+            variant_widths: vec![8],
+            variant_formats: vec!["webp".to_string()],
+            ..UploadConfig::default()
+        };
+        (create_routes(pool, auth, &uploads), token)
+    }
+
+    fn upload_request(token: &str, file_name: &str, bytes: &[u8]) -> axum::http::Request<Body> {
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: image/png\r\n\r\n",
+            BOUNDARY, file_name
+        )
+        .into_bytes();
+        body.extend_from_slice(bytes);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+
+        axum::http::Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .header(header::CONTENT_TYPE, format!("multipart/form-data; boundary={}", BOUNDARY))
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn json_body(response: Response) -> Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_upload_and_serve() {
+        let dir = tempfile::tempdir().unwrap();
+        let (app, token) = create_test_app(dir.path()).await;
+        let png = test_image(MediaType::Png, 16, 12);
+
+        let response = app.clone().oneshot(upload_request(&token, "shot.png", &png)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let media = json_body(response).await["data"].clone();
+        let url = media["url"].as_str().unwrap().trim_start_matches("/api/media").to_string();
+        assert_eq!(media["content_type"], "image/png");
+
+        // The same content is stored once
+        let response = app.clone().oneshot(upload_request(&token, "copy.png", &png)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["data"]["id"], media["id"]);
+
+        let request = axum::http::Request::builder().uri(&url).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], CACHE_CONTROL);
+        let etag = response.headers()[header::ETAG].clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], &png[..]);
+
+        let request = axum::http::Request::builder()
+            .uri(&url)
+            .header(header::IF_NONE_MATCH, etag)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        let request = axum::http::Request::builder().uri(format!("/{}", "0".repeat(64))).body(Body::empty()).unwrap();
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_serve_variant() {
+        let dir = tempfile::tempdir().unwrap();
+        let (app, token) = create_test_app(dir.path()).await;
+        let response = app
+            .clone()
+            .oneshot(upload_request(&token, "shot.png", &test_image(MediaType::Png, 16, 12)))
+            .await
+            .unwrap();
+        let url = json_body(response).await["data"]["url"].as_str().unwrap().trim_start_matches("/api/media").to_string();
+
+        let get = |query: &str| axum::http::Request::builder().uri(format!("{}?{}", url, query)).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(get("w=8&fmt=webp")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/webp");
+        assert_eq!(response.headers()[header::CACHE_CONTROL], CACHE_CONTROL);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(MediaType::sniff(&body), Some(MediaType::Webp));
+
+        // The rendered variant is listed with its dimensions
+        let request = axum::http::Request::builder()
+            .uri("/")
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+        let listed = json_body(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(listed["data"][0]["variants"][0]["url"], format!("/api/media{}?w=8&fmt=webp", url));
+        assert_eq!(listed["data"][0]["variants"][0]["height"], 6);
+
+        assert_eq!(app.clone().oneshot(get("w=9")).await.unwrap().status(), StatusCode::BAD_REQUEST);
+        assert_eq!(app.clone().oneshot(get("fmt=tiff")).await.unwrap().status(), StatusCode::BAD_REQUEST);
+        assert_eq!(app.oneshot(get("w=wide")).await.unwrap().status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let (app, token) = create_test_app(dir.path()).await;
+
+        let response = app.clone().oneshot(upload_request(&token, "page.png", b"<html></html>")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let mut large = test_image(MediaType::Png, 8, 8);
+        large.resize(4096, 0);
+        let response = app.clone().oneshot(upload_request(&token, "large.png", &large)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let mut request = upload_request(&token, "shot.png", &test_image(MediaType::Png, 8, 8));
+        request.headers_mut().remove(header::AUTHORIZATION);
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::UNAUTHORIZED);
+    }
+}
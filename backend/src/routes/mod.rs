@@ -2,15 +2,63 @@ pub mod projects;
 pub mod skills;
 pub mod profile;
 pub mod contact;
+pub mod auth;
+pub mod api_keys;
+pub mod technologies;
+pub mod search;
+pub mod media;
+pub mod posts;
+pub mod experience;
+pub mod education;
+pub mod certifications;
+pub mod export;
+pub mod feeds;
+pub mod sitemap;
+pub mod docs;
 
 use axum::Router;
 use sqlx::SqlitePool;
 
+use crate::{auth::AuthConfig, config::AppConfig};
+
 /// Create the main API router with all routes
-pub fn create_router(pool: SqlitePool) -> Router {
-    Router::new()
-        .nest("/api/projects", projects::create_routes(pool.clone()))
-        .nest("/api/skills", skills::create_routes(pool.clone()))
-        .nest("/api/profile", profile::create_routes(pool.clone()))
-        .nest("/api/contact", contact::create_routes(pool))
+pub fn create_router(pool: SqlitePool, auth: AuthConfig) -> Router {
+    create_router_with_config(pool, auth, &AppConfig::default())
+}
+
+/// Create the main API router, leaving out disabled features
+pub fn create_router_with_config(pool: SqlitePool, auth: AuthConfig, config: &AppConfig) -> Router {
+    let features = &config.features;
+    let mut router = Router::new()
+        .nest("/api/auth", auth::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/projects", projects::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/skills", skills::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/profile", profile::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/technologies", technologies::create_routes(pool.clone()))
+        .nest("/api/search", search::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/media", media::create_routes(pool.clone(), auth.clone(), &config.uploads))
+        .nest("/api/posts", posts::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/experience", experience::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/education", education::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/certifications", certifications::create_routes(pool.clone(), auth.clone()))
+        .nest("/api/export", export::create_export_routes(pool.clone()))
+        .nest("/api/import", export::create_import_routes(pool.clone(), auth.clone()))
+        .nest("/feeds", feeds::create_routes(pool.clone(), &config.site))
+        .merge(sitemap::create_routes(pool.clone(), &config.site));
+
+    if features.api_keys {
+        router = router.nest("/api/keys", api_keys::create_routes(pool.clone(), auth.clone()));
+    }
+
+    if features.api_docs {
+        router = router.merge(docs::create_routes(config));
+    }
+
+    let contact_routes = if features.contact_form {
+        contact::create_routes(pool, auth, config)
+    } else {
+        contact::create_admin_routes(pool, auth)
+    };
+
+    router.nest("/api/contact", contact_routes)
 }
\ No newline at end of file
@@ -0,0 +1,378 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    middleware,
+    response::{IntoResponse, Json, Response},
+    routing::{get, post, put},
+    Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use utoipa::IntoParams;
+
+use crate::{
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse, PaginationInfo},
+    markdown::{self, highlight},
+    models::{ApiScope, CreatePost, PostResponse, PostStatus, PostSummary, UpdatePost},
+    services::{post_service::MAX_PAGE_SIZE, PostService},
+};
+
+/// Page size of post listings when none is asked for
+const DEFAULT_PAGE_SIZE: u32 = 10;
+
+/// Query parameters for post listing
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PostQuery {
+    pub tag: Option<String>,
+    /// `draft`, `published` or `scheduled`; only for the admin listing
+    #[param(value_type = Option<PostStatus>)]
+    pub status: Option<String>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+/// Query parameters for the code highlighting stylesheet
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StylesheetQuery {
+    /// One of the bundled themes; an unknown one is answered with the list
+    pub theme: Option<String>,
+}
+
+/// Create post routes (published posts are public; drafts and writes require an admin or a
+/// `posts:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", post(create_post))
+        .route("/admin", get(get_all_posts))
+        .route("/admin/:slug", get(get_any_post))
+        .route("/:slug", put(update_post).delete(delete_post))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::PostsWrite),
+            require_scope,
+        ));
+
+    Router::new()
+        .route("/", get(get_posts))
+        .route("/highlight.css", get(get_stylesheet))
+        .route("/:slug", get(get_post))
+        .merge(admin_routes)
+        .with_state(pool)
+}
+
+/// GET /api/posts - List published posts, newest first, optionally by tag
+#[utoipa::path(
+    get,
+    path = "/api/posts",
+    tag = "posts",
+    params(PostQuery),
+    responses(
+        (status = 200, description = "A page of published posts", body = ApiResponse<Vec<PostSummary>>),
+    )
+)]
+async fn get_posts(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<PostQuery>,
+) -> Result<Json<ApiResponse<Vec<PostSummary>>>, ApiError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let service = PostService::new(pool);
+    let (posts, total_count) = service.list_published(params.tag.as_deref(), page, page_size).await?;
+    let pagination = PaginationInfo::new(Some(page), page_size, total_count, None);
+    Ok(Json(ApiResponse::success_with_pagination(posts, pagination)))
+}
+
+/// GET /api/posts/:slug - Get a published post
+#[utoipa::path(
+    get,
+    path = "/api/posts/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "The post", body = ApiResponse<PostResponse>),
+        (status = 404, description = "No such published post", body = ErrorResponse),
+    )
+)]
+async fn get_post(
+    State(pool): State<SqlitePool>,
+    Path(slug): Path<String>,
+) -> Result<Json<ApiResponse<PostResponse>>, ApiError> {
+    let service = PostService::new(pool);
+    let post = service.get_published(&slug).await?;
+    Ok(Json(ApiResponse::success(post)))
+}
+
+/// GET /api/posts/highlight.css - Stylesheet coloring highlighted code, in one of the bundled
+/// themes
+#[utoipa::path(
+    get,
+    path = "/api/posts/highlight.css",
+    tag = "posts",
+    params(StylesheetQuery),
+    responses(
+        (status = 200, description = "The stylesheet", body = String, content_type = "text/css"),
+        (status = 400, description = "Unknown theme", body = ErrorResponse),
+    )
+)]
+async fn get_stylesheet(Query(params): Query<StylesheetQuery>) -> Result<Response, ApiError> {
+    let theme = params.theme.as_deref().unwrap_or(highlight::DEFAULT_THEME);
+    let css = markdown::stylesheet(theme).ok_or_else(|| {
+        let mut themes = highlight::theme_names();
+        themes.sort_unstable();
+        ApiError::BadRequest(format!("Unknown theme '{}', use one of: {}", theme, themes.join(", ")))
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/css; charset=utf-8"),
+            (header::CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        css,
+    )
+        .into_response())
+}
+
+/// GET /api/posts/admin - List every post, drafts included, optionally by status and tag
+#[utoipa::path(
+    get,
+    path = "/api/posts/admin",
+    tag = "posts",
+    params(PostQuery),
+    responses(
+        (status = 200, description = "A page of posts", body = ApiResponse<Vec<PostSummary>>),
+        (status = 400, description = "Unknown status", body = ErrorResponse),
+    ),
+    security(("bearer" = ["posts:write"]), ("api_key" = ["posts:write"])),
+)]
+async fn get_all_posts(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<PostQuery>,
+) -> Result<Json<ApiResponse<Vec<PostSummary>>>, ApiError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let service = PostService::new(pool);
+    let (posts, total_count) = service
+        .list_posts(params.status.as_deref(), params.tag.as_deref(), page, page_size)
+        .await?;
+    let pagination = PaginationInfo::new(Some(page), page_size, total_count, None);
+    Ok(Json(ApiResponse::success_with_pagination(posts, pagination)))
+}
+
+/// GET /api/posts/admin/:slug - Get any post, drafts included
+#[utoipa::path(
+    get,
+    path = "/api/posts/admin/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "The post", body = ApiResponse<PostResponse>),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+    security(("bearer" = ["posts:write"]), ("api_key" = ["posts:write"])),
+)]
+async fn get_any_post(
+    State(pool): State<SqlitePool>,
+    Path(slug): Path<String>,
+) -> Result<Json<ApiResponse<PostResponse>>, ApiError> {
+    let service = PostService::new(pool);
+    let post = service.get_post(&slug).await?;
+    Ok(Json(ApiResponse::success(post)))
+}
+
+/// POST /api/posts - Create a post
+#[utoipa::path(
+    post,
+    path = "/api/posts",
+    tag = "posts",
+    request_body = CreatePost,
+    responses(
+        (status = 201, description = "The created post", body = ApiResponse<PostResponse>),
+        (status = 400, description = "Invalid post, slug, status or publication date", body = ErrorResponse),
+        (status = 409, description = "Another post has this slug", body = ErrorResponse),
+    ),
+    security(("bearer" = ["posts:write"]), ("api_key" = ["posts:write"])),
+)]
+async fn create_post(
+    State(pool): State<SqlitePool>,
+    Json(post_data): Json<CreatePost>,
+) -> Result<(StatusCode, Json<ApiResponse<PostResponse>>), ApiError> {
+    let service = PostService::new(pool);
+    let post = service.create_post(post_data).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success_with_message(post, "Post created successfully".to_string())),
+    ))
+}
+
+/// PUT /api/posts/:slug - Update a post
+#[utoipa::path(
+    put,
+    path = "/api/posts/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    request_body = UpdatePost,
+    responses(
+        (status = 200, description = "The updated post", body = ApiResponse<PostResponse>),
+        (status = 400, description = "Invalid changes", body = ErrorResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+        (status = 409, description = "Another post has the new slug", body = ErrorResponse),
+    ),
+    security(("bearer" = ["posts:write"]), ("api_key" = ["posts:write"])),
+)]
+async fn update_post(
+    State(pool): State<SqlitePool>,
+    Path(slug): Path<String>,
+    Json(post_data): Json<UpdatePost>,
+) -> Result<Json<ApiResponse<PostResponse>>, ApiError> {
+    let service = PostService::new(pool);
+    let post = service.update_post(&slug, post_data).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        post,
+        "Post updated successfully".to_string(),
+    )))
+}
+
+/// DELETE /api/posts/:slug - Delete a post
+#[utoipa::path(
+    delete,
+    path = "/api/posts/{slug}",
+    tag = "posts",
+    params(("slug" = String, Path, description = "Post slug")),
+    responses(
+        (status = 200, description = "The post was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+    security(("bearer" = ["posts:write"]), ("api_key" = ["posts:write"])),
+)]
+async fn delete_post(
+    State(pool): State<SqlitePool>,
+    Path(slug): Path<String>,
+) -> Result<Json<ApiResponse<Value>>, ApiError> {
+    let service = PostService::new(pool);
+    service.delete_post(&slug).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        json!({}),
+        "Post deleted successfully".to_string(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{Method, Request}};
+    use tower::ServiceExt;
+
+    async fn create_test_app() -> (Router, String) {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+        (create_routes(pool, auth), token)
+    }
+
+    fn request(method: Method, uri: &str, token: Option<&str>, body: Option<Value>) -> Request<Body> {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        }
+    }
+
+    async fn json_body(response: Response) -> Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_drafts_need_auth() {
+        let (app, token) = create_test_app().await;
+        let draft = json!({ "title": "Work in progress", "summary": "Soon", "body": "Not *yet*" });
+
+        let response = app.clone().oneshot(request(Method::POST, "/", None, Some(draft.clone()))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app.clone().oneshot(request(Method::POST, "/", Some(&token), Some(draft))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(json_body(response).await["data"]["slug"], "work-in-progress");
+
+        let response = app.clone().oneshot(request(Method::GET, "/work-in-progress", None, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = app.clone().oneshot(request(Method::GET, "/admin", None, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::GET, "/admin/work-in-progress", Some(&token), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(json_body(response).await["data"]["status"], "draft");
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::GET, "/admin?status=draft", Some(&token), None))
+            .await
+            .unwrap();
+        assert_eq!(json_body(response).await["pagination"]["total_count"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_and_read() {
+        let (app, token) = create_test_app().await;
+        let post = json!({
+            "title": "Hello",
+            "summary": "First post",
+            "body": "# Hello\n\n<script>alert(1)</script>",
+            "tags": ["Rust"],
+            "status": "published"
+        });
+
+        let response = app.clone().oneshot(request(Method::POST, "/", Some(&token), Some(post))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app.clone().oneshot(request(Method::GET, "/?tag=rust", None, None)).await.unwrap();
+        let listing = json_body(response).await;
+        assert_eq!(listing["data"][0]["slug"], "hello");
+        assert!(listing["data"][0].get("body_html").is_none());
+
+        let response = app.clone().oneshot(request(Method::GET, "/hello", None, None)).await.unwrap();
+        let body_html = json_body(response).await["data"]["body_html"].as_str().unwrap().to_string();
+        assert!(body_html.contains("<h1>Hello</h1>"));
+        assert!(!body_html.contains("<script>"));
+
+        let update = json!({ "status": "draft" });
+        let response = app.clone().oneshot(request(Method::PUT, "/hello", Some(&token), Some(update))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.clone().oneshot(request(Method::GET, "/hello", None, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = app.clone().oneshot(request(Method::DELETE, "/hello", Some(&token), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_stylesheet() {
+        let (app, _) = create_test_app().await;
+
+        let response = app.clone().oneshot(request(Method::GET, "/highlight.css", None, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "text/css; charset=utf-8");
+
+        let response = app.oneshot(request(Method::GET, "/highlight.css?theme=nope", None, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
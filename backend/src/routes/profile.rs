@@ -1,29 +1,94 @@
 use axum::{
-    extract::State,
-    response::Json,
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    middleware,
     routing::{get, put},
     Router,
 };
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    error::ApiError,
-    models::{Profile, UpdateProfile},
-    routes::projects::ApiResponse,
-    services::{ProfileService, profile_service::ProfileSummary},
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiScope, Profile, TimelineEntry, UpdateProfile},
+    resume::{PageSize, ResumeLanguage, ResumeTemplate},
+    routes::media::etag_matches,
+    services::{
+        ProfileService, ResumePdfService,
+        profile_service::ProfileSummary,
+        resume_pdf_service::ResumeOptions,
+    },
 };
 
-/// Create profile routes
-pub fn create_routes(pool: SqlitePool) -> Router {
+/// Query parameters for the PDF résumé
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ResumeQuery {
+    /// `classic` (default) or `modern`
+    pub template: Option<String>,
+    /// `a4` (default) or `letter`
+    pub page_size: Option<String>,
+    /// `en` (default) or `fr`
+    pub lang: Option<String>,
+}
+
+impl ResumeQuery {
+    fn options(&self) -> Result<ResumeOptions, ApiError> {
+        let template = match self.template.as_deref() {
+            Some(value) => ResumeTemplate::from_str(value)
+                .ok_or_else(|| unknown_option("template", value, ResumeTemplate::all()))?,
+            None => ResumeTemplate::Classic,
+        };
+        let page_size = match self.page_size.as_deref() {
+            Some(value) => PageSize::from_str(value)
+                .ok_or_else(|| unknown_option("page size", value, PageSize::all()))?,
+            None => PageSize::A4,
+        };
+        let language = match self.lang.as_deref() {
+            Some(value) => ResumeLanguage::from_str(value)
+                .ok_or_else(|| unknown_option("language", value, ResumeLanguage::all()))?,
+            None => ResumeLanguage::En,
+        };
+        Ok(ResumeOptions { template, page_size, language })
+    }
+}
+
+fn unknown_option(name: &str, value: &str, allowed: Vec<&str>) -> ApiError {
+    ApiError::BadRequest(format!("Unknown {} '{}', use one of: {}", name, value, allowed.join(", ")))
+}
+
+/// Create profile routes (reads are public, updates require an admin or a `profile:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", put(update_profile))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ProfileWrite),
+            require_scope,
+        ));
+
     Router::new()
-        .route("/", get(get_profile).put(update_profile))
+        .route("/", get(get_profile))
         .route("/summary", get(get_profile_summary))
         .route("/exists", get(check_profile_exists))
+        .route("/timeline", get(get_timeline))
+        .route("/resume.pdf", get(get_resume_pdf))
+        .merge(admin_routes)
         .with_state(pool)
 }
 
 /// GET /api/profile - Get the profile
+#[utoipa::path(
+    get,
+    path = "/api/profile",
+    tag = "profile",
+    responses(
+        (status = 200, description = "The profile", body = ApiResponse<Profile>),
+        (status = 404, description = "The profile was never created", body = ErrorResponse),
+    )
+)]
 async fn get_profile(
     State(pool): State<SqlitePool>,
 ) -> Result<Json<ApiResponse<Profile>>, ApiError> {
@@ -33,6 +98,17 @@ async fn get_profile(
 }
 
 /// PUT /api/profile - Update the profile
+#[utoipa::path(
+    put,
+    path = "/api/profile",
+    tag = "profile",
+    request_body = UpdateProfile,
+    responses(
+        (status = 200, description = "The updated profile", body = ApiResponse<Profile>),
+        (status = 400, description = "Invalid changes", body = ErrorResponse),
+    ),
+    security(("bearer" = ["profile:write"]), ("api_key" = ["profile:write"])),
+)]
 async fn update_profile(
     State(pool): State<SqlitePool>,
     Json(profile_data): Json<UpdateProfile>,
@@ -46,6 +122,15 @@ async fn update_profile(
 }
 
 /// GET /api/profile/summary - Get profile summary (public info only)
+#[utoipa::path(
+    get,
+    path = "/api/profile/summary",
+    tag = "profile",
+    responses(
+        (status = 200, description = "The public part of the profile", body = ApiResponse<ProfileSummary>),
+        (status = 404, description = "The profile was never created", body = ErrorResponse),
+    )
+)]
 async fn get_profile_summary(
     State(pool): State<SqlitePool>,
 ) -> Result<Json<ApiResponse<ProfileSummary>>, ApiError> {
@@ -54,7 +139,81 @@ async fn get_profile_summary(
     Ok(Json(ApiResponse::success(summary)))
 }
 
+/// GET /api/profile/timeline - Work experience, education and certifications, newest first
+#[utoipa::path(
+    get,
+    path = "/api/profile/timeline",
+    tag = "profile",
+    responses(
+        (status = 200, description = "Timeline entries, newest first", body = ApiResponse<Vec<TimelineEntry>>),
+    )
+)]
+async fn get_timeline(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ApiResponse<Vec<TimelineEntry>>>, ApiError> {
+    let service = ProfileService::new(pool);
+    let timeline = service.get_timeline().await?;
+    Ok(Json(ApiResponse::success(timeline)))
+}
+
+/// GET /api/profile/resume.pdf - The profile, featured projects and skills as a PDF résumé.
+/// Rendered once per content version and options, then served from the cache.
+#[utoipa::path(
+    get,
+    path = "/api/profile/resume.pdf",
+    tag = "profile",
+    params(
+        ResumeQuery,
+        ("If-None-Match" = Option<String>, Header, description = "ETag of a résumé already downloaded"),
+    ),
+    responses(
+        (status = 200, description = "The résumé", content_type = "application/pdf",
+            headers(("ETag" = String, description = "Fingerprint of the content and options"))),
+        (status = 304, description = "The résumé did not change"),
+        (status = 400, description = "Unknown template, page size or language", body = ErrorResponse),
+    )
+)]
+async fn get_resume_pdf(
+    State(pool): State<SqlitePool>,
+    Query(params): Query<ResumeQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let options = params.options()?;
+    let service = ResumePdfService::new(pool);
+    let resume = service.get_resume(options).await?;
+
+    let etag = format!("\"{}\"", resume.fingerprint);
+    // The content behind the URL changes with every edit, so clients must revalidate
+    let cache_headers = [(header::ETAG, etag.clone()), (header::CACHE_CONTROL, "no-cache".to_string())];
+    if etag_matches(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, cache_headers).into_response());
+    }
+
+    let disposition = format!(
+        "inline; filename=\"resume-{}-{}.pdf\"",
+        options.language.as_str(),
+        options.page_size.as_str()
+    );
+    Ok((
+        cache_headers,
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        resume.content,
+    )
+        .into_response())
+}
+
 /// GET /api/profile/exists - Check if profile exists
+#[utoipa::path(
+    get,
+    path = "/api/profile/exists",
+    tag = "profile",
+    responses(
+        (status = 200, description = "Whether the profile was created", body = ApiResponse<ProfileExistsResponse>),
+    )
+)]
 async fn check_profile_exists(
     State(pool): State<SqlitePool>,
 ) -> Result<Json<ApiResponse<ProfileExistsResponse>>, ApiError> {
@@ -64,7 +223,7 @@ async fn check_profile_exists(
 }
 
 /// Response for profile exists endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProfileExistsResponse {
     pub exists: bool,
 }
@@ -74,55 +233,36 @@ mod tests {
     use super::*;
     use axum::{
         body::Body,
-        http::{Method, Request, StatusCode},
+        http::{Method, Request},
     };
     use serde_json::json;
     use sqlx::SqlitePool;
     use tower::ServiceExt;
 
-    async fn create_test_app() -> (Router, SqlitePool) {
+    async fn create_test_app() -> (Router, SqlitePool, String) {
         let pool = SqlitePool::connect("sqlite::memory:")
             .await
             .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
 
-        // Create table
+        // Replace the seeded profile
         sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS profile (
-                id INTEGER PRIMARY KEY,
-                name TEXT NOT NULL,
-                title TEXT NOT NULL,
-                bio TEXT NOT NULL,
-                email TEXT NOT NULL,
-                phone TEXT,
-                location TEXT NOT NULL,
-                avatar_url TEXT,
-                linkedin_url TEXT,
-                github_url TEXT,
-                twitter_url TEXT,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
+            "INSERT OR REPLACE INTO profile (id, name, title, bio, email, location) VALUES (1, 'Test User', 'Test Developer', 'Test bio', 'test@example.com', 'Test Location')"
         )
         .execute(&pool)
         .await
         .unwrap();
 
-        // Insert test profile
-        sqlx::query(
-            "INSERT INTO profile (id, name, title, bio, email, location) VALUES (1, 'Test User', 'Test Developer', 'Test bio', 'test@example.com', 'Test Location')"
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
 
-        let app = create_routes(pool.clone());
-        (app, pool)
+        let app = create_routes(pool.clone(), auth);
+        (app, pool, token)
     }
 
     #[tokio::test]
     async fn test_get_profile() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, _token) = create_test_app().await;
 
         let request = Request::builder()
             .method(Method::GET)
@@ -147,7 +287,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_profile() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, token) = create_test_app().await;
 
         let update_data = json!({
             "name": "Updated User",
@@ -158,6 +298,7 @@ mod tests {
         let request = Request::builder()
             .method(Method::PUT)
             .uri("/")
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(update_data.to_string()))
             .unwrap();
@@ -179,7 +320,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_profile_summary() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, _token) = create_test_app().await;
 
         let request = Request::builder()
             .method(Method::GET)
@@ -204,7 +345,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_check_profile_exists() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, _token) = create_test_app().await;
 
         let request = Request::builder()
             .method(Method::GET)
@@ -227,7 +368,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_profile_validation_error() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, token) = create_test_app().await;
 
         let update_data = json!({
             "email": "invalid-email"
@@ -236,6 +377,7 @@ mod tests {
         let request = Request::builder()
             .method(Method::PUT)
             .uri("/")
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(update_data.to_string()))
             .unwrap();
@@ -243,4 +385,58 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    #[tokio::test]
+    async fn test_update_profile_requires_admin() {
+        let (app, _pool, _token) = create_test_app().await;
+
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri("/")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "name": "Mallory" }).to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_get_resume_pdf() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let app = create_routes(pool, auth);
+
+        let get = |uri: &str, etag: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(etag) = etag {
+                builder = builder.header(header::IF_NONE_MATCH, etag);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let response = app.clone().oneshot(get("/resume.pdf?template=modern&page_size=letter&lang=fr", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/pdf");
+        let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.starts_with(b"%PDF-"));
+
+        let response = app
+            .clone()
+            .oneshot(get("/resume.pdf?template=modern&page_size=letter&lang=fr", Some(&etag)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // Each combination of options is a different document
+        let response = app.clone().oneshot(get("/resume.pdf", Some(&etag))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app.oneshot(get("/resume.pdf?page_size=a3", None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
\ No newline at end of file
@@ -1,147 +1,158 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
-    routing::{delete, get, post, put},
+    middleware,
+    routing::{get, post, put},
     Router,
 };
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
 use serde_json::{json, Value};
 use sqlx::SqlitePool;
+use utoipa::IntoParams;
 
 use crate::{
-    error::{ApiError, ApiResult},
-    models::{CreateProject, Project, ProjectResponse, UpdateProject},
-    services::ProjectService,
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse, PaginationInfo},
+    models::{
+        ApiScope, CreateProject, ProjectDetail, ProjectFilter, ProjectResponse, ProjectSort, SetProjectGallery, SetProjectSkills,
+        UpdateProject,
+    },
+    services::{project_service::MAX_PAGE_SIZE, ProjectService},
 };
 
-/// Query parameters for project listing
-#[derive(Debug, Deserialize)]
+/// Query parameters for project listing; all filters combine
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ProjectQuery {
     pub category: Option<String>,
     pub featured: Option<bool>,
+    /// Full-text search of titles, descriptions and technologies; `"quoted phrases"` and `prefix*` are supported
     pub search: Option<String>,
+    pub technology: Option<String>,
+    /// RFC 3339 timestamp or `YYYY-MM-DD` date (inclusive)
+    pub created_after: Option<String>,
+    /// RFC 3339 timestamp or `YYYY-MM-DD` date (exclusive)
+    pub created_before: Option<String>,
+    /// `created_at`, `updated_at` or `title`, prefixed with `-` for descending order
+    pub sort: Option<String>,
     pub page: Option<u32>,
     pub page_size: Option<u32>,
+    /// Opaque cursor from `pagination.next_cursor`
+    pub cursor: Option<String>,
 }
 
-/// Response wrapper for API responses
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ApiResponse<T> {
-    pub success: bool,
-    pub data: Option<T>,
-    pub message: Option<String>,
-    pub pagination: Option<PaginationInfo>,
-}
-
-/// Pagination information
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PaginationInfo {
-    pub page: u32,
-    pub page_size: u32,
-    pub total_count: u64,
-    pub total_pages: u64,
-}
-
-impl<T> ApiResponse<T> {
-    pub fn success(data: T) -> Self {
-        Self {
-            success: true,
-            data: Some(data),
-            message: None,
-            pagination: None,
-        }
-    }
+/// Create project routes (reads are public, writes require an admin or a `projects:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", post(create_project))
+        .route("/:id", put(update_project).delete(delete_project))
+        .route("/:id/skills", put(set_project_skills))
+        .route("/:id/gallery", put(set_project_gallery))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ProjectsWrite),
+            require_scope,
+        ));
 
-    pub fn success_with_message(data: T, message: String) -> Self {
-        Self {
-            success: true,
-            data: Some(data),
-            message: Some(message),
-            pagination: None,
-        }
-    }
-
-    pub fn success_with_pagination(data: T, pagination: PaginationInfo) -> Self {
-        Self {
-            success: true,
-            data: Some(data),
-            message: None,
-            pagination: Some(pagination),
-        }
-    }
-}
-
-/// Create project routes
-pub fn create_routes(pool: SqlitePool) -> Router {
     Router::new()
-        .route("/", get(get_projects).post(create_project))
-        .route("/:id", get(get_project_by_id).put(update_project).delete(delete_project))
+        .route("/", get(get_projects))
+        .route("/:id", get(get_project_by_id))
+        .merge(admin_routes)
         .with_state(pool)
 }
 
-/// GET /api/projects - Get all projects with optional filtering and pagination
+/// GET /api/projects - List projects with combinable filters, sorting and pagination
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    params(ProjectQuery),
+    responses(
+        (status = 200, description = "A page of projects", body = ApiResponse<Vec<ProjectResponse>>),
+        (status = 400, description = "Invalid filter, sort or cursor", body = ErrorResponse),
+    )
+)]
 async fn get_projects(
     State(pool): State<SqlitePool>,
     Query(params): Query<ProjectQuery>,
 ) -> Result<Json<ApiResponse<Vec<ProjectResponse>>>, ApiError> {
     let service = ProjectService::new(pool);
 
-    // Handle pagination
-    if let (Some(page), Some(page_size)) = (params.page, params.page_size) {
-        let (projects, total_count) = service.get_projects_paginated(page, page_size).await?;
-        let total_pages = (total_count as f64 / page_size as f64).ceil() as u64;
-        
-        let pagination = PaginationInfo {
-            page,
-            page_size,
-            total_count,
-            total_pages,
-        };
+    let sort = match params.sort.as_deref() {
+        Some(sort) => ProjectSort::from_str(sort).ok_or_else(|| {
+            ApiError::BadRequest(format!("Invalid sort '{}'. Valid sorts: {}", sort, ProjectSort::all().join(", ")))
+        })?,
+        None => ProjectSort::default(),
+    };
 
-        let project_responses: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
-        return Ok(Json(ApiResponse::success_with_pagination(project_responses, pagination)));
-    }
+    let filter = ProjectFilter {
+        category: params.category,
+        featured: params.featured,
+        technology: params.technology,
+        created_after: params.created_after.as_deref().map(|value| parse_date_param("created_after", value)).transpose()?,
+        created_before: params.created_before.as_deref().map(|value| parse_date_param("created_before", value)).transpose()?,
+        search: params.search,
+    };
 
-    // Handle search
-    if let Some(search_query) = params.search {
-        let projects = service.search_projects(&search_query).await?;
-        let project_responses: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
-        return Ok(Json(ApiResponse::success(project_responses)));
-    }
+    // Without a page or cursor, the first page is returned
+    let page = match (params.page, &params.cursor) {
+        (None, None) => Some(1),
+        (page, _) => page,
+    };
+    let page_size = params.page_size.unwrap_or(MAX_PAGE_SIZE);
 
-    // Handle category filtering
-    if let Some(category) = params.category {
-        let projects = service.get_projects_by_category(&category).await?;
-        let project_responses: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
-        return Ok(Json(ApiResponse::success(project_responses)));
-    }
+    let listing = service
+        .list_projects(filter, sort, page, page_size, params.cursor.as_deref())
+        .await?;
 
-    // Handle featured filtering
-    if let Some(true) = params.featured {
-        let projects = service.get_featured_projects().await?;
-        let project_responses: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
-        return Ok(Json(ApiResponse::success(project_responses)));
+    let pagination = PaginationInfo::new(page, page_size, listing.total_count, listing.next_cursor);
+    let project_responses: Vec<ProjectResponse> = listing.projects.into_iter().map(ProjectResponse::from).collect();
+    Ok(Json(ApiResponse::success_with_pagination(project_responses, pagination)))
+}
+
+/// Parse a date filter given as an RFC 3339 timestamp or a `YYYY-MM-DD` date (midnight UTC)
+fn parse_date_param(name: &str, value: &str) -> Result<DateTime<Utc>, ApiError> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
     }
 
-    // Default: get all projects
-    let projects = service.get_all_projects().await?;
-    let project_responses: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
-    Ok(Json(ApiResponse::success(project_responses)))
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        .map_err(|_| ApiError::BadRequest(format!("{} must be an RFC 3339 timestamp or a YYYY-MM-DD date", name)))
 }
 
-/// GET /api/projects/:id - Get a specific project by ID
+/// GET /api/projects/:id - Get a specific project by ID with its linked skills
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "The project", body = ApiResponse<ProjectDetail>),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    )
+)]
 async fn get_project_by_id(
     State(pool): State<SqlitePool>,
     Path(id): Path<i32>,
-) -> Result<Json<ApiResponse<ProjectResponse>>, ApiError> {
+) -> Result<Json<ApiResponse<ProjectDetail>>, ApiError> {
     let service = ProjectService::new(pool);
-    let project = service.get_project_by_id(id).await?;
-    let project_response = ProjectResponse::from(project);
-    Ok(Json(ApiResponse::success(project_response)))
+    let project = service.get_project_detail(id).await?;
+    Ok(Json(ApiResponse::success(project)))
 }
 
 /// POST /api/projects - Create a new project
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProject,
+    responses(
+        (status = 200, description = "The created project", body = ApiResponse<ProjectResponse>),
+        (status = 400, description = "Invalid project", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
 async fn create_project(
     State(pool): State<SqlitePool>,
     Json(project_data): Json<CreateProject>,
@@ -156,6 +167,19 @@ async fn create_project(
 }
 
 /// PUT /api/projects/:id - Update an existing project
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = UpdateProject,
+    responses(
+        (status = 200, description = "The updated project", body = ApiResponse<ProjectResponse>),
+        (status = 400, description = "Invalid changes", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
 async fn update_project(
     State(pool): State<SqlitePool>,
     Path(id): Path<i32>,
@@ -170,7 +194,72 @@ async fn update_project(
     )))
 }
 
+/// PUT /api/projects/:id/skills - Replace the skills linked to a project
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/skills",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = SetProjectSkills,
+    responses(
+        (status = 200, description = "The project with its new skills", body = ApiResponse<ProjectDetail>),
+        (status = 400, description = "Too many or unknown skills", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
+async fn set_project_skills(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+    Json(links): Json<SetProjectSkills>,
+) -> Result<Json<ApiResponse<ProjectDetail>>, ApiError> {
+    let service = ProjectService::new(pool);
+    let project = service.set_project_skills(id, links).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        project,
+        "Project skills updated successfully".to_string(),
+    )))
+}
+
+/// PUT /api/projects/:id/gallery - Replace the ordered gallery of a project with uploaded media
+#[utoipa::path(
+    put,
+    path = "/api/projects/{id}/gallery",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = SetProjectGallery,
+    responses(
+        (status = 200, description = "The project with its new gallery", body = ApiResponse<ProjectDetail>),
+        (status = 400, description = "Too many items, unknown media or alt text too long", body = ErrorResponse),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
+async fn set_project_gallery(
+    State(pool): State<SqlitePool>,
+    Path(id): Path<i32>,
+    Json(gallery): Json<SetProjectGallery>,
+) -> Result<Json<ApiResponse<ProjectDetail>>, ApiError> {
+    let service = ProjectService::new(pool);
+    let project = service.set_project_gallery(id, gallery).await?;
+    Ok(Json(ApiResponse::success_with_message(
+        project,
+        "Project gallery updated successfully".to_string(),
+    )))
+}
+
 /// DELETE /api/projects/:id - Delete a project
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "The project was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such project", body = ErrorResponse),
+    ),
+    security(("bearer" = ["projects:write"]), ("api_key" = ["projects:write"])),
+)]
 async fn delete_project(
     State(pool): State<SqlitePool>,
     Path(id): Path<i32>,
@@ -194,36 +283,18 @@ mod tests {
     use sqlx::SqlitePool;
     use tower::ServiceExt;
 
-    async fn create_test_app() -> (Router, SqlitePool) {
+    async fn create_test_app() -> (Router, SqlitePool, String) {
         let pool = SqlitePool::connect("sqlite::memory:")
             .await
             .unwrap();
 
-        // Create table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS projects (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                long_description TEXT,
-                technologies TEXT NOT NULL,
-                github_url TEXT,
-                demo_url TEXT,
-                image_url TEXT,
-                category TEXT NOT NULL,
-                featured BOOLEAN DEFAULT FALSE,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
-
-        let app = create_routes(pool.clone());
-        (app, pool)
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+
+        let app = create_routes(pool.clone(), auth);
+        (app, pool, token)
     }
 
     fn create_test_project_json() -> serde_json::Value {
@@ -242,11 +313,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_project() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, token) = create_test_app().await;
         
         let request = Request::builder()
             .method(Method::POST)
             .uri("/")
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(create_test_project_json().to_string()))
             .unwrap();
@@ -255,7 +327,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<Project> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<ProjectResponse> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
@@ -268,7 +340,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_projects() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // First create a project
         let service = ProjectService::new(pool);
@@ -295,7 +367,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<Vec<Project>> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<Vec<ProjectResponse>> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
@@ -307,7 +379,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_project_by_id() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // First create a project
         let service = ProjectService::new(pool);
@@ -326,7 +398,7 @@ mod tests {
 
         let request = Request::builder()
             .method(Method::GET)
-            .uri(&format!("/{}", created_project.id))
+            .uri(format!("/{}", created_project.id))
             .body(Body::empty())
             .unwrap();
 
@@ -334,7 +406,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<Project> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<ProjectResponse> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
@@ -346,7 +418,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_project() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First create a project
         let service = ProjectService::new(pool);
@@ -370,7 +442,8 @@ mod tests {
 
         let request = Request::builder()
             .method(Method::PUT)
-            .uri(&format!("/{}", created_project.id))
+            .uri(format!("/{}", created_project.id))
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(update_data.to_string()))
             .unwrap();
@@ -379,7 +452,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<Project> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<ProjectResponse> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
@@ -391,7 +464,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_project() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First create a project
         let service = ProjectService::new(pool);
@@ -410,7 +483,8 @@ mod tests {
 
         let request = Request::builder()
             .method(Method::DELETE)
-            .uri(&format!("/{}", created_project.id))
+            .uri(format!("/{}", created_project.id))
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -426,7 +500,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_projects_with_pagination() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // Create multiple projects
         let service = ProjectService::new(pool);
@@ -455,7 +529,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<Vec<Project>> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<Vec<ProjectResponse>> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
@@ -466,13 +540,13 @@ mod tests {
         
         assert!(projects.len() <= 3);
         assert_eq!(pagination.total_count, 5);
-        assert_eq!(pagination.page, 1);
+        assert_eq!(pagination.page, Some(1));
         assert_eq!(pagination.page_size, 3);
     }
 
     #[tokio::test]
     async fn test_get_projects_by_category() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // Create projects with different categories
         let service = ProjectService::new(pool);
@@ -512,7 +586,7 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
 
         let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
-        let response_json: ApiResponse<Vec<Project>> = serde_json::from_slice(&body).unwrap();
+        let response_json: ApiResponse<Vec<ProjectResponse>> = serde_json::from_slice(&body).unwrap();
         
         assert!(response_json.success);
         assert!(response_json.data.is_some());
@@ -522,4 +596,273 @@ mod tests {
         assert_eq!(projects[0].category, "web");
         assert_eq!(projects[0].title, "Web Project");
     }
+
+    #[tokio::test]
+    async fn test_create_project_requires_admin() {
+        let (app, _pool, _token) = create_test_app().await;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("content-type", "application/json")
+            .body(Body::from(create_test_project_json().to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_create_project_with_api_key() {
+        let (app, pool, _token) = create_test_app().await;
+        let key = crate::auth::test_api_key(&pool, &["projects:write"]).await;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("x-api-key", key)
+            .header("content-type", "application/json")
+            .body(Body::from(create_test_project_json().to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_create_project_with_wrong_scope_forbidden() {
+        let (app, pool, _token) = create_test_app().await;
+        let key = crate::auth::test_api_key(&pool, &["skills:write"]).await;
+
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri("/")
+            .header("authorization", format!("Bearer {}", key))
+            .header("content-type", "application/json")
+            .body(Body::from(create_test_project_json().to_string()))
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_get_projects_by_technology() {
+        let (app, pool, _token) = create_test_app().await;
+
+        let service = ProjectService::new(pool);
+        for (title, technologies) in [("Rust Project", vec!["Rust", "SQLite"]), ("Web Project", vec!["JavaScript"])] {
+            let project_data = CreateProject {
+                title: title.to_string(),
+                description: "A test project description".to_string(),
+                long_description: None,
+                technologies: technologies.into_iter().map(String::from).collect(),
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "web".to_string(),
+                featured: Some(false),
+            };
+            service.create_project(project_data).await.unwrap();
+        }
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/?technology=RUST")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<Vec<ProjectResponse>> = serde_json::from_slice(&body).unwrap();
+
+        let projects = response_json.data.unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].title, "Rust Project");
+        assert_eq!(projects[0].technologies, vec!["Rust", "SQLite"]);
+    }
+
+    #[tokio::test]
+    async fn test_project_skills_routes() {
+        let (app, pool, token) = create_test_app().await;
+
+        let skill = crate::services::SkillService::new(pool.clone())
+            .create_skill(crate::models::CreateSkill {
+                name: "Docker".to_string(),
+                category: "DevOps".to_string(),
+                level: 3,
+                years_experience: None,
+                description: None,
+            })
+            .await
+            .unwrap();
+        let project = ProjectService::new(pool)
+            .create_project(CreateProject {
+                title: "Test Project".to_string(),
+                description: "A test project description".to_string(),
+                long_description: None,
+                technologies: vec!["Rust".to_string()],
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "web".to_string(),
+                featured: None,
+            })
+            .await
+            .unwrap();
+
+        let body = json!({ "skill_ids": [skill.id] }).to_string();
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/{}/skills", project.id))
+            .header("content-type", "application/json")
+            .body(Body::from(body.clone()))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let request = Request::builder()
+            .method(Method::PUT)
+            .uri(format!("/{}/skills", project.id))
+            .header("authorization", format!("Bearer {}", token))
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/{}", project.id))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<ProjectDetail> = serde_json::from_slice(&body).unwrap();
+
+        let detail = response_json.data.unwrap();
+        assert_eq!(detail.project.title, "Test Project");
+        assert_eq!(detail.skills.len(), 1);
+        assert_eq!(detail.skills[0].name, "Docker");
+        assert_eq!(detail.skills[0].level, 3);
+    }
+
+    #[tokio::test]
+    async fn test_project_gallery_routes() {
+        let (app, pool, token) = create_test_app().await;
+
+        let media = crate::database::MediaRepository::new(pool.clone());
+        let (cover, _) = media.create(&"a".repeat(64), crate::models::MediaType::Png, 10, None, None).await.unwrap();
+        let (detail_shot, _) = media.create(&"b".repeat(64), crate::models::MediaType::Webp, 20, None, None).await.unwrap();
+        let project = ProjectService::new(pool)
+            .create_project(CreateProject {
+                title: "Gallery Project".to_string(),
+                description: "A test project description".to_string(),
+                long_description: None,
+                technologies: vec!["Rust".to_string()],
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "web".to_string(),
+                featured: None,
+            })
+            .await
+            .unwrap();
+
+        let set_gallery = |items: Value| {
+            Request::builder()
+                .method(Method::PUT)
+                .uri(format!("/{}/gallery", project.id))
+                .header("authorization", format!("Bearer {}", token))
+                .header("content-type", "application/json")
+                .body(Body::from(json!({ "items": items }).to_string()))
+                .unwrap()
+        };
+
+        let request = set_gallery(json!([{ "media_id": 999 }]));
+        assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::BAD_REQUEST);
+
+        let request = set_gallery(json!([
+            { "media_id": detail_shot.id, "alt_text": "Dashboard with live charts" },
+            { "media_id": cover.id, "alt_text": "Home page" }
+        ]));
+        assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::OK);
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/{}", project.id))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let detail = serde_json::from_slice::<ApiResponse<ProjectDetail>>(&body).unwrap().data.unwrap();
+
+        let gallery: Vec<(i32, &str)> = detail.gallery.iter().map(|item| (item.media.id, item.alt_text.as_str())).collect();
+        assert_eq!(gallery, vec![(detail_shot.id, "Dashboard with live charts"), (cover.id, "Home page")]);
+        assert_eq!(detail.gallery[1].media.url, format!("/api/media/{}", "a".repeat(64)));
+    }
+
+    #[tokio::test]
+    async fn test_get_projects_combined_filters_and_cursor() {
+        let (app, pool, _token) = create_test_app().await;
+
+        let service = ProjectService::new(pool);
+        for (title, category, featured) in [("Alpha", "web", true), ("Beta", "web", false), ("Gamma", "web", true), ("Delta", "mobile", true)] {
+            let project_data = CreateProject {
+                title: title.to_string(),
+                description: "A test project description".to_string(),
+                long_description: None,
+                technologies: vec!["Rust".to_string()],
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: category.to_string(),
+                featured: Some(featured),
+            };
+            service.create_project(project_data).await.unwrap();
+        }
+
+        let get = |uri: String| {
+            let app = app.clone();
+            async move {
+                let request = Request::builder().method(Method::GET).uri(uri).body(Body::empty()).unwrap();
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, serde_json::from_slice::<Value>(&body).unwrap())
+            }
+        };
+
+        // Category and featured both apply
+        let (status, body) = get("/?category=web&featured=true&sort=title&page_size=1".to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"][0]["title"], "Alpha");
+        assert_eq!(body["pagination"]["total_count"], 2);
+        assert_eq!(body["pagination"]["total_pages"], 2);
+        assert_eq!(body["pagination"]["page"], 1);
+
+        let cursor = body["pagination"]["next_cursor"].as_str().unwrap().to_string();
+        let (status, body) = get(format!("/?category=web&featured=true&sort=title&page_size=1&cursor={}", cursor)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"][0]["title"], "Gamma");
+        assert!(body["pagination"].get("page").is_none());
+        assert!(body["pagination"].get("next_cursor").is_none());
+
+        // Pagination info is always present
+        let (_, body) = get("/".to_string()).await;
+        assert_eq!(body["pagination"]["total_count"], 4);
+
+        let (status, _) = get("/?sort=popularity".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get("/?created_after=yesterday".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (_, body) = get("/?created_after=2000-01-01&created_before=2999-01-01T00:00:00Z".to_string()).await;
+        assert_eq!(body["pagination"]["total_count"], 4);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Query, State},
+    response::Json,
+    middleware,
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use sqlx::SqlitePool;
+use utoipa::IntoParams;
+
+use crate::{
+    auth::{optional_scope, AuthConfig, AuthState, Principal},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiScope, SearchHit, SearchResultType},
+    services::SearchService,
+};
+
+/// Number of hits returned when no limit is given
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+
+/// Query parameters for search
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Words that must all match; `"quoted phrases"` and `prefix*` are supported
+    #[param(max_length = 200)]
+    pub q: String,
+    /// Comma-separated result types, e.g. `project,skill`; `contact_message` needs credentials
+    pub types: Option<String>,
+    /// At most 50 hits, 20 by default
+    #[param(minimum = 1, maximum = 50)]
+    pub limit: Option<u32>,
+}
+
+/// Create search routes (public; contact messages are included for an admin or a `contact:read` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    Router::new()
+        .route("/", get(search))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::ContactRead),
+            optional_scope,
+        ))
+        .with_state(pool)
+}
+
+/// GET /api/search - Ranked full-text search across projects, skills, profile and messages
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Hits, best first", body = ApiResponse<Vec<SearchHit>>),
+        (status = 400, description = "Empty or too long query, unknown type or invalid limit", body = ErrorResponse),
+    ),
+    security((), ("bearer" = ["contact:read"]), ("api_key" = ["contact:read"])),
+)]
+async fn search(
+    State(pool): State<SqlitePool>,
+    principal: Option<Principal>,
+    Query(params): Query<SearchQuery>,
+) -> Result<Json<ApiResponse<Vec<SearchHit>>>, ApiError> {
+    let mut types = Vec::new();
+    for name in params.types.iter().flat_map(|types| types.split(',')) {
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        match SearchResultType::from_str(name) {
+            Some(result_type) => types.push(result_type),
+            None => {
+                return Err(ApiError::BadRequest(format!(
+                    "Invalid result type '{}'. Valid types: {}",
+                    name,
+                    SearchResultType::all().join(", ")
+                )));
+            }
+        }
+    }
+
+    let service = SearchService::new(pool);
+    let hits = service
+        .search(
+            &params.q,
+            &types,
+            principal.is_some(),
+            params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+        )
+        .await?;
+
+    Ok(Json(ApiResponse::success(hits)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    use crate::{models::CreateContactMessage, services::ContactService, spam::SpamPipeline};
+
+    async fn create_test_app() -> (Router, String) {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        ContactService::new(pool.clone(), Some(SpamPipeline::new(5.0, 10.0)))
+            .submit_message(CreateContactMessage {
+                name: "John Doe".to_string(),
+                email: "john.doe@example.com".to_string(),
+                subject: "Rust consulting".to_string(),
+                message: "Hello, could we talk about a Rust consulting engagement?".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+
+        (create_routes(pool, auth), token)
+    }
+
+    async fn get_hits(app: Router, uri: &str, token: Option<&str>) -> (StatusCode, Option<Vec<SearchHit>>) {
+        let mut request = Request::builder().method(Method::GET).uri(uri);
+        if let Some(token) = token {
+            request = request.header("authorization", format!("Bearer {}", token));
+        }
+
+        let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let hits = serde_json::from_slice::<ApiResponse<Vec<SearchHit>>>(&body).ok().and_then(|r| r.data);
+        (status, hits)
+    }
+
+    #[tokio::test]
+    async fn test_messages_only_visible_to_admins() {
+        let (app, token) = create_test_app().await;
+
+        let (status, hits) = get_hits(app.clone(), "/?q=consult*", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(hits.unwrap().is_empty());
+
+        let (status, hits) = get_hits(app.clone(), "/?q=consult*", Some(&token)).await;
+        assert_eq!(status, StatusCode::OK);
+        let hits = hits.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].result_type, SearchResultType::ContactMessage);
+        assert_eq!(hits[0].title, "Rust <mark>consulting</mark>");
+
+        let (status, _) = get_hits(app.clone(), "/?q=rust&types=contact_message", None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (status, _) = get_hits(app, "/?q=rust", Some("not-a-token")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parameters() {
+        let (app, _token) = create_test_app().await;
+
+        let (status, _) = get_hits(app.clone(), "/?q=rust&types=project,post", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = get_hits(app, "/?q=", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::{
+    config::SiteConfig,
+    error::{ApiError, ErrorCode},
+    services::{sitemap_service::SitemapCache, SitemapService},
+};
+
+const XML: &str = "application/xml; charset=utf-8";
+
+/// Crawlers come back rarely; an hour keeps the sitemap fresh enough
+const CACHE_CONTROL: &str = "public, max-age=3600";
+
+/// State shared by the sitemap routes
+#[derive(Clone)]
+struct SitemapState {
+    pool: SqlitePool,
+    site: SiteConfig,
+    cache: SitemapCache,
+}
+
+impl SitemapState {
+    fn service(&self) -> SitemapService {
+        SitemapService::new(self.pool.clone(), &self.site, self.cache.clone())
+    }
+}
+
+/// Create the `/sitemap.xml`, `/sitemaps/N.xml` and `/robots.txt` routes (public)
+pub fn create_routes(pool: SqlitePool, site: &SiteConfig) -> Router {
+    Router::new()
+        .route("/sitemap.xml", get(get_sitemap))
+        .route("/sitemaps/:file", get(get_sitemap_file))
+        .route("/robots.txt", get(get_robots))
+        .with_state(SitemapState {
+            pool,
+            site: site.clone(),
+            cache: SitemapCache::default(),
+        })
+}
+
+/// GET /sitemap.xml - Every public page, or an index of the sitemap files once they do not fit in one
+async fn get_sitemap(State(state): State<SitemapState>) -> Result<Response, ApiError> {
+    let sitemap = state.service().sitemap().await?;
+    Ok(xml(sitemap.root.clone()))
+}
+
+/// GET /sitemaps/:n.xml - One of the files listed by the sitemap index
+async fn get_sitemap_file(
+    State(state): State<SitemapState>,
+    Path(file): Path<String>,
+) -> Result<Response, ApiError> {
+    let sitemap = state.service().sitemap().await?;
+    file.strip_suffix(".xml")
+        .and_then(|number| number.parse::<usize>().ok())
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|i| sitemap.files.get(i))
+        .map(|content| xml(content.clone()))
+        .ok_or_else(|| ApiError::NotFound(ErrorCode::NotFound, format!("Sitemap '{}' not found", file)))
+}
+
+/// GET /robots.txt - Crawling rules, pointing at the sitemap
+async fn get_robots(State(state): State<SitemapState>) -> Response {
+    let robots = state.service().robots();
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8"), (header::CACHE_CONTROL, CACHE_CONTROL)], robots).into_response()
+}
+
+fn xml(content: String) -> Response {
+    ([(header::CONTENT_TYPE, XML), (header::CACHE_CONTROL, CACHE_CONTROL)], content).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::{Request, StatusCode}};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_sitemap_routes() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let app = create_routes(pool, &SiteConfig::default());
+
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(get("/robots.txt")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("Sitemap: http://localhost:5173/sitemap.xml"));
+
+        let response = app.clone().oneshot(get("/sitemap.xml")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], XML);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("<loc>http://localhost:5173/</loc>"));
+
+        // A single file needs no index
+        let response = app.oneshot(get("/sitemaps/1.xml")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
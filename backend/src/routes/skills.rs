@@ -1,37 +1,58 @@
 use axum::{
     extract::{Path, Query, State},
     response::Json,
-    routing::{delete, get, post, put},
+    middleware,
+    routing::{get, post, put},
     Router,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    error::ApiError,
-    models::{CreateSkill, Skill, UpdateSkill},
-    routes::projects::ApiResponse,
+    auth::{require_scope, AuthConfig, AuthState},
+    error::{ApiError, ApiResponse, ErrorResponse},
+    models::{ApiScope, CreateSkill, Skill, SkillDetail, UpdateSkill},
     services::SkillService,
 };
 
 /// Query parameters for skill listing
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SkillQuery {
     pub category: Option<String>,
     pub min_level: Option<i32>,
 }
 
-/// Create skill routes
-pub fn create_routes(pool: SqlitePool) -> Router {
+/// Create skill routes (reads are public, writes require an admin or a `skills:write` key)
+pub fn create_routes(pool: SqlitePool, auth: AuthConfig) -> Router {
+    let admin_routes = Router::new()
+        .route("/", post(create_skill))
+        .route("/:id", put(update_skill).delete(delete_skill))
+        .route_layer(middleware::from_fn_with_state(
+            (AuthState::new(pool.clone(), auth), ApiScope::SkillsWrite),
+            require_scope,
+        ));
+
     Router::new()
-        .route("/", get(get_skills).post(create_skill))
-        .route("/:id", get(get_skill_by_id).put(update_skill).delete(delete_skill))
+        .route("/", get(get_skills))
+        .route("/:id", get(get_skill_by_id))
         .route("/categories", get(get_categories))
+        .merge(admin_routes)
         .with_state(pool)
 }
 
 /// GET /api/skills - Get all skills with optional filtering
+#[utoipa::path(
+    get,
+    path = "/api/skills",
+    tag = "skills",
+    params(SkillQuery),
+    responses(
+        (status = 200, description = "The skills", body = ApiResponse<Vec<Skill>>),
+    )
+)]
 async fn get_skills(
     State(pool): State<SqlitePool>,
     Query(params): Query<SkillQuery>,
@@ -55,17 +76,39 @@ async fn get_skills(
     Ok(Json(ApiResponse::success(skills)))
 }
 
-/// GET /api/skills/:id - Get a specific skill by ID
+/// GET /api/skills/:id - Get a specific skill by ID with the projects using it
+#[utoipa::path(
+    get,
+    path = "/api/skills/{id}",
+    tag = "skills",
+    params(("id" = i32, Path, description = "Skill ID")),
+    responses(
+        (status = 200, description = "The skill", body = ApiResponse<SkillDetail>),
+        (status = 404, description = "No such skill", body = ErrorResponse),
+    )
+)]
 async fn get_skill_by_id(
     State(pool): State<SqlitePool>,
     Path(id): Path<i32>,
-) -> Result<Json<ApiResponse<Skill>>, ApiError> {
+) -> Result<Json<ApiResponse<SkillDetail>>, ApiError> {
     let service = SkillService::new(pool);
-    let skill = service.get_skill_by_id(id).await?;
+    let skill = service.get_skill_detail(id).await?;
     Ok(Json(ApiResponse::success(skill)))
 }
 
 /// POST /api/skills - Create a new skill
+#[utoipa::path(
+    post,
+    path = "/api/skills",
+    tag = "skills",
+    request_body = CreateSkill,
+    responses(
+        (status = 200, description = "The created skill", body = ApiResponse<Skill>),
+        (status = 400, description = "Invalid skill or unknown category", body = ErrorResponse),
+        (status = 409, description = "A skill with this name already exists", body = ErrorResponse),
+    ),
+    security(("bearer" = ["skills:write"]), ("api_key" = ["skills:write"])),
+)]
 async fn create_skill(
     State(pool): State<SqlitePool>,
     Json(skill_data): Json<CreateSkill>,
@@ -79,6 +122,19 @@ async fn create_skill(
 }
 
 /// PUT /api/skills/:id - Update an existing skill
+#[utoipa::path(
+    put,
+    path = "/api/skills/{id}",
+    tag = "skills",
+    params(("id" = i32, Path, description = "Skill ID")),
+    request_body = UpdateSkill,
+    responses(
+        (status = 200, description = "The updated skill", body = ApiResponse<Skill>),
+        (status = 400, description = "Invalid changes", body = ErrorResponse),
+        (status = 404, description = "No such skill", body = ErrorResponse),
+    ),
+    security(("bearer" = ["skills:write"]), ("api_key" = ["skills:write"])),
+)]
 async fn update_skill(
     State(pool): State<SqlitePool>,
     Path(id): Path<i32>,
@@ -93,6 +149,17 @@ async fn update_skill(
 }
 
 /// DELETE /api/skills/:id - Delete a skill
+#[utoipa::path(
+    delete,
+    path = "/api/skills/{id}",
+    tag = "skills",
+    params(("id" = i32, Path, description = "Skill ID")),
+    responses(
+        (status = 200, description = "The skill was deleted", body = ApiResponse<Value>),
+        (status = 404, description = "No such skill", body = ErrorResponse),
+    ),
+    security(("bearer" = ["skills:write"]), ("api_key" = ["skills:write"])),
+)]
 async fn delete_skill(
     State(pool): State<SqlitePool>,
     Path(id): Path<i32>,
@@ -106,6 +173,14 @@ async fn delete_skill(
 }
 
 /// GET /api/skills/categories - Get all available skill categories
+#[utoipa::path(
+    get,
+    path = "/api/skills/categories",
+    tag = "skills",
+    responses(
+        (status = 200, description = "Categories in use and available", body = ApiResponse<SkillCategoriesResponse>),
+    )
+)]
 async fn get_categories(
     State(pool): State<SqlitePool>,
 ) -> Result<Json<ApiResponse<SkillCategoriesResponse>>, ApiError> {
@@ -124,7 +199,7 @@ async fn get_categories(
 }
 
 /// Response for skill categories endpoint
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SkillCategoriesResponse {
     pub used: Vec<String>,
     pub available: Vec<String>,
@@ -141,31 +216,18 @@ mod tests {
     use sqlx::SqlitePool;
     use tower::ServiceExt;
 
-    async fn create_test_app() -> (Router, SqlitePool) {
+    async fn create_test_app() -> (Router, SqlitePool, String) {
         let pool = SqlitePool::connect("sqlite::memory:")
             .await
             .unwrap();
 
-        // Create table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS skills (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                category TEXT NOT NULL,
-                level INTEGER NOT NULL CHECK (level >= 1 AND level <= 5),
-                years_experience INTEGER,
-                description TEXT,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
-
-        let app = create_routes(pool.clone());
-        (app, pool)
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let auth = AuthConfig::new("a-test-secret-that-is-long-enough!", chrono::Duration::hours(1));
+        let token = crate::auth::test_admin_token(&pool, &auth).await;
+
+        let app = create_routes(pool.clone(), auth);
+        (app, pool, token)
     }
 
     fn create_test_skill_json() -> serde_json::Value {
@@ -180,11 +242,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_skill() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, token) = create_test_app().await;
         
         let request = Request::builder()
             .method(Method::POST)
             .uri("/")
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(create_test_skill_json().to_string()))
             .unwrap();
@@ -206,7 +269,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_skills() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // First create a skill
         let service = SkillService::new(pool);
@@ -241,7 +304,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_skills_by_category() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // Create skills with different categories
         let service = SkillService::new(pool);
@@ -286,7 +349,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_skills_by_min_level() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, _token) = create_test_app().await;
         
         // Create skills with different levels
         let service = SkillService::new(pool);
@@ -331,7 +394,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_categories() {
-        let (app, _pool) = create_test_app().await;
+        let (app, _pool, _token) = create_test_app().await;
 
         let request = Request::builder()
             .method(Method::GET)
@@ -355,7 +418,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_skill() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First create a skill
         let service = SkillService::new(pool);
@@ -375,7 +438,8 @@ mod tests {
 
         let request = Request::builder()
             .method(Method::PUT)
-            .uri(&format!("/{}", created_skill.id))
+            .uri(format!("/{}", created_skill.id))
+            .header("authorization", format!("Bearer {}", token))
             .header("content-type", "application/json")
             .body(Body::from(update_data.to_string()))
             .unwrap();
@@ -396,7 +460,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_skill() {
-        let (app, pool) = create_test_app().await;
+        let (app, pool, token) = create_test_app().await;
         
         // First create a skill
         let service = SkillService::new(pool);
@@ -411,7 +475,8 @@ mod tests {
 
         let request = Request::builder()
             .method(Method::DELETE)
-            .uri(&format!("/{}", created_skill.id))
+            .uri(format!("/{}", created_skill.id))
+            .header("authorization", format!("Bearer {}", token))
             .body(Body::empty())
             .unwrap();
 
@@ -424,4 +489,19 @@ mod tests {
         assert!(response_json.success);
         assert!(response_json.message.is_some());
     }
+
+    #[tokio::test]
+    async fn test_delete_skill_requires_admin() {
+        let (app, _pool, _token) = create_test_app().await;
+
+        let request = Request::builder()
+            .method(Method::DELETE)
+            .uri("/1")
+            .header("authorization", "Bearer not-a-valid-token")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+use axum::{
+    extract::State,
+    response::Json,
+    routing::get,
+    Router,
+};
+use sqlx::SqlitePool;
+
+use crate::{
+    error::{ApiError, ApiResponse},
+    models::Technology,
+    services::TechnologyService,
+};
+
+/// Create technology routes (read-only; technologies are managed through projects)
+pub fn create_routes(pool: SqlitePool) -> Router {
+    Router::new()
+        .route("/", get(get_technologies))
+        .with_state(pool)
+}
+
+/// GET /api/technologies - Get technologies with the number of projects using each
+#[utoipa::path(
+    get,
+    path = "/api/technologies",
+    tag = "technologies",
+    responses(
+        (status = 200, description = "Every technology", body = ApiResponse<Vec<Technology>>),
+    )
+)]
+async fn get_technologies(
+    State(pool): State<SqlitePool>,
+) -> Result<Json<ApiResponse<Vec<Technology>>>, ApiError> {
+    let service = TechnologyService::new(pool);
+    let technologies = service.get_all_technologies().await?;
+    Ok(Json(ApiResponse::success(technologies)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{Method, Request, StatusCode},
+    };
+    use tower::ServiceExt;
+
+    use crate::{models::CreateProject, services::ProjectService};
+
+    #[tokio::test]
+    async fn test_get_technologies_with_counts() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let service = ProjectService::new(pool.clone());
+        for (title, technologies) in [("First", vec!["Rust", "SQLite"]), ("Second", vec!["rust", "Svelte"])] {
+            let project_data = CreateProject {
+                title: title.to_string(),
+                description: "A test project description".to_string(),
+                long_description: None,
+                technologies: technologies.into_iter().map(String::from).collect(),
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "web".to_string(),
+                featured: None,
+            };
+            service.create_project(project_data).await.unwrap();
+        }
+
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri("/")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = create_routes(pool).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response_json: ApiResponse<Vec<Technology>> = serde_json::from_slice(&body).unwrap();
+
+        let technologies = response_json.data.unwrap();
+        let summary: Vec<(&str, i64)> = technologies.iter().map(|t| (t.name.as_str(), t.project_count)).collect();
+        assert_eq!(summary, vec![("Rust", 2), ("SQLite", 1), ("Svelte", 1)]);
+    }
+}
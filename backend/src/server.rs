@@ -0,0 +1,118 @@
+use std::net::SocketAddr;
+
+use axum::{
+    http::{header, HeaderValue, Method},
+    middleware,
+    routing::get,
+    Router,
+};
+use sqlx::SqlitePool;
+use tower_http::cors::CorsLayer;
+use crate::{
+    auth::{AuthConfig, AuthState},
+    config::{AppConfig, AuthSettings},
+    error::ApiResult,
+    models::{AdminUser, CreateAdminUser},
+    notifier::Notifiers,
+    problem::{self, ErrorExposure},
+    request_id::{self, REQUEST_ID_HEADER},
+    rate_limit::{self, RateLimiter},
+    routes,
+    services::{AuthService, MediaService, NotificationService},
+};
+
+/// Build the complete application: API routes, health check, rate limiting, error negotiation,
+/// request IDs and CORS
+pub fn create_app(pool: SqlitePool, auth_config: AuthConfig, config: &AppConfig) -> Router {
+    // Origins are checked when the configuration is loaded
+    let origins: Vec<HeaderValue> = config
+        .cors
+        .allowed_origins
+        .iter()
+        .filter_map(|origin| origin.parse().ok())
+        .collect();
+
+    let cors = CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
+        .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION, header::ACCEPT, REQUEST_ID_HEADER])
+        .expose_headers([REQUEST_ID_HEADER])
+        .allow_credentials(false);
+
+    let mut app = Router::new()
+        .route("/", get(|| async { "Portfolio Backend API v1.0" }))
+        .route("/health", get(health_check))
+        .merge(routes::create_router_with_config(pool.clone(), auth_config.clone(), config));
+
+    // Preflight requests are answered by the CORS layer without taking a token
+    if config.rate_limit.enabled {
+        let limiter = RateLimiter::from_config(pool.clone(), &config.rate_limit);
+        app = app.layer(middleware::from_fn_with_state(limiter, rate_limit::rate_limit));
+    }
+
+    // Outside rate limiting so its 429s are negotiated too, inside the request span
+    let exposure = ErrorExposure {
+        dev_mode: config.server.dev_mode,
+        auth: AuthState::new(pool, auth_config),
+    };
+    app.layer(middleware::from_fn_with_state(exposure, problem::negotiate_errors))
+        .layer(middleware::from_fn(request_id::request_id))
+        .layer(cors)
+}
+
+/// Create the configured first admin account if no admin exists yet
+pub async fn bootstrap_admin(
+    pool: &SqlitePool,
+    auth_config: &AuthConfig,
+    settings: &AuthSettings,
+) -> ApiResult<Option<AdminUser>> {
+    let (Some(username), Some(password)) = (&settings.admin_username, &settings.admin_password) else {
+        return Ok(None);
+    };
+
+    let auth_service = AuthService::new(pool.clone(), auth_config.clone());
+    auth_service
+        .bootstrap_admin(CreateAdminUser {
+            username: username.clone(),
+            password: password.clone(),
+        })
+        .await
+}
+
+/// Bind to the configured address and serve the application until the process stops.
+/// Queued notifications are delivered and image derivatives rendered in the background
+/// while serving.
+pub async fn serve(pool: SqlitePool, auth_config: AuthConfig, config: &AppConfig) -> std::io::Result<()> {
+    if config.notifications.enabled {
+        let notifiers = Notifiers::from_config(&config.notifications).map_err(std::io::Error::other)?;
+        NotificationService::new(pool.clone(), config.notifications.clone()).spawn_worker(notifiers);
+        println!("📬 Contact notifications sent through {}", config.notifications.channels().join(", "));
+    }
+
+    if config.uploads.generate_variants {
+        MediaService::new(pool.clone(), &config.uploads).spawn_worker();
+        println!(
+            "🖼️  Image derivatives rendered at widths {:?} as {}",
+            config.uploads.variant_widths,
+            config.uploads.variant_formats.join(", ")
+        );
+    }
+
+    let app = create_app(pool, auth_config, config);
+    let addr = config.bind_address();
+
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    println!("🚀 Portfolio Backend API running on http://{}", addr);
+    println!("📊 Health check available at http://{}/health", addr);
+    if config.features.api_docs {
+        println!("📖 API documentation available at http://{}/api/docs/", addr);
+    }
+
+    // The peer address identifies clients for rate limiting
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await
+}
+
+/// Health check endpoint
+async fn health_check() -> &'static str {
+    "OK"
+}
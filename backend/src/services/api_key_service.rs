@@ -0,0 +1,275 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    database::ApiKeyRepository,
+    models::{ApiKey, ApiKeyResponse, ApiScope, CreateApiKey, CreatedApiKey, api_key::API_KEY_PREFIX},
+    error::{ApiError, ApiResult, ErrorCode, FieldError},
+};
+
+/// Number of characters of a key kept in clear to identify it in listings
+const KEY_PREFIX_LEN: usize = 12;
+
+/// Service for API key management and verification
+pub struct ApiKeyService {
+    repository: ApiKeyRepository,
+}
+
+impl ApiKeyService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: ApiKeyRepository::new(pool),
+        }
+    }
+
+    /// Create a new API key; the plaintext key is returned only once
+    pub async fn create_key(&self, mut key_data: CreateApiKey, created_by: Option<i32>) -> ApiResult<CreatedApiKey> {
+        key_data.name = key_data.name.trim().to_string();
+        info!("Creating API key: {}", key_data.name);
+
+        if let Err(validation_errors) = key_data.validate() {
+            warn!("Validation failed for API key creation: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        // Reject unknown scopes and normalize the list
+        let mut scopes = Vec::new();
+        for (index, scope) in key_data.scopes.iter().enumerate() {
+            match ApiScope::from_str(scope.trim()) {
+                Some(scope) if !scopes.contains(&scope.as_str()) => scopes.push(scope.as_str()),
+                Some(_) => {}
+                None => {
+                    return Err(FieldError::new(
+                        format!("scopes[{}]", index),
+                        "scope",
+                        format!("Invalid scope '{}'. Valid scopes: {}", scope, ApiScope::all().join(", ")),
+                    )
+                    .into());
+                }
+            }
+        }
+
+        if key_data.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+            return Err(FieldError::new("expires_at", "future", "Expiry date must be in the future").into());
+        }
+
+        let key = generate_key();
+        let scopes_json = serde_json::to_string(&scopes)?;
+
+        match self
+            .repository
+            .create(
+                &key_data.name,
+                &key[..KEY_PREFIX_LEN],
+                &hash_key(&key),
+                &scopes_json,
+                created_by,
+                key_data.expires_at,
+            )
+            .await
+        {
+            Ok(api_key) => {
+                info!("Successfully created API key: {} (ID: {})", api_key.name, api_key.id);
+                Ok(CreatedApiKey {
+                    key,
+                    api_key: ApiKeyResponse::from(api_key),
+                })
+            }
+            Err(e) => {
+                error!("Failed to create API key '{}': {}", key_data.name, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get all API keys
+    pub async fn get_all_keys(&self) -> ApiResult<Vec<ApiKey>> {
+        info!("Fetching all API keys");
+
+        match self.repository.get_all().await {
+            Ok(keys) => Ok(keys),
+            Err(e) => {
+                error!("Failed to fetch API keys: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Revoke an API key
+    pub async fn revoke_key(&self, id: i32) -> ApiResult<()> {
+        info!("Revoking API key with ID: {}", id);
+
+        match self.repository.revoke(id).await {
+            Ok(true) => {
+                info!("Successfully revoked API key with ID: {}", id);
+                Ok(())
+            }
+            Ok(false) => {
+                warn!("API key with ID {} not found or already revoked", id);
+                Err(ApiError::NotFound(ErrorCode::ApiKeyNotFound, format!("Active API key with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to revoke API key {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Verify a plaintext key grants a scope, recording its use
+    pub async fn authenticate(&self, key: &str, scope: ApiScope) -> ApiResult<ApiKey> {
+        let api_key = match self.repository.get_by_hash(&hash_key(key)).await {
+            Ok(Some(api_key)) if api_key.is_active() => api_key,
+            Ok(_) => {
+                warn!("Rejected unknown, revoked or expired API key");
+                return Err(ApiError::Unauthorized);
+            }
+            Err(e) => {
+                error!("Failed to look up API key: {}", e);
+                return Err(ApiError::Database(e));
+            }
+        };
+
+        if !api_key.has_scope(scope) {
+            warn!("API key {} lacks scope {}", api_key.id, scope.as_str());
+            return Err(ApiError::Forbidden);
+        }
+
+        if let Err(e) = self.repository.touch_last_used(api_key.id).await {
+            error!("Failed to record use of API key {}: {}", api_key.id, e);
+            return Err(ApiError::Database(e));
+        }
+
+        Ok(api_key)
+    }
+}
+
+/// Generate a new random API key
+fn generate_key() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    format!("{}{}", API_KEY_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Hash a plaintext key for storage and lookup (keys are random, so a fast hash is enough)
+fn hash_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::SqlitePool;
+
+    async fn create_test_service() -> ApiKeyService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        // Create table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS api_keys (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                key_prefix TEXT NOT NULL,
+                key_hash TEXT NOT NULL UNIQUE,
+                scopes TEXT NOT NULL,
+                created_by INTEGER,
+                expires_at DATETIME,
+                last_used_at DATETIME,
+                revoked_at DATETIME,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        ApiKeyService::new(pool)
+    }
+
+    fn create_test_key() -> CreateApiKey {
+        CreateApiKey {
+            name: "CI pipeline".to_string(),
+            scopes: vec!["projects:write".to_string(), "skills:write".to_string()],
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_authenticate_key() {
+        let service = create_test_service().await;
+        let created = service.create_key(create_test_key(), None).await.unwrap();
+
+        assert!(created.key.starts_with(API_KEY_PREFIX));
+        assert!(created.key.starts_with(&created.api_key.key_prefix));
+        assert_eq!(created.api_key.scopes, vec!["projects:write", "skills:write"]);
+
+        let key = service.authenticate(&created.key, ApiScope::ProjectsWrite).await.unwrap();
+        assert_eq!(key.id, created.api_key.id);
+        assert!(key.last_used_at.is_none());
+
+        let keys = service.get_all_keys().await.unwrap();
+        assert!(keys[0].last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_key_stored_as_hash_only() {
+        let service = create_test_service().await;
+        let created = service.create_key(create_test_key(), None).await.unwrap();
+
+        let keys = service.get_all_keys().await.unwrap();
+        assert_ne!(keys[0].key_hash, created.key);
+        assert_eq!(keys[0].key_hash, hash_key(&created.key));
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope_forbidden() {
+        let service = create_test_service().await;
+        let created = service.create_key(create_test_key(), None).await.unwrap();
+
+        let result = service.authenticate(&created.key, ApiScope::ContactRead).await;
+        assert!(matches!(result, Err(ApiError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_and_unknown_keys_rejected() {
+        let service = create_test_service().await;
+        let created = service.create_key(create_test_key(), None).await.unwrap();
+
+        service.revoke_key(created.api_key.id).await.unwrap();
+
+        let result = service.authenticate(&created.key, ApiScope::ProjectsWrite).await;
+        assert!(matches!(result, Err(ApiError::Unauthorized)));
+
+        let result = service.authenticate("pfk_unknown", ApiScope::ProjectsWrite).await;
+        assert!(matches!(result, Err(ApiError::Unauthorized)));
+
+        assert!(matches!(service.revoke_key(created.api_key.id).await, Err(ApiError::NotFound(..))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_scope_rejected() {
+        let service = create_test_service().await;
+        let mut key_data = create_test_key();
+        key_data.scopes = vec!["everything:write".to_string()];
+
+        let result = service.create_key(key_data, None).await;
+        assert!(matches!(result, Err(ApiError::ValidationErrors(_))));
+    }
+
+    #[tokio::test]
+    async fn test_past_expiry_rejected() {
+        let service = create_test_service().await;
+        let mut key_data = create_test_key();
+        key_data.expires_at = Some(Utc::now() - chrono::Duration::days(1));
+
+        let result = service.create_key(key_data, None).await;
+        assert!(matches!(result, Err(ApiError::ValidationErrors(_))));
+    }
+}
@@ -0,0 +1,313 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use sqlx::SqlitePool;
+use tokio::sync::OnceCell;
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    auth::AuthConfig,
+    database::AdminRepository,
+    models::{AdminUser, CreateAdminUser, LoginRequest, LoginResponse},
+    error::{ApiError, ApiResult, ErrorCode},
+};
+
+/// Service for admin authentication business logic
+pub struct AuthService {
+    repository: AdminRepository,
+    config: AuthConfig,
+}
+
+impl AuthService {
+    pub fn new(pool: SqlitePool, config: AuthConfig) -> Self {
+        Self {
+            repository: AdminRepository::new(pool),
+            config,
+        }
+    }
+
+    /// Check credentials and issue a session token
+    pub async fn login(&self, mut credentials: LoginRequest) -> ApiResult<LoginResponse> {
+        credentials.sanitize();
+
+        if let Err(validation_errors) = credentials.validate() {
+            warn!("Validation failed for login request: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        let admin = match self.repository.get_by_username(&credentials.username).await {
+            Ok(admin) => admin,
+            Err(e) => {
+                error!("Failed to fetch admin '{}': {}", credentials.username, e);
+                return Err(ApiError::Database(e));
+            }
+        };
+
+        // Verify against a dummy hash for unknown users so timing does not reveal which usernames exist
+        let stored_hash = match &admin {
+            Some(admin) => admin.password_hash.clone(),
+            None => dummy_hash().await?.to_string(),
+        };
+        let password_ok = verify_password(credentials.password, stored_hash).await?;
+
+        let admin = match admin {
+            Some(admin) if password_ok => admin,
+            _ => {
+                warn!("Failed login attempt for username: {}", credentials.username);
+                return Err(ApiError::Unauthorized);
+            }
+        };
+
+        if let Err(e) = self.repository.touch_last_login(admin.id).await {
+            error!("Failed to record login for admin {}: {}", admin.id, e);
+            return Err(ApiError::Database(e));
+        }
+
+        let (token, expires_at) = self.config.issue_token(&admin);
+        info!("Admin '{}' logged in", admin.username);
+
+        Ok(LoginResponse {
+            token,
+            token_type: "Bearer".to_string(),
+            expires_at,
+            admin,
+        })
+    }
+
+    /// Resolve a session token to the admin it was issued for
+    pub async fn authenticate_token(&self, token: &str) -> ApiResult<AdminUser> {
+        let claims = self.config.verify_token(token)?;
+
+        match self.repository.get_by_id(claims.sub).await {
+            Ok(Some(admin)) if admin.username == claims.username => Ok(admin),
+            Ok(_) => {
+                warn!("Session token refers to unknown admin {}", claims.sub);
+                Err(ApiError::Unauthorized)
+            }
+            Err(e) => {
+                error!("Failed to fetch admin {}: {}", claims.sub, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get admin user by ID
+    pub async fn get_admin_by_id(&self, id: i32) -> ApiResult<AdminUser> {
+        match self.repository.get_by_id(id).await {
+            Ok(Some(admin)) => Ok(admin),
+            Ok(None) => Err(ApiError::NotFound(ErrorCode::AdminNotFound, format!("Admin with ID {} not found", id))),
+            Err(e) => {
+                error!("Failed to fetch admin {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Create a new admin user
+    pub async fn create_admin(&self, mut admin_data: CreateAdminUser) -> ApiResult<AdminUser> {
+        admin_data.sanitize();
+        info!("Creating admin user: {}", admin_data.username);
+
+        if let Err(validation_errors) = admin_data.validate() {
+            warn!("Validation failed for admin creation: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        match self.repository.get_by_username(&admin_data.username).await {
+            Ok(Some(_)) => {
+                return Err(ApiError::Conflict(ErrorCode::AdminAlreadyExists, "An admin with this username already exists".to_string()));
+            }
+            Ok(None) => {}
+            Err(e) => return Err(ApiError::Database(e)),
+        }
+
+        let password_hash = hash_password(admin_data.password).await?;
+
+        match self.repository.create(&admin_data.username, &password_hash).await {
+            Ok(admin) => {
+                info!("Successfully created admin: {} (ID: {})", admin.username, admin.id);
+                Ok(admin)
+            }
+            Err(e) => {
+                error!("Failed to create admin '{}': {}", admin_data.username, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Create the first admin account if none exists yet
+    pub async fn bootstrap_admin(&self, admin_data: CreateAdminUser) -> ApiResult<Option<AdminUser>> {
+        let count = self.repository.count().await?;
+        if count > 0 {
+            info!("Admin accounts already exist, skipping bootstrap");
+            return Ok(None);
+        }
+
+        self.create_admin(admin_data).await.map(Some)
+    }
+}
+
+/// Hash a password with argon2id and a random salt
+pub async fn hash_password(password: String) -> ApiResult<String> {
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| ApiError::InternalServerError(format!("Failed to hash password: {}", e)))
+    })
+    .await
+    .map_err(|e| ApiError::InternalServerError(format!("Password hashing task failed: {}", e)))?
+}
+
+/// Check a password against a stored argon2 hash
+pub async fn verify_password(password: String, password_hash: String) -> ApiResult<bool> {
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&password_hash)
+            .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+            .unwrap_or(false)
+    })
+    .await
+    .map_err(|e| ApiError::InternalServerError(format!("Password verification task failed: {}", e)))
+}
+
+/// A valid argon2 hash of a random password, used to equalize login timing. Computed once, off
+/// the async workers like every other hash.
+async fn dummy_hash() -> ApiResult<&'static str> {
+    static DUMMY_HASH: OnceCell<String> = OnceCell::const_new();
+    DUMMY_HASH
+        .get_or_try_init(|| hash_password(SaltString::generate(&mut OsRng).as_str().to_string()))
+        .await
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use sqlx::SqlitePool;
+
+    async fn create_test_service() -> AuthService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        // Create table
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS admin_users (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                username TEXT NOT NULL UNIQUE,
+                password_hash TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                last_login_at DATETIME
+            );
+            "#
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        AuthService::new(pool, AuthConfig::new("a-test-secret-that-is-long-enough!", Duration::hours(1)))
+    }
+
+    fn create_test_admin() -> CreateAdminUser {
+        CreateAdminUser {
+            username: "Admin".to_string(),
+            password: "correct horse battery staple".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_password_hash_roundtrip() {
+        let hash = hash_password("s3cret-password".to_string()).await.unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("s3cret-password".to_string(), hash.clone()).await.unwrap());
+        assert!(!verify_password("wrong".to_string(), hash).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_create_admin_and_login() {
+        let service = create_test_service().await;
+        let admin = service.create_admin(create_test_admin()).await.unwrap();
+        assert_eq!(admin.username, "admin");
+
+        let response = service
+            .login(LoginRequest {
+                username: "ADMIN".to_string(),
+                password: "correct horse battery staple".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.token_type, "Bearer");
+        assert_eq!(response.admin.id, admin.id);
+
+        let authenticated = service.authenticate_token(&response.token).await.unwrap();
+        assert_eq!(authenticated.id, admin.id);
+        assert!(authenticated.last_login_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_login_wrong_password() {
+        let service = create_test_service().await;
+        service.create_admin(create_test_admin()).await.unwrap();
+
+        let result = service
+            .login(LoginRequest {
+                username: "admin".to_string(),
+                password: "not the password".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(ApiError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn test_login_unknown_user() {
+        let service = create_test_service().await;
+
+        let result = service
+            .login(LoginRequest {
+                username: "nobody".to_string(),
+                password: "whatever".to_string(),
+            })
+            .await;
+
+        assert!(matches!(result, Err(ApiError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn test_create_duplicate_admin() {
+        let service = create_test_service().await;
+        service.create_admin(create_test_admin()).await.unwrap();
+
+        let result = service.create_admin(create_test_admin()).await;
+        assert!(matches!(result, Err(ApiError::Conflict(..))));
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_admin_only_once() {
+        let service = create_test_service().await;
+
+        assert!(service.bootstrap_admin(create_test_admin()).await.unwrap().is_some());
+
+        let second = CreateAdminUser {
+            username: "other".to_string(),
+            password: "another long password".to_string(),
+        };
+        assert!(service.bootstrap_admin(second).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_token_for_deleted_admin_rejected() {
+        let service = create_test_service().await;
+        let admin = service.create_admin(create_test_admin()).await.unwrap();
+        let (token, _) = service.config.issue_token(&admin);
+
+        service.repository.delete(admin.id).await.unwrap();
+
+        assert!(matches!(service.authenticate_token(&token).await, Err(ApiError::Unauthorized)));
+    }
+}
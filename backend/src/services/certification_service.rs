@@ -0,0 +1,197 @@
+use sqlx::SqlitePool;
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    database::CertificationRepository,
+    models::{Certification, CreateCertification, UpdateCertification, timeline::check_period},
+    error::{ApiError, ApiResult, ErrorCode, FieldError},
+};
+
+/// Service for certification business logic
+pub struct CertificationService {
+    repository: CertificationRepository,
+}
+
+impl CertificationService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: CertificationRepository::new(pool),
+        }
+    }
+
+    /// Get all certifications
+    pub async fn get_all_certifications(&self) -> ApiResult<Vec<Certification>> {
+        info!("Fetching all certifications");
+
+        match self.repository.get_all().await {
+            Ok(certifications) => {
+                info!("Successfully fetched {} certifications", certifications.len());
+                Ok(certifications)
+            }
+            Err(e) => {
+                error!("Failed to fetch certifications: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get certification by ID
+    pub async fn get_certification_by_id(&self, id: i32) -> ApiResult<Certification> {
+        info!("Fetching certification with ID: {}", id);
+
+        match self.repository.get_by_id(id).await {
+            Ok(Some(certification)) => Ok(certification),
+            Ok(None) => {
+                warn!("Certification with ID {} not found", id);
+                Err(ApiError::NotFound(ErrorCode::CertificationNotFound, format!("Certification with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to fetch certification {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Create a new certification
+    pub async fn create_certification(&self, mut certification_data: CreateCertification) -> ApiResult<Certification> {
+        info!("Creating new certification: {}", certification_data.name);
+
+        if let Err(validation_errors) = certification_data.validate() {
+            warn!("Validation failed for certification creation: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+        check_expiry(&certification_data.issued_month, certification_data.expires_month.as_deref())?;
+
+        certification_data.name = certification_data.name.trim().to_string();
+        certification_data.issuer = certification_data.issuer.trim().to_string();
+
+        match self.repository.create(&certification_data).await {
+            Ok(certification) => {
+                info!("Successfully created certification with ID: {}", certification.id);
+                Ok(certification)
+            }
+            Err(e) => {
+                error!("Failed to create certification: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Update an existing certification
+    pub async fn update_certification(
+        &self,
+        id: i32,
+        mut certification_data: UpdateCertification,
+    ) -> ApiResult<Certification> {
+        info!("Updating certification with ID: {}", id);
+
+        if let Err(validation_errors) = certification_data.validate() {
+            warn!("Validation failed for certification update: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+        if !certification_data.has_updates() {
+            return Err(ApiError::BadRequest("No updates provided".to_string()));
+        }
+
+        let existing = self.get_certification_by_id(id).await?;
+        check_expiry(
+            certification_data.issued_month.as_deref().unwrap_or(&existing.issued_month),
+            certification_data.expires_month.as_deref().or(existing.expires_month.as_deref()),
+        )?;
+
+        if let Some(ref mut name) = certification_data.name {
+            *name = name.trim().to_string();
+        }
+        if let Some(ref mut issuer) = certification_data.issuer {
+            *issuer = issuer.trim().to_string();
+        }
+
+        match self.repository.update(id, &certification_data).await {
+            Ok(Some(certification)) => {
+                info!("Successfully updated certification with ID: {}", id);
+                Ok(certification)
+            }
+            Ok(None) => {
+                warn!("Certification with ID {} not found for update", id);
+                Err(ApiError::NotFound(ErrorCode::CertificationNotFound, format!("Certification with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to update certification {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Delete a certification
+    pub async fn delete_certification(&self, id: i32) -> ApiResult<()> {
+        info!("Deleting certification with ID: {}", id);
+
+        match self.repository.delete(id).await {
+            Ok(true) => {
+                info!("Successfully deleted certification with ID: {}", id);
+                Ok(())
+            }
+            Ok(false) => {
+                warn!("Certification with ID {} not found for deletion", id);
+                Err(ApiError::NotFound(ErrorCode::CertificationNotFound, format!("Certification with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to delete certification {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+}
+
+pub(crate) fn check_expiry(issued_month: &str, expires_month: Option<&str>) -> ApiResult<()> {
+    check_period(issued_month, expires_month)
+        .map_err(|_| FieldError::new("expires_month", "period", "A certification cannot expire before it is issued").into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_service() -> CertificationService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        CertificationService::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_certification_crud() {
+        let service = create_test_service().await;
+
+        let certification = CreateCertification {
+            name: "Certified Kubernetes Administrator".to_string(),
+            issuer: "CNCF".to_string(),
+            issued_month: "2022-04".to_string(),
+            expires_month: Some("2021-04".to_string()),
+            credential_id: None,
+            credential_url: Some("https://example.com/credential/42".to_string()),
+        };
+        assert!(matches!(service.create_certification(certification.clone()).await, Err(ApiError::ValidationErrors(_))));
+
+        let created = service
+            .create_certification(CreateCertification {
+                expires_month: Some("2025-04".to_string()),
+                ..certification
+            })
+            .await
+            .unwrap();
+        assert_eq!(created.expires_month.as_deref(), Some("2025-04"));
+
+        let update = UpdateCertification {
+            credential_url: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        assert!(service.update_certification(created.id, update).await.is_err());
+
+        service.delete_certification(created.id).await.unwrap();
+        assert!(service.get_all_certifications().await.unwrap().is_empty());
+    }
+}
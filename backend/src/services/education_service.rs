@@ -0,0 +1,192 @@
+use sqlx::SqlitePool;
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    database::EducationRepository,
+    models::{Education, CreateEducation, UpdateEducation, timeline::check_period},
+    error::{ApiError, ApiResult, ErrorCode},
+};
+
+/// Service for education business logic
+pub struct EducationService {
+    repository: EducationRepository,
+}
+
+impl EducationService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: EducationRepository::new(pool),
+        }
+    }
+
+    /// Get all education entries
+    pub async fn get_all_education(&self) -> ApiResult<Vec<Education>> {
+        info!("Fetching all education entries");
+
+        match self.repository.get_all().await {
+            Ok(education) => {
+                info!("Successfully fetched {} education entries", education.len());
+                Ok(education)
+            }
+            Err(e) => {
+                error!("Failed to fetch education entries: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get education entry by ID
+    pub async fn get_education_by_id(&self, id: i32) -> ApiResult<Education> {
+        info!("Fetching education entry with ID: {}", id);
+
+        match self.repository.get_by_id(id).await {
+            Ok(Some(education)) => Ok(education),
+            Ok(None) => {
+                warn!("Education entry with ID {} not found", id);
+                Err(ApiError::NotFound(ErrorCode::EducationNotFound, format!("Education entry with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to fetch education entry {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Create a new education entry
+    pub async fn create_education(&self, mut education_data: CreateEducation) -> ApiResult<Education> {
+        info!("Creating new education entry: {}", education_data.degree);
+
+        if let Err(validation_errors) = education_data.validate() {
+            warn!("Validation failed for education creation: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+        check_period(&education_data.start_month, education_data.end_month.as_deref())?;
+
+        education_data.institution = education_data.institution.trim().to_string();
+        education_data.degree = education_data.degree.trim().to_string();
+
+        match self.repository.create(&education_data).await {
+            Ok(education) => {
+                info!("Successfully created education entry with ID: {}", education.id);
+                Ok(education)
+            }
+            Err(e) => {
+                error!("Failed to create education entry: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Update an existing education entry
+    pub async fn update_education(&self, id: i32, mut education_data: UpdateEducation) -> ApiResult<Education> {
+        info!("Updating education entry with ID: {}", id);
+
+        if let Err(validation_errors) = education_data.validate() {
+            warn!("Validation failed for education update: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+        if !education_data.has_updates() {
+            return Err(ApiError::BadRequest("No updates provided".to_string()));
+        }
+
+        let existing = self.get_education_by_id(id).await?;
+        check_period(
+            education_data.start_month.as_deref().unwrap_or(&existing.start_month),
+            education_data.end_month.as_deref().or(existing.end_month.as_deref()),
+        )?;
+
+        if let Some(ref mut institution) = education_data.institution {
+            *institution = institution.trim().to_string();
+        }
+        if let Some(ref mut degree) = education_data.degree {
+            *degree = degree.trim().to_string();
+        }
+
+        match self.repository.update(id, &education_data).await {
+            Ok(Some(education)) => {
+                info!("Successfully updated education entry with ID: {}", id);
+                Ok(education)
+            }
+            Ok(None) => {
+                warn!("Education entry with ID {} not found for update", id);
+                Err(ApiError::NotFound(ErrorCode::EducationNotFound, format!("Education entry with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to update education entry {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Delete an education entry
+    pub async fn delete_education(&self, id: i32) -> ApiResult<()> {
+        info!("Deleting education entry with ID: {}", id);
+
+        match self.repository.delete(id).await {
+            Ok(true) => {
+                info!("Successfully deleted education entry with ID: {}", id);
+                Ok(())
+            }
+            Ok(false) => {
+                warn!("Education entry with ID {} not found for deletion", id);
+                Err(ApiError::NotFound(ErrorCode::EducationNotFound, format!("Education entry with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to delete education entry {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_service() -> EducationService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        EducationService::new(pool)
+    }
+
+    fn create_test_education() -> CreateEducation {
+        CreateEducation {
+            institution: "Université Lyon 1".to_string(),
+            degree: "Master".to_string(),
+            field_of_study: Some("Computer Science".to_string()),
+            location: Some("Lyon".to_string()),
+            start_month: "2015-09".to_string(),
+            end_month: Some("2017-06".to_string()),
+            description: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_education_crud() {
+        let service = create_test_service().await;
+
+        let created = service.create_education(create_test_education()).await.unwrap();
+        assert_eq!(created.degree, "Master");
+
+        let backwards = UpdateEducation {
+            start_month: Some("2018-01".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(service.update_education(created.id, backwards).await, Err(ApiError::ValidationErrors(_))));
+
+        let update = UpdateEducation {
+            description: Some("Thesis on query planning".to_string()),
+            ..Default::default()
+        };
+        let updated = service.update_education(created.id, update).await.unwrap();
+        assert_eq!(updated.description.as_deref(), Some("Thesis on query planning"));
+        assert_eq!(updated.end_month.as_deref(), Some("2017-06"));
+
+        service.delete_education(created.id).await.unwrap();
+        assert!(matches!(service.get_education_by_id(created.id).await, Err(ApiError::NotFound(..))));
+    }
+}
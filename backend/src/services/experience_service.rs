@@ -0,0 +1,271 @@
+use sqlx::SqlitePool;
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    database::ExperienceRepository,
+    models::{
+        Experience, CreateExperience, UpdateExperience, Technology,
+        experience::MAX_HIGHLIGHT_LEN, technology::MAX_TECHNOLOGY_NAME_LEN, timeline::check_period,
+    },
+    error::{ApiError, ApiResult, ErrorCode, FieldError},
+};
+
+/// Service for work experience business logic
+pub struct ExperienceService {
+    repository: ExperienceRepository,
+}
+
+impl ExperienceService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: ExperienceRepository::new(pool),
+        }
+    }
+
+    /// Get all experiences
+    pub async fn get_all_experiences(&self) -> ApiResult<Vec<Experience>> {
+        info!("Fetching all experiences");
+
+        match self.repository.get_all().await {
+            Ok(experiences) => {
+                info!("Successfully fetched {} experiences", experiences.len());
+                Ok(experiences)
+            }
+            Err(e) => {
+                error!("Failed to fetch experiences: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get experience by ID
+    pub async fn get_experience_by_id(&self, id: i32) -> ApiResult<Experience> {
+        info!("Fetching experience with ID: {}", id);
+
+        match self.repository.get_by_id(id).await {
+            Ok(Some(experience)) => Ok(experience),
+            Ok(None) => {
+                warn!("Experience with ID {} not found", id);
+                Err(ApiError::NotFound(ErrorCode::ExperienceNotFound, format!("Experience with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to fetch experience {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Create a new experience
+    pub async fn create_experience(&self, experience_data: CreateExperience) -> ApiResult<Experience> {
+        info!("Creating new experience: {} at {}", experience_data.role, experience_data.company);
+
+        if let Err(validation_errors) = experience_data.validate() {
+            warn!("Validation failed for experience creation: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+        let experience_data = normalize(experience_data)?;
+
+        match self.repository.create(&experience_data).await {
+            Ok(experience) => {
+                info!("Successfully created experience with ID: {}", experience.id);
+                Ok(experience)
+            }
+            Err(e) => {
+                error!("Failed to create experience: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Update an existing experience
+    pub async fn update_experience(&self, id: i32, experience_data: UpdateExperience) -> ApiResult<Experience> {
+        info!("Updating experience with ID: {}", id);
+
+        if let Err(validation_errors) = experience_data.validate() {
+            warn!("Validation failed for experience update: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+        if !experience_data.has_updates() {
+            return Err(ApiError::BadRequest("No updates provided".to_string()));
+        }
+        if experience_data.is_current == Some(true) && experience_data.end_month.is_some() {
+            return Err(FieldError::new("end_month", "period", "A current position cannot have an end month").into());
+        }
+
+        let existing = self.get_experience_by_id(id).await?;
+        let experience_data = normalize(experience_data.apply_to(existing))?;
+
+        match self.repository.update(id, &experience_data).await {
+            Ok(Some(experience)) => {
+                info!("Successfully updated experience with ID: {}", id);
+                Ok(experience)
+            }
+            Ok(None) => {
+                warn!("Experience with ID {} not found for update", id);
+                Err(ApiError::NotFound(ErrorCode::ExperienceNotFound, format!("Experience with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to update experience {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Delete an experience
+    pub async fn delete_experience(&self, id: i32) -> ApiResult<()> {
+        info!("Deleting experience with ID: {}", id);
+
+        match self.repository.delete(id).await {
+            Ok(true) => {
+                info!("Successfully deleted experience with ID: {}", id);
+                Ok(())
+            }
+            Ok(false) => {
+                warn!("Experience with ID {} not found for deletion", id);
+                Err(ApiError::NotFound(ErrorCode::ExperienceNotFound, format!("Experience with ID {} not found", id)))
+            }
+            Err(e) => {
+                error!("Failed to delete experience {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+}
+
+/// Trim an experience and check its period: a position is either current or has an end month
+pub(crate) fn normalize(mut experience: CreateExperience) -> ApiResult<CreateExperience> {
+    match (experience.is_current, &experience.end_month) {
+        (true, Some(_)) => {
+            return Err(FieldError::new("end_month", "period", "A current position cannot have an end month").into())
+        }
+        (false, None) => {
+            return Err(
+                FieldError::new("end_month", "required", "End month is required unless the position is current").into(),
+            )
+        }
+        _ => {}
+    }
+    check_period(&experience.start_month, experience.end_month.as_deref())?;
+
+    experience.company = experience.company.trim().to_string();
+    experience.role = experience.role.trim().to_string();
+    experience.location = experience
+        .location
+        .map(|location| location.trim().to_string())
+        .filter(|location| !location.is_empty());
+
+    experience.highlights = experience
+        .highlights
+        .iter()
+        .map(|highlight| highlight.trim().to_string())
+        .filter(|highlight| !highlight.is_empty())
+        .collect();
+    if experience.highlights.iter().any(|highlight| highlight.chars().count() > MAX_HIGHLIGHT_LEN) {
+        return Err(FieldError::new(
+            "highlights",
+            "length",
+            format!("Highlights must be at most {} characters", MAX_HIGHLIGHT_LEN),
+        )
+        .into());
+    }
+
+    experience.technologies = Technology::normalize_names(&experience.technologies);
+    if let Some(name) = experience.technologies.iter().find(|name| name.chars().count() > MAX_TECHNOLOGY_NAME_LEN) {
+        return Err(FieldError::new(
+            "technologies",
+            "length",
+            format!("Technology '{}' must be at most {} characters", name, MAX_TECHNOLOGY_NAME_LEN),
+        )
+        .into());
+    }
+
+    Ok(experience)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn create_test_service() -> ExperienceService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        ExperienceService::new(pool)
+    }
+
+    fn create_test_experience() -> CreateExperience {
+        CreateExperience {
+            company: " Acme ".to_string(),
+            role: "Backend developer".to_string(),
+            location: Some("  ".to_string()),
+            start_month: "2019-03".to_string(),
+            end_month: None,
+            is_current: true,
+            highlights: vec!["  Built the billing API ".to_string(), "".to_string()],
+            technologies: vec!["Rust".to_string(), "rust ".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_normalizes() {
+        let service = create_test_service().await;
+
+        let created = service.create_experience(create_test_experience()).await.unwrap();
+        assert_eq!(created.company, "Acme");
+        assert_eq!(created.location, None);
+        assert_eq!(created.highlights.0, vec!["Built the billing API"]);
+        assert_eq!(created.technologies.0, vec!["Rust"]);
+    }
+
+    #[tokio::test]
+    async fn test_period_rules() {
+        let service = create_test_service().await;
+
+        let both = CreateExperience {
+            end_month: Some("2020-01".to_string()),
+            ..create_test_experience()
+        };
+        assert!(matches!(service.create_experience(both).await, Err(ApiError::ValidationErrors(_))));
+
+        let neither = CreateExperience {
+            is_current: false,
+            ..create_test_experience()
+        };
+        assert!(matches!(service.create_experience(neither).await, Err(ApiError::ValidationErrors(_))));
+
+        let created = service.create_experience(create_test_experience()).await.unwrap();
+        let backwards = UpdateExperience {
+            end_month: Some("2018-12".to_string()),
+            ..Default::default()
+        };
+        match service.update_experience(created.id, backwards).await {
+            Err(ApiError::ValidationErrors(errors)) => {
+                assert_eq!((errors[0].field.as_str(), errors[0].code.as_str()), ("end_month", "period"));
+            }
+            other => panic!("Expected a validation error, got {:?}", other),
+        }
+
+        let ended = UpdateExperience {
+            end_month: Some("2023-06".to_string()),
+            ..Default::default()
+        };
+        let updated = service.update_experience(created.id, ended).await.unwrap();
+        assert!(!updated.is_current);
+        assert_eq!(updated.end_month.as_deref(), Some("2023-06"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_experience() {
+        let service = create_test_service().await;
+
+        let update = UpdateExperience {
+            role: Some("Lead".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(service.update_experience(42, update).await, Err(ApiError::NotFound(..))));
+        assert!(matches!(service.delete_experience(42).await, Err(ApiError::NotFound(..))));
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sqlx::SqlitePool;
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    database::{ProfileRepository, ProjectRepository, SkillRepository},
+    models::{
+        CreateProject, CreateSkill, ImportSummary, PortfolioExport, ProjectResponse, UpdateProfile,
+        EXPORT_FORMAT_VERSION, skill::SkillCategory,
+    },
+    services::{project_service::normalize_technologies, ProfileService, ProjectService, SkillService},
+    error::{ApiError, ApiResult},
+};
+
+/// Service for exporting and importing portfolio content
+pub struct ExportService {
+    pool: SqlitePool,
+    profile_service: ProfileService,
+    project_service: ProjectService,
+    skill_service: SkillService,
+}
+
+impl ExportService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool: pool.clone(),
+            profile_service: ProfileService::new(pool.clone()),
+            project_service: ProjectService::new(pool.clone()),
+            skill_service: SkillService::new(pool),
+        }
+    }
+
+    /// Export the profile, projects and skills
+    pub async fn export(&self) -> ApiResult<PortfolioExport> {
+        info!("Exporting portfolio content");
+
+        let profile = match self.profile_service.get_profile().await {
+            Ok(profile) => Some(profile),
+            Err(ApiError::NotFound(..)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let projects = self
+            .project_service
+            .get_all_projects()
+            .await?
+            .into_iter()
+            .map(ProjectResponse::from)
+            .collect();
+        let skills = self.skill_service.get_all_skills().await?;
+
+        Ok(PortfolioExport {
+            format_version: EXPORT_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            profile,
+            projects,
+            skills,
+        })
+    }
+
+    /// Import an export in one transaction, matching projects by title and skills by name
+    /// (case-insensitive).
+    ///
+    /// Everything is validated before anything is written, and a failed write rolls the others
+    /// back, so an invalid file leaves the database untouched.
+    pub async fn import(&self, data: PortfolioExport) -> ApiResult<ImportSummary> {
+        info!(
+            "Importing portfolio content: {} projects, {} skills",
+            data.projects.len(),
+            data.skills.len()
+        );
+
+        if data.format_version != EXPORT_FORMAT_VERSION {
+            return Err(ApiError::BadRequest(format!(
+                "Unsupported export format version {} (expected {})",
+                data.format_version, EXPORT_FORMAT_VERSION
+            )));
+        }
+
+        let profile = data.profile.map(UpdateProfile::from);
+        let projects: Vec<CreateProject> = data.projects.into_iter().map(CreateProject::from).collect();
+        let skills: Vec<CreateSkill> = data.skills.into_iter().map(CreateSkill::from).collect();
+
+        if let Some(profile) = &profile {
+            profile.validate().map_err(ApiError::from_validation_errors)?;
+        }
+
+        let mut seen = HashSet::new();
+        let mut planned_projects = Vec::with_capacity(projects.len());
+        for mut project in projects {
+            if let Err(validation_errors) = project.validate() {
+                warn!("Validation failed for imported project '{}': {:?}", project.title, validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+            project.title = project.title.trim().to_string();
+            project.category = project.category.trim().to_lowercase();
+            project.technologies = normalize_technologies(&project.technologies)?;
+            if !seen.insert(project.title.to_lowercase()) {
+                return Err(ApiError::BadRequest(format!("Project '{}' appears twice in the file", project.title)));
+            }
+            planned_projects.push(project);
+        }
+
+        let mut seen = HashSet::new();
+        let mut planned_skills = Vec::with_capacity(skills.len());
+        for mut skill in skills {
+            if let Err(validation_errors) = skill.validate() {
+                warn!("Validation failed for imported skill '{}': {:?}", skill.name, validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+            if SkillCategory::from_str(&skill.category).is_none() {
+                return Err(ApiError::BadRequest(format!("Invalid skill category: {}", skill.category)));
+            }
+            skill.name = skill.name.trim().to_string();
+            skill.category = skill.category.trim().to_string();
+            if !seen.insert(skill.name.to_lowercase()) {
+                return Err(ApiError::BadRequest(format!("Skill '{}' appears twice in the file", skill.name)));
+            }
+            planned_skills.push(skill);
+        }
+
+        let mut summary = ImportSummary::default();
+        let result = async {
+            let mut tx = self.pool.begin().await?;
+
+            // Insert or update is decided from what the transaction sees, not from an earlier read
+            let profile_exists = ProfileRepository::exists_on(&mut tx).await?;
+            let existing_projects = ProjectRepository::get_all_on(&mut tx).await?;
+            let existing_skills = SkillRepository::get_all_on(&mut tx).await?;
+
+            if let Some(profile) = &profile {
+                if !profile_exists {
+                    ProfileRepository::insert_initial(
+                        &mut tx,
+                        profile.name.as_deref().unwrap_or_default(),
+                        profile.title.as_deref().unwrap_or_default(),
+                        profile.bio.as_deref().unwrap_or_default(),
+                        profile.email.as_deref().unwrap_or_default(),
+                        profile.location.as_deref().unwrap_or_default(),
+                    )
+                    .await?;
+                }
+                ProfileRepository::update_on(&mut tx, profile).await?;
+                summary.profile_updated = true;
+            }
+
+            for project in &planned_projects {
+                let existing = existing_projects
+                    .iter()
+                    .find(|p| p.title.trim().to_lowercase() == project.title.to_lowercase());
+                match existing {
+                    Some(existing) => {
+                        ProjectRepository::update_on(&mut tx, existing.id, &project.clone().into()).await?;
+                        summary.projects_updated += 1;
+                    }
+                    None => {
+                        ProjectRepository::insert(&mut tx, project).await?;
+                        summary.projects_created += 1;
+                    }
+                }
+            }
+
+            for skill in &planned_skills {
+                let existing = existing_skills
+                    .iter()
+                    .find(|s| s.name.trim().to_lowercase() == skill.name.to_lowercase());
+                match existing {
+                    Some(existing) => {
+                        SkillRepository::update_on(&mut tx, existing.id, &skill.clone().into()).await?;
+                        summary.skills_updated += 1;
+                    }
+                    None => {
+                        SkillRepository::insert(&mut tx, skill).await?;
+                        summary.skills_created += 1;
+                    }
+                }
+            }
+
+            tx.commit().await
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!("Failed to import portfolio content: {}", e);
+            return Err(ApiError::Database(e));
+        }
+
+        info!("Import completed: {:?}", summary);
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{initialize_database, seed_database};
+
+    async fn create_test_service(seed: bool) -> ExportService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        initialize_database(pool.clone()).await.unwrap();
+        if seed {
+            seed_database(&pool).await.unwrap();
+        }
+
+        ExportService::new(pool)
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let source = create_test_service(true).await;
+        let export = source.export().await.unwrap();
+        assert!(export.profile.is_some());
+        assert!(!export.projects.is_empty());
+        assert!(!export.skills.is_empty());
+
+        // Survives serialization
+        let json = serde_json::to_string(&export).unwrap();
+        let export: PortfolioExport = serde_json::from_str(&json).unwrap();
+
+        let target = create_test_service(false).await;
+        let summary = target.import(export.clone()).await.unwrap();
+        assert!(summary.profile_updated);
+        assert_eq!(summary.projects_created, export.projects.len());
+        assert_eq!(summary.skills_created, export.skills.len());
+
+        let imported = target.export().await.unwrap();
+        assert_eq!(imported.projects.len(), export.projects.len());
+        assert_eq!(imported.skills.len(), export.skills.len());
+        assert_eq!(imported.profile.unwrap().name, export.profile.unwrap().name);
+    }
+
+    #[tokio::test]
+    async fn test_import_is_idempotent() {
+        let service = create_test_service(true).await;
+        let export = service.export().await.unwrap();
+
+        let summary = service.import(export.clone()).await.unwrap();
+        assert_eq!(summary.projects_created, 0);
+        assert_eq!(summary.projects_updated, export.projects.len());
+        assert_eq!(summary.skills_created, 0);
+        assert_eq!(summary.skills_updated, export.skills.len());
+
+        let after = service.export().await.unwrap();
+        assert_eq!(after.projects.len(), export.projects.len());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_import_writes_nothing() {
+        let source = create_test_service(true).await;
+        let mut export = source.export().await.unwrap();
+        export.skills[0].level = 42;
+
+        let target = create_test_service(false).await;
+        let result = target.import(export).await;
+        assert!(matches!(result, Err(ApiError::ValidationErrors(fields)) if fields[0].field == "level"));
+
+        let after = target.export().await.unwrap();
+        assert!(after.projects.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_with_duplicate_skills_writes_nothing() {
+        let source = create_test_service(true).await;
+        let mut export = source.export().await.unwrap();
+        let mut duplicate = export.skills[0].clone();
+        duplicate.name = format!(" {} ", duplicate.name.to_uppercase());
+        export.skills.push(duplicate);
+
+        let target = create_test_service(false).await;
+        let before = target.export().await.unwrap();
+        let result = target.import(export).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(message)) if message.contains("appears twice")));
+
+        let after = target.export().await.unwrap();
+        assert_eq!(after.profile.map(|p| p.name), before.profile.map(|p| p.name));
+        assert!(after.projects.is_empty());
+        assert!(after.skills.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_format_version() {
+        let service = create_test_service(false).await;
+        let mut export = service.export().await.unwrap();
+        export.format_version = EXPORT_FORMAT_VERSION + 1;
+
+        assert!(matches!(service.import(export).await, Err(ApiError::BadRequest(_))));
+    }
+}
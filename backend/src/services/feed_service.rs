@@ -0,0 +1,244 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tracing::{info, error};
+use crate::{
+    config::SiteConfig,
+    database::{ContentChangeRepository, PostRepository},
+    feed::{Feed, FeedContent, FeedEntry},
+    models::{Post, PostFilter, Profile, Project},
+    services::{ProfileService, ProjectService},
+    error::{ApiError, ApiResult},
+};
+
+/// Entries in a feed; readers only look for what is new
+pub const FEED_SIZE: usize = 50;
+
+/// What sets a feed apart from the others
+struct FeedSource<'a> {
+    /// Source of the entries in `content_changes`, also the name of the feed's file
+    name: &'a str,
+    title: &'a str,
+    description: &'a str,
+    /// Site page listing the same entries
+    home_path: &'a str,
+}
+
+/// Service building the syndication feeds of projects and posts
+pub struct FeedService {
+    profile_service: ProfileService,
+    project_service: ProjectService,
+    posts: PostRepository,
+    changes: ContentChangeRepository,
+    site: SiteConfig,
+}
+
+impl FeedService {
+    pub fn new(pool: SqlitePool, site: &SiteConfig) -> Self {
+        Self {
+            profile_service: ProfileService::new(pool.clone()),
+            project_service: ProjectService::new(pool.clone()),
+            posts: PostRepository::new(pool.clone()),
+            changes: ContentChangeRepository::new(pool),
+            site: site.clone(),
+        }
+    }
+
+    /// Feed of the most recently added projects
+    pub async fn projects_feed(&self) -> ApiResult<Feed> {
+        let profile = self.profile().await?;
+        let changed_at = self.changed_at("projects").await?;
+        let projects = self.project_service.get_all_projects().await?;
+        let entries = projects
+            .into_iter()
+            .take(FEED_SIZE)
+            .map(|project| self.project_entry(project))
+            .collect();
+
+        let source = FeedSource {
+            name: "projects",
+            title: "Projects",
+            description: "New and updated projects",
+            home_path: &self.site.pages.projects,
+        };
+        Ok(self.feed(source, profile.as_ref(), changed_at, entries))
+    }
+
+    /// Feed of the most recently published posts
+    pub async fn posts_feed(&self) -> ApiResult<Feed> {
+        let profile = self.profile().await?;
+        let changed_at = self.changed_at("posts").await?;
+        let filter = PostFilter {
+            public_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        let posts = match self.posts.find(&filter, FEED_SIZE as i64, 0).await {
+            Ok(posts) => posts,
+            Err(e) => {
+                error!("Failed to fetch posts for the feed: {}", e);
+                return Err(ApiError::Database(e));
+            }
+        };
+        let entries = posts.into_iter().map(|post| self.post_entry(post)).collect();
+
+        let source = FeedSource {
+            name: "posts",
+            title: "Blog",
+            description: "Latest posts",
+            home_path: &self.site.pages.blog,
+        };
+        Ok(self.feed(source, profile.as_ref(), changed_at, entries))
+    }
+
+    async fn profile(&self) -> ApiResult<Option<Profile>> {
+        match self.profile_service.get_profile().await {
+            Ok(profile) => Ok(Some(profile)),
+            Err(ApiError::NotFound(..)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Last change to the source of a feed or to the profile naming its author, deletions included
+    async fn changed_at(&self, source: &str) -> ApiResult<Option<DateTime<Utc>>> {
+        match self.changes.changes().await {
+            Ok(changes) => Ok(changes
+                .into_iter()
+                .filter(|(changed, _)| changed == source || changed == "profile")
+                .map(|(_, changed_at)| changed_at)
+                .max()),
+            Err(e) => {
+                error!("Failed to fetch content changes for the {} feed: {}", source, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    fn feed(
+        &self,
+        source: FeedSource,
+        profile: Option<&Profile>,
+        changed_at: Option<DateTime<Utc>>,
+        entries: Vec<FeedEntry>,
+    ) -> Feed {
+        let author = profile.map_or("Portfolio", |profile| profile.name.as_str());
+        // Entries also change when a scheduled post comes out, which touches no row
+        let updated = entries
+            .iter()
+            .map(|entry| entry.updated)
+            .chain(changed_at)
+            .max()
+            .unwrap_or_default();
+        info!("Built the {} feed with {} entries", source.name, entries.len());
+
+        Feed {
+            title: format!("{} — {}", author, source.title),
+            description: source.description.to_string(),
+            home_url: self.site.page_url(source.home_path),
+            feed_url: self.site.api_url(&format!("/feeds/{}", source.name)),
+            author: author.to_string(),
+            updated,
+            entries,
+        }
+    }
+
+    fn project_entry(&self, project: Project) -> FeedEntry {
+        let mut categories = vec![project.category];
+        for technology in project.technologies.0 {
+            if !categories.iter().any(|category| category.eq_ignore_ascii_case(&technology)) {
+                categories.push(technology);
+            }
+        }
+
+        FeedEntry {
+            url: self.site.project_url(project.id),
+            title: project.title,
+            content: FeedContent::Text(project.long_description.unwrap_or_else(|| project.description.clone())),
+            summary: project.description,
+            published: project.created_at,
+            updated: project.updated_at,
+            categories,
+        }
+    }
+
+    fn post_entry(&self, post: Post) -> FeedEntry {
+        let published: DateTime<Utc> = post.published_at.unwrap_or(post.created_at);
+        FeedEntry {
+            url: self.site.post_url(&post.slug),
+            title: post.title,
+            summary: post.summary,
+            content: FeedContent::Html(post.body_html),
+            published,
+            // A scheduled post appears in the feed when its date comes, after its last edit
+            updated: post.updated_at.max(published),
+            categories: post.tags.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{CreatePost, CreateProject},
+        services::PostService,
+    };
+
+    #[tokio::test]
+    async fn test_feeds() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let site = SiteConfig {
+            url: "https://example.com/".to_string(),
+            api_url: "https://api.example.com".to_string(),
+            ..Default::default()
+        };
+        let service = FeedService::new(pool.clone(), &site);
+
+        let project = ProjectService::new(pool.clone())
+            .create_project(CreateProject {
+                title: "Search engine".to_string(),
+                description: "Full-text search".to_string(),
+                long_description: None,
+                technologies: vec!["Rust".to_string(), "Web".to_string()],
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "Web".to_string(),
+                featured: None,
+            })
+            .await
+            .unwrap();
+
+        let feed = service.projects_feed().await.unwrap();
+        assert_eq!(feed.feed_url, "https://api.example.com/feeds/projects");
+        assert_eq!(feed.entries.len(), 1);
+        let entry = &feed.entries[0];
+        assert_eq!(entry.url, format!("https://example.com/projects/{}", project.id));
+        assert_eq!(entry.categories, vec!["web", "Rust"]);
+        assert_eq!(entry.content, FeedContent::Text("Full-text search".to_string()));
+        assert!(feed.updated >= project.updated_at);
+
+        let posts = PostService::new(pool);
+        for (title, status) in [("Hello", "published"), ("Draft", "draft")] {
+            posts
+                .create_post(CreatePost {
+                    slug: None,
+                    title: title.to_string(),
+                    summary: format!("About {}", title),
+                    body: "Some *text*".to_string(),
+                    tags: vec!["rust".to_string()],
+                    status: Some(status.to_string()),
+                    published_at: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let feed = service.posts_feed().await.unwrap();
+        assert_eq!(feed.entries.len(), 1);
+        assert_eq!(feed.entries[0].url, "https://example.com/blog/hello");
+        assert_eq!(feed.entries[0].content, FeedContent::Html("<p>Some <em>text</em></p>\n".to_string()));
+        assert_eq!(feed.entries[0].categories, vec!["rust"]);
+    }
+}
@@ -0,0 +1,828 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sqlx::{SqliteConnection, SqlitePool};
+use validator::Validate;
+use tracing::{info, warn, error};
+use crate::{
+    database::{
+        CertificationRepository, EducationRepository, ExperienceRepository, ProfileRepository, ProjectRepository,
+        SkillRepository,
+    },
+    models::{
+        CreateCertification, CreateEducation, CreateExperience, CreateProject, CreateSkill, ImportAction, ImportChange,
+        JsonResume, JsonResumeImportReport, Profile, ProjectResponse, UpdateExperience, UpdateProfile,
+        json_resume::{category_from_group, infer_skill_category, level_from_label, month_from_date, non_empty, ResumeContent},
+        skill::SkillCategory,
+    },
+    services::{
+        CertificationService, EducationService, ExperienceService, ProfileService, ProjectService, SkillService,
+        certification_service::check_expiry, experience_service::normalize as normalize_experience,
+        project_service::normalize_technologies,
+    },
+    error::{ApiError, ApiResult, FieldError},
+};
+
+/// Level given to imported skills when neither the document nor an existing skill has one
+const DEFAULT_SKILL_LEVEL: i32 = 3;
+
+/// Category given to new projects without a `type`
+const DEFAULT_PROJECT_CATEGORY: &str = "other";
+
+/// Service mapping the portfolio to and from JSON Resume documents
+pub struct JsonResumeService {
+    pool: SqlitePool,
+    profile_service: ProfileService,
+    skill_service: SkillService,
+    project_service: ProjectService,
+    experience_service: ExperienceService,
+    education_service: EducationService,
+    certification_service: CertificationService,
+}
+
+/// A record of the document merged with its existing counterpart, if any
+struct Planned<T> {
+    name: String,
+    existing_id: Option<i32>,
+    action: ImportAction,
+    record: T,
+}
+
+impl<T: PartialEq> Planned<T> {
+    fn new(name: String, existing: Option<(i32, T)>, record: T) -> Self {
+        let (existing_id, action) = match existing {
+            None => (None, ImportAction::Create),
+            Some((id, current)) if current == record => (Some(id), ImportAction::Unchanged),
+            Some((id, _)) => (Some(id), ImportAction::Update),
+        };
+        Self {
+            name,
+            existing_id,
+            action,
+            record,
+        }
+    }
+
+    fn change(&self) -> ImportChange {
+        ImportChange {
+            name: self.name.clone(),
+            action: self.action,
+        }
+    }
+}
+
+/// Everything an import writes, validated before the transaction starts
+#[derive(Default)]
+struct ImportPlan {
+    profile: Option<(ImportAction, UpdateProfile)>,
+    skills: Vec<Planned<CreateSkill>>,
+    projects: Vec<Planned<CreateProject>>,
+    experiences: Vec<Planned<CreateExperience>>,
+    education: Vec<Planned<CreateEducation>>,
+    certifications: Vec<Planned<CreateCertification>>,
+}
+
+impl ImportPlan {
+    fn report(&self, dry_run: bool) -> JsonResumeImportReport {
+        JsonResumeImportReport {
+            dry_run,
+            profile: self.profile.as_ref().map(|(action, _)| *action),
+            skills: self.skills.iter().map(Planned::change).collect(),
+            projects: self.projects.iter().map(Planned::change).collect(),
+            experiences: self.experiences.iter().map(Planned::change).collect(),
+            education: self.education.iter().map(Planned::change).collect(),
+            certifications: self.certifications.iter().map(Planned::change).collect(),
+        }
+    }
+}
+
+impl JsonResumeService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool: pool.clone(),
+            profile_service: ProfileService::new(pool.clone()),
+            skill_service: SkillService::new(pool.clone()),
+            project_service: ProjectService::new(pool.clone()),
+            experience_service: ExperienceService::new(pool.clone()),
+            education_service: EducationService::new(pool.clone()),
+            certification_service: CertificationService::new(pool),
+        }
+    }
+
+    /// Export the portfolio as a JSON Resume document
+    pub async fn export(&self) -> ApiResult<JsonResume> {
+        info!("Exporting portfolio as JSON Resume");
+
+        let content = ResumeContent {
+            profile: self.find_profile().await?,
+            skills: self.skill_service.get_all_skills().await?,
+            projects: self
+                .project_service
+                .get_all_projects()
+                .await?
+                .into_iter()
+                .map(ProjectResponse::from)
+                .collect(),
+            experiences: self.experience_service.get_all_experiences().await?,
+            education: self.education_service.get_all_education().await?,
+            certifications: self.certification_service.get_all_certifications().await?,
+        };
+
+        Ok(JsonResume::from_content(content, Utc::now()))
+    }
+
+    /// Import a JSON Resume document in one transaction. Records are matched with existing ones
+    /// (skills by name, projects by title, positions and degrees by organization, title and
+    /// start month, certifications by name and issuer) and fields the document cannot carry
+    /// are kept. A dry run performs the same writes, then rolls them back.
+    pub async fn import(&self, resume: JsonResume, dry_run: bool) -> ApiResult<JsonResumeImportReport> {
+        info!(
+            "Importing JSON Resume{}: {} positions, {} skill groups, {} projects",
+            if dry_run { " (dry run)" } else { "" },
+            resume.work.len(),
+            resume.skills.len(),
+            resume.projects.len()
+        );
+
+        let plan = self.plan(resume).await?;
+
+        let result = async {
+            let mut tx = self.pool.begin().await?;
+            apply(&mut tx, &plan).await?;
+            if dry_run {
+                tx.rollback().await
+            } else {
+                tx.commit().await
+            }
+        }
+        .await;
+
+        if let Err(e) = result {
+            error!("Failed to import JSON Resume: {}", e);
+            return Err(ApiError::Database(e));
+        }
+
+        let report = plan.report(dry_run);
+        info!("JSON Resume import completed: {:?}", report);
+        Ok(report)
+    }
+
+    async fn find_profile(&self) -> ApiResult<Option<Profile>> {
+        match self.profile_service.get_profile().await {
+            Ok(profile) => Ok(Some(profile)),
+            Err(ApiError::NotFound(..)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Validate the document and merge it with the existing content
+    async fn plan(&self, resume: JsonResume) -> ApiResult<ImportPlan> {
+        let mut plan = ImportPlan::default();
+
+        if let Some(basics) = &resume.basics {
+            let update = basics.to_update_profile();
+            if let Err(validation_errors) = update.validate() {
+                warn!("Validation failed for imported basics: {:?}", validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+            let action = match self.find_profile().await? {
+                None => ImportAction::Create,
+                Some(profile) => {
+                    let current = UpdateProfile::from(profile);
+                    if merge_profile(&update, &current) == current {
+                        ImportAction::Unchanged
+                    } else {
+                        ImportAction::Update
+                    }
+                }
+            };
+            plan.profile = Some((action, update));
+        }
+
+        self.plan_skills(&resume, &mut plan).await?;
+        self.plan_projects(&resume, &mut plan).await?;
+        self.plan_experiences(&resume, &mut plan).await?;
+        self.plan_education(&resume, &mut plan).await?;
+        self.plan_certifications(&resume, &mut plan).await?;
+
+        Ok(plan)
+    }
+
+    /// Each keyword of a skill group is a skill; a group without keywords is a skill itself.
+    /// New skills get an inferred category, existing ones keep theirs unless the group is
+    /// named after a category.
+    async fn plan_skills(&self, resume: &JsonResume, plan: &mut ImportPlan) -> ApiResult<()> {
+        let existing_skills = self.skill_service.get_all_skills().await?;
+        let mut seen = HashSet::new();
+
+        for group in &resume.skills {
+            let group_name = group.name.as_deref().unwrap_or_default();
+            let level = group.level.as_deref().and_then(level_from_label);
+            let keywords = if group.keywords.is_empty() {
+                group.name.iter().cloned().collect()
+            } else {
+                group.keywords.clone()
+            };
+
+            for keyword in keywords {
+                let name = keyword.trim();
+                // The same keyword often appears in several groups; the first one wins
+                if name.is_empty() || !seen.insert(name.to_lowercase()) {
+                    continue;
+                }
+
+                let existing = existing_skills.iter().find(|skill| same_name(&skill.name, name));
+                let category = match existing {
+                    Some(skill) if category_from_group(group_name).is_none() => skill.category.clone(),
+                    _ => infer_skill_category(group_name, name)
+                        .unwrap_or(SkillCategory::Other)
+                        .as_str()
+                        .to_string(),
+                };
+                let skill = CreateSkill {
+                    name: existing.map_or_else(|| name.to_string(), |skill| skill.name.clone()),
+                    category,
+                    level: level.or(existing.map(|skill| skill.level)).unwrap_or(DEFAULT_SKILL_LEVEL),
+                    years_experience: existing.and_then(|skill| skill.years_experience),
+                    description: existing.and_then(|skill| skill.description.clone()),
+                };
+                if let Err(validation_errors) = skill.validate() {
+                    warn!("Validation failed for imported skill '{}': {:?}", name, validation_errors);
+                    return Err(ApiError::from_validation_errors(validation_errors));
+                }
+
+                let existing = existing.map(|skill| (skill.id, CreateSkill::from(skill.clone())));
+                plan.skills.push(Planned::new(skill.name.clone(), existing, skill));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Projects carry their technologies as keywords and their category as `type`;
+    /// a GitHub URL fills the repository link, any other URL the demo link
+    async fn plan_projects(&self, resume: &JsonResume, plan: &mut ImportPlan) -> ApiResult<()> {
+        let existing_projects: Vec<ProjectResponse> = self
+            .project_service
+            .get_all_projects()
+            .await?
+            .into_iter()
+            .map(ProjectResponse::from)
+            .collect();
+        let mut seen = HashSet::new();
+
+        for (index, project) in resume.projects.iter().enumerate() {
+            let title = non_empty(&project.name)
+                .ok_or_else(|| FieldError::new(format!("projects[{}].name", index), "required", "Project name is required"))?;
+            if !seen.insert(title.to_lowercase()) {
+                return Err(ApiError::BadRequest(format!("Project '{}' appears twice in the document", title)));
+            }
+
+            let existing = existing_projects
+                .iter()
+                .find(|p| same_name(&p.title, &title))
+                .cloned()
+                .map(CreateProject::from);
+
+            let description = non_empty(&project.description)
+                .or_else(|| existing.as_ref().map(|p| p.description.clone()))
+                .ok_or_else(|| {
+                    FieldError::new(format!("projects[{}].description", index), "required", "Project description is required")
+                })?;
+            let highlights: Vec<&str> = project
+                .highlights
+                .iter()
+                .map(|highlight| highlight.trim())
+                .filter(|highlight| !highlight.is_empty())
+                .collect();
+            let long_description = if highlights.is_empty() {
+                existing.as_ref().and_then(|p| p.long_description.clone())
+            } else {
+                Some(highlights.join("\n"))
+            };
+            let technologies = if project.keywords.is_empty() {
+                existing.as_ref().map(|p| p.technologies.clone()).unwrap_or_default()
+            } else {
+                project.keywords.clone()
+            };
+
+            let url = non_empty(&project.url);
+            let is_repository = url.as_deref().is_some_and(|url| url.contains("://github.com/"));
+            let (github_url, demo_url) = match url {
+                Some(url) if is_repository => (Some(url), existing.as_ref().and_then(|p| p.demo_url.clone())),
+                Some(url) => (existing.as_ref().and_then(|p| p.github_url.clone()), Some(url)),
+                None => (
+                    existing.as_ref().and_then(|p| p.github_url.clone()),
+                    existing.as_ref().and_then(|p| p.demo_url.clone()),
+                ),
+            };
+
+            let record = CreateProject {
+                title: existing.as_ref().map_or_else(|| title.clone(), |p| p.title.clone()),
+                description,
+                long_description,
+                technologies: normalize_technologies(&technologies)?,
+                github_url,
+                demo_url,
+                image_url: existing.as_ref().and_then(|p| p.image_url.clone()),
+                category: non_empty(&project.kind)
+                    .map(|kind| kind.to_lowercase())
+                    .or_else(|| existing.as_ref().map(|p| p.category.clone()))
+                    .unwrap_or_else(|| DEFAULT_PROJECT_CATEGORY.to_string()),
+                featured: existing.as_ref().and_then(|p| p.featured),
+            };
+            if let Err(validation_errors) = record.validate() {
+                warn!("Validation failed for imported project '{}': {:?}", title, validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+
+            let existing_id = existing_projects
+                .iter()
+                .find(|p| same_name(&p.title, &title))
+                .map(|p| p.id);
+            let existing = existing_id.zip(existing);
+            plan.projects.push(Planned::new(record.title.clone(), existing, record));
+        }
+
+        Ok(())
+    }
+
+    /// Positions without an end date are current; their technologies are kept, since
+    /// JSON Resume has no field for them
+    async fn plan_experiences(&self, resume: &JsonResume, plan: &mut ImportPlan) -> ApiResult<()> {
+        let existing_experiences = self.experience_service.get_all_experiences().await?;
+        let mut seen = HashSet::new();
+
+        for (index, work) in resume.work.iter().enumerate() {
+            let field = |name: &str| format!("work[{}].{}", index, name);
+            let company = non_empty(&work.name)
+                .ok_or_else(|| FieldError::new(field("name"), "required", "Company name is required"))?;
+            let role = non_empty(&work.position)
+                .ok_or_else(|| FieldError::new(field("position"), "required", "Position is required"))?;
+            let start_month = required_month(&work.start_date, &field("startDate"))?;
+            let end_month = optional_month(&work.end_date, &field("endDate"))?;
+
+            let key = (company.to_lowercase(), role.to_lowercase(), start_month.clone());
+            let name = format!("{} at {}", role, company);
+            if !seen.insert(key) {
+                return Err(ApiError::BadRequest(format!("Position '{}' appears twice in the document", name)));
+            }
+
+            let existing = existing_experiences.iter().find(|experience| {
+                same_name(&experience.company, &company)
+                    && same_name(&experience.role, &role)
+                    && experience.start_month == start_month
+            });
+
+            let incoming = CreateExperience {
+                company,
+                role,
+                location: non_empty(&work.location),
+                start_month,
+                is_current: end_month.is_none(),
+                end_month,
+                highlights: work.highlights.clone(),
+                technologies: Vec::new(),
+            };
+            if let Err(validation_errors) = incoming.validate() {
+                warn!("Validation failed for imported position '{}': {:?}", name, validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+
+            let record = match existing {
+                Some(experience) => CreateExperience {
+                    location: incoming.location.or_else(|| experience.location.clone()),
+                    technologies: experience.technologies.0.clone(),
+                    ..incoming
+                },
+                None => incoming,
+            };
+            let record = normalize_experience(record)?;
+
+            let existing = existing.map(|experience| {
+                (experience.id, UpdateExperience::default().apply_to(experience.clone()))
+            });
+            plan.experiences.push(Planned::new(name, existing, record));
+        }
+
+        Ok(())
+    }
+
+    /// `studyType` is the degree and `area` the field of study; an entry with only an area
+    /// uses it as the degree
+    async fn plan_education(&self, resume: &JsonResume, plan: &mut ImportPlan) -> ApiResult<()> {
+        let existing_education = self.education_service.get_all_education().await?;
+        let mut seen = HashSet::new();
+
+        for (index, entry) in resume.education.iter().enumerate() {
+            let field = |name: &str| format!("education[{}].{}", index, name);
+            let institution = non_empty(&entry.institution)
+                .ok_or_else(|| FieldError::new(field("institution"), "required", "Institution is required"))?;
+            let (degree, field_of_study) = match (non_empty(&entry.study_type), non_empty(&entry.area)) {
+                (Some(degree), area) => (degree, area),
+                (None, Some(area)) => (area, None),
+                (None, None) => {
+                    return Err(FieldError::new(field("studyType"), "required", "Degree is required").into())
+                }
+            };
+            let start_month = required_month(&entry.start_date, &field("startDate"))?;
+            let end_month = optional_month(&entry.end_date, &field("endDate"))?;
+
+            let key = (institution.to_lowercase(), degree.to_lowercase(), start_month.clone());
+            let name = format!("{}, {}", degree, institution);
+            if !seen.insert(key) {
+                return Err(ApiError::BadRequest(format!("Education '{}' appears twice in the document", name)));
+            }
+
+            let existing = existing_education.iter().find(|education| {
+                same_name(&education.institution, &institution)
+                    && same_name(&education.degree, &degree)
+                    && education.start_month == start_month
+            });
+
+            let record = CreateEducation {
+                institution: existing.map_or(institution, |education| education.institution.clone()),
+                degree: existing.map_or(degree, |education| education.degree.clone()),
+                field_of_study: field_of_study.or_else(|| existing.and_then(|education| education.field_of_study.clone())),
+                location: existing.and_then(|education| education.location.clone()),
+                start_month,
+                end_month: end_month.or_else(|| existing.and_then(|education| education.end_month.clone())),
+                description: existing.and_then(|education| education.description.clone()),
+            };
+            if let Err(validation_errors) = record.validate() {
+                warn!("Validation failed for imported education '{}': {:?}", name, validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+            crate::models::timeline::check_period(&record.start_month, record.end_month.as_deref())
+                .map_err(|error| FieldError { field: field("endDate"), ..error })?;
+
+            let existing = existing.map(|education| (education.id, CreateEducation::from(education.clone())));
+            plan.education.push(Planned::new(name, existing, record));
+        }
+
+        Ok(())
+    }
+
+    /// The certificate date is the issue month; expiry and credential ID are kept
+    async fn plan_certifications(&self, resume: &JsonResume, plan: &mut ImportPlan) -> ApiResult<()> {
+        let existing_certifications = self.certification_service.get_all_certifications().await?;
+        let mut seen = HashSet::new();
+
+        for (index, certificate) in resume.certificates.iter().enumerate() {
+            let field = |name: &str| format!("certificates[{}].{}", index, name);
+            let name = non_empty(&certificate.name)
+                .ok_or_else(|| FieldError::new(field("name"), "required", "Certificate name is required"))?;
+            let issuer = non_empty(&certificate.issuer)
+                .ok_or_else(|| FieldError::new(field("issuer"), "required", "Issuer is required"))?;
+            let issued_month = required_month(&certificate.date, &field("date"))?;
+
+            if !seen.insert((name.to_lowercase(), issuer.to_lowercase())) {
+                return Err(ApiError::BadRequest(format!("Certificate '{}' appears twice in the document", name)));
+            }
+
+            let existing = existing_certifications.iter().find(|certification| {
+                same_name(&certification.name, &name) && same_name(&certification.issuer, &issuer)
+            });
+
+            let record = CreateCertification {
+                name: existing.map_or(name, |certification| certification.name.clone()),
+                issuer: existing.map_or(issuer, |certification| certification.issuer.clone()),
+                issued_month,
+                expires_month: existing.and_then(|certification| certification.expires_month.clone()),
+                credential_id: existing.and_then(|certification| certification.credential_id.clone()),
+                credential_url: non_empty(&certificate.url)
+                    .or_else(|| existing.and_then(|certification| certification.credential_url.clone())),
+            };
+            if let Err(validation_errors) = record.validate() {
+                warn!("Validation failed for imported certificate '{}': {:?}", record.name, validation_errors);
+                return Err(ApiError::from_validation_errors(validation_errors));
+            }
+            check_expiry(&record.issued_month, record.expires_month.as_deref())?;
+
+            let existing = existing.map(|certification| (certification.id, CreateCertification::from(certification.clone())));
+            plan.certifications.push(Planned::new(record.name.clone(), existing, record));
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the plan on an open transaction, skipping unchanged records
+async fn apply(conn: &mut SqliteConnection, plan: &ImportPlan) -> Result<(), sqlx::Error> {
+    if let Some((action, profile)) = &plan.profile {
+        if *action == ImportAction::Create {
+            ProfileRepository::insert_initial(
+                conn,
+                profile.name.as_deref().unwrap_or_default(),
+                profile.title.as_deref().unwrap_or_default(),
+                profile.bio.as_deref().unwrap_or_default(),
+                profile.email.as_deref().unwrap_or_default(),
+                profile.location.as_deref().unwrap_or_default(),
+            )
+            .await?;
+        }
+        if *action != ImportAction::Unchanged {
+            ProfileRepository::update_on(conn, profile).await?;
+        }
+    }
+
+    for skill in &plan.skills {
+        match (skill.action, skill.existing_id) {
+            (ImportAction::Create, _) => {
+                SkillRepository::insert(conn, &skill.record).await?;
+            }
+            (ImportAction::Update, Some(id)) => {
+                SkillRepository::update_on(conn, id, &skill.record.clone().into()).await?;
+            }
+            _ => {}
+        }
+    }
+
+    for project in &plan.projects {
+        match (project.action, project.existing_id) {
+            (ImportAction::Create, _) => {
+                ProjectRepository::insert(conn, &project.record).await?;
+            }
+            (ImportAction::Update, Some(id)) => {
+                ProjectRepository::update_on(conn, id, &project.record.clone().into()).await?;
+            }
+            _ => {}
+        }
+    }
+
+    for experience in &plan.experiences {
+        match (experience.action, experience.existing_id) {
+            (ImportAction::Create, _) => {
+                ExperienceRepository::insert(conn, &experience.record).await?;
+            }
+            (ImportAction::Update, Some(id)) => {
+                ExperienceRepository::replace(conn, id, &experience.record).await?;
+            }
+            _ => {}
+        }
+    }
+
+    for education in &plan.education {
+        match (education.action, education.existing_id) {
+            (ImportAction::Create, _) => {
+                EducationRepository::insert(conn, &education.record).await?;
+            }
+            (ImportAction::Update, Some(id)) => {
+                EducationRepository::update_on(conn, id, &education.record.clone().into()).await?;
+            }
+            _ => {}
+        }
+    }
+
+    for certification in &plan.certifications {
+        match (certification.action, certification.existing_id) {
+            (ImportAction::Create, _) => {
+                CertificationRepository::insert(conn, &certification.record).await?;
+            }
+            (ImportAction::Update, Some(id)) => {
+                CertificationRepository::update_on(conn, id, &certification.record.clone().into()).await?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The profile once the fields carried by the document are applied
+fn merge_profile(update: &UpdateProfile, current: &UpdateProfile) -> UpdateProfile {
+    let pick = |new: &Option<String>, old: &Option<String>| new.clone().or_else(|| old.clone());
+    UpdateProfile {
+        name: pick(&update.name, &current.name),
+        title: pick(&update.title, &current.title),
+        bio: pick(&update.bio, &current.bio),
+        email: pick(&update.email, &current.email),
+        phone: pick(&update.phone, &current.phone),
+        location: pick(&update.location, &current.location),
+        linkedin_url: pick(&update.linkedin_url, &current.linkedin_url),
+        github_url: pick(&update.github_url, &current.github_url),
+        twitter_url: pick(&update.twitter_url, &current.twitter_url),
+    }
+}
+
+fn required_month(date: &Option<String>, field: &str) -> ApiResult<String> {
+    optional_month(date, field)?.ok_or_else(|| FieldError::new(field, "required", "Date is required").into())
+}
+
+fn optional_month(date: &Option<String>, field: &str) -> ApiResult<Option<String>> {
+    match non_empty(date) {
+        None => Ok(None),
+        Some(date) => month_from_date(&date).map(Some).ok_or_else(|| {
+            FieldError::new(field, "date", "Dates must be written YYYY, YYYY-MM or YYYY-MM-DD").into()
+        }),
+    }
+}
+
+/// Whether a record already stored has this name: trimmed, and lowercased the Unicode way like
+/// the duplicate checks of the document and the rest of the portfolio
+fn same_name(stored: &str, name: &str) -> bool {
+    stored.trim().to_lowercase() == name.trim().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::database::{initialize_database, seed_database};
+
+    async fn create_test_service(seed: bool) -> JsonResumeService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        initialize_database(pool.clone()).await.unwrap();
+        if seed {
+            seed_database(&pool).await.unwrap();
+        }
+
+        JsonResumeService::new(pool)
+    }
+
+    fn create_test_resume() -> JsonResume {
+        serde_json::from_value(json!({
+            "basics": {
+                "name": "Jane Doe",
+                "label": "Backend developer",
+                "email": "jane@example.com",
+                "summary": "I build APIs.",
+                "location": { "city": "Lyon" },
+                "profiles": [{ "network": "GitHub", "username": "jdoe" }]
+            },
+            "work": [{
+                "name": "Acme",
+                "position": "Developer",
+                "startDate": "2020-02-01",
+                "highlights": ["Rewrote the search"]
+            }],
+            "education": [{
+                "institution": "Université Lyon 1",
+                "area": "Computer Science",
+                "studyType": "Master",
+                "startDate": "2015",
+                "endDate": "2017-06"
+            }],
+            "certificates": [{ "name": "CKA", "issuer": "CNCF", "date": "2022-04-12" }],
+            "skills": [
+                { "name": "Web Development", "level": "Advanced", "keywords": ["HTML", "Node.js", "Elm"] },
+                { "name": "Languages", "keywords": ["Haskell", "html"] }
+            ],
+            "projects": [{
+                "name": "Portfolio",
+                "description": "This site",
+                "keywords": ["Rust"],
+                "url": "https://github.com/jdoe/portfolio"
+            }],
+            "volunteer": [{ "organization": "Ignored" }]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_import_then_reimport() {
+        let service = create_test_service(false).await;
+
+        let report = service.import(create_test_resume(), false).await.unwrap();
+        assert!(!report.dry_run);
+        assert_eq!(report.profile, Some(ImportAction::Update));
+        let skills: Vec<&str> = report.skills.iter().map(|change| change.name.as_str()).collect();
+        assert_eq!(skills, vec!["HTML", "Node.js", "Elm", "Haskell"]);
+        assert!(report.experiences.iter().all(|change| change.action == ImportAction::Create));
+
+        let skills = service.skill_service.get_all_skills().await.unwrap();
+        let category = |name: &str| skills.iter().find(|skill| skill.name == name).unwrap().category.clone();
+        assert_eq!(category("HTML"), "Frontend");
+        assert_eq!(category("Node.js"), "Backend");
+        assert_eq!(category("Elm"), "Frontend");
+        assert_eq!(category("Haskell"), "Other");
+        assert!(skills.iter().filter(|skill| skill.name != "Haskell").all(|skill| skill.level == 4));
+
+        let profile = service.profile_service.get_profile().await.unwrap();
+        assert_eq!(profile.github_url.as_deref(), Some("https://github.com/jdoe"));
+        let experience = &service.experience_service.get_all_experiences().await.unwrap()[0];
+        assert!(experience.is_current);
+        assert_eq!(experience.start_month, "2020-02");
+        let project = &service.project_service.get_all_projects().await.unwrap()[0];
+        assert_eq!(project.github_url.as_deref(), Some("https://github.com/jdoe/portfolio"));
+        assert_eq!(project.category, "other");
+
+        // Importing the same document again changes nothing
+        let report = service.import(create_test_resume(), false).await.unwrap();
+        assert!(!report.has_changes(), "{:?}", report);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_writes_nothing() {
+        let service = create_test_service(false).await;
+
+        let report = service.import(create_test_resume(), true).await.unwrap();
+        assert!(report.dry_run);
+        assert!(report.has_changes());
+        assert_eq!(report.projects[0].action, ImportAction::Create);
+
+        assert_ne!(service.profile_service.get_profile().await.unwrap().name, "Jane Doe");
+        assert!(service.skill_service.get_all_skills().await.unwrap().is_empty());
+        assert!(service.project_service.get_all_projects().await.unwrap().is_empty());
+        assert!(service.experience_service.get_all_experiences().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_updates_and_keeps_unmapped_fields() {
+        let service = create_test_service(false).await;
+        service.import(create_test_resume(), false).await.unwrap();
+
+        let experience = &service.experience_service.get_all_experiences().await.unwrap()[0];
+        service
+            .experience_service
+            .update_experience(
+                experience.id,
+                UpdateExperience {
+                    technologies: Some(vec!["Rust".to_string()]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut resume = create_test_resume();
+        resume.work[0].end_date = Some("2023-06".to_string());
+        let report = service.import(resume, false).await.unwrap();
+        assert_eq!(report.experiences[0].action, ImportAction::Update);
+        assert_eq!(report.profile, Some(ImportAction::Unchanged));
+
+        let experience = &service.experience_service.get_all_experiences().await.unwrap()[0];
+        assert!(!experience.is_current);
+        assert_eq!(experience.end_month.as_deref(), Some("2023-06"));
+        assert_eq!(experience.technologies.0, vec!["Rust"]);
+    }
+
+    #[tokio::test]
+    async fn test_reimport_matches_non_ascii_names_ignoring_case() {
+        let service = create_test_service(false).await;
+        let mut resume = create_test_resume();
+        resume.skills[1].keywords.push("Élixir".to_string());
+        service.import(resume.clone(), false).await.unwrap();
+        let skill_count = service.skill_service.get_all_skills().await.unwrap().len();
+
+        resume.skills[1].keywords = vec!["élixir".to_string()];
+        resume.education[0].institution = Some("UNIVERSITÉ LYON 1".to_string());
+        let report = service.import(resume, false).await.unwrap();
+        assert!(report.skills.iter().all(|change| change.action != ImportAction::Create), "{:?}", report);
+        assert_ne!(report.education[0].action, ImportAction::Create);
+
+        assert_eq!(service.skill_service.get_all_skills().await.unwrap().len(), skill_count);
+        assert_eq!(service.education_service.get_all_education().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_document_is_rejected() {
+        let service = create_test_service(false).await;
+
+        let mut resume = create_test_resume();
+        resume.work[0].start_date = Some("sometime".to_string());
+        assert!(matches!(service.import(resume, false).await, Err(ApiError::ValidationErrors(_))));
+
+        let mut resume = create_test_resume();
+        resume.projects[0].keywords.clear();
+        assert!(matches!(service.import(resume, false).await, Err(ApiError::ValidationErrors(_))));
+        assert_ne!(service.profile_service.get_profile().await.unwrap().name, "Jane Doe");
+    }
+
+    #[tokio::test]
+    async fn test_export_roundtrip() {
+        let source = create_test_service(true).await;
+        let exported = source.export().await.unwrap();
+        assert!(exported.basics.is_some());
+        assert!(!exported.skills.is_empty());
+
+        let document = serde_json::to_value(&exported).unwrap();
+        assert!(document["$schema"].as_str().unwrap().contains("jsonresume"));
+        assert!(document["meta"]["lastModified"].is_string());
+
+        // The schema only allows its own top-level sections and ISO 8601 dates
+        let sections = [
+            "$schema", "basics", "work", "volunteer", "education", "awards", "certificates", "publications",
+            "skills", "languages", "interests", "references", "projects", "meta",
+        ];
+        assert!(document.as_object().unwrap().keys().all(|key| sections.contains(&key.as_str())));
+        for project in document["projects"].as_array().unwrap() {
+            assert!(month_from_date(project["startDate"].as_str().unwrap()).is_some());
+        }
+
+        // Re-importing an export into the same portfolio is a no-op
+        let report = source.import(serde_json::from_value(document.clone()).unwrap(), true).await.unwrap();
+        assert!(!report.has_changes(), "{:?}", report);
+
+        let target = create_test_service(false).await;
+        let report = target.import(serde_json::from_value(document).unwrap(), false).await.unwrap();
+        assert!(report.has_changes());
+        assert_eq!(
+            target.skill_service.get_all_skills().await.unwrap().len(),
+            source.skill_service.get_all_skills().await.unwrap().len()
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use sqlx::SqlitePool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error};
+use crate::{
+    config::UploadConfig,
+    database::MediaRepository,
+    media::{content_hash, derivative, is_content_hash, metadata, MediaStorage},
+    models::{Media, MediaResponse, MediaType, MediaVariant, VariantFormat},
+    error::{ApiError, ApiResult, ErrorCode},
+};
+
+/// Largest page of media returned by a listing
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Media whose derivatives are rendered in one pass of the worker
+const WORKER_BATCH_SIZE: i64 = 10;
+
+/// Service for uploaded media and their derivatives
+pub struct MediaService {
+    repository: MediaRepository,
+    storage: MediaStorage,
+    max_file_size_bytes: u64,
+    variant_widths: Vec<u32>,
+    variant_formats: Vec<VariantFormat>,
+    poll_interval: Duration,
+}
+
+impl MediaService {
+    pub fn new(pool: SqlitePool, config: &UploadConfig) -> Self {
+        Self {
+            repository: MediaRepository::new(pool),
+            storage: MediaStorage::new(&config.dir),
+            max_file_size_bytes: config.max_file_size_bytes,
+            variant_widths: config.variant_widths.clone(),
+            // Formats are checked when the configuration is loaded
+            variant_formats: config
+                .variant_formats
+                .iter()
+                .filter_map(|format| VariantFormat::from_str(format))
+                .collect(),
+            poll_interval: Duration::from_secs(config.variant_poll_interval_secs),
+        }
+    }
+
+    /// Store an uploaded image. Its format is recognised from its content and its metadata
+    /// (Exif, GPS position, XMP...) is removed; content already stored is not stored again
+    /// and the existing media is returned, with `false`.
+    pub async fn upload(&self, bytes: &[u8], original_name: Option<&str>) -> ApiResult<(Media, bool)> {
+        if bytes.is_empty() {
+            return Err(ApiError::BadRequest("The uploaded file is empty".to_string()));
+        }
+        if bytes.len() as u64 > self.max_file_size_bytes {
+            return Err(ApiError::PayloadTooLarge(format!(
+                "Files are limited to {} bytes",
+                self.max_file_size_bytes
+            )));
+        }
+
+        let Some(media_type) = MediaType::sniff(bytes) else {
+            warn!("Rejected upload of unsupported content ({:?})", original_name);
+            return Err(ApiError::UnsupportedMediaType(
+                "Only PNG, JPEG, WebP and GIF images are accepted".to_string(),
+            ));
+        };
+
+        let unreadable = |e: String| {
+            warn!("Rejected unreadable {} upload ({:?}): {}", media_type.as_str(), original_name, e);
+            ApiError::UnsupportedMediaType(format!("The file is not a readable {} image", media_type.extension()))
+        };
+        let orientation = derivative::probe(bytes, media_type)
+            .map_err(|e| unreadable(e.to_string()))?
+            .orientation;
+        let bytes = metadata::strip(bytes, media_type, orientation)
+            .ok_or_else(|| unreadable("malformed file structure".to_string()))?;
+        let info = derivative::probe(&bytes, media_type).map_err(|e| unreadable(e.to_string()))?;
+
+        let hash = content_hash(&bytes);
+        if let Err(e) = self.storage.store(&hash, media_type, &bytes).await {
+            error!("Failed to store media {}: {}", hash, e);
+            return Err(ApiError::InternalServerError(format!("Failed to store file: {}", e)));
+        }
+
+        let original_name = original_name.map(str::trim).filter(|name| !name.is_empty());
+        match self
+            .repository
+            .create(&hash, media_type, bytes.len() as i64, Some((info.width, info.height)), original_name)
+            .await
+        {
+            Ok((media, created)) => {
+                if created {
+                    info!("Stored media {} ({}, {} bytes)", media.hash, media.content_type, media.size_bytes);
+                } else {
+                    info!("Upload matched existing media {}", media.hash);
+                }
+                Ok((media, created))
+            }
+            Err(e) => {
+                error!("Failed to record media {}: {}", hash, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get media by content hash
+    pub async fn get_by_hash(&self, hash: &str) -> ApiResult<Media> {
+        let not_found = || ApiError::NotFound(ErrorCode::MediaNotFound, format!("Media {} not found", hash));
+        if !is_content_hash(hash) {
+            return Err(not_found());
+        }
+
+        match self.repository.get_by_hash(hash).await {
+            Ok(Some(media)) => Ok(media),
+            Ok(None) => Err(not_found()),
+            Err(e) => {
+                error!("Failed to fetch media {}: {}", hash, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Path of the stored file
+    pub fn file_path(&self, media: &Media) -> PathBuf {
+        self.storage.path(&media.hash, media.media_type())
+    }
+
+    /// Get a page of media, newest first, with their derivatives and the total count
+    pub async fn list_media(&self, page: u32, page_size: u32) -> ApiResult<(Vec<MediaResponse>, u64)> {
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        let offset = (page.max(1) - 1) as i64 * page_size as i64;
+
+        let listing = async {
+            let media = self.repository.list(page_size as i64, offset).await?;
+            let ids: Vec<i32> = media.iter().map(|media| media.id).collect();
+            let variants = self.repository.list_variants(&ids).await?;
+            let total = self.repository.count().await?;
+
+            let media = media
+                .into_iter()
+                .map(|media| MediaResponse::new(media, variants.clone()))
+                .collect();
+            Ok::<_, sqlx::Error>((media, total as u64))
+        };
+
+        match listing.await {
+            Ok(listing) => Ok(listing),
+            Err(e) => {
+                error!("Failed to list media: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Delete media that no gallery shows, along with its file
+    pub async fn delete_media(&self, hash: &str) -> ApiResult<()> {
+        let media = self.get_by_hash(hash).await?;
+        let variants = match self.repository.list_variants(&[media.id]).await {
+            Ok(variants) => variants,
+            Err(e) => {
+                error!("Failed to fetch variants of media {}: {}", hash, e);
+                return Err(ApiError::Database(e));
+            }
+        };
+
+        match self.repository.count_uses(media.id).await {
+            Ok(0) => {}
+            Ok(uses) => {
+                return Err(ApiError::Conflict(ErrorCode::MediaInUse, format!(
+                    "Media {} is shown in {} project gallery(ies); remove it from them first",
+                    hash, uses
+                )));
+            }
+            Err(e) => {
+                error!("Failed to check uses of media {}: {}", hash, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        if let Err(e) = self.repository.delete(media.id).await {
+            error!("Failed to delete media {}: {}", hash, e);
+            return Err(ApiError::Database(e));
+        }
+
+        // The row is gone, so a file left behind is only wasted space
+        if let Err(e) = self.storage.remove(&media.hash, media.media_type()).await {
+            warn!("Failed to remove file of media {}: {}", hash, e);
+        }
+        for variant in variants {
+            let (width, format) = (variant.width as u32, variant.variant_format());
+            if let Err(e) = self.storage.remove_variant(&media.hash, width, format).await {
+                warn!("Failed to remove {} variant {} of media {}: {}", format.as_str(), width, hash, e);
+            }
+        }
+
+        info!("Deleted media {}", hash);
+        Ok(())
+    }
+
+    /// Derivative of a media at one of the configured widths (the original width when none
+    /// is given) in one of the configured formats (the original one when none is given),
+    /// rendered on first request. Widths beyond the original give the full-size derivative.
+    pub async fn variant(
+        &self,
+        media: &Media,
+        width: Option<u32>,
+        format: Option<VariantFormat>,
+    ) -> ApiResult<(MediaVariant, PathBuf)> {
+        if let Some(width) = width.filter(|width| !self.variant_widths.contains(width)) {
+            let widths: Vec<String> = self.variant_widths.iter().map(u32::to_string).collect();
+            return Err(ApiError::BadRequest(format!(
+                "Width {} is not available, use one of: {}",
+                width,
+                widths.join(", ")
+            )));
+        }
+        let default_format = media.media_type().variant_format();
+        let mut formats = self.variant_formats.clone();
+        if !formats.contains(&default_format) {
+            formats.push(default_format);
+        }
+        let format = format.unwrap_or(default_format);
+        if !formats.contains(&format) {
+            let formats: Vec<&str> = formats.iter().map(VariantFormat::as_str).collect();
+            return Err(ApiError::BadRequest(format!(
+                "Format {} is not available, use one of: {}",
+                format.as_str(),
+                formats.join(", ")
+            )));
+        }
+
+        let mut original = None;
+        let full_width = match media.width {
+            Some(full_width) => full_width as u32,
+            None => {
+                let bytes = self.read_original(media).await?;
+                let info = derivative::probe(&bytes, media.media_type()).map_err(|e| {
+                    error!("Failed to read media {}: {}", media.hash, e);
+                    ApiError::InternalServerError(e.to_string())
+                })?;
+                original = Some(bytes);
+                info.width
+            }
+        };
+        let width = width.map_or(full_width, |width| width.min(full_width));
+
+        let path = self.storage.variant_path(&media.hash, width, format);
+        match self.repository.get_variant(media.id, width, format).await {
+            Ok(Some(variant)) if tokio::fs::try_exists(&path).await.unwrap_or(false) => return Ok((variant, path)),
+            Ok(_) => {}
+            Err(e) => {
+                error!("Failed to fetch variant of media {}: {}", media.hash, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        let original = match original {
+            Some(bytes) => bytes,
+            None => self.read_original(media).await?,
+        };
+        let variant = self.render_variant(media, original, width, format).await?;
+        Ok((variant, path))
+    }
+
+    /// Render the configured derivatives of media uploaded since the last pass, returning
+    /// how many media were processed
+    pub async fn process_pending(&self) -> ApiResult<usize> {
+        let pending = match self.repository.list_unprocessed(WORKER_BATCH_SIZE).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                error!("Failed to fetch media awaiting derivatives: {}", e);
+                return Err(ApiError::Database(e));
+            }
+        };
+
+        for media in &pending {
+            // A media that cannot be rendered is not retried; its derivatives are still
+            // rendered on request, where the failure shows
+            let dimensions = match self.render_all(media).await {
+                Ok(dimensions) => dimensions,
+                Err(e) => {
+                    warn!("Failed to render derivatives of media {}: {}", media.hash, e);
+                    None
+                }
+            };
+            if let Err(e) = self.repository.mark_processed(media.id, dimensions).await {
+                error!("Failed to mark media {} as processed: {}", media.hash, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        Ok(pending.len())
+    }
+
+    /// Render derivatives in the background, checking for new uploads every poll interval
+    pub fn spawn_worker(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                // Errors are logged by process_pending; the next pass tries again
+                match self.process_pending().await {
+                    Ok(processed) if processed > 0 => {
+                        info!("Rendered derivatives of {} media", processed);
+                        // More may be waiting
+                        continue;
+                    }
+                    _ => tokio::time::sleep(self.poll_interval).await,
+                }
+            }
+        })
+    }
+
+    /// Render every configured derivative of a media, returning its dimensions
+    async fn render_all(&self, media: &Media) -> ApiResult<Option<(u32, u32)>> {
+        let original = self.read_original(media).await?;
+        let info = derivative::probe(&original, media.media_type())
+            .map_err(|e| ApiError::InternalServerError(e.to_string()))?;
+
+        let mut widths: Vec<u32> = self.variant_widths.iter().map(|width| (*width).min(info.width)).collect();
+        widths.sort_unstable();
+        widths.dedup();
+
+        for format in &self.variant_formats {
+            for width in &widths {
+                self.render_variant(media, Arc::clone(&original), *width, *format).await?;
+            }
+        }
+
+        info!(
+            "Rendered {} derivatives of media {}",
+            widths.len() * self.variant_formats.len(),
+            media.hash
+        );
+        Ok(Some((info.width, info.height)))
+    }
+
+    async fn read_original(&self, media: &Media) -> ApiResult<Arc<[u8]>> {
+        match tokio::fs::read(self.file_path(media)).await {
+            Ok(bytes) => Ok(bytes.into()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Err(ApiError::NotFound(ErrorCode::MediaNotFound, format!("Media {} not found", media.hash)))
+            }
+            Err(e) => {
+                error!("Failed to read media {}: {}", media.hash, e);
+                Err(ApiError::InternalServerError(format!("Failed to read file: {}", e)))
+            }
+        }
+    }
+
+    async fn render_variant(
+        &self,
+        media: &Media,
+        original: Arc<[u8]>,
+        width: u32,
+        format: VariantFormat,
+    ) -> ApiResult<MediaVariant> {
+        // Decoding and encoding take long enough to stall the runtime
+        let media_type = media.media_type();
+        let rendered = tokio::task::spawn_blocking(move || derivative::render(&original, media_type, width, format))
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Rendering task failed: {}", e)))?
+            .map_err(|e| {
+                error!("Failed to render {} variant {} of media {}: {}", format.as_str(), width, media.hash, e);
+                ApiError::InternalServerError(e.to_string())
+            })?;
+
+        if let Err(e) = self.storage.store_variant(&media.hash, width, format, &rendered.bytes).await {
+            error!("Failed to store variant of media {}: {}", media.hash, e);
+            return Err(ApiError::InternalServerError(format!("Failed to store file: {}", e)));
+        }
+
+        match self
+            .repository
+            .save_variant(media.id, format, (width, rendered.height), rendered.bytes.len() as i64)
+            .await
+        {
+            Ok(variant) => Ok(variant),
+            Err(e) => {
+                error!("Failed to record variant of media {}: {}", media.hash, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media::derivative::test_image;
+
+    async fn create_test_service(dir: &std::path::Path) -> MediaService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let config = UploadConfig {
+            dir: dir.to_path_buf(),
+            max_file_size_bytes: 4096,
+            variant_widths: vec![16, 64],
+            variant_formats: vec!["webp".to_string()],
+            ..UploadConfig::default()
+        };
+        MediaService::new(pool, &config)
+    }
+
+    #[tokio::test]
+    async fn test_upload_deduplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = create_test_service(dir.path()).await;
+        let png = test_image(MediaType::Png, 40, 30);
+
+        let (media, created) = service.upload(&png, Some("screenshot.png")).await.unwrap();
+        assert!(created);
+        assert_eq!(media.content_type, "image/png");
+        assert_eq!(media.size_bytes, png.len() as i64);
+        assert_eq!((media.width, media.height), (Some(40), Some(30)));
+        assert!(service.file_path(&media).exists());
+
+        let (again, created) = service.upload(&png, Some("copy.png")).await.unwrap();
+        assert!(!created);
+        assert_eq!(again.id, media.id);
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejects_other_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = create_test_service(dir.path()).await;
+
+        assert!(matches!(
+            service.upload(b"<html>not an image</html>", Some("fake.png")).await,
+            Err(ApiError::UnsupportedMediaType(_))
+        ));
+        // Looks like a PNG but cannot be read as one
+        assert!(matches!(
+            service.upload(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR-broken", None).await,
+            Err(ApiError::UnsupportedMediaType(_))
+        ));
+        assert!(matches!(service.upload(&[0u8; 4097], None).await, Err(ApiError::PayloadTooLarge(_))));
+        assert!(matches!(service.upload(b"", None).await, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_variants_rendered_in_background_and_on_demand() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = create_test_service(dir.path()).await;
+        let (media, _) = service.upload(&test_image(MediaType::Jpeg, 40, 20), None).await.unwrap();
+
+        assert_eq!(service.process_pending().await.unwrap(), 1);
+        assert_eq!(service.process_pending().await.unwrap(), 0);
+        let (listed, _) = service.list_media(1, 10).await.unwrap();
+        let sizes: Vec<(i32, i32, &str)> =
+            listed[0].variants.iter().map(|v| (v.width, v.height, v.format.as_str())).collect();
+        // 64 is wider than the original, so the full-size variant stands in for it
+        assert_eq!(sizes, vec![(16, 8, "webp"), (40, 20, "webp")]);
+
+        let (variant, path) = service.variant(&media, Some(64), Some(VariantFormat::Webp)).await.unwrap();
+        assert_eq!(variant.width, 40);
+        assert!(path.exists());
+
+        // The original format is always available; others have to be configured
+        let (variant, path) = service.variant(&media, Some(16), None).await.unwrap();
+        assert_eq!((variant.width, variant.format.as_str()), (16, "jpeg"));
+        assert_eq!(MediaType::sniff(&std::fs::read(&path).unwrap()), Some(MediaType::Jpeg));
+        assert!(matches!(
+            service.variant(&media, Some(16), Some(VariantFormat::Avif)).await,
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(service.variant(&media, Some(100), None).await, Err(ApiError::BadRequest(_))));
+
+        service.delete_media(&media.hash).await.unwrap();
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_delete_media() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = create_test_service(dir.path()).await;
+        let (media, _) = service.upload(&test_image(MediaType::Png, 8, 8), None).await.unwrap();
+        let path = service.file_path(&media);
+
+        service.delete_media(&media.hash).await.unwrap();
+        assert!(!path.exists());
+        assert!(matches!(service.get_by_hash(&media.hash).await, Err(ApiError::NotFound(..))));
+        assert!(matches!(service.get_by_hash("../secret").await, Err(ApiError::NotFound(..))));
+    }
+}
@@ -3,8 +3,38 @@ pub mod project_service;
 pub mod skill_service;
 pub mod profile_service;
 pub mod contact_service;
+pub mod auth_service;
+pub mod api_key_service;
+pub mod export_service;
+pub mod technology_service;
+pub mod search_service;
+pub mod notification_service;
+pub mod media_service;
+pub mod post_service;
+pub mod experience_service;
+pub mod education_service;
+pub mod certification_service;
+pub mod json_resume_service;
+pub mod resume_pdf_service;
+pub mod feed_service;
+pub mod sitemap_service;
 
 pub use project_service::ProjectService;
 pub use skill_service::SkillService;
 pub use profile_service::ProfileService;
-pub use contact_service::ContactService;
\ No newline at end of file
+pub use contact_service::ContactService;
+pub use auth_service::AuthService;
+pub use api_key_service::ApiKeyService;
+pub use export_service::ExportService;
+pub use technology_service::TechnologyService;
+pub use search_service::SearchService;
+pub use notification_service::NotificationService;
+pub use media_service::MediaService;
+pub use post_service::PostService;
+pub use experience_service::ExperienceService;
+pub use education_service::EducationService;
+pub use certification_service::CertificationService;
+pub use json_resume_service::JsonResumeService;
+pub use resume_pdf_service::ResumePdfService;
+pub use feed_service::FeedService;
+pub use sitemap_service::SitemapService;
\ No newline at end of file
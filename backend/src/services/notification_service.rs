@@ -0,0 +1,418 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::SqlitePool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn, error};
+use crate::{
+    config::NotificationConfig,
+    database::NotificationRepository,
+    models::{ContactMessage, Notification, NotificationJob, NotificationKind},
+    notifier::{template, Notifiers},
+    error::{ApiError, ApiResult},
+};
+
+/// Number of queued notifications handled per pass of the worker
+const BATCH_SIZE: i64 = 20;
+
+/// Longest wait between two attempts at the same notification
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Outcome of one pass over the notification queue
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DeliveryReport {
+    pub sent: usize,
+    pub retried: usize,
+    pub failed: usize,
+}
+
+/// Service queueing notifications about contact messages and delivering them
+pub struct NotificationService {
+    repository: NotificationRepository,
+    config: NotificationConfig,
+}
+
+impl NotificationService {
+    pub fn new(pool: SqlitePool, config: NotificationConfig) -> Self {
+        Self {
+            repository: NotificationRepository::new(pool),
+            config,
+        }
+    }
+
+    /// Queue the notifications a new contact message calls for.
+    /// Likely spam only goes to the spam channel, and never gets an auto-reply.
+    pub async fn enqueue_for_message(&self, message: &ContactMessage) -> ApiResult<Vec<NotificationJob>> {
+        if !self.config.enabled {
+            return Ok(Vec::new());
+        }
+
+        let templates = &self.config.templates;
+        let mut notifications = Vec::new();
+
+        if message.is_spam {
+            if let Some(channel) = &self.config.spam_channel {
+                let notification = self.render(NotificationKind::Spam, message, &templates.spam_subject, &templates.owner_body);
+                notifications.push((channel, notification));
+            }
+        } else {
+            let notification = self.render(NotificationKind::Owner, message, &templates.owner_subject, &templates.owner_body);
+            notifications.push((&self.config.owner_channel, notification));
+
+            if let Some(channel) = &self.config.auto_reply_channel {
+                let notification = self.render(
+                    NotificationKind::AutoReply,
+                    message,
+                    &templates.auto_reply_subject,
+                    &templates.auto_reply_body,
+                );
+                notifications.push((channel, notification));
+            }
+        }
+
+        let mut jobs = Vec::with_capacity(notifications.len());
+        for (channel, notification) in notifications {
+            match self.repository.enqueue(&notification, channel).await {
+                Ok(job) => {
+                    info!("Queued {} notification {} for contact message {}", job.kind, job.id, message.id);
+                    jobs.push(job);
+                }
+                Err(e) => {
+                    error!("Failed to queue notification for contact message {}: {}", message.id, e);
+                    return Err(ApiError::Database(e));
+                }
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /// Get the notifications queued for a contact message
+    pub async fn get_for_message(&self, contact_message_id: i32) -> ApiResult<Vec<NotificationJob>> {
+        match self.repository.get_for_message(contact_message_id).await {
+            Ok(jobs) => Ok(jobs),
+            Err(e) => {
+                error!("Failed to fetch notifications of contact message {}: {}", contact_message_id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Attempt every due notification once, scheduling retries for transient failures
+    pub async fn deliver_due(&self, notifiers: &Notifiers) -> ApiResult<DeliveryReport> {
+        let jobs = match self.repository.get_due(Utc::now(), BATCH_SIZE).await {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                error!("Failed to fetch due notifications: {}", e);
+                return Err(ApiError::Database(e));
+            }
+        };
+
+        let mut report = DeliveryReport::default();
+        for job in jobs {
+            let result = match (job.notification(), notifiers.get(&job.channel)) {
+                (Some(notification), Some(notifier)) => notifier.send(&notification).await.map_err(|e| {
+                    let permanent = e.is_permanent();
+                    (e.to_string(), permanent)
+                }),
+                (None, _) => Err((format!("unknown notification kind '{}'", job.kind), true)),
+                (_, None) => Err((format!("channel '{}' is not configured", job.channel), true)),
+            };
+
+            let outcome = match result {
+                Ok(()) => {
+                    info!("Sent notification {} through {}", job.id, job.channel);
+                    report.sent += 1;
+                    self.repository.mark_sent(job.id).await
+                }
+                Err((message, permanent)) => {
+                    let attempts = job.attempts as u32 + 1;
+                    if permanent || attempts >= self.config.max_attempts {
+                        warn!("Giving up on notification {} after {} attempt(s): {}", job.id, attempts, message);
+                        report.failed += 1;
+                        self.repository.record_failure(job.id, &message, None).await
+                    } else {
+                        let delay = retry_delay(self.config.retry_delay_secs, attempts);
+                        warn!("Notification {} failed, retrying in {}s: {}", job.id, delay.as_secs(), message);
+                        report.retried += 1;
+                        let retry_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+                        self.repository.record_failure(job.id, &message, Some(retry_at)).await
+                    }
+                }
+            };
+
+            if let Err(e) = outcome {
+                error!("Failed to record delivery of notification {}: {}", job.id, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Deliver queued notifications in the background until the runtime shuts down
+    pub fn spawn_worker(self, notifiers: Notifiers) -> JoinHandle<()> {
+        let interval = Duration::from_secs(self.config.poll_interval_secs);
+
+        tokio::spawn(async move {
+            loop {
+                // Errors are logged by deliver_due; the next pass tries again
+                if let Ok(report) = self.deliver_due(&notifiers).await {
+                    if report != DeliveryReport::default() {
+                        info!("Notification delivery: {:?}", report);
+                    }
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    fn render(&self, kind: NotificationKind, message: &ContactMessage, subject: &str, body: &str) -> Notification {
+        let (to, reply_to) = match kind {
+            NotificationKind::Owner | NotificationKind::Spam => (self.config.owner_email.clone(), Some(message.email.clone())),
+            NotificationKind::AutoReply => (Some(message.email.clone()), self.config.owner_email.clone()),
+        };
+
+        Notification {
+            kind,
+            contact_message_id: Some(message.id),
+            to,
+            reply_to,
+            // Subjects are a single header line
+            subject: template::render(subject, message).split_whitespace().collect::<Vec<_>>().join(" "),
+            body: template::render(body, message),
+        }
+    }
+}
+
+/// Delay before the next attempt, doubling after each failure
+fn retry_delay(base_secs: u64, attempts: u32) -> Duration {
+    Duration::from_secs(base_secs)
+        .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+        .min(MAX_RETRY_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use axum::async_trait;
+
+    use crate::{
+        models::{CreateContactMessage, NotificationStatus},
+        notifier::{Notifier, NotifyError},
+        services::ContactService,
+        spam::{FormTokens, SpamPipeline},
+    };
+
+    /// Notifier recording what it sends, failing the first `failures` attempts
+    #[derive(Clone, Default)]
+    struct RecordingNotifier {
+        sent: Arc<Mutex<Vec<Notification>>>,
+        failures: Arc<Mutex<u32>>,
+        permanent: bool,
+    }
+
+    #[async_trait]
+    impl Notifier for RecordingNotifier {
+        async fn send(&self, notification: &Notification) -> Result<(), NotifyError> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(match self.permanent {
+                    true => NotifyError::Rejected("mailbox unavailable".to_string()),
+                    false => NotifyError::Unavailable("connection refused".to_string()),
+                });
+            }
+            self.sent.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        pool
+    }
+
+    fn create_test_config() -> NotificationConfig {
+        NotificationConfig {
+            enabled: true,
+            owner_channel: "stdout".to_string(),
+            spam_channel: Some("file".to_string()),
+            auto_reply_channel: Some("smtp".to_string()),
+            owner_email: Some("owner@example.com".to_string()),
+            retry_delay_secs: 0,
+            max_attempts: 3,
+            ..NotificationConfig::default()
+        }
+    }
+
+    async fn submit(pool: &SqlitePool, subject: &str, message: &str) -> ContactMessage {
+        let form_tokens = FormTokens::new("a-test-key", chrono::Duration::hours(1));
+        let spam = SpamPipeline::from_config(pool.clone(), &crate::config::SpamConfig::default(), form_tokens);
+        ContactService::new(pool.clone(), Some(spam))
+            .submit_message(CreateContactMessage {
+                name: "John Doe".to_string(),
+                email: "john.doe@example.com".to_string(),
+                subject: subject.to_string(),
+                message: message.to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_owner_notification_and_auto_reply() {
+        let pool = create_test_pool().await;
+        let service = NotificationService::new(pool.clone(), create_test_config());
+        let message = submit(&pool, "Project\ninquiry", "Could we talk about a web project next week?").await;
+
+        let jobs = service.enqueue_for_message(&message).await.unwrap();
+        assert_eq!(jobs.len(), 2);
+
+        let owner = jobs[0].notification().unwrap();
+        assert_eq!(jobs[0].channel, "stdout");
+        assert_eq!(owner.kind, NotificationKind::Owner);
+        assert_eq!(owner.to.as_deref(), Some("owner@example.com"));
+        assert_eq!(owner.reply_to.as_deref(), Some("john.doe@example.com"));
+        assert_eq!(owner.subject, "New contact message: Project inquiry");
+        assert!(owner.body.contains("Could we talk about a web project next week?"));
+
+        let auto_reply = jobs[1].notification().unwrap();
+        assert_eq!(jobs[1].channel, "smtp");
+        assert_eq!(auto_reply.kind, NotificationKind::AutoReply);
+        assert_eq!(auto_reply.to.as_deref(), Some("john.doe@example.com"));
+        assert!(auto_reply.body.starts_with("Hello John Doe,"));
+    }
+
+    #[tokio::test]
+    async fn test_spam_goes_to_spam_channel_only() {
+        let pool = create_test_pool().await;
+        let message = submit(&pool, "Congratulations", "You are the lottery winner, click here now").await;
+
+        let jobs = NotificationService::new(pool.clone(), create_test_config())
+            .enqueue_for_message(&message)
+            .await
+            .unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].channel, "file");
+        assert_eq!(jobs[0].kind, "spam");
+        assert_eq!(jobs[0].subject, "[Likely spam] Congratulations");
+
+        // Without a spam channel, spam is not reported at all
+        let config = NotificationConfig {
+            spam_channel: None,
+            ..create_test_config()
+        };
+        let jobs = NotificationService::new(pool, config).enqueue_for_message(&message).await.unwrap();
+        assert!(jobs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submission_queues_notifications() {
+        let pool = create_test_pool().await;
+        let message = ContactService::new(pool.clone(), Some(SpamPipeline::new(5.0, 10.0)))
+            .with_notifications(NotificationService::new(pool.clone(), create_test_config()))
+            .submit_message(CreateContactMessage {
+                name: "Jane Smith".to_string(),
+                email: "jane@example.com".to_string(),
+                subject: "Hello".to_string(),
+                message: "Could we talk about a web project next week?".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let service = NotificationService::new(pool, create_test_config());
+        let jobs = service.get_for_message(message.id).await.unwrap();
+        let kinds: Vec<&str> = jobs.iter().map(|job| job.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["owner", "auto_reply"]);
+        assert!(jobs.iter().all(|job| job.status == NotificationStatus::Pending.as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_queues_nothing() {
+        let pool = create_test_pool().await;
+        let message = submit(&pool, "Hello", "Could we talk about a web project next week?").await;
+
+        let service = NotificationService::new(pool, NotificationConfig::default());
+        assert!(service.enqueue_for_message(&message).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_retried_then_given_up() {
+        let pool = create_test_pool().await;
+        let config = NotificationConfig {
+            auto_reply_channel: None,
+            ..create_test_config()
+        };
+        let service = NotificationService::new(pool.clone(), config);
+        let message = submit(&pool, "Hello", "Could we talk about a web project next week?").await;
+        service.enqueue_for_message(&message).await.unwrap();
+
+        let notifier = RecordingNotifier::default();
+        *notifier.failures.lock().unwrap() = 1;
+        let notifiers = Notifiers::default().with("stdout", notifier.clone());
+
+        let report = service.deliver_due(&notifiers).await.unwrap();
+        assert_eq!(report, DeliveryReport { sent: 0, retried: 1, failed: 0 });
+
+        let report = service.deliver_due(&notifiers).await.unwrap();
+        assert_eq!(report, DeliveryReport { sent: 1, retried: 0, failed: 0 });
+        assert_eq!(notifier.sent.lock().unwrap().len(), 1);
+
+        let jobs = service.get_for_message(message.id).await.unwrap();
+        assert_eq!(jobs[0].status, NotificationStatus::Sent.as_str());
+        assert_eq!(jobs[0].attempts, 2);
+        assert!(jobs[0].sent_at.is_some());
+
+        // Nothing left to do
+        let report = service.deliver_due(&notifiers).await.unwrap();
+        assert_eq!(report, DeliveryReport::default());
+
+        // A notifier that keeps failing is given up after max_attempts
+        let message = submit(&pool, "Again", "Could we talk about a web project next week?").await;
+        service.enqueue_for_message(&message).await.unwrap();
+        *notifier.failures.lock().unwrap() = 10;
+        for _ in 0..3 {
+            service.deliver_due(&notifiers).await.unwrap();
+        }
+        let jobs = service.get_for_message(message.id).await.unwrap();
+        assert_eq!(jobs[0].status, NotificationStatus::Failed.as_str());
+        assert_eq!(jobs[0].attempts, 3);
+        assert_eq!(jobs[0].last_error.as_deref(), Some("Delivery failed: connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failures_not_retried() {
+        let pool = create_test_pool().await;
+        let service = NotificationService::new(pool.clone(), create_test_config());
+        let message = submit(&pool, "Hello", "Could we talk about a web project next week?").await;
+        service.enqueue_for_message(&message).await.unwrap();
+
+        let notifier = RecordingNotifier {
+            permanent: true,
+            ..Default::default()
+        };
+        *notifier.failures.lock().unwrap() = 1;
+        // The auto-reply channel has no notifier
+        let notifiers = Notifiers::default().with("stdout", notifier);
+
+        let report = service.deliver_due(&notifiers).await.unwrap();
+        assert_eq!(report, DeliveryReport { sent: 0, retried: 0, failed: 2 });
+
+        let jobs = service.get_for_message(message.id).await.unwrap();
+        assert!(jobs.iter().all(|job| job.status == NotificationStatus::Failed.as_str()));
+        assert_eq!(jobs[1].last_error.as_deref(), Some("channel 'smtp' is not configured"));
+    }
+
+    #[test]
+    fn test_retry_delay_doubles() {
+        assert_eq!(retry_delay(30, 1), Duration::from_secs(30));
+        assert_eq!(retry_delay(30, 3), Duration::from_secs(120));
+        assert_eq!(retry_delay(30, 40), MAX_RETRY_DELAY);
+    }
+}
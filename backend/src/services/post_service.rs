@@ -0,0 +1,450 @@
+use sqlx::SqlitePool;
+use validator::Validate;
+use tracing::{info, warn, error};
+use chrono::{DateTime, Utc};
+use crate::{
+    database::PostRepository,
+    markdown,
+    models::{
+        Post, PostFilter, PostRecord, PostResponse, PostStatus, PostSummary, CreatePost, UpdatePost,
+        post::{MAX_SLUG_LEN, MAX_TAG_LEN},
+    },
+    error::{ApiError, ApiResult, ErrorCode, FieldError},
+};
+
+/// Largest page of posts returned by a listing
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// Service for blog post business logic
+pub struct PostService {
+    repository: PostRepository,
+}
+
+impl PostService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: PostRepository::new(pool),
+        }
+    }
+
+    /// Page of the posts readers can see now, newest first
+    pub async fn list_published(&self, tag: Option<&str>, page: u32, page_size: u32) -> ApiResult<(Vec<PostSummary>, u64)> {
+        let filter = PostFilter {
+            public_at: Some(Utc::now()),
+            status: None,
+            tag: tag.map(normalize_tag),
+        };
+        self.list(filter, page, page_size).await
+    }
+
+    /// Page of every post, drafts included, newest first
+    pub async fn list_posts(
+        &self,
+        status: Option<&str>,
+        tag: Option<&str>,
+        page: u32,
+        page_size: u32,
+    ) -> ApiResult<(Vec<PostSummary>, u64)> {
+        let filter = PostFilter {
+            public_at: None,
+            status: status.map(parse_status).transpose()?,
+            tag: tag.map(normalize_tag),
+        };
+        self.list(filter, page, page_size).await
+    }
+
+    async fn list(&self, filter: PostFilter, page: u32, page_size: u32) -> ApiResult<(Vec<PostSummary>, u64)> {
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        let offset = (page.max(1) - 1) as i64 * page_size as i64;
+        let now = Utc::now();
+
+        let listing = async {
+            let posts = self.repository.find(&filter, page_size as i64, offset).await?;
+            let total = self.repository.count_matching(&filter).await?;
+            Ok::<_, sqlx::Error>((posts, total as u64))
+        };
+
+        match listing.await {
+            Ok((posts, total)) => {
+                info!("Successfully fetched {} of {} posts", posts.len(), total);
+                Ok((posts.into_iter().map(|post| PostSummary::at(post, now)).collect(), total))
+            }
+            Err(e) => {
+                error!("Failed to list posts: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Get a post readers can see now; drafts and posts scheduled later are not found
+    pub async fn get_published(&self, slug: &str) -> ApiResult<PostResponse> {
+        let now = Utc::now();
+        match self.find(slug).await? {
+            post if post.is_public_at(now) => Ok(PostResponse::at(post, now)),
+            _ => {
+                warn!("Post '{}' is not published", slug);
+                Err(ApiError::NotFound(ErrorCode::PostNotFound, format!("Post '{}' not found", slug)))
+            }
+        }
+    }
+
+    /// Get any post, drafts included
+    pub async fn get_post(&self, slug: &str) -> ApiResult<PostResponse> {
+        let post = self.find(slug).await?;
+        Ok(PostResponse::at(post, Utc::now()))
+    }
+
+    async fn find(&self, slug: &str) -> ApiResult<Post> {
+        info!("Fetching post: {}", slug);
+
+        match self.repository.get_by_slug(slug).await {
+            Ok(Some(post)) => Ok(post),
+            Ok(None) => {
+                warn!("Post '{}' not found", slug);
+                Err(ApiError::NotFound(ErrorCode::PostNotFound, format!("Post '{}' not found", slug)))
+            }
+            Err(e) => {
+                error!("Failed to fetch post '{}': {}", slug, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Create a post, rendering its Markdown
+    pub async fn create_post(&self, post: CreatePost) -> ApiResult<PostResponse> {
+        info!("Creating new post: {}", post.title);
+
+        if let Err(validation_errors) = post.validate() {
+            warn!("Validation failed for post creation: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        let now = Utc::now();
+        let slug = match &post.slug {
+            Some(slug) => slug.trim().to_string(),
+            None => Post::slugify(&post.title),
+        };
+        let status = match &post.status {
+            Some(status) => parse_status(status)?,
+            None => PostStatus::Draft,
+        };
+        let published_at = publication_date(status, post.published_at, now)?;
+
+        let record = self
+            .record(slug, None, post.title.trim(), post.summary.trim(), post.body, &post.tags, status, published_at)
+            .await?;
+
+        match self.repository.create(&record).await {
+            Ok(created) => {
+                info!("Successfully created post '{}' with ID: {}", created.slug, created.id);
+                Ok(PostResponse::at(created, now))
+            }
+            Err(e) => {
+                error!("Failed to create post: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Update a post; fields left out keep their value
+    pub async fn update_post(&self, slug: &str, update: UpdatePost) -> ApiResult<PostResponse> {
+        info!("Updating post: {}", slug);
+
+        if let Err(validation_errors) = update.validate() {
+            warn!("Validation failed for post update: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        let existing = self.find(slug).await?;
+        let now = Utc::now();
+
+        // A scheduled post whose date has come is updated as the published post it now is
+        let status = match &update.status {
+            Some(status) => parse_status(status)?,
+            None => existing.status_at(now),
+        };
+        // Publishing a post scheduled for later publishes it now, unless told otherwise
+        let published_at = update.published_at.or(match existing.published_at {
+            Some(at) if status == PostStatus::Published && at > now => None,
+            at => at,
+        });
+        let published_at = publication_date(status, published_at, now)?;
+
+        let record = self
+            .record(
+                update.slug.map(|slug| slug.trim().to_string()).unwrap_or(existing.slug),
+                Some(existing.id),
+                update.title.as_deref().unwrap_or(&existing.title).trim(),
+                update.summary.as_deref().unwrap_or(&existing.summary).trim(),
+                update.body.unwrap_or(existing.body_markdown),
+                update.tags.as_deref().unwrap_or(&existing.tags.0),
+                status,
+                published_at,
+            )
+            .await?;
+
+        match self.repository.update(existing.id, &record).await {
+            Ok(Some(updated)) => {
+                info!("Successfully updated post '{}'", updated.slug);
+                Ok(PostResponse::at(updated, now))
+            }
+            Ok(None) => Err(ApiError::NotFound(ErrorCode::PostNotFound, format!("Post '{}' not found", slug))),
+            Err(e) => {
+                error!("Failed to update post '{}': {}", slug, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Delete a post
+    pub async fn delete_post(&self, slug: &str) -> ApiResult<()> {
+        info!("Deleting post: {}", slug);
+
+        let post = self.find(slug).await?;
+        match self.repository.delete(post.id).await {
+            Ok(_) => {
+                info!("Successfully deleted post '{}'", slug);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to delete post '{}': {}", slug, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Check the slug and tags of a post and render its body
+    #[allow(clippy::too_many_arguments)]
+    async fn record(
+        &self,
+        slug: String,
+        id: Option<i32>,
+        title: &str,
+        summary: &str,
+        body_markdown: String,
+        tags: &[String],
+        status: PostStatus,
+        published_at: Option<DateTime<Utc>>,
+    ) -> ApiResult<PostRecord> {
+        if !Post::is_valid_slug(&slug) {
+            return Err(FieldError::new(
+                "slug",
+                "slug",
+                format!(
+                    "Slug must be lowercase letters and digits separated by dashes, at most {} characters, and not reserved",
+                    MAX_SLUG_LEN
+                ),
+            )
+            .into());
+        }
+        let blank: Vec<FieldError> = [("title", title), ("summary", summary)]
+            .into_iter()
+            .filter(|(_, value)| value.is_empty())
+            .map(|(field, _)| FieldError::new(field, "required", "Cannot be blank"))
+            .collect();
+        if !blank.is_empty() {
+            return Err(ApiError::ValidationErrors(blank));
+        }
+        let tags = normalize_tags(tags)?;
+
+        match self.repository.slug_exists(&slug, id).await {
+            Ok(false) => {}
+            Ok(true) => return Err(ApiError::Conflict(ErrorCode::PostSlugTaken, format!("A post with slug '{}' already exists", slug))),
+            Err(e) => {
+                error!("Failed to check post slug '{}': {}", slug, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        // Highlighting long code blocks takes long enough to stall the runtime
+        let (body_markdown, rendered) = tokio::task::spawn_blocking(move || {
+            let rendered = markdown::render(&body_markdown);
+            (body_markdown, rendered)
+        })
+        .await
+        .map_err(|e| ApiError::InternalServerError(format!("Rendering task failed: {}", e)))?;
+
+        Ok(PostRecord {
+            slug,
+            title: title.to_string(),
+            summary: summary.to_string(),
+            body_markdown,
+            reading_time_minutes: rendered.reading_time_minutes(),
+            body_html: rendered.html,
+            tags,
+            status,
+            published_at,
+        })
+    }
+}
+
+fn parse_status(status: &str) -> ApiResult<PostStatus> {
+    PostStatus::from_str(status).ok_or_else(|| {
+        FieldError::new(
+            "status",
+            "status",
+            format!("Unknown status '{}', use one of: {}", status, PostStatus::all().join(", ")),
+        )
+        .into()
+    })
+}
+
+/// Publication date of a post with the given status: published posts default to now and
+/// cannot be dated later, scheduled posts must be dated later. Drafts keep any date given.
+fn publication_date(
+    status: PostStatus,
+    published_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> ApiResult<Option<DateTime<Utc>>> {
+    match (status, published_at) {
+        (PostStatus::Draft, published_at) => Ok(published_at),
+        (PostStatus::Published, None) => Ok(Some(now)),
+        (PostStatus::Published, Some(at)) if at <= now => Ok(Some(at)),
+        (PostStatus::Published, Some(_)) => Err(FieldError::new(
+            "published_at",
+            "publication_date",
+            "A published post cannot be dated in the future, schedule it instead",
+        )
+        .into()),
+        (PostStatus::Scheduled, Some(at)) if at > now => Ok(Some(at)),
+        (PostStatus::Scheduled, _) => Err(FieldError::new(
+            "published_at",
+            "publication_date",
+            "A scheduled post needs a publication date in the future",
+        )
+        .into()),
+    }
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Trimmed, lowercased tags without blanks or duplicates, in their first order
+fn normalize_tags(tags: &[String]) -> ApiResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags.iter().map(|tag| normalize_tag(tag)) {
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(FieldError::new("tags", "length", format!("Tags must be at most {} characters", MAX_TAG_LEN)).into());
+        }
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use sqlx::SqlitePool;
+
+    async fn create_test_service() -> PostService {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        PostService::new(pool)
+    }
+
+    fn create_test_post() -> CreatePost {
+        CreatePost {
+            slug: None,
+            title: "Été à Paris".to_string(),
+            summary: "Notes from a summer".to_string(),
+            body: "Some **words**.\n\n```rust\nfn main() {}\n```\n".to_string(),
+            tags: vec![" Rust ".to_string(), "rust".to_string(), "Travel".to_string()],
+            status: None,
+            published_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_renders_and_normalizes() {
+        let service = create_test_service().await;
+
+        let created = service.create_post(create_test_post()).await.unwrap();
+        assert_eq!(created.post.slug, "ete-a-paris");
+        assert_eq!(created.post.status, "draft");
+        assert_eq!(created.post.tags, vec!["rust", "travel"]);
+        assert_eq!(created.post.reading_time_minutes, 1);
+        assert!(created.body_html.contains("<strong>words</strong>"));
+        assert!(created.body_html.contains("language-rust"));
+
+        let duplicate = service.create_post(create_test_post()).await;
+        assert!(matches!(duplicate, Err(ApiError::Conflict(..))));
+
+        let reserved = CreatePost { slug: Some("admin".to_string()), ..create_test_post() };
+        assert!(matches!(service.create_post(reserved).await, Err(ApiError::ValidationErrors(_))));
+    }
+
+    #[tokio::test]
+    async fn test_publication_rules() {
+        let service = create_test_service().await;
+        let now = Utc::now();
+
+        let scheduled_without_date = CreatePost {
+            slug: Some("a".to_string()),
+            status: Some("scheduled".to_string()),
+            ..create_test_post()
+        };
+        assert!(matches!(service.create_post(scheduled_without_date).await, Err(ApiError::ValidationErrors(_))));
+
+        let published_later = CreatePost {
+            slug: Some("b".to_string()),
+            status: Some("published".to_string()),
+            published_at: Some(now + Duration::days(1)),
+            ..create_test_post()
+        };
+        assert!(matches!(service.create_post(published_later).await, Err(ApiError::ValidationErrors(_))));
+
+        let published = CreatePost {
+            slug: Some("c".to_string()),
+            status: Some("published".to_string()),
+            ..create_test_post()
+        };
+        let published = service.create_post(published).await.unwrap();
+        assert!(published.post.published_at.is_some());
+
+        // Publishing a scheduled post publishes it now
+        let scheduled = CreatePost {
+            slug: Some("d".to_string()),
+            status: Some("scheduled".to_string()),
+            published_at: Some(now + Duration::days(1)),
+            ..create_test_post()
+        };
+        service.create_post(scheduled).await.unwrap();
+        let update = UpdatePost { status: Some("published".to_string()), ..Default::default() };
+        let updated = service.update_post("d", update).await.unwrap();
+        assert_eq!(updated.post.status, "published");
+        assert!(updated.post.published_at.unwrap() <= Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_drafts_are_hidden() {
+        let service = create_test_service().await;
+
+        service.create_post(create_test_post()).await.unwrap();
+        assert!(matches!(service.get_published("ete-a-paris").await, Err(ApiError::NotFound(..))));
+        assert_eq!(service.list_published(None, 1, 10).await.unwrap().1, 0);
+        assert_eq!(service.list_posts(Some("draft"), None, 1, 10).await.unwrap().1, 1);
+
+        let update = UpdatePost {
+            slug: Some("summer".to_string()),
+            status: Some("published".to_string()),
+            ..Default::default()
+        };
+        service.update_post("ete-a-paris", update).await.unwrap();
+
+        let post = service.get_published("summer").await.unwrap();
+        assert_eq!(post.post.title, "Été à Paris");
+        let (posts, total) = service.list_published(Some("TRAVEL"), 1, 10).await.unwrap();
+        assert_eq!((posts.len(), total), (1, 1));
+
+        service.delete_post("summer").await.unwrap();
+        assert!(matches!(service.get_post("summer").await, Err(ApiError::NotFound(..))));
+    }
+}
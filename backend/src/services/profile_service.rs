@@ -2,20 +2,26 @@ use sqlx::SqlitePool;
 use validator::Validate;
 use tracing::{info, warn, error};
 use crate::{
-    database::ProfileRepository,
-    models::{Profile, UpdateProfile},
-    error::{ApiError, ApiResult},
+    database::{CertificationRepository, EducationRepository, ExperienceRepository, ProfileRepository},
+    models::{Profile, TimelineEntry, UpdateProfile},
+    error::{ApiError, ApiResult, ErrorCode},
 };
 
 /// Service for profile-related business logic
 pub struct ProfileService {
     repository: ProfileRepository,
+    experiences: ExperienceRepository,
+    education: EducationRepository,
+    certifications: CertificationRepository,
 }
 
 impl ProfileService {
     pub fn new(pool: SqlitePool) -> Self {
         Self {
-            repository: ProfileRepository::new(pool),
+            repository: ProfileRepository::new(pool.clone()),
+            experiences: ExperienceRepository::new(pool.clone()),
+            education: EducationRepository::new(pool.clone()),
+            certifications: CertificationRepository::new(pool),
         }
     }
 
@@ -30,7 +36,7 @@ impl ProfileService {
             }
             Ok(None) => {
                 warn!("Profile not found");
-                Err(ApiError::NotFound("Profile not found".to_string()))
+                Err(ApiError::NotFound(ErrorCode::ProfileNotFound, "Profile not found".to_string()))
             }
             Err(e) => {
                 error!("Failed to fetch profile: {}", e);
@@ -39,6 +45,31 @@ impl ProfileService {
         }
     }
 
+    /// Get work experience, education and certifications merged into one timeline, newest first
+    pub async fn get_timeline(&self) -> ApiResult<Vec<TimelineEntry>> {
+        info!("Fetching profile timeline");
+
+        let entries = async {
+            let mut entries: Vec<TimelineEntry> = Vec::new();
+            entries.extend(self.experiences.get_all().await?.into_iter().map(TimelineEntry::Experience));
+            entries.extend(self.education.get_all().await?.into_iter().map(TimelineEntry::Education));
+            entries.extend(self.certifications.get_all().await?.into_iter().map(TimelineEntry::Certification));
+            Ok::<_, sqlx::Error>(entries)
+        };
+
+        match entries.await {
+            Ok(mut entries) => {
+                TimelineEntry::sort_newest_first(&mut entries);
+                info!("Successfully fetched {} timeline entries", entries.len());
+                Ok(entries)
+            }
+            Err(e) => {
+                error!("Failed to fetch profile timeline: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
     /// Update the profile
     pub async fn update_profile(&self, mut profile_data: UpdateProfile) -> ApiResult<Profile> {
         info!("Updating profile");
@@ -96,7 +127,7 @@ impl ProfileService {
             }
             Ok(None) => {
                 warn!("Profile not found for update");
-                Err(ApiError::NotFound("Profile not found".to_string()))
+                Err(ApiError::NotFound(ErrorCode::ProfileNotFound, "Profile not found".to_string()))
             }
             Err(e) => {
                 error!("Failed to update profile: {}", e);
@@ -133,19 +164,15 @@ impl ProfileService {
             social_links,
         })
     }
-
-    /// Validate URL format (basic validation)
-    fn is_valid_url(&self, url: &str) -> bool {
-        url.starts_with("http://") || url.starts_with("https://")
-    }
 }
 
 /// Profile summary for public display
-#[derive(Debug, serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 pub struct ProfileSummary {
     pub name: String,
     pub title: String,
     pub location: String,
+    /// `[platform, url]` pairs
     pub social_links: Vec<(String, String)>,
 }
 
@@ -233,7 +260,7 @@ mod tests {
         assert!(result.is_err());
         
         match result.unwrap_err() {
-            ApiError::Validation(_) | ApiError::ValidationErrors(_) => {},
+            ApiError::ValidationErrors(_) => {},
             _ => panic!("Expected validation error"),
         }
     }
@@ -273,4 +300,67 @@ mod tests {
         assert_eq!(summary.title, "Test Title");
         assert_eq!(summary.location, "Test Location");
     }
+
+    #[tokio::test]
+    async fn test_get_timeline() {
+        use crate::{
+            models::{CreateCertification, CreateEducation, CreateExperience},
+            services::{CertificationService, EducationService, ExperienceService},
+        };
+
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        EducationService::new(pool.clone())
+            .create_education(CreateEducation {
+                institution: "INSA Lyon".to_string(),
+                degree: "Engineering degree".to_string(),
+                field_of_study: None,
+                location: None,
+                start_month: "2014-09".to_string(),
+                end_month: Some("2019-06".to_string()),
+                description: None,
+            })
+            .await
+            .unwrap();
+        ExperienceService::new(pool.clone())
+            .create_experience(CreateExperience {
+                company: "Acme".to_string(),
+                role: "Developer".to_string(),
+                location: None,
+                start_month: "2019-09".to_string(),
+                end_month: None,
+                is_current: true,
+                highlights: vec![],
+                technologies: vec!["Rust".to_string()],
+            })
+            .await
+            .unwrap();
+        CertificationService::new(pool.clone())
+            .create_certification(CreateCertification {
+                name: "AWS Solutions Architect".to_string(),
+                issuer: "Amazon".to_string(),
+                issued_month: "2021-03".to_string(),
+                expires_month: None,
+                credential_id: None,
+                credential_url: None,
+            })
+            .await
+            .unwrap();
+
+        let timeline = ProfileService::new(pool).get_timeline().await.unwrap();
+        let kinds: Vec<&str> = timeline
+            .iter()
+            .map(|entry| match entry {
+                TimelineEntry::Experience(_) => "experience",
+                TimelineEntry::Education(_) => "education",
+                TimelineEntry::Certification(_) => "certification",
+            })
+            .collect();
+        assert_eq!(kinds, vec!["certification", "experience", "education"]);
+
+        let json = serde_json::to_value(&timeline[1]).unwrap();
+        assert_eq!(json["type"], "experience");
+        assert_eq!(json["technologies"][0], "Rust");
+    }
 }
\ No newline at end of file
@@ -2,20 +2,32 @@ use sqlx::SqlitePool;
 use validator::Validate;
 use tracing::{info, warn, error};
 use crate::{
-    database::ProjectRepository,
-    models::{Project, CreateProject, UpdateProject},
-    error::{ApiError, ApiResult},
+    database::{MediaRepository, ProjectRepository, ProjectSkillRepository},
+    models::{
+        Project, ProjectDetail, ProjectResponse, CreateProject, UpdateProject, SetProjectSkills, Technology,
+        SetProjectGallery, media::MAX_ALT_TEXT_LEN,
+        ProjectCursor, ProjectFilter, ProjectPage, ProjectSort, technology::MAX_TECHNOLOGY_NAME_LEN,
+        search::to_match_expression,
+    },
+    error::{ApiError, ApiResult, ErrorCode, FieldError},
 };
 
+/// Largest page of projects returned by a listing
+pub const MAX_PAGE_SIZE: u32 = 100;
+
 /// Service for project-related business logic
 pub struct ProjectService {
     repository: ProjectRepository,
+    links: ProjectSkillRepository,
+    media: MediaRepository,
 }
 
 impl ProjectService {
     pub fn new(pool: SqlitePool) -> Self {
         Self {
-            repository: ProjectRepository::new(pool),
+            repository: ProjectRepository::new(pool.clone()),
+            links: ProjectSkillRepository::new(pool.clone()),
+            media: MediaRepository::new(pool),
         }
     }
 
@@ -46,7 +58,7 @@ impl ProjectService {
             }
             Ok(None) => {
                 warn!("Project with ID {} not found", id);
-                Err(ApiError::NotFound(format!("Project with ID {} not found", id)))
+                Err(ApiError::NotFound(ErrorCode::ProjectNotFound, format!("Project with ID {} not found", id)))
             }
             Err(e) => {
                 error!("Failed to fetch project {}: {}", id, e);
@@ -55,6 +67,125 @@ impl ProjectService {
         }
     }
 
+    /// Get a project with the skills it demonstrates and its gallery
+    pub async fn get_project_detail(&self, id: i32) -> ApiResult<ProjectDetail> {
+        let project = self.get_project_by_id(id).await?;
+
+        let related = async {
+            let skills = self.links.get_skills_for_project(id).await?;
+            let gallery = self.media.get_gallery(id).await?;
+            Ok::<_, sqlx::Error>((skills, gallery))
+        };
+
+        match related.await {
+            Ok((skills, gallery)) => Ok(ProjectDetail {
+                project: ProjectResponse::from(project),
+                skills,
+                gallery,
+            }),
+            Err(e) => {
+                error!("Failed to fetch skills and gallery for project {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
+    /// Replace the gallery of a project with uploaded media, in the given order
+    pub async fn set_project_gallery(&self, id: i32, gallery: SetProjectGallery) -> ApiResult<ProjectDetail> {
+        info!("Setting gallery for project with ID: {}", id);
+
+        if let Err(validation_errors) = gallery.validate() {
+            warn!("Validation failed for project gallery: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        let mut items: Vec<(i32, String)> = Vec::with_capacity(gallery.items.len());
+        for (index, item) in gallery.items.into_iter().enumerate() {
+            let alt_text = item.alt_text.trim().to_string();
+            if alt_text.chars().count() > MAX_ALT_TEXT_LEN {
+                return Err(FieldError::new(
+                    format!("items[{}].alt_text", index),
+                    "length",
+                    format!("Alt text must be at most {} characters", MAX_ALT_TEXT_LEN),
+                )
+                .into());
+            }
+            if items.iter().any(|(media_id, _)| *media_id == item.media_id) {
+                return Err(FieldError::new(
+                    format!("items[{}].media_id", index),
+                    "unique",
+                    format!("Media {} appears more than once", item.media_id),
+                )
+                .into());
+            }
+            items.push((item.media_id, alt_text));
+        }
+
+        // Make sure the project exists before touching its gallery
+        self.get_project_by_id(id).await?;
+
+        let media_ids: Vec<i32> = items.iter().map(|(media_id, _)| *media_id).collect();
+        match self.media.find_missing(&media_ids).await {
+            Ok(missing) if missing.is_empty() => {}
+            Ok(missing) => {
+                let ids: Vec<String> = missing.iter().map(|id| id.to_string()).collect();
+                return Err(FieldError::new("items", "exists", format!("Unknown media IDs: {}", ids.join(", "))).into());
+            }
+            Err(e) => {
+                error!("Failed to check media for project {}: {}", id, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        if let Err(e) = self.media.set_gallery(id, &items).await {
+            error!("Failed to set gallery for project {}: {}", id, e);
+            return Err(ApiError::Database(e));
+        }
+
+        info!("Set {} gallery items on project {}", items.len(), id);
+        self.get_project_detail(id).await
+    }
+
+    /// Replace the skills linked to a project with manual links
+    pub async fn set_project_skills(&self, id: i32, mut links: SetProjectSkills) -> ApiResult<ProjectDetail> {
+        info!("Setting skills for project with ID: {}", id);
+
+        if let Err(validation_errors) = links.validate() {
+            warn!("Validation failed for project skills: {:?}", validation_errors);
+            return Err(ApiError::from_validation_errors(validation_errors));
+        }
+
+        // Make sure the project exists before touching its links
+        self.get_project_by_id(id).await?;
+
+        let mut seen = Vec::new();
+        links.skill_ids.retain(|skill_id| {
+            let first = !seen.contains(skill_id);
+            seen.push(*skill_id);
+            first
+        });
+
+        match self.links.find_missing_skills(&links.skill_ids).await {
+            Ok(missing) if missing.is_empty() => {}
+            Ok(missing) => {
+                let ids: Vec<String> = missing.iter().map(|id| id.to_string()).collect();
+                return Err(FieldError::new("skill_ids", "exists", format!("Unknown skill IDs: {}", ids.join(", "))).into());
+            }
+            Err(e) => {
+                error!("Failed to check skills for project {}: {}", id, e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        if let Err(e) = self.links.set_for_project(id, &links.skill_ids).await {
+            error!("Failed to set skills for project {}: {}", id, e);
+            return Err(ApiError::Database(e));
+        }
+
+        info!("Linked {} skills to project {}", links.skill_ids.len(), id);
+        self.get_project_detail(id).await
+    }
+
     /// Get projects by category
     pub async fn get_projects_by_category(&self, category: &str) -> ApiResult<Vec<Project>> {
         info!("Fetching projects for category: {}", category);
@@ -71,6 +202,22 @@ impl ProjectService {
         }
     }
 
+    /// Get projects using a technology, matched case-insensitively
+    pub async fn get_projects_by_technology(&self, technology: &str) -> ApiResult<Vec<Project>> {
+        info!("Fetching projects using technology: {}", technology);
+
+        match self.repository.get_by_technology(&Technology::slug_for(technology)).await {
+            Ok(projects) => {
+                info!("Successfully fetched {} projects using '{}'", projects.len(), technology);
+                Ok(projects)
+            }
+            Err(e) => {
+                error!("Failed to fetch projects using '{}': {}", technology, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
     /// Get featured projects
     pub async fn get_featured_projects(&self) -> ApiResult<Vec<Project>> {
         info!("Fetching featured projects");
@@ -98,9 +245,7 @@ impl ProjectService {
         }
 
         // Additional business logic validation
-        if project_data.technologies.is_empty() {
-            return Err(ApiError::Validation("At least one technology must be specified".to_string()));
-        }
+        project_data.technologies = normalize_technologies(&project_data.technologies)?;
 
         // Sanitize and normalize data
         project_data.title = project_data.title.trim().to_string();
@@ -108,9 +253,14 @@ impl ProjectService {
         project_data.category = project_data.category.trim().to_lowercase();
 
         // Check for duplicate titles (business rule)
-        if let Ok(existing_projects) = self.repository.search(&project_data.title).await {
-            if existing_projects.iter().any(|p| p.title.to_lowercase() == project_data.title.to_lowercase()) {
-                return Err(ApiError::Conflict("A project with this title already exists".to_string()));
+        match self.repository.title_exists(&project_data.title).await {
+            Ok(true) => {
+                return Err(ApiError::Conflict(ErrorCode::ProjectAlreadyExists, "A project with this title already exists".to_string()));
+            }
+            Ok(false) => {}
+            Err(e) => {
+                error!("Failed to check for duplicate project title '{}': {}", project_data.title, e);
+                return Err(ApiError::Database(e));
             }
         }
 
@@ -151,6 +301,9 @@ impl ProjectService {
         if let Some(ref mut category) = project_data.category {
             *category = category.trim().to_lowercase();
         }
+        if let Some(ref mut technologies) = project_data.technologies {
+            *technologies = normalize_technologies(technologies)?;
+        }
 
         match self.repository.update(id, &project_data).await {
             Ok(Some(project)) => {
@@ -159,7 +312,7 @@ impl ProjectService {
             }
             Ok(None) => {
                 warn!("Project with ID {} not found for update", id);
-                Err(ApiError::NotFound(format!("Project with ID {} not found", id)))
+                Err(ApiError::NotFound(ErrorCode::ProjectNotFound, format!("Project with ID {} not found", id)))
             }
             Err(e) => {
                 error!("Failed to update project {}: {}", id, e);
@@ -179,7 +332,7 @@ impl ProjectService {
             }
             Ok(false) => {
                 warn!("Project with ID {} not found for deletion", id);
-                Err(ApiError::NotFound(format!("Project with ID {} not found", id)))
+                Err(ApiError::NotFound(ErrorCode::ProjectNotFound, format!("Project with ID {} not found", id)))
             }
             Err(e) => {
                 error!("Failed to delete project {}: {}", id, e);
@@ -192,11 +345,11 @@ impl ProjectService {
     pub async fn search_projects(&self, query: &str) -> ApiResult<Vec<Project>> {
         info!("Searching projects with query: '{}'", query);
         
-        if query.trim().is_empty() {
+        let Some(match_expression) = to_match_expression(query) else {
             return Err(ApiError::BadRequest("Search query cannot be empty".to_string()));
-        }
+        };
 
-        match self.repository.search(query).await {
+        match self.repository.search(&match_expression).await {
             Ok(projects) => {
                 info!("Found {} projects matching query '{}'", projects.len(), query);
                 Ok(projects)
@@ -208,30 +361,92 @@ impl ProjectService {
         }
     }
 
-    /// Get projects with pagination
-    pub async fn get_projects_paginated(&self, page: u32, page_size: u32) -> ApiResult<(Vec<Project>, u64)> {
-        info!("Fetching projects page {} with size {}", page, page_size);
-        
-        if page_size == 0 || page_size > 100 {
-            return Err(ApiError::BadRequest("Page size must be between 1 and 100".to_string()));
+    /// List projects matching a filter, one page at a time.
+    /// Pages are addressed either by number or by a cursor returned with the previous page.
+    pub async fn list_projects(
+        &self,
+        mut filter: ProjectFilter,
+        sort: ProjectSort,
+        page: Option<u32>,
+        page_size: u32,
+        cursor: Option<&str>,
+    ) -> ApiResult<ProjectPage> {
+        info!("Listing projects: {:?}, sort {}, page {:?}, size {}", filter, sort.as_str(), page, page_size);
+
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            return Err(ApiError::BadRequest(format!("Page size must be between 1 and {}", MAX_PAGE_SIZE)));
+        }
+        if page == Some(0) {
+            return Err(ApiError::BadRequest("Page numbers start at 1".to_string()));
         }
 
-        let offset = (page.saturating_sub(1) * page_size) as i64;
+        let after = match cursor {
+            Some(_) if page.is_some() => {
+                return Err(ApiError::BadRequest("Use either page or cursor, not both".to_string()));
+            }
+            Some(cursor) => match ProjectCursor::decode(cursor) {
+                Some(cursor) if cursor.sort == sort.as_str() => Some((cursor.key, cursor.id)),
+                Some(_) => return Err(ApiError::BadRequest("Cursor was issued for a different sort".to_string())),
+                None => return Err(ApiError::BadRequest("Invalid cursor".to_string())),
+            },
+            None => None,
+        };
+
+        if let (Some(after), Some(before)) = (filter.created_after, filter.created_before) {
+            if after >= before {
+                return Err(ApiError::BadRequest("created_after must be earlier than created_before".to_string()));
+            }
+        }
+
+        // Normalize filters the same way stored values are
+        filter.category = filter.category.map(|category| category.trim().to_lowercase());
+        filter.technology = filter.technology.map(|technology| Technology::slug_for(&technology));
+        filter.search = filter.search.and_then(|search| to_match_expression(&search));
+
         let limit = page_size as i64;
+        let offset = (page.unwrap_or(1) - 1) as i64 * limit;
 
-        match tokio::try_join!(
-            self.repository.get_paginated(limit, offset),
-            self.repository.count()
+        // Fetch one extra project to know whether another page follows
+        let (mut projects, total_count) = match tokio::try_join!(
+            self.repository.find(&filter, sort, after, limit + 1, offset),
+            self.repository.count_matching(&filter)
         ) {
-            Ok((projects, total_count)) => {
-                info!("Successfully fetched {} projects (page {}, total: {})", projects.len(), page, total_count);
-                Ok((projects, total_count as u64))
-            }
+            Ok(result) => result,
             Err(e) => {
-                error!("Failed to fetch paginated projects: {}", e);
-                Err(ApiError::Database(e))
+                error!("Failed to list projects: {}", e);
+                return Err(ApiError::Database(e));
             }
-        }
+        };
+
+        let next_cursor = if projects.len() > page_size as usize {
+            projects.truncate(page_size as usize);
+            let last_id = projects.last().map(|project| project.id).unwrap_or_default();
+
+            match self.repository.sort_key(last_id, sort.field).await {
+                Ok(Some(key)) => Some(
+                    ProjectCursor {
+                        sort: sort.as_str().to_string(),
+                        key,
+                        id: last_id,
+                    }
+                    .encode(),
+                ),
+                Ok(None) => None,
+                Err(e) => {
+                    error!("Failed to build cursor after project {}: {}", last_id, e);
+                    return Err(ApiError::Database(e));
+                }
+            }
+        } else {
+            None
+        };
+
+        info!("Listed {} of {} matching projects", projects.len(), total_count);
+        Ok(ProjectPage {
+            projects,
+            total_count: total_count as u64,
+            next_cursor,
+        })
     }
 
     /// Check if update data contains any changes
@@ -248,6 +463,25 @@ impl ProjectService {
     }
 }
 
+/// Canonicalize technology names, requiring at least one valid entry
+pub(crate) fn normalize_technologies(technologies: &[String]) -> ApiResult<Vec<String>> {
+    let technologies = Technology::normalize_names(technologies);
+
+    if technologies.is_empty() {
+        return Err(FieldError::new("technologies", "required", "At least one technology must be specified").into());
+    }
+    if let Some(name) = technologies.iter().find(|name| name.chars().count() > MAX_TECHNOLOGY_NAME_LEN) {
+        return Err(FieldError::new(
+            "technologies",
+            "length",
+            format!("Technology '{}' must be at most {} characters", name, MAX_TECHNOLOGY_NAME_LEN),
+        )
+        .into());
+    }
+
+    Ok(technologies)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,28 +492,7 @@ mod tests {
             .await
             .unwrap();
 
-        // Create table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS projects (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                title TEXT NOT NULL,
-                description TEXT NOT NULL,
-                long_description TEXT,
-                technologies TEXT NOT NULL,
-                github_url TEXT,
-                demo_url TEXT,
-                image_url TEXT,
-                category TEXT NOT NULL,
-                featured BOOLEAN DEFAULT FALSE,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
 
         ProjectService::new(pool)
     }
@@ -323,7 +536,7 @@ mod tests {
         assert!(result.is_err());
         
         match result.unwrap_err() {
-            ApiError::Validation(_) => {},
+            ApiError::ValidationErrors(fields) => assert_eq!(fields[0].field, "title"),
             _ => panic!("Expected validation error"),
         }
     }
@@ -341,7 +554,7 @@ mod tests {
         assert!(result.is_err());
         
         match result.unwrap_err() {
-            ApiError::Conflict(_) => {},
+            ApiError::Conflict(..) => {},
             _ => panic!("Expected conflict error"),
         }
     }
@@ -379,7 +592,7 @@ mod tests {
         assert!(result.is_err());
         
         match result.unwrap_err() {
-            ApiError::NotFound(_) => {},
+            ApiError::NotFound(..) => {},
             _ => panic!("Expected not found error"),
         }
     }
@@ -392,7 +605,7 @@ mod tests {
         service.create_project(project_data).await.unwrap();
         
         let results = service.search_projects("Test").await.unwrap();
-        assert!(results.len() >= 1);
+        assert!(!results.is_empty());
         assert!(results.iter().any(|p| p.title.contains("Test")));
     }
 
@@ -407,8 +620,216 @@ mod tests {
             service.create_project(project_data).await.unwrap();
         }
         
-        let (projects, total) = service.get_projects_paginated(1, 3).await.unwrap();
-        assert!(projects.len() <= 3);
-        assert_eq!(total, 5);
+        let page = service
+            .list_projects(ProjectFilter::default(), ProjectSort::default(), Some(1), 3, None)
+            .await
+            .unwrap();
+        assert_eq!(page.projects.len(), 3);
+        assert_eq!(page.total_count, 5);
+        assert!(page.next_cursor.is_some());
+
+        let page = service
+            .list_projects(ProjectFilter::default(), ProjectSort::default(), Some(2), 3, None)
+            .await
+            .unwrap();
+        assert_eq!(page.projects.len(), 2);
+        assert!(page.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_projects_combines_filters() {
+        let service = create_test_service().await;
+
+        for (title, category, featured, technology) in [
+            ("Featured Web", "web", true, "Rust"),
+            ("Plain Web", "web", false, "Rust"),
+            ("Featured Mobile", "mobile", true, "Kotlin"),
+        ] {
+            let mut project_data = create_test_project();
+            project_data.title = title.to_string();
+            project_data.category = category.to_string();
+            project_data.featured = Some(featured);
+            project_data.technologies = vec![technology.to_string()];
+            service.create_project(project_data).await.unwrap();
+        }
+
+        let list = |filter: ProjectFilter| service.list_projects(filter, ProjectSort::from_str("title").unwrap(), None, 10, None);
+
+        let page = list(ProjectFilter {
+            category: Some("Web".to_string()),
+            featured: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let titles: Vec<&str> = page.projects.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["Featured Web"]);
+        assert_eq!(page.total_count, 1);
+
+        let page = list(ProjectFilter {
+            technology: Some("RUST".to_string()),
+            search: Some("web".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let titles: Vec<&str> = page.projects.iter().map(|p| p.title.as_str()).collect();
+        assert_eq!(titles, vec!["Featured Web", "Plain Web"]);
+
+        let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+        let page = list(ProjectFilter {
+            created_before: Some(tomorrow),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(page.total_count, 3);
+
+        let page = list(ProjectFilter {
+            created_after: Some(tomorrow),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(page.total_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_list_projects_with_cursor() {
+        let service = create_test_service().await;
+
+        for i in 0..5 {
+            let mut project_data = create_test_project();
+            project_data.title = format!("Project {}", i);
+            service.create_project(project_data).await.unwrap();
+        }
+
+        let sort = ProjectSort::from_str("-title").unwrap();
+        let mut titles = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = service
+                .list_projects(ProjectFilter::default(), sort, None, 2, cursor.as_deref())
+                .await
+                .unwrap();
+            assert_eq!(page.total_count, 5);
+            titles.extend(page.projects.into_iter().map(|p| p.title));
+
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        assert_eq!(titles, vec!["Project 4", "Project 3", "Project 2", "Project 1", "Project 0"]);
+
+        // A cursor only makes sense for the sort it was issued for
+        let page = service.list_projects(ProjectFilter::default(), sort, None, 2, None).await.unwrap();
+        let result = service
+            .list_projects(ProjectFilter::default(), ProjectSort::default(), None, 2, page.next_cursor.as_deref())
+            .await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+
+        let result = service.list_projects(ProjectFilter::default(), sort, None, 2, Some("garbage")).await;
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_technologies_canonicalized() {
+        let service = create_test_service().await;
+
+        let mut project_data = create_test_project();
+        project_data.technologies = vec![" Rust ".to_string(), "rust".to_string(), "SQLite".to_string()];
+        let first = service.create_project(project_data).await.unwrap();
+        assert_eq!(first.technology_names(), ["Rust", "SQLite"]);
+
+        let mut project_data = create_test_project();
+        project_data.title = "Another Project".to_string();
+        project_data.technologies = vec!["RUST".to_string(), "Axum".to_string()];
+        let second = service.create_project(project_data).await.unwrap();
+        // The first spelling of a technology is kept
+        assert_eq!(second.technology_names(), ["Rust", "Axum"]);
+
+        let projects = service.get_projects_by_technology("rust").await.unwrap();
+        assert_eq!(projects.len(), 2);
+
+        let projects = service.get_projects_by_technology("Axum").await.unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].id, second.id);
+
+        let update = UpdateProject {
+            technologies: Some(vec!["Go".to_string()]),
+            ..Default::default()
+        };
+        let updated = service.update_project(first.id, update).await.unwrap();
+        assert_eq!(updated.technology_names(), ["Go"]);
+        assert_eq!(service.get_projects_by_technology("rust").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_blank_technologies_rejected() {
+        let service = create_test_service().await;
+
+        let mut project_data = create_test_project();
+        project_data.technologies = vec!["  ".to_string()];
+        let result = service.create_project(project_data).await;
+        assert!(matches!(result, Err(ApiError::ValidationErrors(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_project_skills() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let service = ProjectService::new(pool.clone());
+        let skills = crate::services::SkillService::new(pool);
+        let rust = skills
+            .create_skill(crate::models::CreateSkill {
+                name: "Rust".to_string(),
+                category: "Backend".to_string(),
+                level: 4,
+                years_experience: None,
+                description: None,
+            })
+            .await
+            .unwrap();
+        let docker = skills
+            .create_skill(crate::models::CreateSkill {
+                name: "Docker".to_string(),
+                category: "DevOps".to_string(),
+                level: 3,
+                years_experience: None,
+                description: None,
+            })
+            .await
+            .unwrap();
+
+        // Technologies matching a skill name are suggested on creation
+        let project = service.create_project(create_test_project()).await.unwrap();
+        let detail = service.get_project_detail(project.id).await.unwrap();
+        assert_eq!(detail.skills.len(), 1);
+        assert_eq!(detail.skills[0].id, rust.id);
+        assert_eq!(detail.skills[0].source, "suggested");
+
+        let links = SetProjectSkills { skill_ids: vec![docker.id, rust.id, docker.id] };
+        let detail = service.set_project_skills(project.id, links).await.unwrap();
+        let linked: Vec<(i32, &str)> = detail.skills.iter().map(|s| (s.id, s.source.as_str())).collect();
+        assert_eq!(linked, vec![(rust.id, "manual"), (docker.id, "manual")]);
+
+        // Manual links survive a technology change
+        let update = UpdateProject {
+            technologies: Some(vec!["Go".to_string()]),
+            ..Default::default()
+        };
+        service.update_project(project.id, update).await.unwrap();
+        assert_eq!(service.get_project_detail(project.id).await.unwrap().skills.len(), 2);
+
+        let links = SetProjectSkills { skill_ids: vec![9999] };
+        let result = service.set_project_skills(project.id, links).await;
+        assert!(matches!(result, Err(ApiError::ValidationErrors(_))));
+
+        let links = SetProjectSkills { skill_ids: vec![] };
+        let result = service.set_project_skills(9999, links).await;
+        assert!(matches!(result, Err(ApiError::NotFound(..))));
     }
 }
\ No newline at end of file
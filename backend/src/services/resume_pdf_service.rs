@@ -0,0 +1,147 @@
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use tracing::{info, warn, error};
+use crate::{
+    database::ResumeCacheRepository,
+    resume::{self, PageSize, ResumeData, ResumeLanguage, ResumeTemplate},
+    services::{ProfileService, ProjectService, SkillService},
+    error::{ApiError, ApiResult},
+};
+
+/// Bump whenever the templates change, so PDFs rendered by the previous version are not served
+const RENDERER_VERSION: &str = "1";
+
+/// How the résumé is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeOptions {
+    pub template: ResumeTemplate,
+    pub page_size: PageSize,
+    pub language: ResumeLanguage,
+}
+
+/// Rendered résumé, with the fingerprint of what it was rendered from
+#[derive(Debug, Clone)]
+pub struct ResumePdf {
+    pub content: Vec<u8>,
+    pub fingerprint: String,
+}
+
+/// Service rendering the profile, featured projects and skills as a PDF résumé
+pub struct ResumePdfService {
+    cache: ResumeCacheRepository,
+    profile_service: ProfileService,
+    project_service: ProjectService,
+    skill_service: SkillService,
+}
+
+impl ResumePdfService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            cache: ResumeCacheRepository::new(pool.clone()),
+            profile_service: ProfileService::new(pool.clone()),
+            project_service: ProjectService::new(pool.clone()),
+            skill_service: SkillService::new(pool),
+        }
+    }
+
+    /// Get the résumé, rendering it only if its content changed since it was last cached
+    pub async fn get_resume(&self, options: ResumeOptions) -> ApiResult<ResumePdf> {
+        let data = ResumeData {
+            profile: self.profile_service.get_profile().await?,
+            projects: self.project_service.get_featured_projects().await?,
+            skills: self.skill_service.get_all_skills().await?,
+        };
+        let fingerprint = fingerprint(&data, options)?;
+        let ResumeOptions { template, page_size, language } = options;
+        let key = (template.as_str(), page_size.as_str(), language.as_str());
+
+        match self.cache.get(key.0, key.1, key.2, &fingerprint).await {
+            Ok(Some(content)) => return Ok(ResumePdf { content, fingerprint }),
+            Ok(None) => {}
+            Err(e) => {
+                error!("Failed to read cached resume: {}", e);
+                return Err(ApiError::Database(e));
+            }
+        }
+
+        info!("Rendering {} resume ({}, {})", key.0, key.1, key.2);
+        // Laying out a long résumé is CPU-bound
+        let content = tokio::task::spawn_blocking(move || resume::render(&data, template, page_size, language))
+            .await
+            .map_err(|e| ApiError::InternalServerError(format!("Rendering task failed: {}", e)))?;
+
+        // A failed write only costs a render on the next request
+        if let Err(e) = self.cache.put(key.0, key.1, key.2, &fingerprint, &content).await {
+            warn!("Failed to cache rendered resume: {}", e);
+        }
+
+        Ok(ResumePdf { content, fingerprint })
+    }
+}
+
+/// Hash of everything the PDF is rendered from. The records carry their `updated_at`,
+/// so any edit changes it.
+fn fingerprint(data: &ResumeData, options: ResumeOptions) -> ApiResult<String> {
+    let content = serde_json::to_vec(data)
+        .map_err(|e| ApiError::InternalServerError(format!("Failed to serialize resume: {}", e)))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(RENDERER_VERSION.as_bytes());
+    for option in [options.template.as_str(), options.page_size.as_str(), options.language.as_str()] {
+        hasher.update(option.as_bytes());
+        hasher.update([0]);
+    }
+    hasher.update(&content);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::UpdateProfile;
+
+    #[tokio::test]
+    async fn test_resume_is_cached_until_content_changes() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let service = ResumePdfService::new(pool.clone());
+
+        let options = ResumeOptions {
+            template: ResumeTemplate::Modern,
+            page_size: PageSize::A4,
+            language: ResumeLanguage::En,
+        };
+        let first = service.get_resume(options).await.unwrap();
+        assert!(first.content.starts_with(b"%PDF-"));
+
+        // Served from the cache while nothing changes
+        sqlx::query("UPDATE resume_cache SET content = ?")
+            .bind(&b"cached"[..])
+            .execute(&pool)
+            .await
+            .unwrap();
+        let second = service.get_resume(options).await.unwrap();
+        assert_eq!(second.content, b"cached");
+        assert_eq!(second.fingerprint, first.fingerprint);
+
+        // Other options are rendered separately
+        let letter = service
+            .get_resume(ResumeOptions { page_size: PageSize::Letter, ..options })
+            .await
+            .unwrap();
+        assert_ne!(letter.fingerprint, first.fingerprint);
+
+        ProfileService::new(pool.clone())
+            .update_profile(UpdateProfile {
+                title: Some("Staff Engineer".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let third = service.get_resume(options).await.unwrap();
+        assert_ne!(third.fingerprint, first.fingerprint);
+        assert!(third.content.starts_with(b"%PDF-"));
+    }
+}
@@ -0,0 +1,245 @@
+use sqlx::SqlitePool;
+use tracing::{info, warn, error};
+use crate::{
+    database::{repositories::search_repository::{MATCH_END, MATCH_START}, SearchRepository},
+    models::{search::to_match_expression, SearchHit, SearchResultType},
+    error::{ApiError, ApiResult},
+};
+
+/// Longest accepted search query
+const MAX_QUERY_LEN: usize = 200;
+
+/// Largest number of hits returned by one search
+pub const MAX_SEARCH_RESULTS: u32 = 50;
+
+/// Service for full-text search across the portfolio
+pub struct SearchService {
+    repository: SearchRepository,
+}
+
+impl SearchService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: SearchRepository::new(pool),
+        }
+    }
+
+    /// Search the given result types (all visible ones when empty).
+    /// Contact messages are only searched when `include_private` is set.
+    pub async fn search(
+        &self,
+        query: &str,
+        types: &[SearchResultType],
+        include_private: bool,
+        limit: u32,
+    ) -> ApiResult<Vec<SearchHit>> {
+        info!("Searching for '{}' in {:?}", query, types);
+
+        if query.chars().count() > MAX_QUERY_LEN {
+            return Err(ApiError::BadRequest(format!("Search query must be at most {} characters", MAX_QUERY_LEN)));
+        }
+        if limit == 0 || limit > MAX_SEARCH_RESULTS {
+            return Err(ApiError::BadRequest(format!("Limit must be between 1 and {}", MAX_SEARCH_RESULTS)));
+        }
+
+        let match_expression = to_match_expression(query)
+            .ok_or_else(|| ApiError::BadRequest("Search query cannot be empty".to_string()))?;
+
+        if !include_private && types.iter().any(SearchResultType::is_private) {
+            warn!("Rejected anonymous search of private result types");
+            return Err(ApiError::Forbidden);
+        }
+
+        let kinds: Vec<&str> = if types.is_empty() {
+            SearchResultType::all()
+                .into_iter()
+                .filter(|kind| include_private || SearchResultType::from_str(kind).is_some_and(|t| !t.is_private()))
+                .collect()
+        } else {
+            types.iter().map(SearchResultType::as_str).collect()
+        };
+
+        let rows = match self.repository.search(&match_expression, &kinds, limit as i64).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                error!("Failed to search for '{}': {}", query, e);
+                return Err(ApiError::Database(e));
+            }
+        };
+
+        let hits: Vec<SearchHit> = rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(SearchHit {
+                    result_type: SearchResultType::from_str(&row.kind)?,
+                    id: row.ref_id as i32,
+                    title: highlight_html(&row.title),
+                    snippet: highlight_html(&row.snippet),
+                    score: -row.rank,
+                })
+            })
+            .collect();
+
+        info!("Found {} hits for '{}'", hits.len(), query);
+        Ok(hits)
+    }
+}
+
+/// Escape text for HTML and turn match markers into `<mark>` elements
+fn highlight_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            MATCH_START => html.push_str("<mark>"),
+            MATCH_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateContactMessage, CreateProject, CreateSkill, UpdateProject};
+    use crate::services::{ContactService, ProjectService, SkillService};
+    use crate::spam::SpamPipeline;
+
+    async fn create_test_pool() -> SqlitePool {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        pool
+    }
+
+    fn create_test_project(title: &str, long_description: &str, technologies: &[&str]) -> CreateProject {
+        CreateProject {
+            title: title.to_string(),
+            description: "A portfolio project".to_string(),
+            long_description: Some(long_description.to_string()),
+            technologies: technologies.iter().map(|t| t.to_string()).collect(),
+            github_url: None,
+            demo_url: None,
+            image_url: None,
+            category: "web".to_string(),
+            featured: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_and_highlights() {
+        let pool = create_test_pool().await;
+        let projects = ProjectService::new(pool.clone());
+        projects
+            .create_project(create_test_project("Task Manager", "Handles <b>deadlines</b> & reminders", &["Rust"]))
+            .await
+            .unwrap();
+        projects
+            .create_project(create_test_project("Weather Dashboard", "Shows forecasts for managers", &["Svelte"]))
+            .await
+            .unwrap();
+
+        let service = SearchService::new(pool);
+
+        // Stemming matches "managers", the title match ranks first
+        let hits = service.search("manager", &[], false, 10).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].result_type, SearchResultType::Project);
+        assert_eq!(hits[0].title, "Task <mark>Manager</mark>");
+        assert!(hits[1].snippet.contains("<mark>managers</mark>"));
+        assert!(hits[0].score > hits[1].score);
+
+        // Snippets are escaped
+        let hits = service.search("deadlines", &[], false, 10).await.unwrap();
+        assert!(hits[0].snippet.contains("&lt;b&gt;<mark>deadlines</mark>&lt;/b&gt; &amp; reminders"));
+
+        // Technologies, long descriptions and prefix queries are searchable
+        let projects_only = [SearchResultType::Project];
+        assert_eq!(service.search("svel*", &projects_only, false, 10).await.unwrap().len(), 1);
+        assert_eq!(service.search("rust", &projects_only, false, 10).await.unwrap().len(), 1);
+
+        // Phrases must match in order
+        assert_eq!(service.search("\"forecasts for\"", &[], false, 10).await.unwrap().len(), 1);
+        assert!(service.search("\"for forecasts\"", &[], false, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_follows_changes() {
+        let pool = create_test_pool().await;
+        let projects = ProjectService::new(pool.clone());
+        let project = projects
+            .create_project(create_test_project("Chat App", "Realtime messaging", &["Elixir"]))
+            .await
+            .unwrap();
+
+        let service = SearchService::new(pool.clone());
+        assert_eq!(service.search("elixir", &[], false, 10).await.unwrap().len(), 1);
+
+        let update = UpdateProject {
+            title: Some("Chat Server".to_string()),
+            technologies: Some(vec!["Go".to_string()]),
+            ..Default::default()
+        };
+        projects.update_project(project.id, update).await.unwrap();
+        assert!(service.search("elixir", &[], false, 10).await.unwrap().is_empty());
+        assert_eq!(service.search("server go", &[], false, 10).await.unwrap().len(), 1);
+
+        projects.delete_project(project.id).await.unwrap();
+        assert!(service.search("server", &[], false, 10).await.unwrap().is_empty());
+
+        SkillService::new(pool)
+            .create_skill(CreateSkill {
+                name: "Kubernetes".to_string(),
+                category: "DevOps".to_string(),
+                level: 3,
+                years_experience: None,
+                description: Some("Cluster operations".to_string()),
+            })
+            .await
+            .unwrap();
+        let hits = service.search("cluster", &[SearchResultType::Skill], false, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].result_type, SearchResultType::Skill);
+    }
+
+    #[tokio::test]
+    async fn test_contact_messages_only_for_admins() {
+        let pool = create_test_pool().await;
+        ContactService::new(pool.clone(), Some(SpamPipeline::new(5.0, 10.0)))
+            .submit_message(CreateContactMessage {
+                name: "Jane Smith".to_string(),
+                email: "jane@example.com".to_string(),
+                subject: "Freelance mission".to_string(),
+                message: "Would you be available for a Rust mission next month?".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let service = SearchService::new(pool);
+        assert!(service.search("mission", &[], false, 10).await.unwrap().is_empty());
+
+        let hits = service.search("mission", &[], true, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].result_type, SearchResultType::ContactMessage);
+
+        let result = service.search("mission", &[SearchResultType::ContactMessage], false, 10).await;
+        assert!(matches!(result, Err(ApiError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_queries_rejected() {
+        let service = SearchService::new(create_test_pool().await);
+
+        assert!(matches!(service.search("  \"\" ", &[], false, 10).await, Err(ApiError::BadRequest(_))));
+        assert!(matches!(service.search("rust", &[], false, 0).await, Err(ApiError::BadRequest(_))));
+        assert!(matches!(service.search(&"a".repeat(201), &[], false, 10).await, Err(ApiError::BadRequest(_))));
+
+        // FTS5 syntax in user input is treated as text
+        assert!(service.search("title:(x OR", &[], false, 10).await.unwrap().is_empty());
+    }
+}
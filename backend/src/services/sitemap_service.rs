@@ -0,0 +1,200 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use tracing::{info, error};
+use crate::{
+    config::SiteConfig,
+    database::{ContentChangeRepository, SitemapRepository},
+    sitemap::{self, SitemapUrl},
+    error::{ApiError, ApiResult},
+};
+
+/// Sitemap built from one revision of the content
+#[derive(Debug)]
+pub struct Sitemap {
+    revision: i64,
+    /// When the next scheduled post goes public, making this sitemap out of date
+    expires_at: Option<DateTime<Utc>>,
+    /// `/sitemap.xml`: every page, or an index of `files` once there are too many for one file
+    pub root: String,
+    /// `/sitemaps/N.xml`, numbered from 1
+    pub files: Vec<String>,
+}
+
+/// Last sitemap built, shared between requests
+pub type SitemapCache = Arc<RwLock<Option<Arc<Sitemap>>>>;
+
+/// Service building the sitemap from the database, again only once the content changed
+pub struct SitemapService {
+    repository: SitemapRepository,
+    changes: ContentChangeRepository,
+    site: SiteConfig,
+    cache: SitemapCache,
+}
+
+impl SitemapService {
+    pub fn new(pool: SqlitePool, site: &SiteConfig, cache: SitemapCache) -> Self {
+        Self {
+            repository: SitemapRepository::new(pool.clone()),
+            changes: ContentChangeRepository::new(pool),
+            site: site.clone(),
+            cache,
+        }
+    }
+
+    /// Get the sitemap, rebuilding it if the content changed since it was built
+    pub async fn sitemap(&self) -> ApiResult<Arc<Sitemap>> {
+        let now = Utc::now();
+        let revision = self.changes.revision().await.map_err(database_error)?;
+
+        let cached = self.cache.read().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some(sitemap) = cached {
+            if sitemap.revision == revision && sitemap.expires_at.is_none_or(|at| at > now) {
+                return Ok(sitemap);
+            }
+        }
+
+        let sitemap = Arc::new(self.build(revision, now).await.map_err(database_error)?);
+        info!("Built the sitemap for content revision {} ({} files)", revision, sitemap.files.len().max(1));
+        *self.cache.write().unwrap_or_else(|e| e.into_inner()) = Some(sitemap.clone());
+        Ok(sitemap)
+    }
+
+    /// robots.txt pointing at the sitemap
+    pub fn robots(&self) -> String {
+        sitemap::robots(&self.site.page_url("/sitemap.xml"))
+    }
+
+    async fn build(&self, revision: i64, now: DateTime<Utc>) -> Result<Sitemap, sqlx::Error> {
+        let changes = self.changes.changes().await?;
+        let changed = |sources: &[&str]| {
+            changes
+                .iter()
+                .filter(|(source, _)| sources.contains(&source.as_str()))
+                .map(|(_, changed_at)| *changed_at)
+                .max()
+        };
+        let pages = &self.site.pages;
+        let mut urls = Vec::new();
+        let mut page = |path: &str, lastmod: Option<DateTime<Utc>>| {
+            if !path.is_empty() {
+                urls.push(SitemapUrl { loc: self.site.page_url(path), lastmod });
+            }
+        };
+
+        page(&pages.home, changed(&["profile", "projects", "skills", "posts"]));
+        page(&pages.about, changed(&["profile", "skills"]));
+        page(&pages.projects, changed(&["projects"]));
+        page(&pages.contact, changed(&["profile"]));
+        page(&pages.blog, changed(&["posts"]));
+
+        if !pages.project.is_empty() {
+            for (id, updated_at) in self.repository.projects().await? {
+                urls.push(SitemapUrl { loc: self.site.project_url(id), lastmod: Some(updated_at) });
+            }
+        }
+        if !pages.post.is_empty() {
+            for (slug, updated_at, published_at) in self.repository.public_posts(now).await? {
+                urls.push(SitemapUrl {
+                    loc: self.site.post_url(&slug),
+                    lastmod: Some(updated_at.max(published_at)),
+                });
+            }
+        }
+        let expires_at = self.repository.next_publication(now).await?;
+
+        let max_urls = self.site.sitemap_max_urls.max(1);
+        if urls.len() <= max_urls {
+            return Ok(Sitemap { revision, expires_at, root: sitemap::urlset(&urls), files: Vec::new() });
+        }
+
+        let chunks: Vec<&[SitemapUrl]> = urls.chunks(max_urls).collect();
+        let entries: Vec<SitemapUrl> = chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| SitemapUrl {
+                loc: self.site.page_url(&format!("/sitemaps/{}.xml", i + 1)),
+                lastmod: chunk.iter().filter_map(|url| url.lastmod).max(),
+            })
+            .collect();
+        Ok(Sitemap {
+            revision,
+            expires_at,
+            root: sitemap::index(&entries),
+            files: chunks.into_iter().map(sitemap::urlset).collect(),
+        })
+    }
+}
+
+fn database_error(e: sqlx::Error) -> ApiError {
+    error!("Failed to build the sitemap: {}", e);
+    ApiError::Database(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{CreateProject, CreateSkill},
+        services::{ProjectService, SkillService},
+    };
+
+    #[tokio::test]
+    async fn test_sitemap_rebuilt_on_change() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+        let site = SiteConfig {
+            url: "https://example.com".to_string(),
+            sitemap_max_urls: 6,
+            ..Default::default()
+        };
+        let cache = SitemapCache::default();
+        let service = SitemapService::new(pool.clone(), &site, cache);
+
+        let first = service.sitemap().await.unwrap();
+        assert!(first.root.contains("<loc>https://example.com/about</loc>"));
+        assert!(first.files.is_empty());
+        assert!(Arc::ptr_eq(&first, &service.sitemap().await.unwrap()));
+
+        // Skills have no page of their own but still invalidate the sitemap
+        SkillService::new(pool.clone())
+            .create_skill(CreateSkill {
+                name: "Rust".to_string(),
+                category: "Backend".to_string(),
+                level: 5,
+                years_experience: None,
+                description: None,
+            })
+            .await
+            .unwrap();
+        let second = service.sitemap().await.unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+
+        let projects = ProjectService::new(pool);
+        for title in ["One", "Two"] {
+            projects
+                .create_project(CreateProject {
+                    title: title.to_string(),
+                    description: "A project".to_string(),
+                    long_description: None,
+                    technologies: vec!["Go".to_string()],
+                    github_url: None,
+                    demo_url: None,
+                    image_url: None,
+                    category: "Web".to_string(),
+                    featured: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        // Seven pages no longer fit in one file of six
+        let third = service.sitemap().await.unwrap();
+        assert_eq!(third.files.len(), 2);
+        assert!(third.root.contains("<sitemap><loc>https://example.com/sitemaps/2.xml</loc>"));
+        assert!(third.files[1].contains("/projects/1</loc>"));
+    }
+}
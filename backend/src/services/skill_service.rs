@@ -2,21 +2,23 @@ use sqlx::SqlitePool;
 use validator::Validate;
 use tracing::{info, warn, error};
 use crate::{
-    database::SkillRepository,
-    models::{Skill, CreateSkill, UpdateSkill},
+    database::{ProjectSkillRepository, SkillRepository},
+    models::{Skill, SkillDetail, CreateSkill, UpdateSkill},
     models::skill::SkillCategory,
-    error::{ApiError, ApiResult},
+    error::{ApiError, ApiResult, ErrorCode},
 };
 
 /// Service for skill-related business logic
 pub struct SkillService {
     repository: SkillRepository,
+    links: ProjectSkillRepository,
 }
 
 impl SkillService {
     pub fn new(pool: SqlitePool) -> Self {
         Self {
-            repository: SkillRepository::new(pool),
+            repository: SkillRepository::new(pool.clone()),
+            links: ProjectSkillRepository::new(pool),
         }
     }
 
@@ -47,7 +49,7 @@ impl SkillService {
             }
             Ok(None) => {
                 warn!("Skill with ID {} not found", id);
-                Err(ApiError::NotFound(format!("Skill with ID {} not found", id)))
+                Err(ApiError::NotFound(ErrorCode::SkillNotFound, format!("Skill with ID {} not found", id)))
             }
             Err(e) => {
                 error!("Failed to fetch skill {}: {}", id, e);
@@ -56,6 +58,19 @@ impl SkillService {
         }
     }
 
+    /// Get a skill with the projects demonstrating it
+    pub async fn get_skill_detail(&self, id: i32) -> ApiResult<SkillDetail> {
+        let skill = self.get_skill_by_id(id).await?;
+
+        match self.links.get_projects_for_skill(id).await {
+            Ok(projects) => Ok(SkillDetail { skill, projects }),
+            Err(e) => {
+                error!("Failed to fetch projects for skill {}: {}", id, e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+
     /// Get skills by category
     pub async fn get_skills_by_category(&self, category: &str) -> ApiResult<Vec<Skill>> {
         info!("Fetching skills for category: {}", category);
@@ -81,7 +96,7 @@ impl SkillService {
     pub async fn get_skills_by_min_level(&self, min_level: i32) -> ApiResult<Vec<Skill>> {
         info!("Fetching skills with minimum level: {}", min_level);
         
-        if min_level < 1 || min_level > 5 {
+        if !(1..=5).contains(&min_level) {
             return Err(ApiError::BadRequest("Skill level must be between 1 and 5".to_string()));
         }
         
@@ -119,7 +134,7 @@ impl SkillService {
         // Check for duplicate skill names (case-insensitive)
         if let Ok(existing_skills) = self.repository.get_all().await {
             if existing_skills.iter().any(|s| s.name.to_lowercase() == skill_data.name.to_lowercase()) {
-                return Err(ApiError::Conflict("A skill with this name already exists".to_string()));
+                return Err(ApiError::Conflict(ErrorCode::SkillAlreadyExists, "A skill with this name already exists".to_string()));
             }
         }
 
@@ -172,7 +187,7 @@ impl SkillService {
             }
             Ok(None) => {
                 warn!("Skill with ID {} not found for update", id);
-                Err(ApiError::NotFound(format!("Skill with ID {} not found", id)))
+                Err(ApiError::NotFound(ErrorCode::SkillNotFound, format!("Skill with ID {} not found", id)))
             }
             Err(e) => {
                 error!("Failed to update skill {}: {}", id, e);
@@ -192,7 +207,7 @@ impl SkillService {
             }
             Ok(false) => {
                 warn!("Skill with ID {} not found for deletion", id);
-                Err(ApiError::NotFound(format!("Skill with ID {} not found", id)))
+                Err(ApiError::NotFound(ErrorCode::SkillNotFound, format!("Skill with ID {} not found", id)))
             }
             Err(e) => {
                 error!("Failed to delete skill {}: {}", id, e);
@@ -242,23 +257,7 @@ mod tests {
             .await
             .unwrap();
 
-        // Create table
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS skills (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                category TEXT NOT NULL,
-                level INTEGER NOT NULL CHECK (level >= 1 AND level <= 5),
-                years_experience INTEGER,
-                description TEXT,
-                created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#
-        )
-        .execute(&pool)
-        .await
-        .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
 
         SkillService::new(pool)
     }
@@ -316,7 +315,7 @@ mod tests {
         assert!(result.is_err());
         
         match result.unwrap_err() {
-            ApiError::Conflict(_) => {},
+            ApiError::Conflict(..) => {},
             _ => panic!("Expected conflict error"),
         }
     }
@@ -329,7 +328,7 @@ mod tests {
         service.create_skill(skill_data).await.unwrap();
         
         let skills = service.get_skills_by_category("Backend").await.unwrap();
-        assert!(skills.len() >= 1);
+        assert!(!skills.is_empty());
         assert!(skills.iter().all(|s| s.category == "Backend"));
     }
 
@@ -341,7 +340,7 @@ mod tests {
         service.create_skill(skill_data).await.unwrap();
         
         let skills = service.get_skills_by_min_level(3).await.unwrap();
-        assert!(skills.len() >= 1);
+        assert!(!skills.is_empty());
         assert!(skills.iter().all(|s| s.level >= 3));
     }
 
@@ -354,4 +353,45 @@ mod tests {
         assert!(categories.contains(&"Backend"));
         assert!(categories.contains(&"Database"));
     }
+
+    #[tokio::test]
+    async fn test_skill_usage_from_suggested_links() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let project = crate::services::ProjectService::new(pool.clone())
+            .create_project(crate::models::CreateProject {
+                title: "Portfolio".to_string(),
+                description: "Portfolio backend".to_string(),
+                long_description: None,
+                technologies: vec!["rust".to_string(), "SQLite".to_string()],
+                github_url: None,
+                demo_url: None,
+                image_url: None,
+                category: "web".to_string(),
+                featured: None,
+            })
+            .await
+            .unwrap();
+
+        // Creating a skill links it to projects whose technologies match its name
+        let service = SkillService::new(pool);
+        let skill = service.create_skill(create_test_skill()).await.unwrap();
+        assert_eq!(skill.project_count, 1);
+        assert_eq!(skill.last_used_at, Some(project.created_at));
+
+        let detail = service.get_skill_detail(skill.id).await.unwrap();
+        assert_eq!(detail.projects.len(), 1);
+        assert_eq!(detail.projects[0].id, project.id);
+        assert_eq!(detail.projects[0].source, "suggested");
+
+        // Renaming the skill drops suggestions that no longer match
+        let update = UpdateSkill {
+            name: Some("Go".to_string()),
+            ..Default::default()
+        };
+        let renamed = service.update_skill(skill.id, update).await.unwrap();
+        assert_eq!(renamed.project_count, 0);
+        assert!(renamed.last_used_at.is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use sqlx::SqlitePool;
+use tracing::{info, error};
+use crate::{
+    database::TechnologyRepository,
+    models::Technology,
+    error::{ApiError, ApiResult},
+};
+
+/// Service for technology-related business logic
+pub struct TechnologyService {
+    repository: TechnologyRepository,
+}
+
+impl TechnologyService {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            repository: TechnologyRepository::new(pool),
+        }
+    }
+
+    /// Get technologies used by projects with their usage counts
+    pub async fn get_all_technologies(&self) -> ApiResult<Vec<Technology>> {
+        info!("Fetching all technologies");
+
+        match self.repository.get_all_with_counts().await {
+            Ok(technologies) => {
+                info!("Successfully fetched {} technologies", technologies.len());
+                Ok(technologies)
+            }
+            Err(e) => {
+                error!("Failed to fetch technologies: {}", e);
+                Err(ApiError::Database(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Sitemaps (https://www.sitemaps.org/protocol.html) and robots.txt
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use crate::feed::xml::{escape, rfc3339};
+
+const NAMESPACE: &str = "http://www.sitemaps.org/schemas/sitemap/0.9";
+
+/// A page, or a sitemap file in an index
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapUrl {
+    pub loc: String,
+    pub lastmod: Option<DateTime<Utc>>,
+}
+
+/// Render a sitemap listing pages
+pub fn urlset(urls: &[SitemapUrl]) -> String {
+    render("urlset", "url", urls)
+}
+
+/// Render a sitemap index listing sitemap files
+pub fn index(sitemaps: &[SitemapUrl]) -> String {
+    render("sitemapindex", "sitemap", sitemaps)
+}
+
+fn render(root: &str, element: &str, urls: &[SitemapUrl]) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(out, "<{} xmlns=\"{}\">", root, NAMESPACE);
+    for url in urls {
+        let _ = write!(out, "  <{}><loc>{}</loc>", element, escape(&url.loc));
+        if let Some(lastmod) = url.lastmod {
+            let _ = write!(out, "<lastmod>{}</lastmod>", rfc3339(lastmod));
+        }
+        let _ = writeln!(out, "</{}>", element);
+    }
+    let _ = writeln!(out, "</{}>", root);
+    out
+}
+
+/// robots.txt letting crawlers in and pointing them at the sitemap
+pub fn robots(sitemap_url: &str) -> String {
+    format!("User-agent: *\nAllow: /\n\nSitemap: {}\n", sitemap_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render() {
+        let urls = vec![
+            SitemapUrl {
+                loc: "https://example.com/projects?a=1&b=2".to_string(),
+                lastmod: Some(Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap()),
+            },
+            SitemapUrl {
+                loc: "https://example.com/contact".to_string(),
+                lastmod: None,
+            },
+        ];
+
+        let sitemap = urlset(&urls);
+        assert!(sitemap.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns="));
+        assert!(sitemap.contains(
+            "<url><loc>https://example.com/projects?a=1&amp;b=2</loc><lastmod>2024-03-01T09:30:00Z</lastmod></url>"
+        ));
+        assert!(sitemap.contains("<url><loc>https://example.com/contact</loc></url>"));
+
+        let index = index(&urls[1..]);
+        assert!(index.contains("<sitemapindex xmlns="));
+        assert!(index.contains("<sitemap><loc>https://example.com/contact</loc></sitemap>"));
+
+        assert!(robots("https://example.com/sitemap.xml").ends_with("Sitemap: https://example.com/sitemap.xml\n"));
+    }
+}
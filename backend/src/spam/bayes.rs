@@ -0,0 +1,133 @@
+use std::collections::BTreeSet;
+
+use axum::async_trait;
+use sqlx::SqlitePool;
+
+use crate::{
+    database::{repositories::spam_repository::TokenCounts, SpamRepository},
+    models::{CreateContactMessage, SpamSignal},
+};
+
+use super::SpamFilter;
+
+/// Tokens considered per message, so long messages do not dominate the model
+const MAX_TOKENS: usize = 200;
+/// Signals weaker than this are noise and left out of the verdict
+const MIN_REPORTED_SCORE: f64 = 0.5;
+
+/// Distinct tokens of a message, as used to train and query the model: lowercase words of
+/// 3 to 24 characters, plus the sender's domain
+pub fn tokenize(email: &str, subject: &str, message: &str) -> Vec<String> {
+    let mut tokens = BTreeSet::new();
+
+    if let Some((_, domain)) = email.rsplit_once('@') {
+        tokens.insert(format!("domain:{}", domain.trim().to_lowercase()));
+    }
+
+    let words = subject
+        .split(|c: char| !c.is_alphanumeric())
+        .chain(message.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| (3..=24).contains(&word.chars().count()))
+        .map(str::to_lowercase);
+    for word in words {
+        if tokens.len() >= MAX_TOKENS {
+            break;
+        }
+        tokens.insert(word);
+    }
+
+    tokens.into_iter().collect()
+}
+
+/// Probability that a message is spam, from naive Bayes with add-one smoothing over the
+/// tokens the model has seen
+pub fn spam_probability(counts: &[TokenCounts], spam_messages: i64, ham_messages: i64) -> f64 {
+    let (spam, ham) = (spam_messages as f64, ham_messages as f64);
+
+    let log_odds = counts.iter().fold((spam / ham).ln(), |log_odds, token| {
+        let in_spam = (token.spam_count as f64 + 1.0) / (spam + 2.0);
+        let in_ham = (token.ham_count as f64 + 1.0) / (ham + 2.0);
+        log_odds + (in_spam / in_ham).ln()
+    });
+
+    1.0 / (1.0 + (-log_odds).exp())
+}
+
+/// Scores messages with a naive Bayes model trained from admins marking messages as spam or ham
+pub struct BayesFilter {
+    repository: SpamRepository,
+    min_messages: i64,
+    score: f64,
+}
+
+impl BayesFilter {
+    /// The model is only consulted once it knows `min_messages` of each label; a certain spam
+    /// adds `score` and a certain ham takes it off
+    pub fn new(pool: SqlitePool, min_messages: u32, score: f64) -> Self {
+        Self {
+            repository: SpamRepository::new(pool),
+            min_messages: min_messages as i64,
+            score,
+        }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for BayesFilter {
+    fn name(&self) -> &'static str {
+        "bayes"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let (spam_messages, ham_messages) = self.repository.corpus_sizes().await?;
+        if spam_messages < self.min_messages || ham_messages < self.min_messages {
+            return Ok(Vec::new());
+        }
+
+        let tokens = tokenize(&message.email, &message.subject, &message.message);
+        let counts = self.repository.token_counts(&tokens).await?;
+        let probability = spam_probability(&counts, spam_messages, ham_messages);
+
+        let score = self.score * (2.0 * probability - 1.0);
+        Ok(if score.abs() >= MIN_REPORTED_SCORE {
+            vec![SpamSignal::new(
+                self.name(),
+                score,
+                format!("{:.0}% spam probability", probability * 100.0),
+            )]
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(token: &str, spam_count: i64, ham_count: i64) -> TokenCounts {
+        TokenCounts {
+            token: token.to_string(),
+            spam_count,
+            ham_count,
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("Jane@Example.COM", "Cheap SEO", "Cheap, cheap backlinks! a an 2024");
+        assert_eq!(tokens, vec!["2024", "backlinks", "cheap", "domain:example.com", "seo"]);
+    }
+
+    #[test]
+    fn test_spam_probability() {
+        let spammy = [counts("backlinks", 9, 0), counts("cheap", 8, 1)];
+        assert!(spam_probability(&spammy, 10, 10) > 0.95);
+
+        let hammy = [counts("portfolio", 0, 9), counts("rust", 1, 7)];
+        assert!(spam_probability(&hammy, 10, 10) < 0.05);
+
+        // Unknown tokens leave the prior
+        assert!((spam_probability(&[], 10, 30) - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use axum::async_trait;
+
+use crate::models::{CreateContactMessage, SpamSignal};
+
+use super::SpamFilter;
+
+/// Score for a sender using a throwaway address
+const DISPOSABLE_SCORE: f64 = 3.0;
+
+/// Well-known disposable email providers
+const DISPOSABLE_DOMAINS: &[&str] = &[
+    "10minutemail.com",
+    "33mail.com",
+    "dispostable.com",
+    "emailondeck.com",
+    "fakeinbox.com",
+    "getairmail.com",
+    "getnada.com",
+    "guerrillamail.com",
+    "guerrillamail.net",
+    "maildrop.cc",
+    "mailinator.com",
+    "mailnesia.com",
+    "mintemail.com",
+    "mohmal.com",
+    "mytemp.email",
+    "sharklasers.com",
+    "spamgourmet.com",
+    "temp-mail.org",
+    "tempail.com",
+    "tempmail.com",
+    "tempmailo.com",
+    "throwawaymail.com",
+    "trashmail.com",
+    "yopmail.com",
+];
+
+/// Flags senders whose address belongs to a disposable email provider
+pub struct DisposableEmailFilter {
+    domains: HashSet<String>,
+}
+
+impl DisposableEmailFilter {
+    /// The built-in list extended with `extra_domains`
+    pub fn new(extra_domains: &[String]) -> Self {
+        let domains = DISPOSABLE_DOMAINS
+            .iter()
+            .map(|domain| domain.to_string())
+            .chain(extra_domains.iter().map(|domain| domain.trim().to_lowercase()))
+            .collect();
+
+        Self { domains }
+    }
+
+    /// Whether a domain or one of its parents is listed
+    fn is_disposable(&self, domain: &str) -> bool {
+        let mut domain = domain;
+        loop {
+            if self.domains.contains(domain) {
+                return true;
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => domain = parent,
+                _ => return false,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for DisposableEmailFilter {
+    fn name(&self) -> &'static str {
+        "disposable_email"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let domain = message
+            .email
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim().to_lowercase())
+            .unwrap_or_default();
+
+        Ok(if self.is_disposable(&domain) {
+            vec![SpamSignal::new(
+                self.name(),
+                DISPOSABLE_SCORE,
+                format!("Disposable email domain '{}'", domain),
+            )]
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_disposable_domains() {
+        let filter = DisposableEmailFilter::new(&["Burner.Example".to_string()]);
+        let check = |email: &str| {
+            let message = CreateContactMessage {
+                email: email.to_string(),
+                ..Default::default()
+            };
+            let filter = &filter;
+            async move { filter.check(&message).await.unwrap().len() }
+        };
+
+        assert_eq!(check("someone@mailinator.com").await, 1);
+        assert_eq!(check("someone@eu.mailinator.com").await, 1);
+        assert_eq!(check("someone@burner.example").await, 1);
+        assert_eq!(check("someone@example.com").await, 0);
+        assert_eq!(check("someone@notmailinator.com").await, 0);
+    }
+}
@@ -0,0 +1,115 @@
+use axum::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use crate::{
+    database::ContactRepository,
+    models::{CreateContactMessage, SpamSignal},
+};
+
+use super::SpamFilter;
+
+/// Score for a message already received recently
+const DUPLICATE_SCORE: f64 = 3.0;
+/// Shorter messages ("Hello", "Thanks!") repeat innocently
+const MIN_COMPARED_LEN: usize = 20;
+
+/// Flags messages identical to one received recently, from any sender
+pub struct DuplicateFilter {
+    repository: ContactRepository,
+    window_hours: u32,
+}
+
+impl DuplicateFilter {
+    pub fn new(pool: SqlitePool, window_hours: u32) -> Self {
+        Self {
+            repository: ContactRepository::new(pool),
+            window_hours,
+        }
+    }
+}
+
+/// Message text compared without regard to case or spacing
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[async_trait]
+impl SpamFilter for DuplicateFilter {
+    fn name(&self) -> &'static str {
+        "duplicate"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let body = normalize(&message.message);
+        if body.len() < MIN_COMPARED_LEN {
+            return Ok(Vec::new());
+        }
+
+        let since = Utc::now() - Duration::hours(self.window_hours as i64);
+        let copies = self
+            .repository
+            .get_bodies_since(since)
+            .await?
+            .iter()
+            .filter(|previous| normalize(previous) == body)
+            .count();
+
+        Ok(if copies > 0 {
+            vec![SpamSignal::new(
+                self.name(),
+                DUPLICATE_SCORE,
+                format!("Same message received {} time(s) in the last {} hours", copies, self.window_hours),
+            )]
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SpamVerdict;
+
+    #[tokio::test]
+    async fn test_duplicates_detected() {
+        let pool = SqlitePool::connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::database::initialize_database(pool.clone()).await.unwrap();
+
+        let filter = DuplicateFilter::new(pool.clone(), 24);
+        let message = CreateContactMessage {
+            name: "Jane".to_string(),
+            email: "jane@example.com".to_string(),
+            subject: "Offer".to_string(),
+            message: "We can get your website to the first page of Google.".to_string(),
+            ..Default::default()
+        };
+        assert!(filter.check(&message).await.unwrap().is_empty());
+
+        ContactRepository::new(pool)
+            .create(&message, &SpamVerdict::default())
+            .await
+            .unwrap();
+
+        let resent = CreateContactMessage {
+            email: "other@example.com".to_string(),
+            message: "We can get your  WEBSITE to the first page of Google.".to_string(),
+            ..message.clone()
+        };
+        let signals = filter.check(&resent).await.unwrap();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].score, DUPLICATE_SCORE);
+
+        let short = CreateContactMessage {
+            message: "Hello".to_string(),
+            ..message
+        };
+        assert!(filter.check(&short).await.unwrap().is_empty());
+    }
+}
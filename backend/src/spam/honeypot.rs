@@ -0,0 +1,54 @@
+use axum::async_trait;
+
+use crate::models::{CreateContactMessage, SpamSignal};
+
+use super::SpamFilter;
+
+/// Flags submissions that fill in the hidden `website` field
+pub struct HoneypotFilter {
+    score: f64,
+}
+
+impl HoneypotFilter {
+    /// `score` is normally the reject threshold: people never see the field
+    pub fn new(score: f64) -> Self {
+        Self { score }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for HoneypotFilter {
+    fn name(&self) -> &'static str {
+        "honeypot"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let filled = message.website.as_deref().is_some_and(|value| !value.trim().is_empty());
+
+        Ok(if filled {
+            vec![SpamSignal::new(self.name(), self.score, "Hidden form field was filled in")]
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_filled_honeypot_flagged() {
+        let filter = HoneypotFilter::new(12.0);
+        let mut message = CreateContactMessage::default();
+        assert!(filter.check(&message).await.unwrap().is_empty());
+
+        message.website = Some("  ".to_string());
+        assert!(filter.check(&message).await.unwrap().is_empty());
+
+        message.website = Some("https://cheap-seo.example".to_string());
+        let signals = filter.check(&message).await.unwrap();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].score, 12.0);
+    }
+}
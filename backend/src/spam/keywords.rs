@@ -0,0 +1,157 @@
+use axum::async_trait;
+use regex::Regex;
+use tracing::warn;
+
+use crate::{
+    config::SpamConfig,
+    models::{CreateContactMessage, SpamSignal},
+};
+
+use super::SpamFilter;
+
+/// Score for a message written mostly in capitals
+const CAPS_SCORE: f64 = 2.0;
+/// Score for a message full of exclamation marks
+const EXCLAMATION_SCORE: f64 = 2.0;
+
+/// Flags configured words, phrases and regular expressions in the subject or message
+pub struct KeywordFilter {
+    keywords: Vec<String>,
+    patterns: Vec<Regex>,
+    score: f64,
+}
+
+impl KeywordFilter {
+    pub fn new(keywords: &[String], patterns: Vec<Regex>, score: f64) -> Self {
+        Self {
+            keywords: keywords
+                .iter()
+                .map(|keyword| keyword.trim().to_lowercase())
+                .filter(|keyword| !keyword.is_empty())
+                .collect(),
+            patterns,
+            score,
+        }
+    }
+
+    /// Build the filter from the configuration; invalid patterns, which validation
+    /// reports, are skipped
+    pub fn from_config(config: &SpamConfig) -> Self {
+        let patterns = config
+            .patterns
+            .iter()
+            .filter_map(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    warn!("Skipping invalid spam pattern '{}': {}", pattern, e);
+                    None
+                }
+            })
+            .collect();
+
+        Self::new(&config.keywords, patterns, config.keyword_score)
+    }
+}
+
+#[async_trait]
+impl SpamFilter for KeywordFilter {
+    fn name(&self) -> &'static str {
+        "keywords"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let text = format!("{}\n{}", message.subject, message.message);
+        let lowercase = text.to_lowercase();
+
+        let keywords = self
+            .keywords
+            .iter()
+            .filter(|keyword| lowercase.contains(keyword.as_str()))
+            .map(|keyword| SpamSignal::new(self.name(), self.score, format!("Contains '{}'", keyword)));
+        let patterns = self
+            .patterns
+            .iter()
+            .filter(|pattern| pattern.is_match(&text))
+            .map(|pattern| SpamSignal::new(self.name(), self.score, format!("Matches /{}/", pattern.as_str())));
+
+        Ok(keywords.chain(patterns).collect())
+    }
+}
+
+/// Flags shouting: mostly capital letters or a string of exclamation marks
+pub struct ShoutingFilter;
+
+#[async_trait]
+impl SpamFilter for ShoutingFilter {
+    fn name(&self) -> &'static str {
+        "shouting"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let mut signals = Vec::new();
+
+        let (caps, letters) = message
+            .message
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .fold((0, 0), |(caps, total), c| (caps + c.is_uppercase() as usize, total + 1));
+        if letters > 0 && caps as f32 / letters as f32 > 0.5 {
+            signals.push(SpamSignal::new(self.name(), CAPS_SCORE, "Mostly capital letters"));
+        }
+
+        let exclamations = message.message.matches('!').count();
+        if exclamations > 5 {
+            signals.push(SpamSignal::new(
+                self.name(),
+                EXCLAMATION_SCORE,
+                format!("{} exclamation marks", exclamations),
+            ));
+        }
+
+        Ok(signals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(subject: &str, body: &str) -> CreateContactMessage {
+        CreateContactMessage {
+            subject: subject.to_string(),
+            message: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keywords_and_patterns() {
+        let filter = KeywordFilter::new(
+            &["Casino".to_string(), "click here".to_string()],
+            vec![Regex::new(r"(?i)seo\s+services").unwrap()],
+            2.5,
+        );
+
+        let signals = filter
+            .check(&message("Our casino", "CLICK HERE for the best SEO  services"))
+            .await
+            .unwrap();
+        assert_eq!(signals.len(), 3);
+        assert!(signals.iter().all(|signal| signal.score == 2.5 && signal.filter == "keywords"));
+
+        let normal = message("Project inquiry", "I'd like to discuss a web application.");
+        assert!(filter.check(&normal).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shouting() {
+        let signals = ShoutingFilter
+            .check(&message("Hi", "CLAIM YOUR PRIZE NOW!!!!!!"))
+            .await
+            .unwrap();
+        assert_eq!(signals.len(), 2);
+
+        let calm = message("Hi", "Hello, I saw your portfolio. Are you available in May?");
+        assert!(ShoutingFilter.check(&calm).await.unwrap().is_empty());
+    }
+}
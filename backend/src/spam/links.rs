@@ -0,0 +1,93 @@
+use axum::async_trait;
+
+use crate::models::{CreateContactMessage, SpamSignal};
+
+use super::SpamFilter;
+
+/// Score for each link beyond the allowed number
+const EXTRA_LINK_SCORE: f64 = 1.5;
+/// Score for link markup, which the plain-text form never produces
+const MARKUP_SCORE: f64 = 3.0;
+
+/// Flags messages with many links or with HTML / BBCode links
+pub struct LinkFilter {
+    max_links: usize,
+}
+
+impl LinkFilter {
+    pub fn new(max_links: u32) -> Self {
+        Self {
+            max_links: max_links as usize,
+        }
+    }
+}
+
+/// Number of URLs in a text, counting `http://`, `https://` and bare `www.` addresses
+fn count_links(text: &str) -> usize {
+    let lowercase = text.to_lowercase();
+    let schemes = lowercase.matches("http://").count() + lowercase.matches("https://").count();
+    let bare = lowercase
+        .match_indices("www.")
+        .filter(|(index, _)| !lowercase[..*index].ends_with("//"))
+        .count();
+    schemes + bare
+}
+
+#[async_trait]
+impl SpamFilter for LinkFilter {
+    fn name(&self) -> &'static str {
+        "links"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let mut signals = Vec::new();
+
+        let links = count_links(&message.subject) + count_links(&message.message);
+        if links > self.max_links {
+            let extra = links - self.max_links;
+            signals.push(SpamSignal::new(
+                self.name(),
+                extra as f64 * EXTRA_LINK_SCORE,
+                format!("{} links ({} allowed)", links, self.max_links),
+            ));
+        }
+
+        let lowercase = message.message.to_lowercase();
+        if lowercase.contains("<a href") || lowercase.contains("[url") {
+            signals.push(SpamSignal::new(self.name(), MARKUP_SCORE, "Contains link markup"));
+        }
+
+        Ok(signals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_links() {
+        assert_eq!(count_links("no links here"), 0);
+        assert_eq!(count_links("see https://www.example.com and www.example.org"), 2);
+        assert_eq!(count_links("HTTP://A.EXAMPLE http://b.example"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_extra_links_and_markup() {
+        let filter = LinkFilter::new(2);
+        let mut message = CreateContactMessage {
+            message: "My work: https://a.example and https://b.example".to_string(),
+            ..Default::default()
+        };
+        assert!(filter.check(&message).await.unwrap().is_empty());
+
+        message.message = "http://a.example http://b.example http://c.example www.d.example".to_string();
+        let signals = filter.check(&message).await.unwrap();
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].score, 2.0 * EXTRA_LINK_SCORE);
+
+        message.message = "[url=http://a.example]cheap[/url]".to_string();
+        let signals = filter.check(&message).await.unwrap();
+        assert_eq!(signals[0].score, MARKUP_SCORE);
+    }
+}
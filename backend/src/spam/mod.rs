@@ -0,0 +1,157 @@
+//! Spam scoring of contact form submissions through pluggable filters
+pub mod bayes;
+pub mod disposable;
+pub mod duplicate;
+pub mod honeypot;
+pub mod keywords;
+pub mod links;
+pub mod timing;
+
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::SqlitePool;
+use tracing::warn;
+
+use crate::{
+    config::SpamConfig,
+    models::{CreateContactMessage, SpamDecision, SpamSignal, SpamVerdict},
+};
+
+pub use bayes::BayesFilter;
+pub use disposable::DisposableEmailFilter;
+pub use duplicate::DuplicateFilter;
+pub use honeypot::HoneypotFilter;
+pub use keywords::{KeywordFilter, ShoutingFilter};
+pub use links::LinkFilter;
+pub use timing::{FormTokens, TimingFilter};
+
+/// A source of evidence about whether a submission is spam
+#[async_trait]
+pub trait SpamFilter: Send + Sync {
+    /// Name recorded with each signal
+    fn name(&self) -> &'static str;
+
+    /// Signals raised by the submission, if any
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error>;
+}
+
+/// Filters whose scores are added up and compared to the thresholds
+#[derive(Clone)]
+pub struct SpamPipeline {
+    filters: Vec<Arc<dyn SpamFilter>>,
+    quarantine_threshold: f64,
+    reject_threshold: f64,
+}
+
+impl SpamPipeline {
+    /// A pipeline without filters, which accepts everything until some are added
+    pub fn new(quarantine_threshold: f64, reject_threshold: f64) -> Self {
+        Self {
+            filters: Vec::new(),
+            quarantine_threshold,
+            reject_threshold,
+        }
+    }
+
+    /// Build the pipeline with every built-in filter, as configured
+    pub fn from_config(pool: SqlitePool, config: &SpamConfig, form_tokens: FormTokens) -> Self {
+        Self::new(config.quarantine_threshold, config.reject_threshold)
+            .with(HoneypotFilter::new(config.reject_threshold))
+            .with(TimingFilter::new(form_tokens, config.min_submit_secs))
+            .with(KeywordFilter::from_config(config))
+            .with(ShoutingFilter)
+            .with(LinkFilter::new(config.max_links))
+            .with(DisposableEmailFilter::new(&config.disposable_domains))
+            .with(DuplicateFilter::new(pool.clone(), config.duplicate_window_hours))
+            .with(BayesFilter::new(pool, config.bayes_min_messages, config.bayes_score))
+    }
+
+    /// Add a filter
+    pub fn with(mut self, filter: impl SpamFilter + 'static) -> Self {
+        self.filters.push(Arc::new(filter));
+        self
+    }
+
+    /// Score a submission with every filter. A failing filter is skipped rather than
+    /// failing the submission, so a database hiccup never loses a message.
+    pub async fn evaluate(&self, message: &CreateContactMessage) -> SpamVerdict {
+        let mut signals = Vec::new();
+
+        for filter in &self.filters {
+            match filter.check(message).await {
+                Ok(found) => signals.extend(found),
+                Err(e) => warn!("Spam filter '{}' failed, skipping it: {}", filter.name(), e),
+            }
+        }
+
+        let score = signals.iter().map(|signal| signal.score).sum::<f64>();
+        let decision = if score >= self.reject_threshold {
+            SpamDecision::Reject
+        } else if score >= self.quarantine_threshold {
+            SpamDecision::Quarantine
+        } else {
+            SpamDecision::Accept
+        };
+
+        SpamVerdict {
+            score,
+            decision,
+            signals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedFilter(f64);
+
+    #[async_trait]
+    impl SpamFilter for FixedFilter {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn check(&self, _message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+            Ok(vec![SpamSignal::new(self.name(), self.0, "Fixed score")])
+        }
+    }
+
+    struct FailingFilter;
+
+    #[async_trait]
+    impl SpamFilter for FailingFilter {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn check(&self, _message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+            Err(sqlx::Error::PoolTimedOut)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_thresholds_decide() {
+        let message = CreateContactMessage::default();
+
+        let verdict = SpamPipeline::new(5.0, 10.0).evaluate(&message).await;
+        assert_eq!(verdict.decision, SpamDecision::Accept);
+        assert!(verdict.signals.is_empty());
+
+        let pipeline = SpamPipeline::new(5.0, 10.0).with(FixedFilter(3.0)).with(FailingFilter);
+        assert_eq!(pipeline.evaluate(&message).await.decision, SpamDecision::Accept);
+
+        let pipeline = pipeline.with(FixedFilter(2.0));
+        let verdict = pipeline.evaluate(&message).await;
+        assert_eq!(verdict.decision, SpamDecision::Quarantine);
+        assert_eq!(verdict.score, 5.0);
+        assert_eq!(verdict.signals.len(), 2);
+
+        let pipeline = pipeline.with(FixedFilter(6.0)).with(FixedFilter(-1.0));
+        let verdict = pipeline.evaluate(&message).await;
+        assert_eq!(verdict.decision, SpamDecision::Reject);
+        assert_eq!(verdict.score, 10.0);
+    }
+}
@@ -0,0 +1,173 @@
+use std::{fmt, sync::Arc};
+
+use axum::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    auth::AuthConfig,
+    config::SpamConfig,
+    models::{CreateContactMessage, SpamSignal},
+};
+
+use super::SpamFilter;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Score when no form token is sent, as from clients that never loaded the form
+const MISSING_TOKEN_SCORE: f64 = 0.5;
+/// Score for a forged or expired token
+const INVALID_TOKEN_SCORE: f64 = 2.0;
+/// Score for a submission sent faster than a person could type it
+const TOO_FAST_SCORE: f64 = 4.0;
+
+/// Signed tokens recording when the contact form was displayed
+#[derive(Clone)]
+pub struct FormTokens {
+    key: Arc<[u8]>,
+    ttl: Duration,
+}
+
+impl FormTokens {
+    pub fn new(key: impl AsRef<[u8]>, ttl: Duration) -> Self {
+        Self {
+            key: Arc::from(key.as_ref()),
+            ttl,
+        }
+    }
+
+    /// Tokens signed with a key derived from the session secret
+    pub fn from_config(auth: &AuthConfig, config: &SpamConfig) -> Self {
+        Self::new(auth.derive_key("form-token"), Duration::seconds(config.form_token_ttl_secs as i64))
+    }
+
+    /// Issue a token for a form displayed now
+    pub fn issue(&self) -> String {
+        self.issue_at(Utc::now())
+    }
+
+    pub fn issue_at(&self, at: DateTime<Utc>) -> String {
+        let payload = at.timestamp_millis().to_string();
+        format!("{}.{}", payload, URL_SAFE_NO_PAD.encode(self.sign(&payload)))
+    }
+
+    /// When a valid, unexpired token was issued
+    pub fn verify(&self, token: &str) -> Option<DateTime<Utc>> {
+        let (payload, signature) = token.trim().split_once('.')?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature).ok()?;
+
+        let issued_at = Utc.timestamp_millis_opt(payload.parse().ok()?).single()?;
+        let age = Utc::now() - issued_at;
+        (age <= self.ttl && age >= -Duration::seconds(5)).then_some(issued_at)
+    }
+
+    fn sign(&self, payload: &str) -> Vec<u8> {
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length")
+    }
+}
+
+impl fmt::Debug for FormTokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormTokens")
+            .field("key", &"<redacted>")
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+/// Flags submissions sent too soon after the form was displayed, or without a valid form token
+pub struct TimingFilter {
+    tokens: FormTokens,
+    min_submit: Duration,
+}
+
+impl TimingFilter {
+    pub fn new(tokens: FormTokens, min_submit_secs: u64) -> Self {
+        Self {
+            tokens,
+            min_submit: Duration::seconds(min_submit_secs as i64),
+        }
+    }
+}
+
+#[async_trait]
+impl SpamFilter for TimingFilter {
+    fn name(&self) -> &'static str {
+        "timing"
+    }
+
+    async fn check(&self, message: &CreateContactMessage) -> Result<Vec<SpamSignal>, sqlx::Error> {
+        let Some(token) = message.form_token.as_deref() else {
+            return Ok(vec![SpamSignal::new(self.name(), MISSING_TOKEN_SCORE, "No form token")]);
+        };
+        let Some(issued_at) = self.tokens.verify(token) else {
+            return Ok(vec![SpamSignal::new(self.name(), INVALID_TOKEN_SCORE, "Invalid or expired form token")]);
+        };
+
+        let elapsed = Utc::now() - issued_at;
+        Ok(if elapsed < self.min_submit {
+            vec![SpamSignal::new(
+                self.name(),
+                TOO_FAST_SCORE,
+                format!("Submitted {} ms after the form was displayed", elapsed.num_milliseconds().max(0)),
+            )]
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens() -> FormTokens {
+        FormTokens::new("a-test-key", Duration::hours(1))
+    }
+
+    #[test]
+    fn test_token_round_trip() {
+        let tokens = tokens();
+        let issued_at = Utc::now() - Duration::minutes(5);
+        let token = tokens.issue_at(issued_at);
+
+        assert_eq!(tokens.verify(&token).unwrap().timestamp_millis(), issued_at.timestamp_millis());
+
+        // Other keys, tampered timestamps and expired tokens are rejected
+        assert!(FormTokens::new("another-key", Duration::hours(1)).verify(&token).is_none());
+        let (_, signature) = token.split_once('.').unwrap();
+        assert!(tokens.verify(&format!("{}.{}", issued_at.timestamp_millis() - 60_000, signature)).is_none());
+        assert!(tokens.verify(&tokens.issue_at(Utc::now() - Duration::hours(2))).is_none());
+        assert!(tokens.verify("garbage").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_timing_signals() {
+        let filter = TimingFilter::new(tokens(), 3);
+        let mut message = CreateContactMessage::default();
+
+        let signals = filter.check(&message).await.unwrap();
+        assert_eq!(signals[0].score, MISSING_TOKEN_SCORE);
+
+        message.form_token = Some("123.forged".to_string());
+        assert_eq!(filter.check(&message).await.unwrap()[0].score, INVALID_TOKEN_SCORE);
+
+        message.form_token = Some(tokens().issue());
+        assert_eq!(filter.check(&message).await.unwrap()[0].score, TOO_FAST_SCORE);
+
+        message.form_token = Some(tokens().issue_at(Utc::now() - Duration::seconds(30)));
+        assert!(filter.check(&message).await.unwrap().is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use axum::http::StatusCode;
 use axum_test::TestServer;
-use portfolio_backend::{database, routes};
+use portfolio_backend::{auth::AuthConfig, database, models::CreateAdminUser, routes, services::AuthService};
 use serde_json::{json, Value};
 use sqlx::SqlitePool;
 
@@ -13,15 +13,36 @@ async fn setup_test_server() -> TestServer {
         .await
         .expect("Failed to initialize database");
 
-    let router = routes::create_router(pool);
+    let auth = AuthConfig::new("integration-test-secret-long-enough", chrono::Duration::hours(1));
+    AuthService::new(pool.clone(), auth.clone())
+        .create_admin(CreateAdminUser {
+            username: "admin".to_string(),
+            password: "integration-test-password".to_string(),
+        })
+        .await
+        .expect("Failed to create admin");
+
+    let router = routes::create_router(pool, auth);
     TestServer::new(router).expect("Failed to create test server")
 }
 
+async fn admin_token(server: &TestServer) -> String {
+    let response = server
+        .post("/api/auth/login")
+        .json(&json!({ "username": "admin", "password": "integration-test-password" }))
+        .await;
+    response.assert_status_ok();
+
+    response.json::<Value>()["data"]["token"]
+        .as_str()
+        .expect("Login response should carry a token")
+        .to_string()
+}
+
 #[tokio::test]
 async fn test_server_setup() {
     let _server = setup_test_server().await;
     // If we get here, the setup is working correctly
-    assert!(true);
 }
 
 #[tokio::test]
@@ -46,13 +67,14 @@ async fn test_get_projects_endpoint() {
     let response = server.get("/api/projects").await;
     response.assert_status_ok();
     
-    let projects: Value = response.json();
+    let projects: Value = response.json::<Value>()["data"].clone();
     assert!(projects.is_array());
 }
 
 #[tokio::test]
 async fn test_create_project_endpoint() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     let new_project = json!({
         "title": "Test Project",
@@ -64,19 +86,21 @@ async fn test_create_project_endpoint() {
 
     let response = server
         .post("/api/projects")
+        .authorization_bearer(&token)
         .json(&new_project)
         .await;
     
-    response.assert_status(StatusCode::CREATED);
+    response.assert_status_ok();
     
-    let created_project: Value = response.json();
+    let created_project: Value = response.json::<Value>()["data"].clone();
     assert_eq!(created_project["title"], "Test Project");
-    assert_eq!(created_project["category"], "Web");
+    assert_eq!(created_project["category"], "web");
 }
 
 #[tokio::test]
 async fn test_get_project_by_id_endpoint() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     // First create a project
     let new_project = json!({
@@ -89,17 +113,18 @@ async fn test_get_project_by_id_endpoint() {
 
     let create_response = server
         .post("/api/projects")
+        .authorization_bearer(&token)
         .json(&new_project)
         .await;
     
-    let created_project: Value = create_response.json();
+    let created_project: Value = create_response.json::<Value>()["data"].clone();
     let project_id = created_project["id"].as_i64().unwrap();
 
     // Now get the project by ID
     let response = server.get(&format!("/api/projects/{}", project_id)).await;
     response.assert_status_ok();
     
-    let project: Value = response.json();
+    let project: Value = response.json::<Value>()["data"].clone();
     assert_eq!(project["id"], project_id);
     assert_eq!(project["title"], "Test Project");
 }
@@ -107,6 +132,7 @@ async fn test_get_project_by_id_endpoint() {
 #[tokio::test]
 async fn test_update_project_endpoint() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     // Create a project
     let new_project = json!({
@@ -119,10 +145,11 @@ async fn test_update_project_endpoint() {
 
     let create_response = server
         .post("/api/projects")
+        .authorization_bearer(&token)
         .json(&new_project)
         .await;
     
-    let created_project: Value = create_response.json();
+    let created_project: Value = create_response.json::<Value>()["data"].clone();
     let project_id = created_project["id"].as_i64().unwrap();
 
     // Update the project
@@ -133,12 +160,13 @@ async fn test_update_project_endpoint() {
 
     let response = server
         .put(&format!("/api/projects/{}", project_id))
+        .authorization_bearer(&token)
         .json(&update_data)
         .await;
     
     response.assert_status_ok();
     
-    let updated_project: Value = response.json();
+    let updated_project: Value = response.json::<Value>()["data"].clone();
     assert_eq!(updated_project["title"], "Updated Title");
     assert_eq!(updated_project["featured"], true);
 }
@@ -146,6 +174,7 @@ async fn test_update_project_endpoint() {
 #[tokio::test]
 async fn test_delete_project_endpoint() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     // Create a project
     let new_project = json!({
@@ -158,15 +187,16 @@ async fn test_delete_project_endpoint() {
 
     let create_response = server
         .post("/api/projects")
+        .authorization_bearer(&token)
         .json(&new_project)
         .await;
     
-    let created_project: Value = create_response.json();
+    let created_project: Value = create_response.json::<Value>()["data"].clone();
     let project_id = created_project["id"].as_i64().unwrap();
 
     // Delete the project
-    let response = server.delete(&format!("/api/projects/{}", project_id)).await;
-    response.assert_status(StatusCode::NO_CONTENT);
+    let response = server.delete(&format!("/api/projects/{}", project_id)).authorization_bearer(&token).await;
+    response.assert_status_ok();
 
     // Verify it's deleted
     let get_response = server.get(&format!("/api/projects/{}", project_id)).await;
@@ -180,13 +210,14 @@ async fn test_get_skills_endpoint() {
     let response = server.get("/api/skills").await;
     response.assert_status_ok();
     
-    let skills: Value = response.json();
+    let skills: Value = response.json::<Value>()["data"].clone();
     assert!(skills.is_array());
 }
 
 #[tokio::test]
 async fn test_create_skill_endpoint() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     let new_skill = json!({
         "name": "TypeScript",
@@ -198,12 +229,13 @@ async fn test_create_skill_endpoint() {
 
     let response = server
         .post("/api/skills")
+        .authorization_bearer(&token)
         .json(&new_skill)
         .await;
     
-    response.assert_status(StatusCode::CREATED);
+    response.assert_status_ok();
     
-    let created_skill: Value = response.json();
+    let created_skill: Value = response.json::<Value>()["data"].clone();
     assert_eq!(created_skill["name"], "TypeScript");
     assert_eq!(created_skill["level"], 4);
 }
@@ -215,7 +247,7 @@ async fn test_get_profile_endpoint() {
     let response = server.get("/api/profile").await;
     response.assert_status_ok();
     
-    let profile: Value = response.json();
+    let profile: Value = response.json::<Value>()["data"].clone();
     assert!(profile["name"].is_string());
     assert!(profile["email"].is_string());
 }
@@ -223,6 +255,7 @@ async fn test_get_profile_endpoint() {
 #[tokio::test]
 async fn test_update_profile_endpoint() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     let update_data = json!({
         "name": "Updated Name",
@@ -231,12 +264,13 @@ async fn test_update_profile_endpoint() {
 
     let response = server
         .put("/api/profile")
+        .authorization_bearer(&token)
         .json(&update_data)
         .await;
     
     response.assert_status_ok();
     
-    let updated_profile: Value = response.json();
+    let updated_profile: Value = response.json::<Value>()["data"].clone();
     assert_eq!(updated_profile["name"], "Updated Name");
     assert_eq!(updated_profile["title"], "Senior Developer");
 }
@@ -257,16 +291,17 @@ async fn test_contact_message_endpoint() {
         .json(&contact_message)
         .await;
     
-    response.assert_status(StatusCode::CREATED);
+    response.assert_status_ok();
     
-    let created_message: Value = response.json();
-    assert_eq!(created_message["name"], "John Doe");
-    assert_eq!(created_message["email"], "john@example.com");
+    let created_message: Value = response.json::<Value>()["data"].clone();
+    assert!(created_message["id"].is_number());
+    assert!(created_message["submitted_at"].is_string());
 }
 
 #[tokio::test]
 async fn test_invalid_project_creation() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     let invalid_project = json!({
         "title": "", // Empty title should fail validation
@@ -277,6 +312,7 @@ async fn test_invalid_project_creation() {
 
     let response = server
         .post("/api/projects")
+        .authorization_bearer(&token)
         .json(&invalid_project)
         .await;
     
@@ -303,5 +339,105 @@ async fn test_cors_headers() {
     let headers = response.headers();
     // Note: CORS headers might not be present in all responses, 
     // but the server should handle CORS properly
-    assert!(headers.len() > 0); // Just verify we get some headers back
-}
\ No newline at end of file
+    assert!(!headers.is_empty()); // Just verify we get some headers back
+}
+#[tokio::test]
+async fn test_anonymous_writes_rejected() {
+    let server = setup_test_server().await;
+
+    let new_project = json!({
+        "title": "Anonymous Project",
+        "description": "Should not be created",
+        "technologies": ["Rust"],
+        "category": "Web"
+    });
+
+    server
+        .post("/api/projects")
+        .json(&new_project)
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    server
+        .put("/api/profile")
+        .json(&json!({ "name": "Mallory" }))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    server
+        .get("/api/contact/messages")
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    server
+        .post("/api/projects")
+        .authorization_bearer("forged.token")
+        .json(&new_project)
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+
+    // Public data stays readable without a session
+    server.get("/api/projects").await.assert_status_ok();
+    server.get("/api/profile").await.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_api_key_workflow() {
+    let server = setup_test_server().await;
+    let token = admin_token(&server).await;
+
+    let response = server
+        .post("/api/keys")
+        .authorization_bearer(&token)
+        .json(&json!({ "name": "CI pipeline", "scopes": ["projects:write", "skills:write"] }))
+        .await;
+    response.assert_status_ok();
+
+    let created: Value = response.json::<Value>()["data"].clone();
+    let key = created["key"].as_str().unwrap().to_string();
+    let key_id = created["api_key"]["id"].as_i64().unwrap();
+
+    // The key can push projects without a human login
+    server
+        .post("/api/projects")
+        .add_header(
+            axum::http::HeaderName::from_static("x-api-key"),
+            axum::http::HeaderValue::from_str(&key).unwrap(),
+        )
+        .json(&json!({
+            "title": "Pushed by CI",
+            "description": "Created with an API key",
+            "technologies": ["Rust"],
+            "category": "Web"
+        }))
+        .await
+        .assert_status_ok();
+
+    // ...but cannot read contact messages
+    server
+        .get("/api/contact/messages")
+        .authorization_bearer(&key)
+        .await
+        .assert_status(StatusCode::FORBIDDEN);
+
+    let listed: Value = server
+        .get("/api/keys")
+        .authorization_bearer(&token)
+        .await
+        .json::<Value>()["data"]
+        .clone();
+    assert!(listed[0]["last_used_at"].is_string());
+
+    server
+        .delete(&format!("/api/keys/{}", key_id))
+        .authorization_bearer(&token)
+        .await
+        .assert_status_ok();
+
+    server
+        .post("/api/skills")
+        .authorization_bearer(&key)
+        .json(&json!({ "name": "Go", "category": "Backend", "level": 3 }))
+        .await
+        .assert_status(StatusCode::UNAUTHORIZED);
+}
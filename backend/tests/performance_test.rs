@@ -1,6 +1,6 @@
 use axum_test::TestServer;
-use portfolio_backend::{database, routes};
-use serde_json::json;
+use portfolio_backend::{auth::AuthConfig, database, models::CreateAdminUser, routes, services::AuthService};
+use serde_json::{json, Value};
 use sqlx::SqlitePool;
 use std::time::Instant;
 use tokio::time::{sleep, Duration};
@@ -14,10 +14,32 @@ async fn setup_test_server() -> TestServer {
         .await
         .expect("Failed to initialize database");
 
-    let router = routes::create_router(pool);
+    let auth = AuthConfig::new("integration-test-secret-long-enough", chrono::Duration::hours(1));
+    AuthService::new(pool.clone(), auth.clone())
+        .create_admin(CreateAdminUser {
+            username: "admin".to_string(),
+            password: "integration-test-password".to_string(),
+        })
+        .await
+        .expect("Failed to create admin");
+
+    let router = routes::create_router(pool, auth);
     TestServer::new(router).expect("Failed to create test server")
 }
 
+async fn admin_token(server: &TestServer) -> String {
+    let response = server
+        .post("/api/auth/login")
+        .json(&json!({ "username": "admin", "password": "integration-test-password" }))
+        .await;
+    response.assert_status_ok();
+
+    response.json::<Value>()["data"]["token"]
+        .as_str()
+        .expect("Login response should carry a token")
+        .to_string()
+}
+
 #[tokio::test]
 async fn test_api_response_times() {
     let server = setup_test_server().await;
@@ -66,6 +88,7 @@ async fn test_concurrent_requests() {
 #[tokio::test]
 async fn test_database_performance() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     // Create multiple projects to test database performance
     for i in 0..50 {
@@ -79,6 +102,7 @@ async fn test_database_performance() {
 
         let response = server
             .post("/api/projects")
+        .authorization_bearer(&token)
             .json(&project)
             .await;
         
@@ -108,7 +132,6 @@ async fn test_memory_usage_stability() {
     }
 
     // If we get here without crashing, memory usage is stable
-    assert!(true);
 }
 
 #[tokio::test]
@@ -127,6 +150,7 @@ async fn test_error_handling_performance() {
 #[tokio::test]
 async fn test_large_payload_handling() {
     let server = setup_test_server().await;
+    let token = admin_token(&server).await;
 
     // Create a project with a reasonably large description
     let large_description = "A".repeat(500); // 500 char description
@@ -141,6 +165,7 @@ async fn test_large_payload_handling() {
     let start = Instant::now();
     let response = server
         .post("/api/projects")
+        .authorization_bearer(&token)
         .json(&project)
         .await;
     let duration = start.elapsed();
@@ -8,7 +8,8 @@ import type {
 	UpdateSkill, 
 	Profile, 
 	UpdateProfile, 
-	ContactMessage 
+	ContactMessage,
+	ApiError
 } from '$lib/types';
 
 const API_BASE_URL = import.meta.env.VITE_API_URL || 'http://localhost:3001';
@@ -16,11 +17,7 @@ const API_BASE_URL = import.meta.env.VITE_API_URL || 'http://localhost:3001';
 export interface ApiResponse<T> {
 	success: boolean;
 	data?: T;
-	error?: {
-		code: string;
-		message: string;
-		details?: string[];
-	};
+	error?: ApiError;
 }
 
 class ApiClient {
@@ -69,10 +69,18 @@ export interface ContactMessage {
 	message: string;
 }
 
+export interface FieldError {
+	field: string;
+	code: string;
+	message: string;
+}
+
 export interface ApiError {
 	code: string;
+	status?: number;
 	message: string;
-	details?: string[];
+	details?: string;
+	fields?: FieldError[];
 }
 
 export interface LoadingState {